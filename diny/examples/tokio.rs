@@ -4,6 +4,7 @@
 use futures::{io, SinkExt, StreamExt};
 use tokio::{net, sync::oneshot};
 use async_compat::CompatExt;
+use diny::framing::Framing;
 
 #[derive(diny::AsyncSerialization, Copy, Clone, PartialEq, Debug)]
 pub struct Id(u32);
@@ -16,6 +17,10 @@ pub struct Pong(Id);
 
 const ADDR: &str = "127.0.0.1:8090";
 
+// Length-delimited frames let either peer skip messages it is unable to
+// decode (e.g. ones added by a newer version) without losing its place.
+const FRAMING: Framing = Framing::new().with_max_frame_len(1024);
+
 async fn server(ready: oneshot::Sender<()>) -> io::Result<()> {
     let listener = net::TcpListener::bind(ADDR).await?;
     assert!(ready.send(()).is_ok());
@@ -26,15 +31,20 @@ async fn server(ready: oneshot::Sender<()>) -> io::Result<()> {
     let mut stream = diny::deserializer(
         diny_test::format(),
         io::BufReader::new(rx.compat()),
-    ).into_stream();
+    ).into_framed_try_stream(FRAMING);
 
     let mut sink = diny::serializer(
         diny_test::format(),
         io::BufWriter::new(tx.compat()),
-    ).into_sink();
+    ).into_framed_sink(FRAMING);
     
-    while let Some(Ping(id)) = stream.next().await {
-        sink.send(Pong(id)).await?;
+    // Errors confined to a single frame leave the stream positioned on the
+    // next one, and any error that does not is followed by the end of the stream.
+    while let Some(ping) = stream.next().await {
+        match ping {
+            Ok(Ping(id)) => sink.send(Pong(id)).await?,
+            Err(e)       => eprintln!("skipping frame: {}", e),
+        }
     }
     sink.close().await?;
 
@@ -50,12 +60,12 @@ async fn client(ready: oneshot::Receiver<()>) -> io::Result<()> {
     let mut sink = diny::serializer(
         diny_test::format(),
         io::BufWriter::new(tx.compat()),
-    ).into_sink();
+    ).into_framed_sink(FRAMING);
 
     let mut stream = diny::deserializer(
         diny_test::format(),
         io::BufReader::new(rx.compat()),
-    ).into_stream();
+    ).into_framed_stream(FRAMING);
 
     for i in 0..10 {
         let id = Id(i);
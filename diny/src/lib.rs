@@ -37,6 +37,10 @@
 //! The [Serialize] and [Deserialize] objects returned from the [serializer](serializer::serializer)
 //! and [deserializer](deserializer::deserializer) methods implement sinks and streams (respectively)
 //! and are the simplest way to serialize and deserialize objects that implement [AsyncSerialization].
+//! Use [into_try_stream](Deserializer::into_try_stream) instead of `into_stream` when decoding
+//! errors need to be observed, rather than simply ending the stream.  The `into_framed_*`
+//! variants prefix each object with its encoded length (see [Framing]), which allows a
+//! receiver to skip objects it is unable to decode without losing its place in the stream.
 //!
 //! ```
 //! # #![feature(generic_associated_types)]
@@ -110,7 +114,7 @@
 //! # let format = format();
 //! #
 //! let mut serializer = diny::serializer(format, writer);
-//! # let diny::Serializer { format: _, writer } = 
+//! # let diny::Serializer { format, writer } = 
 //! block_on(async {
 //!     serializer.serialize(&point).await?;
 //!     serializer.serialize(&slope).await?;
@@ -165,7 +169,89 @@
 //! block_on(read).unwrap();
 //! # }
 //! ```
-//! 
+//!
+//! The exact number of bytes an object encodes to can be computed with [encoded_len]
+//! without retaining the encoded bytes (e.g. to write a length header before the body),
+//! and [max_encoded_len] reports the largest encoding of a fixed-size type at compile time.
+//!
+//! ```
+//! # #![feature(generic_associated_types)]
+//! # extern crate diny_core;
+//! # extern crate diny_test;
+//! #
+//! # #[derive(diny::AsyncSerialization)]
+//! # pub struct Point {
+//! #     x: i32,
+//! #     y: i32,
+//! # }
+//! #
+//! # fn main() {
+//! const MAX_POINT_LEN: Option<usize> = diny::max_encoded_len::<diny_test::Formatter, Point>();
+//! assert_eq!(MAX_POINT_LEN, Some(8));
+//!
+//! let point = Point { x: 1, y: 2 };
+//! assert_eq!(diny::encoded_len(&diny_test::format(), &point).unwrap(), 8);
+//! # }
+//! ```
+//!
+//! Where no asynchronous runtime is involved, [to_vec], [to_writer], [from_slice]
+//! and [from_reader] drive the same encoders and decoders to completion over
+//! byte slices and blocking [std::io] readers and writers.
+//!
+//! ```
+//! # #![feature(generic_associated_types)]
+//! # extern crate diny_core;
+//! # extern crate diny_test;
+//! #
+//! # #[derive(Debug, PartialEq, diny::AsyncSerialization)]
+//! # pub struct Point {
+//! #     x: i32,
+//! #     y: i32,
+//! # }
+//! #
+//! # fn main() {
+//! let point = Point { x: 1, y: 2 };
+//! let format = diny_test::format();
+//!
+//! let bytes = diny::to_vec(&format, &point).unwrap();
+//! let recv: Point = diny::from_slice(&format, &bytes).unwrap();
+//! assert_eq!(point, recv);
+//!
+//! let mut file = std::io::Cursor::new(vec!());
+//! diny::to_writer(&format, &mut file, &point).unwrap();
+//! file.set_position(0);
+//! let recv: Point = diny::from_reader(&format, &mut file).unwrap();
+//! assert_eq!(point, recv);
+//! # }
+//! ```
+//!
+//! Every serializable type also [describes](Describe) its shape on the wire, so that
+//! the [schema] of a protocol can be documented, or compared between releases.
+//!
+//! ```
+//! # #![feature(generic_associated_types)]
+//! # extern crate diny_core;
+//! #
+//! # #[derive(diny::AsyncSerialization)]
+//! # pub struct Point {
+//! #     x: i32,
+//! #     y: i32,
+//! # }
+//! #
+//! # fn main() {
+//! use diny::Describe;
+//!
+//! #[derive(diny::AsyncSerialization)]
+//! pub struct Path {
+//!     points: Vec<Point>,
+//! }
+//!
+//! let defs = Path::SCHEMA.definitions();
+//! assert_eq!(defs[0].definition().to_string(), "struct Path { #1 points: [Point] }");
+//! assert_eq!(defs[1].definition().to_string(), "struct Point { #1 x: i32, #2 y: i32 }");
+//! # }
+//! ```
+//!
 //! Additionally, an object's underlying [Encoder](backend::Encodable::Encoder)
 //! and [Decoder](backend::Decodable::Decoder) can be easily incorporated into
 //! custom futures.  See the [Serialize] and [Deserialize] implementations
@@ -180,13 +266,20 @@
 //! the `derive` proc macros are _not_ built by default, and need to be enabled to
 //! become available.
 //!
-//! | Feature        | Description                                                         | Default                       |
-//! |----------------|---------------------------------------------------------------------|:-----------------------------:|
-//! | `derive`       | Support for deriving [AsyncSerialize] and [AsyncDeserialize] traits | <font size="5">&#9744;</font> |
-//! | `unsafe_speed` | Permit using unsafe code to improve performance                     | <font size="5">&#9744;</font> |
-//! | `std`          | Support for Rust's standard library                                 | <font size="5">&#9745;</font> |
-//! | `alloc`        | Support for memory allocation without full `std` support            | <font size="5">&#9744;</font> |
-//! | `test`         | Build the diny_test formatter and re-export it to diny::test        | <font size="5">&#9744;</font> |
+//! | Feature        | Description                                                          | Default                       |
+//! |----------------|----------------------------------------------------------------------|:-----------------------------:|
+//! | `derive`       | Support for deriving [AsyncSerialize] and [AsyncDeserialize] traits  | <font size="5">&#9744;</font> |
+//! | `unsafe_speed` | Permit using unsafe code to improve performance                      | <font size="5">&#9744;</font> |
+//! | `std`          | Support for Rust's standard library                                  | <font size="5">&#9745;</font> |
+//! | `alloc`        | Support for memory allocation without full `std` support             | <font size="5">&#9744;</font> |
+//! | `test`         | Build the diny_test formatter and re-export it to diny::test         | <font size="5">&#9744;</font> |
+//! | `compact`      | Build the diny_compact formatter and re-export it to diny::compact   | <font size="5">&#9744;</font> |
+//! | `bincode`      | Build the diny_bincode formatter and re-export it to diny::bincode   | <font size="5">&#9744;</font> |
+//! | `postcard`     | Build the diny_postcard formatter and re-export it to diny::postcard | <font size="5">&#9744;</font> |
+//! | `msgpack`      | Build the diny_msgpack formatter and re-export it to diny::msgpack   | <font size="5">&#9744;</font> |
+//! | `cbor`         | Build the diny_cbor formatter and re-export it to diny::cbor         | <font size="5">&#9744;</font> |
+//! | `json`         | Build the diny_json formatter and re-export it to diny::json         | <font size="5">&#9744;</font> |
+//! | `protobuf`     | Build the diny_protobuf formatter and re-export it to diny::protobuf | <font size="5">&#9744;</font> |
 //!
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
 extern crate alloc;
@@ -200,3 +293,59 @@ pub use diny_core::*;
 pub mod test {
     pub use diny_test::*;
 }
+
+// If the compact serializer is enabled, pull it in as the 'compact' module locally.
+#[cfg(feature = "compact")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compact")))]
+/// The compact, variable-length integer format
+pub mod compact {
+    pub use diny_compact::*;
+}
+
+// If the bincode serializer is enabled, pull it in as the 'bincode' module locally.
+#[cfg(feature = "bincode")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bincode")))]
+/// The bincode 1.x compatible format
+pub mod bincode {
+    pub use diny_bincode::*;
+}
+
+// If the postcard serializer is enabled, pull it in as the 'postcard' module locally.
+#[cfg(feature = "postcard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "postcard")))]
+/// The postcard compatible format
+pub mod postcard {
+    pub use diny_postcard::*;
+}
+
+// If the msgpack serializer is enabled, pull it in as the 'msgpack' module locally.
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+/// The self-describing MessagePack format
+pub mod msgpack {
+    pub use diny_msgpack::*;
+}
+
+// If the cbor serializer is enabled, pull it in as the 'cbor' module locally.
+#[cfg(feature = "cbor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+/// The self-describing CBOR format, with an optional deterministic mode
+pub mod cbor {
+    pub use diny_cbor::*;
+}
+
+// If the json serializer is enabled, pull it in as the 'json' module locally.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// The human-readable JSON text format
+pub mod json {
+    pub use diny_json::*;
+}
+
+// If the protobuf serializer is enabled, pull it in as the 'protobuf' module locally.
+#[cfg(feature = "protobuf")]
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+/// The Protocol Buffers wire format, keyed by field tags
+pub mod protobuf {
+    pub use diny_protobuf::*;
+}
@@ -0,0 +1,32 @@
+/// The order in which the bytes of multi-byte integers and floating point
+/// numbers, including variant indices, `Option` tags and lengths, are encoded.
+///
+/// This trait is sealed, and implemented only by [LittleEndian] and [BigEndian].
+pub trait ByteOrder: 'static + Copy + Clone + core::fmt::Debug + Default + Send + Sync + private::Sealed {
+    /// Whether the most significant byte is encoded first
+    const BIG_ENDIAN: bool;
+}
+
+/// Least significant byte first, as per bincode's default settings
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LittleEndian;
+
+/// Most significant byte first (i.e. network byte order), as per bincode's
+/// fixed-width integer encoding combined with its `with_big_endian` option
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigEndian;
+
+impl ByteOrder for LittleEndian {
+    const BIG_ENDIAN: bool = false;
+}
+
+impl ByteOrder for BigEndian {
+    const BIG_ENDIAN: bool = true;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::LittleEndian {}
+    impl Sealed for super::BigEndian {}
+}
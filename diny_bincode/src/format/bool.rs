@@ -0,0 +1,33 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend, buffer::buffer_state::BufferState, io};
+use crate::{ByteOrder, Formatter as ThisFormat};
+
+type Error = io::Error;
+type Data = bool;
+const BUF_SIZE: usize = 1;
+
+const TRUE:  u8 = 1;
+const FALSE: u8 = 0;
+
+// A single byte is independent of the byte order
+#[inline(always)]
+#[allow(clippy::extra_unused_type_parameters)]
+fn to_bytes<O: ByteOrder>(v: Data) -> [u8; BUF_SIZE] {
+    match v {
+        true  => [TRUE],
+        false => [FALSE],
+    }
+}
+
+#[inline(always)]
+fn from_bytes<O: ByteOrder>(bytes: [u8; BUF_SIZE]) -> io::Result<Data> {
+    match bytes[0] {
+        TRUE  => Ok(true),
+        FALSE => Ok(false),
+        _ => Err(<ThisFormat<O> as backend::Format>::invalid_data_err()),
+    }
+}
+
+numeric_encode_decode_def!();
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
\ No newline at end of file
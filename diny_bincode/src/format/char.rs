@@ -0,0 +1,116 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend, buffer::buffer_state::BufferState, io};
+use crate::{ByteOrder, Formatter as ThisFormat};
+
+type Error = io::Error;
+type Data = char;
+
+// The maximum number of bytes required to encode a char as UTF-8
+const BUF_SIZE: usize = 4;
+
+/// The total number of bytes in the UTF-8 sequence introduced by `b`, or
+/// `None` if `b` can not begin a sequence.
+#[inline(always)]
+fn utf8_width(b: u8) -> Option<usize> {
+    match b {
+        0x00..=0x7f => Some(1),
+        0xc2..=0xdf => Some(2),
+        0xe0..=0xef => Some(3),
+        0xf0..=0xf4 => Some(4),
+        _           => None,
+    }
+}
+
+pub struct Encoder<O>(BufferState<BUF_SIZE>, PhantomData<O>);
+
+impl<O: ByteOrder> diny::buffer::BufferEncode for Encoder<O> {
+    type Data = Data;
+    type Format = ThisFormat<O>;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(BUF_SIZE);
+
+    fn init_buffer(data: &Self::Data) -> Self {
+        let mut buf = [0u8; BUF_SIZE];
+        let n = data.encode_utf8(&mut buf).len();
+        Encoder(BufferState::with_partial_contents(buf, n), PhantomData)
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        enc.0.start_write(writer, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.write_remaining(writer, cx)
+    }
+}
+
+// Reads the leading byte on its own, in order to determine how many more
+// bytes belong to the UTF-8 sequence.
+pub struct Decoder<O>(BufferState<BUF_SIZE>, PhantomData<O>);
+
+impl<O: ByteOrder> Decoder<O> {
+    fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self.0.read_remaining(reader, cx) {
+                backend::PollDecodeStatus::Fini(()) => {
+                    let bytes = &self.0.buffer()[..self.0.len()];
+                    match utf8_width(bytes[0]) {
+                        Some(width) if width == bytes.len() => {
+                            return core::str::from_utf8(bytes)
+                                .ok()
+                                .and_then(|s| s.chars().next())
+                                .ok_or_else(<ThisFormat<O> as backend::Format>::invalid_data_err)
+                                .into();
+                        }
+                        Some(width) => self.0.extend_len(width - bytes.len()),
+                        None        => return backend::PollDecodeStatus::Error(<ThisFormat<O> as backend::Format>::invalid_data_err()),
+                    }
+                }
+                backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(err) => return backend::PollDecodeStatus::Error(err),
+            }
+        }
+    }
+}
+
+impl<O: ByteOrder> backend::Decode for Decoder<O> {
+    type Data = Data;
+    type Format = ThisFormat<O>;
+
+    fn init() -> Self {
+        Self(BufferState::with_partial_contents([0u8; BUF_SIZE], 1), PhantomData)
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        match dec.read_remaining(reader, cx) {
+            backend::PollDecodeStatus::Fini(d)    => backend::StartDecodeStatus::Fini(d),
+            backend::PollDecodeStatus::Pending    => backend::StartDecodeStatus::Pending(dec),
+            backend::PollDecodeStatus::Error(err) => backend::StartDecodeStatus::Error(err),
+        }
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(reader, cx)
+    }
+}
+
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
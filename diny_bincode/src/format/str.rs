@@ -0,0 +1,44 @@
+use core::task::Context;
+use diny::backend::{self, Encode as _};
+use diny::io;
+use crate::{ByteOrder, Formatter as ThisFormat};
+
+type Data = str;
+type ByteEncoder<O> = <ThisFormat<O> as backend::FormatEncode>::EncodeByteSlice;
+
+pub struct Encoder<O: ByteOrder>(ByteEncoder<O>);
+
+impl<O: ByteOrder> backend::Encode for Encoder<O>
+{
+    type Data = Data;
+    type Format = ThisFormat<O>;
+
+    fn init(data: &Self::Data) -> Self {
+        Self(ByteEncoder::init(data.as_bytes()))
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <Self::Format as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        ByteEncoder::start_encode(format, writer, data.as_bytes(), cx)
+        .map_pending(Encoder)
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<<Self::Format as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.poll_encode(format, writer, data.as_bytes(), cx)
+    }
+}
+
+pub type SerializeAll<'w, O, W> = diny::backend::future::serialize_all::SerializeAll<'w, ThisFormat<O>, W, Data, Encoder<O>>;
+
+pub(crate) fn serialize<'w, O, W>(format: &'w ThisFormat<O>, writer: &'w mut W, data: &'w Data) -> SerializeAll<'w, O, W>
+where
+    O: ByteOrder,
+    W: ::diny::io::AsyncWrite + Unpin,
+{
+    SerializeAll::new(format, writer, data, Encoder::init(data))
+}
\ No newline at end of file
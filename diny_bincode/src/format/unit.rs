@@ -0,0 +1,49 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend, buffer, io};
+use crate::{ByteOrder, Formatter as ThisFormat};
+
+type Error = io::Error;
+type Data = ();
+
+pub struct Encoder<O>(PhantomData<O>);
+
+impl<O: ByteOrder> buffer::BufferEncode for Encoder<O> {
+    type Data = Data;
+    type Format = ThisFormat<O>;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(0);
+
+    fn init_buffer(_data: &Self::Data) -> Self {
+        Encoder(PhantomData)
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, _writer: &mut W, _data: &Self::Data, _cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error> {
+        backend::StartEncodeStatus::Fini
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, _writer: &mut W, _cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error> {
+        backend::PollEncodeStatus::Fini
+    }
+}
+
+pub struct Decoder<O>(PhantomData<O>);
+
+impl<O: ByteOrder> backend::Decode for Decoder<O> {
+    type Data = Data;
+    type Format = ThisFormat<O>;
+
+    fn init() -> Self {
+        Decoder(PhantomData)
+    }
+
+    fn start_decode<R>(_format: &Self::Format, _reader: &mut R, _cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error> {
+        backend::StartDecodeStatus::Fini(())
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, _reader: &mut R, _cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error> {
+        backend::PollDecodeStatus::Fini(())
+    }
+}
+
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
\ No newline at end of file
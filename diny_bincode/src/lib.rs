@@ -0,0 +1,300 @@
+#![feature(generic_associated_types)]
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(feature = "unsafe_speed"), forbid(unsafe_code))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+#![deny(missing_docs)]
+
+//! A [bincode](https://crates.io/crates/bincode) compatible formatter for the `diny` framework.
+//!
+//! The encoding is bit-for-bit identical to that produced by bincode 1.x with its default
+//! settings (i.e. `bincode::serialize`): integers and floating point numbers are written
+//! at their fixed width in little endian byte order, sequence and string lengths as a `u64`,
+//! enum variant indices as a `u32`, `Option` tags as a single byte, and `char`s as
+//! their UTF-8 encoding.  This permits data persisted or exchanged with bincode to be
+//! read and written asynchronously via `diny` without any change to the wire format.
+//!
+//! The byte order is a type parameter of the [Formatter], so that the same encoders
+//! also produce bincode's big endian (i.e. network byte order) variant via [big_endian].
+//!
+//! ```
+//! # #![feature(generic_associated_types)]
+//! use futures::executor::block_on;
+//! use diny::AsyncSerialize;
+//!
+//! let mut bytes = vec!();
+//! block_on(0x0102_0304u32.serialize(&diny_bincode::big_endian(), &mut bytes)).unwrap();
+//! assert_eq!(bytes, [1, 2, 3, 4]);
+//! ```
+//!
+//! See the main `diny` documentation for project status and general usage
+#[macro_use]
+mod macros;
+mod byte_order;
+#[doc(hidden)] pub mod format;
+
+pub use byte_order::{ByteOrder, BigEndian, LittleEndian};
+
+use core::marker::PhantomData;
+use diny::{backend::DecodeLimits, io};
+
+/// Create a bincode formatter instance
+pub fn format() -> Formatter {
+    Formatter::default()
+}
+
+/// Create a bincode formatter instance that encodes in big endian byte order
+pub fn big_endian() -> Formatter<BigEndian> {
+    Formatter::default()
+}
+
+/// A format that encodes data identically to bincode 1.x with its
+/// default, fixed-width integer settings, in the [byte order](ByteOrder) `O`.
+///
+/// No [decode limits](DecodeLimits) are enforced unless they are
+/// explicitly provided via [Formatter::with_limits].
+#[derive(Clone, Debug, Default)]
+pub struct Formatter<O = LittleEndian> {
+    limits: DecodeLimits,
+    order: PhantomData<O>,
+}
+
+impl<O> Formatter<O> {
+    /// Create a formatter that enforces the provided [decode limits](DecodeLimits)
+    pub fn with_limits(limits: DecodeLimits) -> Self {
+        Self { limits, order: PhantomData }
+    }
+}
+
+impl<O: ByteOrder> diny::backend::Format for Formatter<O> {
+    type Error = io::Error;
+
+    fn invalid_input_err() -> Self::Error {
+        io::error::invalid_input()
+    }
+
+    fn invalid_data_err() -> Self::Error {
+        io::error::invalid_data()
+    }
+
+    fn nested_err(err: Self::Error, segment: diny::error::PathSegment) -> Self::Error {
+        io::error::within(err, segment)
+    }
+
+    fn offset_err(err: Self::Error, offset: usize) -> Self::Error {
+        io::error::at_offset(err, offset)
+    }
+}
+
+impl<O: ByteOrder> diny::backend::FormatEncode for Formatter<O> {
+    type EncodeUnit = format::unit::Encoder<O>;
+    type EncodeBool = format::bool::Encoder<O>;
+
+    type EncodeI8   = format::i8  ::Encoder<O>;
+    type EncodeI16  = format::i16 ::Encoder<O>;
+    type EncodeI32  = format::i32 ::Encoder<O>;
+    type EncodeI64  = format::i64 ::Encoder<O>;
+    type EncodeI128 = format::i128::Encoder<O>;
+
+    type EncodeU8   = format::u8  ::Encoder<O>;
+    type EncodeU16  = format::u16 ::Encoder<O>;
+    type EncodeU32  = format::u32 ::Encoder<O>;
+    type EncodeU64  = format::u64 ::Encoder<O>;
+    type EncodeU128 = format::u128::Encoder<O>;
+
+    type EncodeF32  = format::f32 ::Encoder<O>;
+    type EncodeF64  = format::f64 ::Encoder<O>;
+
+    type EncodeByteSlice = format::byte_slice::Encoder<O>;
+
+    type EncodeChar   = format::char::Encoder<O>;
+    type EncodeStr    = format::str ::Encoder<O>;
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    type EncodeString = format::string::Encoder<O>;
+
+    type EncodeVariantIdx  = format::variant_idx ::Encoder<O>;
+    type EncodeSequenceLen = format::sequence_len::Encoder<O>;
+}
+
+impl<O: ByteOrder> diny::backend::FormatEncodeHooks for Formatter<O> {
+    type EncodeStructBegin<S: diny::backend::internal::StructDef> = diny::backend::internal::Nothing<Self>;
+    type EncodeStructField<S: diny::backend::internal::StructDef, const IDX: usize, E: diny::backend::Encode<Format=Self>> = E;
+    type EncodeStructEnd<S: diny::backend::internal::StructDef> = diny::backend::internal::Nothing<Self>;
+    type EncodeStruct<S: diny::backend::internal::StructDef, E: diny::backend::Encode<Format=Self>> = E;
+    type EncodeItemSeparator = diny::backend::internal::Nothing<Self>;
+    type EncodeMapEntryBegin = diny::backend::internal::Nothing<Self>;
+    type EncodeMapEntryEnd   = diny::backend::internal::Nothing<Self>;
+    type EncodeCollectionEnd = diny::backend::internal::Nothing<Self>;
+    type EncodeMapLen        = diny::backend::internal::Rewrapped<diny::backend::internal::MapLen, format::sequence_len::Encoder<O>>;
+    type EncodeMap<E: diny::backend::Encode<Format=Self>> = E;
+    type EncodeVariant<E: diny::backend::internal::EnumDef> = format::variant_idx::Encoder<O>;
+    type EncodeVariantEnd<E: diny::backend::internal::EnumDef> = diny::backend::internal::Nothing<Self>;
+    type EncodeOptionTag = format::option_tag::Encoder<O>;
+    type EncodeRaw = format::byte_slice::Encoder<O>;
+}
+
+impl<O: ByteOrder> diny::backend::FormatSerialize for Formatter<O>
+{
+    type SerializeUnit<'w, W> where W: 'w + io::AsyncWrite + Unpin = format::unit::SerializeAll<'w, O, W>;
+    type SerializeBool<'w, W> where W: 'w + io::AsyncWrite + Unpin = format::bool::SerializeAll<'w, O, W>;
+
+    type SerializeI8  <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::i8  ::SerializeAll<'w, O, W>;
+    type SerializeI16 <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::i16 ::SerializeAll<'w, O, W>;
+    type SerializeI32 <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::i32 ::SerializeAll<'w, O, W>;
+    type SerializeI64 <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::i64 ::SerializeAll<'w, O, W>;
+    type SerializeI128<'w, W> where W: 'w + io::AsyncWrite + Unpin = format::i128::SerializeAll<'w, O, W>;
+
+    type SerializeU8  <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::u8  ::SerializeAll<'w, O, W>;
+    type SerializeU16 <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::u16 ::SerializeAll<'w, O, W>;
+    type SerializeU32 <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::u32 ::SerializeAll<'w, O, W>;
+    type SerializeU64 <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::u64 ::SerializeAll<'w, O, W>;
+    type SerializeU128<'w, W> where W: 'w + io::AsyncWrite + Unpin = format::u128::SerializeAll<'w, O, W>;
+
+    type SerializeF32 <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::f32 ::SerializeAll<'w, O, W>;
+    type SerializeF64 <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::f64 ::SerializeAll<'w, O, W>;
+
+    type SerializeByteSlice<'w, W> where W: 'w + io::AsyncWrite + Unpin = format::byte_slice::SerializeAll<'w, O, W>;
+
+    type SerializeChar  <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::char  ::SerializeAll<'w, O, W>;
+    type SerializeStr   <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::str   ::SerializeAll<'w, O, W>;
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    type SerializeString<'w, W> where W: 'w + io::AsyncWrite + Unpin = format::string::SerializeAll<'w, O, W>;
+
+    type SerializeVariantIdx <'w, W> where W: 'w + io::AsyncWrite + Unpin = format::variant_idx ::SerializeAll<'w, O, W>;
+    type SerializeSequenceLen<'w, W> where W: 'w + io::AsyncWrite + Unpin = format::sequence_len::SerializeAll<'w, O, W>;
+
+    fn serialize_unit<'w, W>(&'w self, writer: &'w mut W, data: &()  ) -> Self::SerializeUnit<'w, W> where W: io::AsyncWrite + Unpin { format::unit::serialize(self, writer, data) } 
+    fn serialize_bool<'w, W>(&'w self, writer: &'w mut W, data: &bool) -> Self::SerializeBool<'w, W> where W: io::AsyncWrite + Unpin { format::bool::serialize(self, writer, data) }
+ 
+    fn serialize_i8  <'w, W>(&'w self, writer: &'w mut W, data: &i8  ) -> Self::SerializeI8  <'w, W> where W: io::AsyncWrite + Unpin { format::i8  ::serialize(self, writer, data) }
+    fn serialize_i16 <'w, W>(&'w self, writer: &'w mut W, data: &i16 ) -> Self::SerializeI16 <'w, W> where W: io::AsyncWrite + Unpin { format::i16 ::serialize(self, writer, data) }
+    fn serialize_i32 <'w, W>(&'w self, writer: &'w mut W, data: &i32 ) -> Self::SerializeI32 <'w, W> where W: io::AsyncWrite + Unpin { format::i32 ::serialize(self, writer, data) }
+    fn serialize_i64 <'w, W>(&'w self, writer: &'w mut W, data: &i64 ) -> Self::SerializeI64 <'w, W> where W: io::AsyncWrite + Unpin { format::i64 ::serialize(self, writer, data) }
+    fn serialize_i128<'w, W>(&'w self, writer: &'w mut W, data: &i128) -> Self::SerializeI128<'w, W> where W: io::AsyncWrite + Unpin { format::i128::serialize(self, writer, data) }
+ 
+    fn serialize_u8  <'w, W>(&'w self, writer: &'w mut W, data: &u8  ) -> Self::SerializeU8  <'w, W> where W: io::AsyncWrite + Unpin { format::u8  ::serialize(self, writer, data) }
+    fn serialize_u16 <'w, W>(&'w self, writer: &'w mut W, data: &u16 ) -> Self::SerializeU16 <'w, W> where W: io::AsyncWrite + Unpin { format::u16 ::serialize(self, writer, data) }
+    fn serialize_u32 <'w, W>(&'w self, writer: &'w mut W, data: &u32 ) -> Self::SerializeU32 <'w, W> where W: io::AsyncWrite + Unpin { format::u32 ::serialize(self, writer, data) }
+    fn serialize_u64 <'w, W>(&'w self, writer: &'w mut W, data: &u64 ) -> Self::SerializeU64 <'w, W> where W: io::AsyncWrite + Unpin { format::u64 ::serialize(self, writer, data) }
+    fn serialize_u128<'w, W>(&'w self, writer: &'w mut W, data: &u128) -> Self::SerializeU128<'w, W> where W: io::AsyncWrite + Unpin { format::u128::serialize(self, writer, data) }
+
+    fn serialize_f32 <'w, W>(&'w self, writer: &'w mut W, data: &f32 ) -> Self::SerializeF32 <'w, W> where W: io::AsyncWrite + Unpin { format::f32 ::serialize(self, writer, data) }
+    fn serialize_f64 <'w, W>(&'w self, writer: &'w mut W, data: &f64 ) -> Self::SerializeF64 <'w, W> where W: io::AsyncWrite + Unpin { format::f64 ::serialize(self, writer, data) }
+
+    fn serialize_byte_slice<'w, W>(&'w self, writer: &'w mut W, data: &'w [u8]) -> Self::SerializeByteSlice<'w, W> where W: io::AsyncWrite + Unpin { format::byte_slice::serialize(self, writer, data) }
+
+    fn serialize_char  <'w, W>(&'w self, writer: &'w mut W, data: &char     ) -> Self::SerializeChar  <'w, W> where W: io::AsyncWrite + Unpin { format::char  ::serialize(self, writer, data) }
+    fn serialize_str   <'w, W>(&'w self, writer: &'w mut W, data: &'w str   ) -> Self::SerializeStr   <'w, W> where W: io::AsyncWrite + Unpin { format::str   ::serialize(self, writer, data) }
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn serialize_string<'w, W>(&'w self, writer: &'w mut W, data: &'w String) -> Self::SerializeString<'w, W> where W: io::AsyncWrite + Unpin { format::string::serialize(self, writer, data) }
+
+    fn serialize_variant_idx <'w, W>(&'w self, writer: &'w mut W, data: &diny::backend::internal::VariantIdx ) -> Self::SerializeVariantIdx <'w, W> where W: io::AsyncWrite + Unpin { format::variant_idx ::serialize(self, writer, data) }
+    fn serialize_sequence_len<'w, W>(&'w self, writer: &'w mut W, data: &diny::backend::internal::SequenceLen) -> Self::SerializeSequenceLen<'w, W> where W: io::AsyncWrite + Unpin { format::sequence_len::serialize(self, writer, data) }
+}
+
+impl<O: ByteOrder> diny::backend::FormatDecode for Formatter<O> {
+    type DecodeUnit = format::unit::Decoder<O>;
+    type DecodeBool = format::bool::Decoder<O>;
+
+    type DecodeI8   = format::i8  ::Decoder<O>;
+    type DecodeI16  = format::i16 ::Decoder<O>;
+    type DecodeI32  = format::i32 ::Decoder<O>;
+    type DecodeI64  = format::i64 ::Decoder<O>;
+    type DecodeI128 = format::i128::Decoder<O>;
+
+    type DecodeU8   = format::u8  ::Decoder<O>;
+    type DecodeU16  = format::u16 ::Decoder<O>;
+    type DecodeU32  = format::u32 ::Decoder<O>;
+    type DecodeU64  = format::u64 ::Decoder<O>;
+    type DecodeU128 = format::u128::Decoder<O>;
+
+    type DecodeF32  = format::f32 ::Decoder<O>;
+    type DecodeF64  = format::f64 ::Decoder<O>;
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    type DecodeByteVec = format::byte_vec::Decoder<O>;
+
+    type DecodeChar = format::char::Decoder<O>;
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    type DecodeString = format::string::Decoder<O>;
+
+    type DecodeVariantIdx  = format::variant_idx ::Decoder<O>;
+    type DecodeSequenceLen = format::sequence_len::Decoder<O>;
+
+    fn decode_limits(&self) -> &DecodeLimits {
+        &self.limits
+    }
+}
+
+impl<O: ByteOrder> diny::backend::FormatDecodeHooks for Formatter<O> {
+    type DecodeStructBegin<S: diny::backend::internal::StructDef> = diny::backend::internal::Nothing<Self>;
+    type DecodeStructField<S: diny::backend::internal::StructDef, const IDX: usize, D: diny::backend::Decode<Format=Self>> = D;
+    type DecodeStructEnd<S: diny::backend::internal::StructDef> = diny::backend::internal::Nothing<Self>;
+    type DecodeStruct<S: diny::backend::internal::StructDef, D: diny::backend::Decode<Format=Self>> = D;
+    type DecodeItemSeparator  = diny::backend::internal::Nothing<Self>;
+    type DecodeMapEntryBegin  = diny::backend::internal::Nothing<Self>;
+    type DecodeMapEntryEnd    = diny::backend::internal::Nothing<Self>;
+    type DecodeCollectionNext = diny::backend::internal::Nothing<Self, bool>;
+    type DecodeCollectionEnd  = diny::backend::internal::Nothing<Self>;
+    type DecodeMapLen         = diny::backend::internal::Rewrapped<diny::backend::internal::MapLen, format::sequence_len::Decoder<O>>;
+    type DecodeVariant<E: diny::backend::internal::EnumDef> = format::variant_idx::Decoder<O>;
+    type DecodeVariantEnd<E: diny::backend::internal::EnumDef> = diny::backend::internal::Nothing<Self>;
+    type DecodeOptionTag = format::option_tag::Decoder<O>;
+    type DecodeUnitVariant<E: diny::backend::internal::EnumDef> = diny::backend::internal::Suffixed<format::unit::Decoder<O>, diny::backend::internal::Nothing<Self>, ()>;
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    type DecodeRaw = format::byte_vec::Decoder<O>;
+}
+
+impl<O: ByteOrder> diny::backend::FormatDeserialize for Formatter<O>
+{
+    type DeserializeUnit<'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::unit::DeserializeExact<'r, O, R>;
+    type DeserializeBool<'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::bool::DeserializeExact<'r, O, R>;
+
+    type DeserializeI8  <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::i8  ::DeserializeExact<'r, O, R>;
+    type DeserializeI16 <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::i16 ::DeserializeExact<'r, O, R>;
+    type DeserializeI32 <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::i32 ::DeserializeExact<'r, O, R>;
+    type DeserializeI64 <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::i64 ::DeserializeExact<'r, O, R>;
+    type DeserializeI128<'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::i128::DeserializeExact<'r, O, R>;
+
+    type DeserializeU8  <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::u8  ::DeserializeExact<'r, O, R>;
+    type DeserializeU16 <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::u16 ::DeserializeExact<'r, O, R>;
+    type DeserializeU32 <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::u32 ::DeserializeExact<'r, O, R>;
+    type DeserializeU64 <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::u64 ::DeserializeExact<'r, O, R>;
+    type DeserializeU128<'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::u128::DeserializeExact<'r, O, R>;
+
+    type DeserializeF32 <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::f32 ::DeserializeExact<'r, O, R>;
+    type DeserializeF64 <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::f64 ::DeserializeExact<'r, O, R>;
+
+    type DeserializeChar  <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::char  ::DeserializeExact<'r, O, R>;
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    type DeserializeString<'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::string::DeserializeExact<'r, O, R>;
+
+    type DeserializeVariantIdx <'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::variant_idx ::DeserializeExact<'r, O, R>;
+    type DeserializeSequenceLen<'r, R> where R: 'r + io::AsyncBufRead + Unpin = format::sequence_len::DeserializeExact<'r, O, R>;
+
+    fn deserialize_unit<'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeUnit<'r, R> where R: io::AsyncBufRead + Unpin { format::unit::deserialize(self, reader) }
+    fn deserialize_bool<'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeBool<'r, R> where R: io::AsyncBufRead + Unpin { format::bool::deserialize(self, reader) }
+
+    fn deserialize_i8  <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeI8  <'r, R> where R: io::AsyncBufRead + Unpin { format::i8  ::deserialize(self, reader) }
+    fn deserialize_i16 <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeI16 <'r, R> where R: io::AsyncBufRead + Unpin { format::i16 ::deserialize(self, reader) }
+    fn deserialize_i32 <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeI32 <'r, R> where R: io::AsyncBufRead + Unpin { format::i32 ::deserialize(self, reader) }
+    fn deserialize_i64 <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeI64 <'r, R> where R: io::AsyncBufRead + Unpin { format::i64 ::deserialize(self, reader) }
+    fn deserialize_i128<'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeI128<'r, R> where R: io::AsyncBufRead + Unpin { format::i128::deserialize(self, reader) }
+
+    fn deserialize_u8  <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeU8  <'r, R> where R: io::AsyncBufRead + Unpin { format::u8  ::deserialize(self, reader) }
+    fn deserialize_u16 <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeU16 <'r, R> where R: io::AsyncBufRead + Unpin { format::u16 ::deserialize(self, reader) }
+    fn deserialize_u32 <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeU32 <'r, R> where R: io::AsyncBufRead + Unpin { format::u32 ::deserialize(self, reader) }
+    fn deserialize_u64 <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeU64 <'r, R> where R: io::AsyncBufRead + Unpin { format::u64 ::deserialize(self, reader) }
+    fn deserialize_u128<'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeU128<'r, R> where R: io::AsyncBufRead + Unpin { format::u128::deserialize(self, reader) }
+
+    fn deserialize_f32 <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeF32 <'r, R> where R: io::AsyncBufRead + Unpin { format::f32 ::deserialize(self, reader) }
+    fn deserialize_f64 <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeF64 <'r, R> where R: io::AsyncBufRead + Unpin { format::f64 ::deserialize(self, reader) }
+
+    fn deserialize_char  <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeChar  <'r, R> where R: io::AsyncBufRead + Unpin { format::char  ::deserialize(self, reader) }
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn deserialize_string<'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeString<'r, R> where R: io::AsyncBufRead + Unpin { format::string::deserialize(self, reader) }
+
+    fn deserialize_variant_idx <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeVariantIdx <'r, R> where R: io::AsyncBufRead + Unpin { format::variant_idx ::deserialize(self, reader) }
+    fn deserialize_sequence_len<'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeSequenceLen<'r, R> where R: io::AsyncBufRead + Unpin { format::sequence_len::deserialize(self, reader) }
+}
\ No newline at end of file
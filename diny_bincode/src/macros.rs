@@ -0,0 +1,27 @@
+macro_rules! serialize_all_def {
+    ($format: ident, $data: ty, $encoder: ident) => {
+        pub(crate) type SerializeAll<'w, O, W> = diny::buffer::BufferEncoder<'w, $format<O>, W, $data, $encoder<O>>;
+
+        pub(crate) fn serialize<'w, O, W>(format: &'w $format<O>, writer: &'w mut W, data: &$data) -> SerializeAll<'w, O, W>
+        where
+            O: $crate::ByteOrder,
+            W: ::diny::io::AsyncWrite + Unpin,
+        {
+            SerializeAll::new(format, writer, <$encoder<O> as ::diny::backend::Encode>::init(data))
+        }
+    };
+}
+
+macro_rules! deserialize_exact_def {
+    ($format: ident, $data: ty, $decoder: ident) => {
+        pub(crate) type DeserializeExact<'r, O, R> = diny::backend::DeserializeExact<'r, $format<O>, R, $data, $decoder<O>>;
+
+        pub(crate) fn deserialize<'r, O, R>(format: &'r $format<O>, reader: &'r mut R) -> DeserializeExact<'r, O, R>
+        where
+            O: $crate::ByteOrder,
+            R: ::diny::io::AsyncBufRead + Unpin,
+        {
+            DeserializeExact::new(format, reader, <$decoder<O> as ::diny::backend::Decode>::init())
+        }
+   };
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+//! The expected byte vectors in this module are those produced by bincode 1.x
+//! configured with `with_fixint_encoding` and `with_big_endian`.
+
+use core::fmt::Debug;
+use diny::{backend::DecodeLimits, AsyncDeserialize, AsyncSerialization, AsyncSerialize, util::AsyncSliceReader};
+use diny_bincode::{BigEndian, Formatter};
+use futures::executor::block_on;
+
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Rec {
+    id: u32,
+    name: String,
+    tag: Option<u8>,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub enum Kind {
+    A,
+    B(i64),
+}
+
+fn be<T>(data: &T) -> Vec<u8>
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    diny_test::roundtrip_all_schedules(&diny_bincode::big_endian(), data)
+}
+
+fn le<T>(data: &T) -> Vec<u8>
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    diny_test::roundtrip_all_schedules(&diny_bincode::format(), data)
+}
+
+fn reversed(mut bytes: Vec<u8>) -> Vec<u8> {
+    bytes.reverse();
+    bytes
+}
+
+#[test]
+fn integers_are_encoded_in_network_byte_order() {
+    assert_eq!(be(&0x01u8), [0x01]);
+    assert_eq!(be(&0x0102u16), [0x01, 0x02]);
+    assert_eq!(be(&0x0102_0304u32), [0x01, 0x02, 0x03, 0x04]);
+    assert_eq!(be(&0x0102_0304_0506_0708u64), [1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(be(&-2i16), [0xff, 0xfe]);
+    assert_eq!(be(&1i128), [&[0u8; 15][..], &[1]].concat());
+}
+
+#[test]
+fn primitives_are_the_reverse_of_the_little_endian_encoding() {
+    macro_rules! assert_reversed {
+        ($($v:expr),*) => { $( assert_eq!(be(&$v), reversed(le(&$v)), "{}", stringify!($v)); )* };
+    }
+
+    assert_reversed!(
+        i16::MIN, i32::MIN, i64::MIN, i128::MIN,
+        u16::MAX - 1, u32::MAX - 1, u64::MAX - 1, u128::MAX - 1,
+        1.5f32, -1.5f64, f64::MIN_POSITIVE,
+        true, ()
+    );
+}
+
+#[test]
+fn floats_are_encoded_in_network_byte_order() {
+    assert_eq!(be(&1.0f32), 1.0f32.to_be_bytes());
+    assert_eq!(be(&-2.5f64), (-2.5f64).to_be_bytes());
+}
+
+#[test]
+fn chars_and_option_tags_are_independent_of_byte_order() {
+    for c in ['a', 'é', '\u{10ffff}'] {
+        assert_eq!(be(&c), le(&c));
+    }
+    assert_eq!(be(&Some(7u8)), [1, 7]);
+    assert_eq!(be(&None::<u8>), [0]);
+}
+
+#[test]
+fn variant_indexes_and_sequence_lengths_are_big_endian() {
+    assert_eq!(be(&Kind::B(-1)), [&1u32.to_be_bytes()[..], &[0xff; 8]].concat());
+    assert_eq!(be(&"abc".to_string()), [&3u64.to_be_bytes()[..], b"abc"].concat());
+    assert_eq!(be(&vec![0x0102u16]), [0, 0, 0, 0, 0, 0, 0, 1, 1, 2]);
+}
+
+#[test]
+fn composites_roundtrip_with_the_same_length() {
+    let rec = Rec { id: 0x0102_0304, name: "diny".to_string(), tag: Some(3), kind: Kind::A };
+    assert_eq!(be(&rec), [
+        &[1, 2, 3, 4][..],
+        &4u64.to_be_bytes(), b"diny",
+        &[1, 3],
+        &0u32.to_be_bytes(),
+    ].concat());
+    assert_eq!(be(&rec).len(), le(&rec).len());
+}
+
+#[test]
+fn little_endian_bytes_do_not_decode_as_big_endian() {
+    let bytes = le(&"abc".to_string());
+    let mut reader = AsyncSliceReader::from(&bytes[..]);
+    let format = Formatter::<BigEndian>::with_limits(DecodeLimits::unlimited().with_max_bytes_len(1024));
+    assert!(block_on(String::deserialize(&format, &mut reader)).is_err());
+}
+
+#[test]
+fn max_encoded_len_is_independent_of_byte_order() {
+    assert_eq!(
+        diny::max_encoded_len::<Formatter<BigEndian>, (u64, Option<u16>)>(),
+        diny::max_encoded_len::<Formatter, (u64, Option<u16>)>(),
+    );
+
+    let mut bytes = vec!();
+    block_on(0x0102u16.serialize(&diny_bincode::big_endian(), &mut bytes)).unwrap();
+    assert_eq!(diny::encoded_len(&diny_bincode::big_endian(), &0x0102u16).unwrap(), bytes.len());
+}
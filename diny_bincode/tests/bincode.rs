@@ -0,0 +1,184 @@
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+//! The expected byte vectors in this module are those produced by
+//! `bincode::serialize` (bincode 1.x, default options) for the same values.
+
+use core::fmt::Debug;
+use std::{collections::BTreeMap, rc::Rc};
+use diny::{io, AsyncDeserialize, AsyncSerialization, AsyncSerialize, util::AsyncSliceReader};
+use futures::executor::block_on;
+
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Rec {
+    id: u32,
+    name: String,
+    tag: Option<u8>,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub enum Kind {
+    A,
+    B(i64),
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Unit;
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Newtype(u16);
+
+fn serialize<T: AsyncSerialize>(send: &T) -> Vec<u8> {
+    let mut tx = Vec::new();
+    block_on(send.serialize(&diny_bincode::format(), &mut tx)).expect("unable to serialize");
+    tx
+}
+
+fn deserialize<T: AsyncDeserialize>(bytes: &[u8]) -> io::Result<T> {
+    let mut rx = AsyncSliceReader::from(bytes);
+    let recv = block_on(T::deserialize(&diny_bincode::format(), &mut rx));
+    if recv.is_ok() {
+        assert_eq!(rx.bytes_read(), bytes.len());
+    }
+    recv
+}
+
+fn test_encoding<T>(send: T, expected: &[u8])
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    let bytes = serialize(&send);
+    assert_eq!(bytes, expected);
+    assert_eq!(deserialize::<T>(&bytes).expect("unable to deserialize"), send);
+    diny_test::roundtrip_all_schedules(&diny_bincode::format(), &send);
+}
+
+fn len(n: u64) -> [u8; 8] {
+    n.to_le_bytes()
+}
+
+
+#[test]
+fn integers_are_fixed_width_little_endian() {
+    test_encoding(200u8, &[0xc8]);
+    test_encoding(-1i8, &[0xff]);
+    test_encoding(0x1234u16, &[0x34, 0x12]);
+    test_encoding(-2i16, &[0xfe, 0xff]);
+    test_encoding(0x0102_0304u32, &[0x04, 0x03, 0x02, 0x01]);
+    test_encoding(i32::MIN, &[0x00, 0x00, 0x00, 0x80]);
+    test_encoding(1u64, &[0x01, 0, 0, 0, 0, 0, 0, 0]);
+    test_encoding(-1i64, &[0xff; 8]);
+    test_encoding(1u128, &[0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    test_encoding(-1i128, &[0xff; 16]);
+}
+
+#[test]
+fn floats_are_ieee754_little_endian() {
+    test_encoding(1.5f32, &[0x00, 0x00, 0xc0, 0x3f]);
+    test_encoding(1.0f64, &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f]);
+    test_encoding(-0.0f64, &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80]);
+}
+
+#[test]
+fn bools_and_units_are_encoded() {
+    test_encoding(false, &[0x00]);
+    test_encoding(true, &[0x01]);
+    test_encoding((), &[]);
+    test_encoding(Unit, &[]);
+    test_encoding(Newtype(7), &[0x07, 0x00]);
+}
+
+#[test]
+fn chars_are_utf8_encoded() {
+    test_encoding('a', &[0x61]);
+    test_encoding('é', &[0xc3, 0xa9]);
+    test_encoding('€', &[0xe2, 0x82, 0xac]);
+    test_encoding('😀', &[0xf0, 0x9f, 0x98, 0x80]);
+    test_encoding(char::MAX, &[0xf4, 0x8f, 0xbf, 0xbf]);
+}
+
+#[test]
+fn strings_and_sequences_have_u64_lengths() {
+    test_encoding(String::new(), &len(0));
+    test_encoding(String::from("hi"), &[&len(2)[..], b"hi"].concat());
+    test_encoding(vec![1u16, 2], &[&len(2)[..], &[0x01, 0x00, 0x02, 0x00]].concat());
+    test_encoding(vec![0xffu8; 3], &[&len(3)[..], &[0xff; 3]].concat());
+    test_encoding(BTreeMap::from([(1u8, true), (2u8, false)]), &[&len(2)[..], &[0x01, 0x01, 0x02, 0x00]].concat());
+}
+
+#[test]
+fn arrays_and_tuples_have_no_length() {
+    test_encoding([1u8, 2, 3], &[0x01, 0x02, 0x03]);
+    test_encoding((1u8, 2u16, 'z'), &[0x01, 0x02, 0x00, b'z']);
+}
+
+#[test]
+fn options_have_a_single_byte_tag() {
+    test_encoding(None::<u32>, &[0x00]);
+    test_encoding(Some(5u32), &[0x01, 0x05, 0x00, 0x00, 0x00]);
+    test_encoding(Some(None::<u8>), &[0x01, 0x00]);
+}
+
+#[test]
+fn enum_variants_have_a_u32_index() {
+    test_encoding(Kind::A, &[0x00, 0x00, 0x00, 0x00]);
+    test_encoding(Kind::B(-2), &[0x01, 0x00, 0x00, 0x00, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    test_encoding(Ok::<u8, u16>(7), &[0x00, 0x00, 0x00, 0x00, 0x07]);
+    test_encoding(Err::<u8, u16>(7), &[0x01, 0x00, 0x00, 0x00, 0x07, 0x00]);
+}
+
+#[test]
+fn wrappers_are_transparent() {
+    test_encoding(Box::new(3u16), &[0x03, 0x00]);
+    test_encoding(Rc::new('a'), &[0x61]);
+}
+
+#[test]
+fn can_serialize_rec() {
+    test_encoding(
+        Rec { id: 1, name: String::from("ab"), tag: Some(2), kind: Kind::B(3) },
+        &[
+            0x01, 0x00, 0x00, 0x00,
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, b'a', b'b',
+            0x01, 0x02,
+            0x01, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+    );
+    test_encoding(
+        Rec { id: 0, name: String::new(), tag: None, kind: Kind::A },
+        &[
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00,
+            0x00, 0x00, 0x00, 0x00,
+        ],
+    );
+}
+
+#[test]
+fn rejects_invalid_tags() {
+    assert_eq!(deserialize::<bool>(&[0x02]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert!(deserialize::<Option<u8>>(&[0x02, 0x00]).is_err());
+    assert!(deserialize::<Kind>(&[0x02, 0x00, 0x00, 0x00]).is_err());
+}
+
+#[test]
+fn rejects_invalid_utf8() {
+    assert_eq!(deserialize::<char>(&[0xff]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<char>(&[0x80]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<char>(&[0xc0, 0x80]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<char>(&[0xed, 0xa0, 0x80]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<char>(&[0xe2, 0x82]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert!(deserialize::<String>(&[&len(1)[..], &[0xff]].concat()).is_err());
+}
+
+#[test]
+fn encoded_len_matches_fixint_encoding() {
+    assert_eq!(diny::encoded_len(&diny_bincode::format(), &'€').unwrap(), 3);
+    assert_eq!(diny::encoded_len(&diny_bincode::format(), &Kind::B(-1)).unwrap(), 12);
+    assert_eq!(diny::max_encoded_len::<diny_bincode::Formatter, char>(), Some(4));
+    assert_eq!(diny::max_encoded_len::<diny_bincode::Formatter, Option<u32>>(), Some(1 + 4));
+    assert_eq!(diny::max_encoded_len::<diny_bincode::Formatter, Rec>(), None);
+}
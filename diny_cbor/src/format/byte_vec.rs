@@ -0,0 +1,77 @@
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+use core::task::Context;
+use diny::{backend, io};
+use crate::Formatter as ThisFormat;
+use super::{bytes, head};
+
+
+type Data = Vec<u8>;
+type ByteEncoder = <ThisFormat as backend::FormatEncode>::EncodeByteSlice;
+
+pub struct Encoder(ByteEncoder);
+
+impl backend::Encode for Encoder
+{
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init(data: &Self::Data) -> Self {
+        Self(ByteEncoder::init(data))
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <Self::Format as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        ByteEncoder::start_encode(format, writer, data, cx)
+        .map_pending(Encoder)
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<<Self::Format as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.poll_encode(format, writer, data, cx)
+    }
+}
+
+pub struct Decoder(bytes::Decoder);
+
+impl backend::Decode for Decoder
+{
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self(bytes::Decoder::new(head::BYTES))
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, <Self::Format as backend::Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.0.read_remaining(format, reader, cx)
+        .lift(dec)
+    }
+
+    // The bytes are read into the emptied storage of the existing vector
+    fn start_decode_into<R>(format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <Self::Format as backend::Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.0.reuse(core::mem::take(data));
+        dec.0.read_remaining(format, reader, cx)
+        .map(|d| *data = d)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <Self::Format as backend::Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.read_remaining(format, reader, cx)
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+use core::task::Context;
+use diny::{backend::{self, Format}, buffer::{self, buffer_state::BufferState}, io};
+use crate::Formatter as ThisFormat;
+use super::head;
+
+type Error = <ThisFormat as Format>::Error;
+
+/// Encodes a byte slice as a head of the given major type, followed by the bytes themselves
+pub enum Encoder {
+    Init,
+    Head(BufferState<{ head::MAX_LEN }>),
+    Cur(buffer::BufferCursor),
+    Fini,
+}
+
+impl Encoder {
+    pub fn start_encode<W>(major: u8, writer: &mut W, data: &[u8], cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut buf = head::encode(major, data.len() as u64);
+        match buf.start_write(writer, cx) {
+            backend::PollEncodeStatus::Fini     => Self::after_head(writer, data, cx),
+            backend::PollEncodeStatus::Pending  => backend::StartEncodeStatus::Pending(Self::Head(buf)),
+            backend::PollEncodeStatus::Error(e) => backend::StartEncodeStatus::Error(e),
+        }
+    }
+
+    fn after_head<W>(writer: &mut W, data: &[u8], cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        if data.is_empty() {
+            backend::StartEncodeStatus::Fini
+        } else {
+            let mut cur = buffer::BufferCursor::new(data);
+            match cur.write_remaining(writer, data, cx) {
+                backend::PollEncodeStatus::Fini     => backend::StartEncodeStatus::Fini,
+                backend::PollEncodeStatus::Pending  => backend::StartEncodeStatus::Pending(Self::Cur(cur)),
+                backend::PollEncodeStatus::Error(e) => backend::StartEncodeStatus::Error(e),
+            }
+        }
+    }
+
+    pub fn poll_encode<W>(&mut self, major: u8, writer: &mut W, data: &[u8], cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Init      => diny::encode_chain!(*self, Self::start_encode(major, writer, data, cx)),
+            Self::Head(buf) => diny::encode_poll_chain!(*self, buf.write_remaining(writer, cx), Self::after_head(writer, data, cx)),
+            Self::Cur(cur)  => diny::encode_poll_fini!(*self, cur.write_remaining(writer, data, cx)),
+            Self::Fini      => backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+enum DecodeCursor {
+    Head(head::ArgDecoder),
+    Cur(buffer::BufferCursor),
+    Fini,
+}
+
+/// Decodes the bytes following a head of the given major type
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct Decoder {
+    data: Vec<u8>,
+    cursor: DecodeCursor,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Decoder {
+    pub fn new(major: u8) -> Self {
+        Self {
+            data: Vec::new(),
+            cursor: DecodeCursor::Head(head::ArgDecoder::new(major)),
+        }
+    }
+
+    /// Reads the bytes into the emptied storage of `data`, rather than into a new vector
+    pub fn reuse(&mut self, mut data: Vec<u8>) {
+        data.clear();
+        self.data = data;
+    }
+
+    fn fini(&mut self) -> backend::PollDecodeStatus<Vec<u8>, Error> {
+        self.cursor = DecodeCursor::Fini;
+        backend::PollDecodeStatus::Fini(core::mem::take(&mut self.data))
+    }
+
+    fn error(&mut self, err: Error) -> backend::PollDecodeStatus<Vec<u8>, Error> {
+        self.cursor = DecodeCursor::Fini;
+        backend::PollDecodeStatus::Error(err)
+    }
+
+    pub fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Vec<u8>, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match &mut self.cursor {
+                DecodeCursor::Head(dec) => match dec.read_len(format, reader, cx) {
+                    backend::PollDecodeStatus::Fini(len) => {
+                        if let Err(kind) = backend::FormatDecode::decode_limits(format).check_bytes_len(len) {
+                            return self.error(ThisFormat::limit_exceeded_err(kind));
+                        } else if len == 0 {
+                            return self.fini();
+                        }
+                        self.data.reserve_exact(diny::backend::limits::prealloc_len::<u8>(len));
+                        self.cursor = DecodeCursor::Cur(buffer::BufferCursor::with_len(len));
+                    }
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => return self.error(e),
+                },
+                DecodeCursor::Cur(cur) => return match cur.fill_vec(reader, &mut self.data, cx) {
+                    backend::PollDecodeStatus::Fini(()) => self.fini(),
+                    backend::PollDecodeStatus::Pending  => backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => self.error(e),
+                },
+                DecodeCursor::Fini => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+}
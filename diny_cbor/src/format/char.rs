@@ -0,0 +1,120 @@
+use core::task::Context;
+use diny::{backend, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::head;
+
+type Error = <ThisFormat as backend::Format>::Error;
+type Data = char;
+
+// The maximum number of bytes required to encode a char as UTF-8
+const MAX_UTF8_LEN: usize = 4;
+
+// A text string head, followed by the UTF-8 encoding of the char.
+// Lengths of at most four bytes always fit within the head itself.
+const BUF_SIZE: usize = 1 + MAX_UTF8_LEN;
+
+pub struct Encoder(BufferState<BUF_SIZE>);
+
+impl diny::buffer::BufferEncode for Encoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(BUF_SIZE);
+
+    fn init_buffer(data: &Self::Data) -> Self {
+        let mut buf = [0u8; BUF_SIZE];
+        let n = data.encode_utf8(&mut buf[1..]).len();
+        head::encode_into(&mut buf, head::TEXT, n as u64);
+        Encoder(BufferState::with_partial_contents(buf, 1 + n))
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        enc.0.start_write(writer, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.write_remaining(writer, cx)
+    }
+}
+
+fn to_char(bytes: &[u8]) -> Result<Data, Error> {
+    let mut chars = core::str::from_utf8(bytes)
+        .map_err(|_| <ThisFormat as backend::Format>::invalid_data_err())?
+        .chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _               => Err(<ThisFormat as backend::Format>::invalid_data_err()),
+    }
+}
+
+pub enum Decoder {
+    Head(head::ArgDecoder),
+    Utf8(BufferState<MAX_UTF8_LEN>),
+    Fini,
+}
+
+impl Decoder {
+    fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self {
+                Self::Head(dec) => match dec.read_len(format, reader, cx) {
+                    backend::PollDecodeStatus::Fini(len) if (1..=MAX_UTF8_LEN).contains(&len) => {
+                        *self = Self::Utf8(BufferState::with_partial_contents([0u8; MAX_UTF8_LEN], len));
+                    }
+                    backend::PollDecodeStatus::Fini(_)    => { *self = Self::Fini; return backend::PollDecodeStatus::Error(<ThisFormat as backend::Format>::invalid_data_err()) }
+                    backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(err) => { *self = Self::Fini; return backend::PollDecodeStatus::Error(err) }
+                },
+                Self::Utf8(buf) => {
+                    let status = buf.read_remaining(reader, cx)
+                        .and_then(|()| to_char(&buf.buffer()[..buf.len()]).into());
+                    if !matches!(status, backend::PollDecodeStatus::Pending) {
+                        *self = Self::Fini;
+                    }
+                    return status;
+                }
+                Self::Fini => return backend::PollDecodeStatus::Error(<ThisFormat as backend::Format>::invalid_input_err()),
+            }
+        }
+    }
+}
+
+impl backend::Decode for Decoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self::Head(head::ArgDecoder::new(head::TEXT))
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.read_remaining(format, reader, cx)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(format, reader, cx)
+    }
+}
+
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
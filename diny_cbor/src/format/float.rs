@@ -0,0 +1,118 @@
+use core::task::Context;
+use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::head::{self, FLOAT16, FLOAT32, FLOAT64};
+
+type Error = <ThisFormat as Format>::Error;
+
+// The initial byte, followed by at most an eight byte, big endian float
+pub const MAX_LEN: usize = 9;
+
+// The half precision quiet NaN that deterministic encoding substitutes for every NaN
+const CANONICAL_NAN: u16 = 0x7e00;
+
+#[inline(always)]
+fn with_payload(initial: u8, payload: &[u8]) -> BufferState<MAX_LEN> {
+    let mut buf = [0u8; MAX_LEN];
+    buf[0] = initial;
+    buf[1..=payload.len()].copy_from_slice(payload);
+    BufferState::with_partial_contents(buf, 1 + payload.len())
+}
+
+/// The half precision bit pattern holding exactly the same value as `v`, if there is one
+fn to_f16(v: f32) -> Option<u16> {
+    let bits = v.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if v.is_nan() {
+        None
+    } else if v.is_infinite() {
+        Some(sign | 0x7c00)
+    } else if exp == 0 {
+        // Zero is the only single precision subnormal small enough to survive
+        if mantissa == 0 { Some(sign) } else { None }
+    } else {
+        let exp = exp - 127;
+        let significand = 0x80_0000 | mantissa;
+        match exp {
+            -14..=15 if mantissa & 0x1fff == 0 => Some(sign | (((exp + 15) as u16) << 10) | (mantissa >> 13) as u16),
+            -24..=-15 => {
+                let shift = -(exp + 1);
+                if significand & ((1 << shift) - 1) == 0 { Some(sign | (significand >> shift) as u16) } else { None }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Widen a half precision bit pattern to the value it represents
+fn from_f16(h: u16) -> f64 {
+    let sign = if h & 0x8000 == 0 { 1.0 } else { -1.0 };
+    let exp = ((h >> 10) & 0x1f) as i32;
+    let mantissa = (h & 0x3ff) as f64;
+
+    sign * match exp {
+        0  => mantissa * 2f64.powi(-24),
+        31 => if mantissa == 0.0 { f64::INFINITY } else { f64::NAN },
+        _  => (1024.0 + mantissa) * 2f64.powi(exp - 25),
+    }
+}
+
+/// Encode `v` in the shortest of the half, single and double precision forms that
+/// holds it exactly, as required by deterministic encoding.
+pub fn encode_shortest(v: f64) -> BufferState<MAX_LEN> {
+    if v.is_nan() {
+        with_payload(FLOAT16, &CANONICAL_NAN.to_be_bytes())
+    } else if (v as f32) as f64 == v {
+        match to_f16(v as f32) {
+            Some(h) => with_payload(FLOAT16, &h.to_be_bytes()),
+            None    => with_payload(FLOAT32, &(v as f32).to_be_bytes()),
+        }
+    } else {
+        with_payload(FLOAT64, &v.to_be_bytes())
+    }
+}
+
+pub fn encode_f32(v: f32) -> BufferState<MAX_LEN> {
+    with_payload(FLOAT32, &v.to_be_bytes())
+}
+
+pub fn encode_f64(v: f64) -> BufferState<MAX_LEN> {
+    with_payload(FLOAT64, &v.to_be_bytes())
+}
+
+// Reads a float of any precision, widened to double precision
+pub struct Decoder(head::Decoder);
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self(head::Decoder::new())
+    }
+
+    pub fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<f64, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.read_remaining(format, reader, cx)
+        .and_then(|h| {
+            let v = match h.initial {
+                FLOAT16 => from_f16(h.arg as u16),
+                FLOAT32 => f32::from_bits(h.arg as u32) as f64,
+                FLOAT64 => f64::from_bits(h.arg),
+                _       => return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+            };
+
+            if format.is_deterministic() {
+                let expected = encode_shortest(v);
+                let expected = &expected.buffer()[..expected.len()];
+                if expected[0] != h.initial || head::from_be(&expected[1..]) != h.arg {
+                    return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err());
+                }
+            }
+
+            backend::PollDecodeStatus::Fini(v)
+        })
+    }
+}
@@ -0,0 +1,188 @@
+use core::task::Context;
+use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+
+type Error = <ThisFormat as Format>::Error;
+
+// The major types of RFC 8949, section 3.1
+pub const UNSIGNED: u8 = 0;
+pub const NEGATIVE: u8 = 1;
+pub const BYTES   : u8 = 2;
+pub const TEXT    : u8 = 3;
+pub const ARRAY   : u8 = 4;
+pub const MAP     : u8 = 5;
+pub const TAG     : u8 = 6;
+pub const SIMPLE  : u8 = 7;
+
+// The complete initial bytes of the simple values and floats, section 3.3
+pub const FALSE  : u8 = 0xf4;
+pub const TRUE   : u8 = 0xf5;
+pub const NULL   : u8 = 0xf6;
+pub const FLOAT16: u8 = 0xf9;
+pub const FLOAT32: u8 = 0xfa;
+pub const FLOAT64: u8 = 0xfb;
+
+// The tags of the bignums, section 3.4.3
+pub const POSITIVE_BIGNUM: u64 = 2;
+pub const NEGATIVE_BIGNUM: u64 = 3;
+
+// The initial byte, followed by at most an eight byte, big endian argument
+pub const MAX_LEN: usize = 9;
+
+// The largest argument that is held within the initial byte itself
+const MAX_INLINE: u64 = 23;
+
+/// The number of bytes required to encode a head with the argument `arg`
+pub const fn encoded_len(arg: u64) -> usize {
+    if arg <= MAX_INLINE {
+        1
+    } else if arg <= u8::MAX as u64 {
+        2
+    } else if arg <= u16::MAX as u64 {
+        3
+    } else if arg <= u32::MAX as u64 {
+        5
+    } else {
+        9
+    }
+}
+
+/// Encode a head of the given `major` type, using the shortest form able to hold `arg`
+pub fn encode(major: u8, arg: u64) -> BufferState<MAX_LEN> {
+    let mut buf = [0u8; MAX_LEN];
+    let n = encode_into(&mut buf, major, arg);
+    BufferState::with_partial_contents(buf, n)
+}
+
+/// Write the shortest head of the given `major` type holding `arg` to the start
+/// of `buf`, returning the number of bytes written.
+pub fn encode_into(buf: &mut [u8], major: u8, arg: u64) -> usize {
+    let n = encoded_len(arg);
+    let major = major << 5;
+    match n {
+        1 => buf[0] = major | arg as u8,
+        2 => { buf[0] = major | 24; buf[1] = arg as u8; }
+        3 => { buf[0] = major | 25; buf[1..3].copy_from_slice(&(arg as u16).to_be_bytes()); }
+        5 => { buf[0] = major | 26; buf[1..5].copy_from_slice(&(arg as u32).to_be_bytes()); }
+        _ => { buf[0] = major | 27; buf[1..9].copy_from_slice(&arg.to_be_bytes()); }
+    }
+    n
+}
+
+/// Interpret `bytes` as an unsigned, big endian integer of at most eight bytes
+#[inline(always)]
+pub fn from_be(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |v, b| (v << 8) | *b as u64)
+}
+
+/// A decoded head
+#[derive(Clone, Copy)]
+pub struct Head {
+    /// The major type, held in the top three bits of the initial byte
+    pub major: u8,
+    /// The initial byte in its entirety
+    pub initial: u8,
+    /// The argument, which for floats is their bit pattern
+    pub arg: u64,
+}
+
+// The number of argument bytes that follow an initial byte with the given
+// additional information, or `None` for the reserved values and the
+// indefinite length marker, neither of which is supported.
+#[inline(always)]
+pub fn arg_len(info: u8) -> Option<usize> {
+    match info {
+        0..=23 => Some(0),
+        24     => Some(1),
+        25     => Some(2),
+        26     => Some(4),
+        27     => Some(8),
+        _      => None,
+    }
+}
+
+// Reads the initial byte on its own, in order to determine how many
+// argument bytes follow it.
+pub struct Decoder(BufferState<MAX_LEN>);
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self(BufferState::with_partial_contents([0u8; MAX_LEN], 1))
+    }
+
+    fn to_head(&self, format: &ThisFormat) -> Result<Head, Error> {
+        let bytes = &self.0.buffer()[..self.0.len()];
+        let initial = bytes[0];
+        let major = initial >> 5;
+        let arg = if bytes.len() > 1 { from_be(&bytes[1..]) } else { (initial & 0x1f) as u64 };
+
+        // The width of a float, unlike that of any other argument, is significant
+        // in its own right, so is checked by the float decoders instead.
+        if format.is_deterministic() && major != SIMPLE && bytes.len() != encoded_len(arg) {
+            Err(ThisFormat::invalid_data_err())
+        } else {
+            Ok(Head { major, initial, arg })
+        }
+    }
+
+    pub fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Head, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self.0.read_remaining(reader, cx) {
+                backend::PollDecodeStatus::Fini(()) => {
+                    if self.0.len() > 1 {
+                        return self.to_head(format).into();
+                    }
+                    match arg_len(self.0.buffer()[0] & 0x1f) {
+                        Some(0) => return self.to_head(format).into(),
+                        Some(n) => self.0.extend_len(n),
+                        None    => return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+                    }
+                }
+                backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(err) => return backend::PollDecodeStatus::Error(err),
+            }
+        }
+    }
+}
+
+// Reads a head of the expected major type, yielding its argument
+pub struct ArgDecoder {
+    major: u8,
+    head: Decoder,
+}
+
+impl ArgDecoder {
+    pub fn new(major: u8) -> Self {
+        Self {
+            major,
+            head: Decoder::new(),
+        }
+    }
+
+    pub fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<u64, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let major = self.major;
+        self.head.read_remaining(format, reader, cx)
+        .and_then(|head|
+            if head.major == major {
+                backend::PollDecodeStatus::Fini(head.arg)
+            } else {
+                backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err())
+            }
+        )
+    }
+
+    /// As [read_remaining](Self::read_remaining), additionally converting the argument to a length
+    pub fn read_len<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<usize, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(format, reader, cx)
+        .and_then(|arg| usize::try_from(arg).map_err(|_| ThisFormat::invalid_data_err()).into())
+    }
+}
@@ -0,0 +1,139 @@
+use core::task::Context;
+use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::head::{self, Head};
+
+type Error = <ThisFormat as Format>::Error;
+
+// The most bytes occupied by the magnitude of a bignum
+const MAX_BIGNUM_LEN: usize = 16;
+
+// A tag, a byte string head (its length is always inline) and the magnitude itself
+pub const MAX_WIDE_LEN: usize = 1 + 1 + MAX_BIGNUM_LEN;
+
+/// An integer as read from either of the integer major types, or a bignum
+///
+/// Negative integers are held as their CBOR argument, i.e. `-1 - value`.
+pub struct Int {
+    negative: bool,
+    magnitude: u128,
+}
+
+impl Int {
+    pub fn from_unsigned(v: u128) -> Self {
+        Self { negative: false, magnitude: v }
+    }
+
+    pub fn from_signed(v: i128) -> Self {
+        if v < 0 {
+            Self { negative: true, magnitude: !(v as u128) }
+        } else {
+            Self { negative: false, magnitude: v as u128 }
+        }
+    }
+
+    pub fn to_unsigned(&self) -> Option<u128> {
+        if self.negative { None } else { Some(self.magnitude) }
+    }
+
+    pub fn to_signed(&self) -> Option<i128> {
+        let v = i128::try_from(self.magnitude).ok()?;
+        if self.negative { Some(-1 - v) } else { Some(v) }
+    }
+}
+
+/// Encode `v` as an integer major type if its magnitude permits, otherwise
+/// as a bignum without any leading zero bytes.
+///
+/// `N` must be at least [MAX_WIDE_LEN] unless `v` is known to fit in 64 bits.
+pub fn encode<const N: usize>(v: &Int) -> BufferState<N> {
+    let mut buf = [0u8; N];
+    let major = if v.negative { head::NEGATIVE } else { head::UNSIGNED };
+
+    let n = match u64::try_from(v.magnitude) {
+        Ok(arg) => head::encode_into(&mut buf, major, arg),
+        Err(_) => {
+            let tag = if v.negative { head::NEGATIVE_BIGNUM } else { head::POSITIVE_BIGNUM };
+            let bytes = v.magnitude.to_be_bytes();
+            let len = MAX_BIGNUM_LEN - v.magnitude.leading_zeros() as usize / 8;
+            head::encode_into(&mut buf[0..], head::TAG, tag);
+            head::encode_into(&mut buf[1..], head::BYTES, len as u64);
+            buf[2..2 + len].copy_from_slice(&bytes[MAX_BIGNUM_LEN - len..]);
+            2 + len
+        }
+    };
+    BufferState::with_partial_contents(buf, n)
+}
+
+// Reads the head of an integer, followed by the magnitude of a bignum if tagged as one
+pub enum Decoder {
+    Head(head::Decoder),
+    BignumHead(bool, head::ArgDecoder),
+    Bignum(bool, BufferState<MAX_BIGNUM_LEN>),
+    Fini,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::Head(head::Decoder::new())
+    }
+
+    fn fini(&mut self, negative: bool, magnitude: u128) -> backend::PollDecodeStatus<Int, Error> {
+        *self = Self::Fini;
+        backend::PollDecodeStatus::Fini(Int { negative, magnitude })
+    }
+
+    fn error(&mut self) -> backend::PollDecodeStatus<Int, Error> {
+        *self = Self::Fini;
+        backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err())
+    }
+
+    pub fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Int, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self {
+                Self::Head(dec) => match dec.read_remaining(format, reader, cx) {
+                    backend::PollDecodeStatus::Fini(Head { major: head::UNSIGNED, arg, .. }) => return self.fini(false, arg as u128),
+                    backend::PollDecodeStatus::Fini(Head { major: head::NEGATIVE, arg, .. }) => return self.fini(true , arg as u128),
+                    backend::PollDecodeStatus::Fini(Head { major: head::TAG, arg: head::POSITIVE_BIGNUM, .. }) => *self = Self::BignumHead(false, head::ArgDecoder::new(head::BYTES)),
+                    backend::PollDecodeStatus::Fini(Head { major: head::TAG, arg: head::NEGATIVE_BIGNUM, .. }) => *self = Self::BignumHead(true , head::ArgDecoder::new(head::BYTES)),
+                    backend::PollDecodeStatus::Fini(_)    => return self.error(),
+                    backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(err) => { *self = Self::Fini; return backend::PollDecodeStatus::Error(err) }
+                },
+                Self::BignumHead(negative, dec) => match dec.read_len(format, reader, cx) {
+                    backend::PollDecodeStatus::Fini(len) if len <= MAX_BIGNUM_LEN => {
+                        let negative = *negative;
+                        *self = Self::Bignum(negative, BufferState::with_partial_contents([0u8; MAX_BIGNUM_LEN], len));
+                        if len == 0 {
+                            return if format.is_deterministic() { self.error() } else { self.fini(negative, 0) };
+                        }
+                    }
+                    backend::PollDecodeStatus::Fini(_)    => return self.error(),
+                    backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(err) => { *self = Self::Fini; return backend::PollDecodeStatus::Error(err) }
+                },
+                Self::Bignum(negative, buf) => match buf.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(()) => {
+                        let negative = *negative;
+                        let bytes = &buf.buffer()[..buf.len()];
+                        let magnitude = bytes.iter().fold(0u128, |v, b| (v << 8) | *b as u128);
+
+                        // Preferred serialization only uses a bignum when an integer major type
+                        // cannot hold the value, and never with leading zero bytes.
+                        return if format.is_deterministic() && (bytes[0] == 0 || magnitude <= u64::MAX as u128) {
+                            self.error()
+                        } else {
+                            self.fini(negative, magnitude)
+                        };
+                    }
+                    backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(err) => { *self = Self::Fini; return backend::PollDecodeStatus::Error(err) }
+                },
+                Self::Fini => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+}
@@ -0,0 +1,468 @@
+macro_rules! fixed_encode_decode_def {
+    () => {
+        pub struct Encoder(BufferState<BUF_SIZE>);
+
+        impl diny::buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(BUF_SIZE);
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(BufferState::with_contents(to_bytes(*data)))
+            }
+        
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                enc.0.start_write(writer, cx)
+                .lift(enc)
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                self.0.write_remaining(writer, cx)
+            }
+        }                
+
+        pub struct Decoder(BufferState<BUF_SIZE>);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(BufferState::init())
+            }
+        
+            fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                match (&mut dec.0).start_read(reader, cx) {
+                    backend::PollDecodeStatus::Fini(())   => from_bytes(*dec.0.buffer()).into(),
+                    backend::PollDecodeStatus::Pending    => backend::StartDecodeStatus::Pending(dec),
+                    backend::PollDecodeStatus::Error(err) => backend::StartDecodeStatus::Error(err),
+                }
+            }
+
+            fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                match (&mut self.0).read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(())   => from_bytes(*self.0.buffer()).into(),
+                    backend::PollDecodeStatus::Pending    => backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(err) => backend::PollDecodeStatus::Error(err),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! int_def {
+    ($t:ty, $bytes:expr, $repr:ty, $from_int:ident, $to_int:ident) => {
+        use core::{convert::TryFrom, task::Context};
+        use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+        use crate::Formatter as ThisFormat;
+        use super::int;
+
+        type Error = <ThisFormat as Format>::Error;
+        type Data = $t;
+
+        pub struct Encoder(BufferState<{ $bytes }>);
+
+        impl diny::buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some($bytes);
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(int::encode(&int::Int::$from_int(<$repr>::from(*data))))
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                enc.0.start_write(writer, cx)
+                .lift(enc)
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                self.0.write_remaining(writer, cx)
+            }
+        }
+
+        #[inline(always)]
+        fn from_int(v: int::Int) -> Result<Data, Error> {
+            v.$to_int()
+            .and_then(|v| Data::try_from(v).ok())
+            .ok_or_else(ThisFormat::invalid_data_err)
+        }
+
+        pub struct Decoder(int::Decoder);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(int::Decoder::new())
+            }
+
+            fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.0.read_remaining(format, reader, cx)
+                .and_then(|v| from_int(v).into())
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.0.read_remaining(format, reader, cx)
+                .and_then(|v| from_int(v).into())
+            }
+        }
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! unsigned_def {
+    ($t:ty, $bytes:expr) => {
+        int_def!($t, $bytes, u128, from_unsigned, to_unsigned);
+    };
+}
+
+macro_rules! signed_def {
+    ($t:ty, $bytes:expr) => {
+        int_def!($t, $bytes, i128, from_signed, to_signed);
+    };
+}
+
+macro_rules! float_def {
+    ($t:ty, $encode:ident) => {
+        use core::task::Context;
+        use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+        use crate::Formatter as ThisFormat;
+        use super::float;
+
+        type Error = <ThisFormat as Format>::Error;
+        type Data = $t;
+
+        // The precision is only chosen once the format, and so whether
+        // encoding must be deterministic, is known.
+        pub enum Encoder {
+            Init(Data),
+            Buf(BufferState<{ float::MAX_LEN }>),
+        }
+
+        fn to_buffer(format: &ThisFormat, v: Data) -> BufferState<{ float::MAX_LEN }> {
+            if format.is_deterministic() {
+                float::encode_shortest(v as f64)
+            } else {
+                float::$encode(v)
+            }
+        }
+
+        impl diny::buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(1 + core::mem::size_of::<Data>());
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder::Init(*data)
+            }
+
+            fn start_encode_buffer<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut buf = to_buffer(format, *data);
+                buf.start_write(writer, cx)
+                .lift(Encoder::Buf(buf))
+            }
+
+            fn poll_encode_buffer<W>(&mut self, format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                loop {
+                    match self {
+                        Self::Init(v) => *self = Self::Buf(to_buffer(format, *v)),
+                        Self::Buf(buf) => return buf.write_remaining(writer, cx),
+                    }
+                }
+            }
+        }
+
+        // Narrowing is only permitted when no precision is lost
+        #[inline(always)]
+        fn from_f64(v: f64) -> Result<Data, Error> {
+            let narrowed = v as Data;
+            if narrowed as f64 == v || v.is_nan() {
+                Ok(narrowed)
+            } else {
+                Err(ThisFormat::invalid_data_err())
+            }
+        }
+
+        pub struct Decoder(float::Decoder);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(float::Decoder::new())
+            }
+
+            fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.0.read_remaining(format, reader, cx)
+                .and_then(|v| from_f64(v).into())
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.0.read_remaining(format, reader, cx)
+                .and_then(|v| from_f64(v).into())
+            }
+        }
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! header_def {
+    ($t: ty, $major: ident) => {
+        header_coders_def!($t, $major);
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! header_coders_def {
+    ($t: ty, $major: ident) => {
+        use core::task::Context;
+        use diny::{backend::{self, Format}, buffer::{self, buffer_state::BufferState}, io};
+        use crate::Formatter as ThisFormat;
+        use super::head;
+
+        pub type Data = $t;
+        pub type Error = <ThisFormat as Format>::Error;
+
+        pub struct Encoder(BufferState<{ head::MAX_LEN }>);
+
+        impl buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(head::MAX_LEN);
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(head::encode(head::$major, **data as u64))
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                enc.0.start_write(writer, cx)
+                .lift(enc)
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                self.0.write_remaining(writer, cx)
+            }
+        }
+
+        pub struct Decoder(head::ArgDecoder);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(head::ArgDecoder::new(head::$major))
+            }
+
+            fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.0.read_len(format, reader, cx)
+                .map(Data::from)
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.0.read_len(format, reader, cx)
+                .map(Data::from)
+            }
+        }
+    };
+}
+
+// A tag is written as the head of a two element array, followed by the tag as an
+// unsigned integer, leaving the payload to be written as the array's second element.
+macro_rules! tag_def {
+    ($t: ty, $repr: ty) => {
+        tag_coders_def!($t, $repr);
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! tag_coders_def {
+    ($t: ty, $repr: ty) => {
+        use core::{convert::TryFrom, task::Context};
+        use diny::{backend::{self, Format}, buffer::{self, buffer_state::BufferState}, io};
+        use crate::Formatter as ThisFormat;
+        use super::head;
+
+        pub type Data = $t;
+        pub type Error = <ThisFormat as Format>::Error;
+
+        const BUF_SIZE: usize = 1 + head::encoded_len(<$repr>::MAX as u64);
+
+        pub struct Encoder(Option<BufferState<BUF_SIZE>>);
+
+        impl buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(BUF_SIZE);
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(
+                    <$repr>::try_from(**data)
+                    .map(|tag| {
+                        let mut buf = [0u8; BUF_SIZE];
+                        head::encode_into(&mut buf, head::ARRAY, 2);
+                        let n = head::encode_into(&mut buf[1..], head::UNSIGNED, tag as u64);
+                        BufferState::with_partial_contents(buf, 1 + n)
+                    })
+                    .ok()
+                )
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                match &mut enc.0 {
+                    Some(buf) => buf.start_write(writer, cx).lift(enc),
+                    None      => backend::StartEncodeStatus::Error(ThisFormat::invalid_data_err()),
+                }
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                match &mut self.0 {
+                    Some(buf) => buf.write_remaining(writer, cx),
+                    None      => backend::PollEncodeStatus::Error(ThisFormat::invalid_data_err()),
+                }
+            }
+        }
+
+        pub enum Decoder {
+            Array(head::ArgDecoder),
+            Tag(head::ArgDecoder),
+            Fini,
+        }
+
+        impl Decoder {
+            fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                loop {
+                    let status = match self {
+                        Self::Array(dec) => match dec.read_remaining(format, reader, cx) {
+                            backend::PollDecodeStatus::Fini(2) => { *self = Self::Tag(head::ArgDecoder::new(head::UNSIGNED)); continue },
+                            backend::PollDecodeStatus::Fini(_) => backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+                            backend::PollDecodeStatus::Pending => return backend::PollDecodeStatus::Pending,
+                            backend::PollDecodeStatus::Error(err) => backend::PollDecodeStatus::Error(err),
+                        },
+                        Self::Tag(dec) => match dec.read_remaining(format, reader, cx) {
+                            backend::PollDecodeStatus::Pending => return backend::PollDecodeStatus::Pending,
+                            status => status.and_then(|tag|
+                                <$repr>::try_from(tag)
+                                .map(|tag| Data::from(tag as usize))
+                                .map_err(|_| ThisFormat::invalid_data_err())
+                                .into()
+                            ),
+                        },
+                        Self::Fini => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+                    };
+                    *self = Self::Fini;
+                    return status;
+                }
+            }
+        }
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self::Array(head::ArgDecoder::new(head::ARRAY))
+            }
+
+            fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.read_remaining(format, reader, cx)
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.read_remaining(format, reader, cx)
+            }
+        }
+    };
+}
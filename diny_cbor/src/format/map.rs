@@ -0,0 +1,141 @@
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+use core::{convert::TryFrom, ops::Range, task::Context};
+use diny::{backend::{self, Encode, Format}, buffer, io};
+use crate::Formatter as ThisFormat;
+use super::head;
+
+type Error = <ThisFormat as Format>::Error;
+
+/// Encodes a map with `E`, which writes its head and entries.
+///
+/// Deterministic encoding orders the entries by the bytewise order of their
+/// encoded keys, so in that case the whole map is encoded up front and its
+/// entries sorted before any of it is written.  Otherwise the entries are
+/// written in the iteration order of the map.
+pub enum Encoder<E> {
+    Init,
+    Direct(E),
+    Sorted(Vec<u8>, buffer::BufferCursor),
+    Fini,
+}
+
+impl<E> Encoder<E>
+where
+    E: Encode<Format=ThisFormat>,
+{
+    fn write_sorted<W>(format: &ThisFormat, writer: &mut W, data: &E::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut bytes = Vec::new();
+        match E::start_encode(format, &mut bytes, data, cx) {
+            backend::StartEncodeStatus::Fini       => {},
+            // Writing to a vector never pends, so neither should a well behaved encoder
+            backend::StartEncodeStatus::Pending(_) => return backend::StartEncodeStatus::Error(ThisFormat::invalid_input_err()),
+            backend::StartEncodeStatus::Error(e)   => return backend::StartEncodeStatus::Error(e),
+        }
+
+        match sort_entries(&bytes) {
+            Some(bytes) => {
+                let mut cur = buffer::BufferCursor::new(&bytes);
+                cur.start_write(writer, &bytes, cx)
+                .lift(Self::Sorted(bytes, cur))
+            }
+            None => backend::StartEncodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+}
+
+impl<E> backend::Encode for Encoder<E>
+where
+    E: Encode<Format=ThisFormat>,
+{
+    type Data = E::Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = E::MAX_ENCODED_LEN;
+
+    fn init(_data: &Self::Data) -> Self {
+        Self::Init
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        if format.is_deterministic() {
+            Self::write_sorted(format, writer, data, cx)
+        } else {
+            E::start_encode(format, writer, data, cx)
+            .map_pending(Self::Direct)
+        }
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Init               => diny::encode_chain!(*self, Self::start_encode(format, writer, data, cx)),
+            Self::Direct(enc)        => diny::encode_poll_fini!(*self, enc.poll_encode(format, writer, data, cx)),
+            Self::Sorted(bytes, cur) => diny::encode_poll_fini!(*self, cur.write_remaining(writer, bytes, cx)),
+            Self::Fini               => backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+}
+
+// The encoding of a map with its entries in the bytewise order of their encoded keys,
+// or `None` if `bytes` does not hold a single, well formed map.
+fn sort_entries(bytes: &[u8]) -> Option<Vec<u8>> {
+    let (major, len, head_len) = head_of(bytes)?;
+    if major != head::MAP {
+        return None;
+    }
+
+    let mut at = head_len;
+    let mut entries: Vec<(Range<usize>, Range<usize>)> = Vec::new();
+    for _ in 0..len {
+        let key = at..at + item_len(bytes.get(at..)?)?;
+        let entry = at..key.end + item_len(bytes.get(key.end..)?)?;
+        at = entry.end;
+        entries.push((key, entry));
+    }
+    if at != bytes.len() {
+        return None;
+    }
+
+    entries.sort_by(|(a, _), (b, _)| bytes[a.clone()].cmp(&bytes[b.clone()]));
+
+    let mut sorted = Vec::with_capacity(bytes.len());
+    sorted.extend_from_slice(&bytes[..head_len]);
+    for (_, entry) in entries {
+        sorted.extend_from_slice(&bytes[entry]);
+    }
+    Some(sorted)
+}
+
+// The major type and argument of the head at the start of `bytes`, along with its length
+fn head_of(bytes: &[u8]) -> Option<(u8, u64, usize)> {
+    let initial = *bytes.first()?;
+    let n = head::arg_len(initial & 0x1f)?;
+    let arg = if n == 0 { (initial & 0x1f) as u64 } else { head::from_be(bytes.get(1..=n)?) };
+    Some((initial >> 5, arg, 1 + n))
+}
+
+// The length of the data item at the start of `bytes`
+fn item_len(bytes: &[u8]) -> Option<usize> {
+    let (major, arg, mut len) = head_of(bytes)?;
+    let nested = match major {
+        head::BYTES | head::TEXT => return len.checked_add(usize::try_from(arg).ok()?),
+        head::ARRAY => arg,
+        head::MAP   => arg.checked_mul(2)?,
+        head::TAG   => 1,
+        // The argument of the remaining types holds the value itself
+        _           => 0,
+    };
+    for _ in 0..nested {
+        len += item_len(bytes.get(len..)?)?;
+    }
+    Some(len)
+}
@@ -0,0 +1,81 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend::{self, Format, internal::StructDef}, buffer::{self, buffer_state::BufferState}, io};
+use crate::Formatter as ThisFormat;
+use super::head;
+
+type Error = <ThisFormat as Format>::Error;
+
+// Structs, tuples and fixed size arrays are all written as an array holding
+// their members, so only the head of that array needs to be written here.
+pub struct Encoder<S>(BufferState<{ head::MAX_LEN }>, PhantomData<fn() -> S>);
+
+impl<S: StructDef> buffer::BufferEncode for Encoder<S> {
+    type Data = ();
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(head::encoded_len(S::FIELD_COUNT as u64));
+
+    fn init_buffer(_data: &Self::Data) -> Self {
+        Self(head::encode(head::ARRAY, S::FIELD_COUNT as u64), PhantomData)
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        enc.0.start_write(writer, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.write_remaining(writer, cx)
+    }
+}
+
+// The array must hold exactly as many members as are expected
+pub struct Decoder<S>(head::ArgDecoder, PhantomData<fn() -> S>);
+
+impl<S: StructDef> Decoder<S> {
+    fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.read_remaining(format, reader, cx)
+        .and_then(|len|
+            if len == S::FIELD_COUNT as u64 {
+                backend::PollDecodeStatus::Fini(())
+            } else {
+                backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err())
+            }
+        )
+    }
+}
+
+impl<S: StructDef> backend::Decode for Decoder<S> {
+    type Data = ();
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self(head::ArgDecoder::new(head::ARRAY), PhantomData)
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.read_remaining(format, reader, cx)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(format, reader, cx)
+    }
+}
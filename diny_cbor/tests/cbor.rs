@@ -0,0 +1,321 @@
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+//! The expected byte vectors in this module follow RFC 8949, and where possible
+//! are taken from the examples of its appendix A.
+
+use core::fmt::Debug;
+use std::collections::{BTreeMap, HashMap};
+use diny::{backend::{DecodeLimits, LimitExceeded}, io, AsyncDeserialize, AsyncSerialization, AsyncSerialize, util::AsyncSliceReader};
+use diny_cbor::Formatter;
+use futures::executor::block_on;
+
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Rec {
+    id: u32,
+    name: String,
+    tag: Option<u8>,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub enum Kind {
+    A,
+    B(i64),
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Skipping {
+    a: u8,
+    #[diny(skip)]
+    b: u8,
+    c: bool,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Empty {}
+
+fn limits() -> DecodeLimits {
+    DecodeLimits::unlimited().with_max_bytes_len(1 << 20)
+}
+
+fn serialize_with<T: AsyncSerialize + ?Sized>(format: &Formatter, send: &T) -> Vec<u8> {
+    let mut tx = Vec::new();
+    block_on(send.serialize(format, &mut tx)).expect("unable to serialize");
+    tx
+}
+
+fn serialize<T: AsyncSerialize + ?Sized>(send: &T) -> Vec<u8> {
+    serialize_with(&diny_cbor::format(), send)
+}
+
+fn deserialize_with<T: AsyncDeserialize>(format: &Formatter, bytes: &[u8]) -> io::Result<T> {
+    let mut rx = AsyncSliceReader::from(bytes);
+    let recv = block_on(T::deserialize(format, &mut rx));
+    if recv.is_ok() {
+        assert_eq!(rx.bytes_read(), bytes.len());
+    }
+    recv
+}
+
+fn deserialize<T: AsyncDeserialize>(bytes: &[u8]) -> io::Result<T> {
+    deserialize_with(&Formatter::with_limits(limits()), bytes)
+}
+
+fn deserialize_deterministic<T: AsyncDeserialize>(bytes: &[u8]) -> io::Result<T> {
+    deserialize_with(&Formatter::with_limits(limits()).deterministic(), bytes)
+}
+
+fn test_encoding_with<T>(format: &Formatter, send: &T, expected: &[u8])
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    let bytes = serialize_with(format, send);
+    assert_eq!(bytes, expected);
+    assert_eq!(&deserialize_with::<T>(format, &bytes).expect("unable to deserialize"), send);
+    diny_test::roundtrip_all_schedules(format, send);
+}
+
+// Everything other than floats and the order of map entries is encoded identically in both modes
+fn test_encoding<T>(send: T, expected: &[u8])
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    test_encoding_with(&diny_cbor::format(), &send, expected);
+    test_encoding_with(&diny_cbor::format().deterministic(), &send, expected);
+}
+
+fn assert_invalid_data<T: Debug>(recv: io::Result<T>) {
+    assert_eq!(recv.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+
+#[test]
+fn unsigned_ints_use_the_shortest_head() {
+    test_encoding(0u8, &[0x00]);
+    test_encoding(23u8, &[0x17]);
+    test_encoding(24u8, &[0x18, 0x18]);
+    test_encoding(100u16, &[0x18, 0x64]);
+    test_encoding(1000u16, &[0x19, 0x03, 0xe8]);
+    test_encoding(1_000_000u32, &[0x1a, 0x00, 0x0f, 0x42, 0x40]);
+    test_encoding(1_000_000_000_000u64, &[0x1b, 0x00, 0x00, 0x00, 0xe8, 0xd4, 0xa5, 0x10, 0x00]);
+    test_encoding(u64::MAX, &[0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+}
+
+#[test]
+fn signed_ints_use_the_shortest_head() {
+    test_encoding(10i8, &[0x0a]);
+    test_encoding(-1i8, &[0x20]);
+    test_encoding(-10i16, &[0x29]);
+    test_encoding(-100i32, &[0x38, 0x63]);
+    test_encoding(-1000i64, &[0x39, 0x03, 0xe7]);
+    test_encoding(i64::MIN, &[0x3b, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+}
+
+#[test]
+fn wide_ints_only_use_bignums_when_necessary() {
+    test_encoding(5u128, &[0x05]);
+    test_encoding(-5i128, &[0x24]);
+    test_encoding(-18_446_744_073_709_551_616i128, &[0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    test_encoding(18_446_744_073_709_551_616u128, &[0xc2, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    test_encoding(-18_446_744_073_709_551_617i128, &[0xc3, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    test_encoding(u128::MAX, &[&[0xc2, 0x50][..], &[0xff; 16]].concat());
+    test_encoding(i128::MIN, &[&[0xc3, 0x50, 0x7f][..], &[0xff; 15]].concat());
+}
+
+#[test]
+fn floats_keep_their_precision_by_default() {
+    let format = diny_cbor::format();
+    test_encoding_with(&format, &1.5f32, &[0xfa, 0x3f, 0xc0, 0x00, 0x00]);
+    test_encoding_with(&format, &1.5f64, &[0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    test_encoding_with(&format, &1.1f64, &[0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]);
+}
+
+#[test]
+fn deterministic_floats_use_the_shortest_exact_encoding() {
+    let format = diny_cbor::format().deterministic();
+    test_encoding_with(&format, &0.0f64, &[0xf9, 0x00, 0x00]);
+    test_encoding_with(&format, &-0.0f32, &[0xf9, 0x80, 0x00]);
+    test_encoding_with(&format, &1.5f32, &[0xf9, 0x3e, 0x00]);
+    test_encoding_with(&format, &65504.0f64, &[0xf9, 0x7b, 0xff]);
+    test_encoding_with(&format, &5.960464477539063e-8f64, &[0xf9, 0x00, 0x01]);
+    test_encoding_with(&format, &-4.0f32, &[0xf9, 0xc4, 0x00]);
+    test_encoding_with(&format, &f64::INFINITY, &[0xf9, 0x7c, 0x00]);
+    test_encoding_with(&format, &100000.0f64, &[0xfa, 0x47, 0xc3, 0x50, 0x00]);
+    test_encoding_with(&format, &f32::MAX, &[0xfa, 0x7f, 0x7f, 0xff, 0xff]);
+    test_encoding_with(&format, &1.1f64, &[0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]);
+
+    assert_eq!(serialize_with(&format, &f32::NAN), [0xf9, 0x7e, 0x00]);
+    assert_eq!(serialize_with(&format, &-f64::NAN), [0xf9, 0x7e, 0x00]);
+    assert!(deserialize_deterministic::<f64>(&[0xf9, 0x7e, 0x00]).unwrap().is_nan());
+}
+
+#[test]
+fn floats_of_any_precision_are_accepted_when_exact() {
+    assert_eq!(deserialize::<f32>(&[0xf9, 0x3e, 0x00]).unwrap(), 1.5);
+    assert_eq!(deserialize::<f64>(&[0xfa, 0x3f, 0xc0, 0x00, 0x00]).unwrap(), 1.5);
+    assert_eq!(deserialize::<f32>(&[0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]).unwrap(), 1.5);
+    assert_invalid_data(deserialize::<f32>(&[0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]));
+}
+
+#[test]
+fn simple_values_are_encoded() {
+    test_encoding(false, &[0xf4]);
+    test_encoding(true, &[0xf5]);
+    test_encoding((), &[0xf6]);
+}
+
+#[test]
+fn chars_and_strings_are_text_encoded() {
+    test_encoding('a', &[0x61, 0x61]);
+    test_encoding('\u{fc}', &[0x62, 0xc3, 0xbc]);
+    test_encoding('€', &[0x63, 0xe2, 0x82, 0xac]);
+    test_encoding('\u{10151}', &[0x64, 0xf0, 0x90, 0x85, 0x91]);
+    test_encoding(String::new(), &[0x60]);
+    test_encoding(String::from("IETF"), &[0x64, b'I', b'E', b'T', b'F']);
+    test_encoding("x".repeat(24), &[&[0x78, 0x18][..], &[b'x'; 24]].concat());
+    test_encoding("x".repeat(256), &[&[0x79, 0x01, 0x00][..], &[b'x'; 256]].concat());
+}
+
+#[test]
+fn byte_slices_are_byte_string_encoded() {
+    assert_eq!(serialize::<[u8]>(&[]), [0x40]);
+    assert_eq!(serialize::<[u8]>(&[1, 2, 3, 4]), [0x44, 0x01, 0x02, 0x03, 0x04]);
+    assert_eq!(serialize::<[u8]>(&[7; 24]), [&[0x58, 0x18][..], &[7; 24]].concat());
+}
+
+#[test]
+fn sequences_and_maps_have_definite_lengths() {
+    test_encoding(Vec::<u8>::new(), &[0x80]);
+    test_encoding(vec![1u8, 2, 3], &[0x83, 0x01, 0x02, 0x03]);
+    test_encoding(vec![0u8; 25], &[&[0x98, 0x19][..], &[0x00; 25]].concat());
+    test_encoding(BTreeMap::<u8, bool>::new(), &[0xa0]);
+    test_encoding(BTreeMap::from([(1u8, 2u8), (3u8, 4u8)]), &[0xa2, 0x01, 0x02, 0x03, 0x04]);
+}
+
+#[test]
+fn deterministic_maps_are_sorted_by_encoded_key() {
+    let format = diny_cbor::format().deterministic();
+
+    // The length of a string is held in its head, so shorter keys sort first
+    let map = BTreeMap::from([(String::from("aa"), 1u8), (String::from("b"), 2)]);
+    assert_eq!(serialize(&map), [0xa2, 0x62, b'a', b'a', 0x01, 0x61, b'b', 0x02]);
+    test_encoding_with(&format, &map, &[0xa2, 0x61, b'b', 0x02, 0x62, b'a', b'a', 0x01]);
+
+    // Negative ints have a higher major type, so sort after every unsigned int
+    let map = BTreeMap::from([(-100i32, ()), (-1, ()), (10, ()), (1000, ())]);
+    assert_eq!(serialize(&map), [0xa4, 0x38, 0x63, 0xf6, 0x20, 0xf6, 0x0a, 0xf6, 0x19, 0x03, 0xe8, 0xf6]);
+    test_encoding_with(&format, &map, &[0xa4, 0x0a, 0xf6, 0x19, 0x03, 0xe8, 0xf6, 0x20, 0xf6, 0x38, 0x63, 0xf6]);
+
+    // Nested maps are sorted in turn
+    let map = BTreeMap::from([(-1i8, map), (1, BTreeMap::new())]);
+    test_encoding_with(&format, &map, &[
+        0xa2,
+        0x01, 0xa0,
+        0x20, 0xa4, 0x0a, 0xf6, 0x19, 0x03, 0xe8, 0xf6, 0x20, 0xf6, 0x38, 0x63, 0xf6,
+    ]);
+
+    // The iteration order of the collection has no bearing on the encoding
+    let keys: Vec<String> = (0..64).map(|i| "k".repeat(i % 5 + 1) + &i.to_string()).collect();
+    let forward: HashMap<String, u8> = keys.iter().map(|k| (k.clone(), 0)).collect();
+    let backward: HashMap<String, u8> = keys.iter().rev().map(|k| (k.clone(), 0)).collect();
+    let sorted = serialize_with(&format, &forward);
+    assert_eq!(serialize_with(&format, &backward), sorted);
+    assert_eq!(deserialize_deterministic::<HashMap<String, u8>>(&sorted).unwrap().len(), keys.len());
+}
+
+#[test]
+fn structs_tuples_and_arrays_are_arrays() {
+    test_encoding(
+        Rec { id: 300, name: String::from("ab"), tag: Some(2), kind: Kind::B(3) },
+        &[0x84, 0x19, 0x01, 0x2c, 0x62, b'a', b'b', 0x82, 0x01, 0x02, 0x82, 0x01, 0x03],
+    );
+    test_encoding(Skipping { a: 1, b: 0, c: true }, &[0x82, 0x01, 0xf5]);
+    test_encoding(Empty {}, &[0xf6]);
+    test_encoding((1u8, String::from("a")), &[0x82, 0x01, 0x61, 0x61]);
+    test_encoding([1u8, 2, 3], &[0x83, 0x01, 0x02, 0x03]);
+    test_encoding([[0u8; 2]; 2], &[0x82, 0x82, 0x00, 0x00, 0x82, 0x00, 0x00]);
+}
+
+#[test]
+fn options_and_enums_are_index_payload_pairs() {
+    test_encoding(None::<u32>, &[0x82, 0x00, 0xf6]);
+    test_encoding(Some(300u32), &[0x82, 0x01, 0x19, 0x01, 0x2c]);
+    test_encoding(Some(None::<u8>), &[0x82, 0x01, 0x82, 0x00, 0xf6]);
+    test_encoding(Kind::A, &[0x82, 0x00, 0xf6]);
+    test_encoding(Kind::B(-2), &[0x82, 0x01, 0x21]);
+    test_encoding(Err::<u8, String>(String::from("e")), &[0x82, 0x01, 0x61, b'e']);
+}
+
+#[test]
+fn accepts_non_shortest_encodings_by_default() {
+    assert_eq!(deserialize::<u8>(&[0x18, 0x05]).unwrap(), 5);
+    assert_eq!(deserialize::<i16>(&[0x39, 0x00, 0x00]).unwrap(), -1);
+    assert_eq!(deserialize::<u16>(&[0x1b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05]).unwrap(), 5);
+    assert_eq!(deserialize::<u64>(&[0xc2, 0x41, 0x05]).unwrap(), 5);
+    assert_eq!(deserialize::<u128>(&[0xc2, 0x40]).unwrap(), 0);
+    assert_eq!(deserialize::<String>(&[0x78, 0x02, b'h', b'i']).unwrap(), "hi");
+    assert_eq!(deserialize::<Vec<u8>>(&[0x99, 0x00, 0x01, 0x05]).unwrap(), vec![5]);
+    assert_eq!(deserialize::<(u8, u8)>(&[0x98, 0x02, 0x01, 0x02]).unwrap(), (1, 2));
+}
+
+#[test]
+fn deterministic_mode_rejects_non_shortest_encodings() {
+    assert_invalid_data(deserialize_deterministic::<u8>(&[0x18, 0x05]));
+    assert_invalid_data(deserialize_deterministic::<i16>(&[0x39, 0x00, 0x00]));
+    assert_invalid_data(deserialize_deterministic::<u64>(&[0xc2, 0x41, 0x05]));
+    assert_invalid_data(deserialize_deterministic::<u128>(&[0xc2, 0x40]));
+    assert_invalid_data(deserialize_deterministic::<u128>(&[0xc2, 0x4a, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]));
+    assert_invalid_data(deserialize_deterministic::<String>(&[0x78, 0x02, b'h', b'i']));
+    assert_invalid_data(deserialize_deterministic::<Vec<u8>>(&[0x99, 0x00, 0x01, 0x05]));
+    assert_invalid_data(deserialize_deterministic::<f32>(&[0xfa, 0x3f, 0xc0, 0x00, 0x00]));
+    assert_invalid_data(deserialize_deterministic::<f64>(&[0xfb, 0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]));
+    assert_invalid_data(deserialize_deterministic::<f32>(&[0xf9, 0x7e, 0x01]));
+}
+
+#[test]
+fn rejects_mismatched_values() {
+    assert_invalid_data(deserialize::<u8>(&[0x19, 0x01, 0x00]));
+    assert_invalid_data(deserialize::<u8>(&[0x20]));
+    assert_invalid_data(deserialize::<i64>(&[0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]));
+    assert_invalid_data(deserialize::<u32>(&[0xf6]));
+    assert_invalid_data(deserialize::<bool>(&[0x01]));
+    assert_invalid_data(deserialize::<()>(&[0xf7]));
+    assert_invalid_data(deserialize::<f64>(&[0x01]));
+    assert_invalid_data(deserialize::<String>(&[0x41, b'a']));
+    assert_invalid_data(deserialize::<String>(&[0x61, 0xff]));
+    assert_invalid_data(deserialize::<char>(&[0x62, b'a', b'b']));
+    assert_invalid_data(deserialize::<char>(&[0x60]));
+    assert_invalid_data(deserialize::<Vec<u8>>(&[0xa1, 0x01, 0x02]));
+    assert_invalid_data(deserialize::<BTreeMap<u8, u8>>(&[0x81, 0x01]));
+    assert_invalid_data(deserialize::<(u8, u8)>(&[0x83, 0x01, 0x02, 0x03]));
+    assert_invalid_data(deserialize::<Option<u8>>(&[0x83, 0x01, 0x02, 0x03]));
+    assert_invalid_data(deserialize::<Option<u8>>(&[0x82, 0x19, 0x01, 0x00, 0x02]));
+    assert_invalid_data(deserialize::<Rec>(&[0x83, 0x19, 0x01, 0x2c, 0x62, b'a', b'b', 0x82, 0x01, 0x02]));
+}
+
+#[test]
+fn rejects_indefinite_lengths() {
+    assert_invalid_data(deserialize::<Vec<u8>>(&[0x9f, 0x01, 0xff]));
+    assert_invalid_data(deserialize::<String>(&[0x7f, 0x61, b'a', 0xff]));
+    assert_invalid_data(deserialize::<BTreeMap<u8, u8>>(&[0xbf, 0x01, 0x02, 0xff]));
+    assert_invalid_data(deserialize::<(u8, u8)>(&[0x9f, 0x01, 0x02, 0xff]));
+}
+
+#[test]
+fn decode_limits_are_enforced() {
+    let err = deserialize::<String>(&[0x7a, 0xff, 0xff, 0xff, 0xff]).unwrap_err();
+    assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::BytesLen));
+}
+
+#[test]
+fn encoded_len_matches_cbor_encoding() {
+    assert_eq!(diny::encoded_len(&diny_cbor::format(), &300u32).unwrap(), 3);
+    assert_eq!(diny::encoded_len(&diny_cbor::format(), &"x".repeat(40)).unwrap(), 42);
+    assert_eq!(diny::max_encoded_len::<Formatter, u64>(), Some(9));
+    assert_eq!(diny::max_encoded_len::<Formatter, f32>(), Some(5));
+    assert_eq!(diny::max_encoded_len::<Formatter, Option<i16>>(), Some(3 + 3));
+    assert_eq!(diny::max_encoded_len::<Formatter, (char, u128)>(), Some(1 + 5 + 18));
+    assert_eq!(diny::max_encoded_len::<Formatter, [u8; 24]>(), Some(2 + 24 * 2));
+}
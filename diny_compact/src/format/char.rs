@@ -0,0 +1,15 @@
+varint_common_def!(char, u32, 3);
+
+#[inline(always)]
+fn to_repr(v: Data) -> Repr {
+    v as Repr
+}
+
+#[inline(always)]
+fn from_repr(v: Repr) -> Option<Data> {
+    core::char::from_u32(v)
+}
+
+varint_encode_decode_def!();
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
@@ -0,0 +1,163 @@
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+use core::fmt::Debug;
+use diny::{io, AsyncDeserialize, AsyncSerialization, AsyncSerialize, util::AsyncSliceReader};
+use futures::executor::block_on;
+
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Rec {
+    id: u64,
+    delta: i32,
+    tags: Vec<u16>,
+    name: String,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub enum Kind {
+    A,
+    B(i64),
+}
+
+fn serialize<T: AsyncSerialize>(send: &T) -> Vec<u8> {
+    let mut tx = Vec::new();
+    block_on(send.serialize(&diny_compact::format(), &mut tx)).expect("unable to serialize");
+    tx
+}
+
+fn deserialize<T: AsyncDeserialize>(bytes: &[u8]) -> io::Result<T> {
+    let mut rx = AsyncSliceReader::from(bytes);
+    let recv = block_on(T::deserialize(&diny_compact::format(), &mut rx));
+    if recv.is_ok() {
+        assert_eq!(rx.bytes_read(), bytes.len());
+    }
+    recv
+}
+
+fn deserialize_bytewise<T: AsyncDeserialize>(bytes: &[u8]) -> io::Result<T> {
+    let mut rx = io::BufReader::with_capacity(1, AsyncSliceReader::from(bytes));
+    block_on(T::deserialize(&diny_compact::format(), &mut rx))
+}
+
+fn test_encoding<T>(send: T, expected: &[u8])
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    let bytes = serialize(&send);
+    assert_eq!(bytes, expected);
+    assert_eq!(deserialize::<T>(&bytes).expect("unable to deserialize"), send);
+    assert_eq!(deserialize_bytewise::<T>(&bytes).expect("unable to deserialize bytewise"), send);
+    diny_test::roundtrip_all_schedules(&diny_compact::format(), &send);
+}
+
+fn test_roundtrip<T>(send: T)
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    let bytes = serialize(&send);
+    assert_eq!(deserialize::<T>(&bytes).expect("unable to deserialize"), send);
+    assert_eq!(deserialize_bytewise::<T>(&bytes).expect("unable to deserialize bytewise"), send);
+    diny_test::roundtrip_all_schedules(&diny_compact::format(), &send);
+}
+
+
+#[test]
+fn unsigned_ints_are_leb128_encoded() {
+    test_encoding(0u64, &[0x00]);
+    test_encoding(127u32, &[0x7f]);
+    test_encoding(128u16, &[0x80, 0x01]);
+    test_encoding(300u32, &[0xac, 0x02]);
+    test_encoding(u16::MAX, &[0xff, 0xff, 0x03]);
+    test_encoding(u32::MAX, &[0xff, 0xff, 0xff, 0xff, 0x0f]);
+    test_encoding(u64::MAX, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+    test_roundtrip(u128::MAX);
+}
+
+#[test]
+fn signed_ints_are_zigzag_encoded() {
+    test_encoding(0i32, &[0x00]);
+    test_encoding(-1i32, &[0x01]);
+    test_encoding(1i64, &[0x02]);
+    test_encoding(-2i16, &[0x03]);
+    test_encoding(i16::MAX, &[0xfe, 0xff, 0x03]);
+    test_encoding(i16::MIN, &[0xff, 0xff, 0x03]);
+    test_encoding(i64::MIN, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+    test_roundtrip(i128::MIN);
+    test_roundtrip(i128::MAX);
+}
+
+#[test]
+fn bytes_are_fixed_width() {
+    test_encoding(200u8, &[200]);
+    test_encoding(-1i8, &[0xff]);
+    test_encoding(true, &[0x01]);
+    test_encoding(1.0f32, &1.0f32.to_le_bytes());
+}
+
+#[test]
+fn lengths_and_indices_are_varints() {
+    test_encoding(vec![1u16, 300], &[0x02, 0x01, 0xac, 0x02]);
+    test_encoding(String::from("hi"), &[0x02, b'h', b'i']);
+    test_encoding(Some(5u8), &[0x01, 0x05]);
+    test_encoding('é', &[0xe9, 0x01]);
+}
+
+#[test]
+fn can_serialize_rec() {
+    test_roundtrip(Rec {
+        id: 1 << 40,
+        delta: -12345,
+        tags: vec![0, 1, 127, 128, u16::MAX],
+        name: String::from("compact"),
+        kind: Kind::B(i64::MIN),
+    });
+    test_roundtrip(Rec {
+        id: 0,
+        delta: 0,
+        tags: vec![],
+        name: String::new(),
+        kind: Kind::A,
+    });
+}
+
+#[test]
+fn rejects_overflowing_varints() {
+    assert_eq!(deserialize::<u16>(&[0xff, 0xff, 0x7f]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<u32>(&[0xff, 0xff, 0xff, 0xff, 0x1f]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<u16>(&[0x80, 0x80, 0x80, 0x00]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<char>(&[0xff, 0xff, 0x7f]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn rejects_truncated_varints() {
+    assert_eq!(deserialize::<u64>(&[0x80]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(deserialize_bytewise::<u64>(&[0x80, 0x80]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn encoded_len_matches_varint_encoding() {
+    assert_eq!(diny::encoded_len(&diny_compact::format(), &300u32).unwrap(), 2);
+    assert_eq!(diny::encoded_len(&diny_compact::format(), &Kind::B(-1)).unwrap(), 2);
+    assert_eq!(diny::max_encoded_len::<diny_compact::Formatter, u64>(), Some(10));
+    assert_eq!(diny::max_encoded_len::<diny_compact::Formatter, Option<u32>>(), Some(5 + 5));
+    assert_eq!(diny::max_encoded_len::<diny_compact::Formatter, Rec>(), None);
+}
+
+#[test]
+fn errors_carry_the_path_and_offset() {
+    let mut bytes = serialize(&Rec { id: 1, delta: 0, tags: vec![1, 2], name: String::new(), kind: Kind::A });
+    assert_eq!(bytes, [0x01, 0x00, 0x02, 0x01, 0x02, 0x00, 0x00]);
+    bytes.splice(4..5, [0xff, 0xff, 0x7f]);
+
+    for err in [deserialize::<Rec>(&bytes).unwrap_err(), deserialize_bytewise::<Rec>(&bytes).unwrap_err()] {
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let context = diny::error::DecodeError::find(&err).expect("no decode context");
+        assert_eq!(context.path().to_string(), "Rec.tags[1]");
+        assert_eq!(context.offset(), Some(7));
+    }
+
+    let err = diny::from_slice::<_, Rec>(&diny_compact::format(), &bytes[..5]).unwrap_err();
+    assert_eq!(err.to_string(), "unexpected end of file at byte 5 in Rec.tags[1]");
+}
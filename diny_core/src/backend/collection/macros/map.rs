@@ -9,8 +9,9 @@ macro_rules! map_collection_def {
                 collection::macros::MapApi,
                 Encode as _,
                 Decode as _,
-                internal::SequenceLen
+                internal::{MapLen, Prefixed, Suffixed},
             },
+            error::PathSegment,
             io
         };
 
@@ -20,6 +21,11 @@ macro_rules! map_collection_def {
         type Len = usize;
         type Idx = usize;
 
+        type EncodeKey<F, K> = Prefixed<<F as backend::FormatEncodeHooks>::EncodeItemSeparator, Prefixed<<F as backend::FormatEncodeHooks>::EncodeMapEntryBegin, <K as backend::Encodable>::Encoder<F>>>;
+        type EncodeValue<F, V> = Suffixed<Prefixed<<F as backend::FormatEncodeHooks>::EncodeItemSeparator, <V as backend::Encodable>::Encoder<F>>, <F as backend::FormatEncodeHooks>::EncodeMapEntryEnd>;
+        type DecodeKey<F, K> = Prefixed<<F as backend::FormatDecodeHooks>::DecodeItemSeparator, Prefixed<<F as backend::FormatDecodeHooks>::DecodeMapEntryBegin, <K as backend::Decodable>::Decoder<F>>>;
+        type DecodeValue<F, V> = Suffixed<Prefixed<<F as backend::FormatDecodeHooks>::DecodeItemSeparator, <V as backend::Decodable>::Decoder<F>>, <F as backend::FormatDecodeHooks>::DecodeMapEntryEnd, V>;
+
         pub enum Encoder<F, K, V $(, $s)?>
         where
             F: backend::FormatEncode,
@@ -28,9 +34,10 @@ macro_rules! map_collection_def {
             $($s: $s_bound $(+ $s_bounds)*,)?
         {
             Init,
-            Len(SequenceLen, <SequenceLen as backend::Encodable>::Encoder<F>),
-            Key(Len, Idx, <K as backend::Encodable>::Encoder<F>),
-            Val(Len, Idx, <V as backend::Encodable>::Encoder<F>),
+            Len(MapLen, <MapLen as backend::Encodable>::Encoder<F>),
+            Key(Len, Idx, EncodeKey<F, K>),
+            Val(Len, Idx, EncodeValue<F, V>),
+            End(F::EncodeCollectionEnd),
             Fini,
             $(#[allow(dead_code)] Phantom(PhantomData<* const $s>))?
         }
@@ -47,8 +54,8 @@ macro_rules! map_collection_def {
             where
                 W: io::AsyncWrite + Unpin,
             {
-                let len: SequenceLen = data.len().into();
-                match <SequenceLen as backend::Encodable>::Encoder::<F>::start_encode(format, writer, &len, cx) {
+                let len: MapLen = data.len().into();
+                match <MapLen as backend::Encodable>::Encoder::<F>::start_encode(format, writer, &len, cx) {
                     backend::StartEncodeStatus::Fini => Self::after_len(format, writer, *len, data, cx),
                     backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Len(len, enc)),
                     backend::StartEncodeStatus::Error(e) => backend::StartEncodeStatus::Error(e),
@@ -71,8 +78,13 @@ macro_rules! map_collection_def {
                 W: io::AsyncWrite + Unpin,
             {
                 for (i, (k, v)) in iter.enumerate() {
-                    match <K as backend::Encodable>::Encoder::<F>::start_encode(format, writer, k, cx) {
-                        backend::StartEncodeStatus::Fini         => match <V as backend::Encodable>::Encoder::<F>::start_encode(format, writer, v, cx) {
+                    let status = if i + idx == 0 {
+                        EncodeKey::<F, K>::start_encode_value(format, writer, k, cx)
+                    } else {
+                        EncodeKey::<F, K>::start_encode(format, writer, k, cx)
+                    };
+                    match status {
+                        backend::StartEncodeStatus::Fini         => match EncodeValue::<F, V>::start_encode(format, writer, v, cx) {
                             backend::StartEncodeStatus::Fini         => continue,
                             backend::StartEncodeStatus::Pending(enc) => return backend::StartEncodeStatus::Pending(Self::Val(len, i + idx, enc)),
                             backend::StartEncodeStatus::Error(e)     => return backend::StartEncodeStatus::Error(e),
@@ -82,7 +94,15 @@ macro_rules! map_collection_def {
                     }
                 }
 
-                backend::StartEncodeStatus::Fini
+                Self::after_items(format, writer, cx)
+            }
+
+            fn after_items<W>(format: &F, writer: &mut W, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                F::EncodeCollectionEnd::start_encode(format, writer, &(), cx)
+                .map_pending(Self::End)
             }
 
             fn items_from_value<'a, W, I>(format: &F, writer: &mut W, len: usize, idx: usize, iter: I, v: &V, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
@@ -92,7 +112,7 @@ macro_rules! map_collection_def {
                 I: Iterator<Item=(&'a K, &'a V)>,
                 W: io::AsyncWrite + Unpin,
             {
-                match <V as backend::Encodable>::Encoder::<F>::start_encode(format, writer, v, cx) {
+                match EncodeValue::<F, V>::start_encode(format, writer, v, cx) {
                     backend::StartEncodeStatus::Fini         => Self::items_from(format, writer, len, idx + 1, iter, cx),
                     backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Val(len, idx, enc)),
                     backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e),
@@ -142,6 +162,7 @@ macro_rules! map_collection_def {
                             None         => backend::PollEncodeStatus::Error(F::invalid_input_err()),
                         }                        
                     }, 
+                    Self::End(enc)           => encode_poll_fini!(*self, enc.poll_encode(format, writer, &(), cx)),
                     _ => backend::PollEncodeStatus::Error(F::invalid_input_err()),
                 }
             }
@@ -153,7 +174,7 @@ macro_rules! map_collection_def {
             V: backend::Encodable,
             $($s: $s_bound $(+ $s_bounds)*,)?
         {
-            type Encoder<F: backend::FormatEncode> = Encoder<F, K, V $(, $s)?>;
+            type Encoder<F: backend::FormatEncode> = <F as backend::FormatEncodeHooks>::EncodeMap<Encoder<F, K, V $(, $s)?>>;
         }
 
         impl<K, V $(, $s)?> backend::AsyncSerialize for Data<K, V $(, $s)?>
@@ -206,9 +227,11 @@ macro_rules! map_collection_def {
             $($s: $s_bound $(+ $s_bounds)*,)?
         {
             Init,
-            Len(<SequenceLen as backend::Decodable>::Decoder<F>),
-            Key(Len, Idx, <K as backend::Decodable>::Decoder<F>),
-            Val(Len, Idx, <V as backend::Decodable>::Decoder<F>),
+            Len(<MapLen as backend::Decodable>::Decoder<F>),
+            Key(Len, Idx, DecodeKey<F, K>),
+            Val(Len, Idx, DecodeValue<F, V>),
+            Next(Idx, F::DecodeCollectionNext),
+            End(F::DecodeCollectionEnd),
             Fini,
             $(#[allow(dead_code)] Phantom(PhantomData<* const $s>))?
         }
@@ -250,60 +273,120 @@ macro_rules! map_collection_def {
             where
                 R: io::AsyncBufRead + Unpin,
             {
-                <SequenceLen as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx)
+                <MapLen as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx)
                 .and_then(
-                    |len| Self::after_len(format, reader, *len, data, cx),
+                    |len| Self::after_len(format, reader, len, data, cx),
                     Self::Len,
                 )
             }
 
-            fn after_len<R>(format: &F, reader: &mut R, len: Len, data: &mut PartialData<K, V $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            fn after_len<R>(format: &F, reader: &mut R, len: MapLen, data: &mut PartialData<K, V $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
             where
                 R: io::AsyncBufRead + Unpin,
             {
-                <Data<K, V $(, $s)?> as MapApi<K, V>>::reserve(&mut data.map, len);
-                Self::items_from(format, reader, len, 0, data, cx)
+                if F::INDEFINITE_LENGTHS && len.is_indefinite() {
+                    return Self::items_from(format, reader, *len, 0, data, cx);
+                }
+                if let Err(kind) = format.decode_limits().check_sequence_len(*len) {
+                    return backend::StartDecodeStatus::Error(F::limit_exceeded_err(kind));
+                }
+                <Data<K, V $(, $s)?> as MapApi<K, V>>::reserve(&mut data.map, backend::limits::prealloc_len::<(K, V)>(*len));
+                Self::items_from(format, reader, *len, 0, data, cx)
             }
 
+            // Each entry of a map of indefinite length is followed by whether there is another,
+            // rather than separated from it
             fn items_from<R>(format: &F, reader: &mut R, len: Len, idx: Idx, data: &mut PartialData<K, V $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
             where
                 R: io::AsyncBufRead + Unpin,
             {
+                let indefinite = F::INDEFINITE_LENGTHS && len == *MapLen::INDEFINITE;
                 for i in idx..len {
-                    match <K as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx) {
-                        backend::StartDecodeStatus::Fini(k) => match <V as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx) {
+                    if indefinite {
+                        if let Err(kind) = format.decode_limits().check_sequence_len(i + 1) {
+                            return backend::StartDecodeStatus::Error(F::limit_exceeded_err(kind));
+                        }
+                    }
+                    let status = if i == 0 || indefinite {
+                        DecodeKey::<F, K>::start_decode_value(format, reader, cx)
+                    } else {
+                        DecodeKey::<F, K>::start_decode(format, reader, cx)
+                    };
+                    match status {
+                        backend::StartDecodeStatus::Fini(k) => match DecodeValue::<F, V>::start_decode(format, reader, cx) {
                             backend::StartDecodeStatus::Fini(v)  => {
                                 <Data<K, V $(, $s)?> as MapApi<K, V>>::append(&mut data.map, k, v);
-                                continue
                             },
                             backend::StartDecodeStatus::Pending(dec) => {
                                 data.key = Some(k);
                                 return backend::StartDecodeStatus::Pending(Self::Val(len, i, dec))
                             }
-                            backend::StartDecodeStatus::Error(e)     => return backend::StartDecodeStatus::Error(e),
+                            backend::StartDecodeStatus::Error(e)     => return backend::StartDecodeStatus::Error(F::nested_err(e, PathSegment::Index(i))),
                         },        
                         backend::StartDecodeStatus::Pending(dec) => return backend::StartDecodeStatus::Pending(Self::Key(len, i, dec)),
-                        backend::StartDecodeStatus::Error(e)     => return backend::StartDecodeStatus::Error(e),
+                        backend::StartDecodeStatus::Error(e)     => return backend::StartDecodeStatus::Error(F::nested_err(e, PathSegment::Index(i))),
+                    }
+                    if indefinite {
+                        match F::DecodeCollectionNext::start_decode(format, reader, cx) {
+                            backend::StartDecodeStatus::Fini(true)   => continue,
+                            backend::StartDecodeStatus::Fini(false)  => break,
+                            backend::StartDecodeStatus::Pending(dec) => return backend::StartDecodeStatus::Pending(Self::Next(i, dec)),
+                            backend::StartDecodeStatus::Error(e)     => return backend::StartDecodeStatus::Error(e),
+                        }
                     }
                 }
         
-                backend::StartDecodeStatus::Fini(())
+                Self::after_items(format, reader, cx)
+            }
+
+            fn after_item<R>(format: &F, reader: &mut R, len: Len, idx: Idx, data: &mut PartialData<K, V $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                if F::INDEFINITE_LENGTHS && len == *MapLen::INDEFINITE {
+                    F::DecodeCollectionNext::start_decode(format, reader, cx)
+                    .and_then(
+                        |more| Self::after_next(format, reader, more, idx, data, cx),
+                        |dec| Self::Next(idx, dec),
+                    )
+                } else {
+                    Self::items_from(format, reader, len, idx + 1, data, cx)
+                }
+            }
+
+            fn after_next<R>(format: &F, reader: &mut R, more: bool, idx: Idx, data: &mut PartialData<K, V $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                if more {
+                    Self::items_from(format, reader, *MapLen::INDEFINITE, idx + 1, data, cx)
+                } else {
+                    Self::after_items(format, reader, cx)
+                }
+            }
+
+            fn after_items<R>(format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                F::DecodeCollectionEnd::start_decode(format, reader, cx)
+                .bimap(|()| (), Self::End)
             }
 
             fn items_from_value<R>(format: &F, reader: &mut R, len: Len, idx: Idx, data: &mut PartialData<K, V $(, $s)?>, k: K, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
             where
                 R: io::AsyncBufRead + Unpin,
             {
-                match <V as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx) {
+                match DecodeValue::<F, V>::start_decode(format, reader, cx) {
                     backend::StartDecodeStatus::Fini(v)      => {
                         <Data<K, V $(, $s)?> as MapApi<K, V>>::append(&mut data.map, k, v);
-                        Self::items_from(format, reader, len, idx + 1, data, cx)
+                        Self::after_item(format, reader, len, idx, data, cx)
                     },
                     backend::StartDecodeStatus::Pending(dec) => {
                         data.key = Some(k);
                         backend::StartDecodeStatus::Pending(Self::Val(len, idx, dec))
                     },
-                    backend::StartDecodeStatus::Error(e)     => backend::StartDecodeStatus::Error(e),
+                    backend::StartDecodeStatus::Error(e)     => backend::StartDecodeStatus::Error(F::nested_err(e, PathSegment::Index(idx))),
                 }
             }
         }
@@ -318,6 +401,25 @@ macro_rules! map_collection_def {
             state: Option<DecodeState<F, K, V $(, $s)?>>,
         }
 
+        impl<F, K, V $(, $s)?> Decoder<F, K, V $(, $s)?>
+        where
+            F: backend::FormatDecode,
+            K: backend::Decodable $(+ $k_bound $(+ $k_bounds)*)?,
+            V: backend::Decodable,
+            $($s: $s_bound $(+ $s_bounds)*,)?
+        {
+            fn start_decode_with<R>(format: &F, reader: &mut R, mut data: PartialData<K, V $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Data<K, V $(, $s)?>, Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                match backend::limits::start_nested(format, || DecodeCursor::after_init(format, reader, &mut data, cx)) {
+                    backend::StartDecodeStatus::Fini(())        => backend::StartDecodeStatus::Fini(data.into_data()),
+                    backend::StartDecodeStatus::Pending(cursor) => backend::StartDecodeStatus::Pending(Self { state: Some(DecodeState { data, cursor }) }),
+                    backend::StartDecodeStatus::Error(e)        => backend::StartDecodeStatus::Error(e),
+                }
+            }
+        }
+
         impl<F, K, V $(, $s)?> backend::Decode for Decoder<F, K, V $(, $s)?>
         where
             F: backend::FormatDecode,
@@ -336,12 +438,21 @@ macro_rules! map_collection_def {
             where
                 R: io::AsyncBufRead + Unpin,
             {
-                let mut data = PartialData::new();
-                match DecodeCursor::after_init(format, reader, &mut data, cx) {
-                    backend::StartDecodeStatus::Fini(())        => backend::StartDecodeStatus::Fini(data.into_data()),
-                    backend::StartDecodeStatus::Pending(cursor) => backend::StartDecodeStatus::Pending(Self { state: Some(DecodeState { data, cursor }) }),
-                    backend::StartDecodeStatus::Error(e)        => backend::StartDecodeStatus::Error(e),
-                }
+                Self::start_decode_with(format, reader, PartialData::new(), cx)
+            }
+
+            // The items are decoded anew, but into the emptied storage of the existing collection
+            fn start_decode_into<R>(format: &F, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut seed = core::mem::replace(data, <Self::Data as MapApi<K, V>>::new());
+                <Self::Data as MapApi<K, V>>::clear(&mut seed);
+                Self::start_decode_with(format, reader, PartialData { map: seed, key: None }, cx)
+                .and_then(
+                    |d| { *data = d; backend::StartDecodeStatus::Fini(()) },
+                    |dec| dec,
+                )
             }
 
             fn poll_decode<R>(&mut self, format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <F as backend::Format>::Error>
@@ -349,22 +460,22 @@ macro_rules! map_collection_def {
                 R: io::AsyncBufRead + Unpin,
             {
                 if let Some(state) = &mut self.state {
-                    match &mut state.cursor {
+                    backend::limits::poll_nested(format, || match &mut state.cursor {
                         DecodeCursor::Init => decode_chain!(state.cursor, DecodeCursor, DecodeCursor::after_init(format, reader, &mut state.data, cx)),
                         DecodeCursor::Len(dec) =>
                             decode_poll_chain!(
                                 state.cursor,
                                 DecodeCursor,
                                 dec.poll_decode(format, reader, cx),
-                                |len: SequenceLen| {
-                                    DecodeCursor::after_len(format, reader, *len, &mut state.data, cx)
+                                |len: MapLen| {
+                                    DecodeCursor::after_len(format, reader, len, &mut state.data, cx)
                                 }
                             ),
                         DecodeCursor::Key(len, idx, dec) =>
                             decode_poll_chain!(
                                 state.cursor,
                                 DecodeCursor,
-                                dec.poll_decode(format, reader, cx),
+                                dec.poll_decode(format, reader, cx).map_err(|e| F::nested_err(e, PathSegment::Index(*idx))),
                                 |k| {
                                     DecodeCursor::items_from_value(format, reader, *len, *idx, &mut state.data, k, cx)
                                 }
@@ -373,7 +484,7 @@ macro_rules! map_collection_def {
                             decode_poll_chain!(
                                 state.cursor,
                                 DecodeCursor,
-                                dec.poll_decode(format, reader, cx),
+                                dec.poll_decode(format, reader, cx).map_err(|e| F::nested_err(e, PathSegment::Index(*idx))),
                                 |v| {
                                     // SAFETY:
                                     // The only way this code gets executured is if the key has already
@@ -382,11 +493,19 @@ macro_rules! map_collection_def {
                                     // is to appease the borrow checker.
                                     let key = state.data.key.take().unwrap();
                                     <Self::Data as MapApi<K, V>>::append(&mut state.data.map, key, v);
-                                    DecodeCursor::items_from(format, reader, *len, *idx + 1, &mut state.data, cx)
+                                    DecodeCursor::after_item(format, reader, *len, *idx, &mut state.data, cx)
                                 }
                             ),
-                        _ => return backend::PollDecodeStatus::Error(F::invalid_input_err()),
-                    }
+                        DecodeCursor::Next(idx, dec) =>
+                            decode_poll_chain!(
+                                state.cursor,
+                                DecodeCursor,
+                                dec.poll_decode(format, reader, cx),
+                                |more| DecodeCursor::after_next(format, reader, more, *idx, &mut state.data, cx)
+                            ),
+                        DecodeCursor::End(dec) => decode_poll_fini!(state.cursor, DecodeCursor, dec.poll_decode(format, reader, cx), |()| ()),
+                        _ => backend::PollDecodeStatus::Error(F::invalid_input_err()),
+                    })
                     // SAFETY:
                     // The only way this code gets executed is if the outer state existed and reached
                     // the DecodeCursor::Fini state as a result of this call.  That cursor state is
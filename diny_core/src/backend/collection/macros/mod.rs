@@ -14,6 +14,7 @@ pub trait MapApi<K, V>
     ;
 
     fn new() -> Self;
+    fn clear(&mut self);
     fn reserve(&mut self, len: usize);
     fn append(&mut self, key: K, value: V);
     fn iter_from(&self, idx: usize) -> Self::Iter<'_>;
@@ -28,6 +29,7 @@ pub trait SeqApi<T>
     ;
 
     fn new() -> Self;
+    fn clear(&mut self);
     fn reserve(&mut self, len: usize);
     fn append(&mut self, value: T);
     fn iter_from(&self, idx: usize) -> Self::Iter<'_>;
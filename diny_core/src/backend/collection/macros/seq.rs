@@ -9,8 +9,9 @@ macro_rules! seq_collection_def {
                 collection::macros::SeqApi,
                 Encode as _,
                 Decode as _,
-                internal::SequenceLen
+                internal::{Prefixed, SequenceLen},
             },
+            error::PathSegment,
             io
         };
 
@@ -20,6 +21,9 @@ macro_rules! seq_collection_def {
         type Len = usize;
         type Idx = usize;
 
+        type EncodeItem<F, T> = Prefixed<<F as backend::FormatEncodeHooks>::EncodeItemSeparator, <T as backend::Encodable>::Encoder<F>>;
+        type DecodeItem<F, T> = Prefixed<<F as backend::FormatDecodeHooks>::DecodeItemSeparator, <T as backend::Decodable>::Decoder<F>>;
+
         pub enum Encoder<F, T $(, $s)?>
         where
             F: backend::FormatEncode,
@@ -28,7 +32,8 @@ macro_rules! seq_collection_def {
         {
             Init,
             Len(SequenceLen, <SequenceLen as backend::Encodable>::Encoder<F>),
-            Cur(Len, Idx, <T as backend::Encodable>::Encoder<F>),
+            Cur(Len, Idx, EncodeItem<F, T>),
+            End(F::EncodeCollectionEnd),
             Fini,
             $(#[allow(dead_code)] Phantom(PhantomData<* const $s>))?
         }
@@ -67,14 +72,27 @@ macro_rules! seq_collection_def {
                 W: io::AsyncWrite + Unpin,
             {
                 for (i, d) in iter.enumerate() {
-                    match <T as backend::Encodable>::Encoder::<F>::start_encode(format, writer, d, cx) {
+                    let status = if i + idx == 0 {
+                        EncodeItem::<F, T>::start_encode_value(format, writer, d, cx)
+                    } else {
+                        EncodeItem::<F, T>::start_encode(format, writer, d, cx)
+                    };
+                    match status {
                         backend::StartEncodeStatus::Fini         => continue,
                         backend::StartEncodeStatus::Pending(enc) => return backend::StartEncodeStatus::Pending(Self::Cur(len, i + idx, enc)),
                         backend::StartEncodeStatus::Error(e)     => return backend::StartEncodeStatus::Error(e),
                     }
                 }
 
-                backend::StartEncodeStatus::Fini
+                Self::after_items(format, writer, cx)
+            }
+
+            fn after_items<W>(format: &F, writer: &mut W, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                F::EncodeCollectionEnd::start_encode(format, writer, &(), cx)
+                .map_pending(Self::End)
             }
         }
 
@@ -112,6 +130,7 @@ macro_rules! seq_collection_def {
                             None    => backend::PollEncodeStatus::Error(F::invalid_input_err()),
                         }                        
                     }, 
+                    Self::End(enc)           => encode_poll_fini!(*self, enc.poll_encode(format, writer, &(), cx)),
                     _ => backend::PollEncodeStatus::Error(F::invalid_input_err()),
                 }
             }
@@ -182,7 +201,9 @@ macro_rules! seq_collection_def {
         {
             Init,
             Len(<SequenceLen as backend::Decodable>::Decoder<F>),
-            Cur(Len, Idx, <T as backend::Decodable>::Decoder<F>),
+            Cur(Len, Idx, DecodeItem<F, T>),
+            Next(Idx, F::DecodeCollectionNext),
+            End(F::DecodeCollectionEnd),
             Fini,
             $(#[allow(dead_code)] Phantom(PhantomData<* const $s>))?
         }
@@ -223,32 +244,93 @@ macro_rules! seq_collection_def {
             {
                 <SequenceLen as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx)
                 .and_then(
-                    |len| Self::after_len(format, reader, *len, data, cx),
+                    |len| Self::after_len(format, reader, len, data, cx),
                     Self::Len,
                 )
             }
 
-            fn after_len<R>(format: &F, reader: &mut R, len: Len, data: &mut PartialData<T $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            fn after_len<R>(format: &F, reader: &mut R, len: SequenceLen, data: &mut PartialData<T $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
             where
                 R: io::AsyncBufRead + Unpin,
             {
-                <Data<T $(, $s)?> as SeqApi<T>>::reserve(data, len);
-                Self::items_from(format, reader, len, 0, data, cx)
+                if F::INDEFINITE_LENGTHS && len.is_indefinite() {
+                    return Self::items_from(format, reader, *len, 0, data, cx);
+                }
+                if let Err(kind) = format.decode_limits().check_sequence_len(*len) {
+                    return backend::StartDecodeStatus::Error(F::limit_exceeded_err(kind));
+                }
+                <Data<T $(, $s)?> as SeqApi<T>>::reserve(data, backend::limits::prealloc_len::<T>(*len));
+                Self::items_from(format, reader, *len, 0, data, cx)
             }
 
+            // Each item of a sequence of indefinite length is followed by whether there is another,
+            // rather than separated from it
             fn items_from<R>(format: &F, reader: &mut R, len: Len, idx: Idx, data: &mut PartialData<T $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
             where
                 R: io::AsyncBufRead + Unpin,
             {
+                let indefinite = F::INDEFINITE_LENGTHS && len == *SequenceLen::INDEFINITE;
                 for i in idx..len {
-                    match <T as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx) {
-                        backend::StartDecodeStatus::Fini(d) => { <Data<T $(, $s)?> as SeqApi<T>>::append(data, d); continue },
+                    if indefinite {
+                        if let Err(kind) = format.decode_limits().check_sequence_len(i + 1) {
+                            return backend::StartDecodeStatus::Error(F::limit_exceeded_err(kind));
+                        }
+                    }
+                    let status = if i == 0 || indefinite {
+                        DecodeItem::<F, T>::start_decode_value(format, reader, cx)
+                    } else {
+                        DecodeItem::<F, T>::start_decode(format, reader, cx)
+                    };
+                    match status {
+                        backend::StartDecodeStatus::Fini(d) => <Data<T $(, $s)?> as SeqApi<T>>::append(data, d),
                         backend::StartDecodeStatus::Pending(dec) => return backend::StartDecodeStatus::Pending(Self::Cur(len, i, dec)),
-                        backend::StartDecodeStatus::Error(e) => return backend::StartDecodeStatus::Error(e),
+                        backend::StartDecodeStatus::Error(e) => return backend::StartDecodeStatus::Error(F::nested_err(e, PathSegment::Index(i))),
+                    }
+                    if indefinite {
+                        match F::DecodeCollectionNext::start_decode(format, reader, cx) {
+                            backend::StartDecodeStatus::Fini(true)   => continue,
+                            backend::StartDecodeStatus::Fini(false)  => break,
+                            backend::StartDecodeStatus::Pending(dec) => return backend::StartDecodeStatus::Pending(Self::Next(i, dec)),
+                            backend::StartDecodeStatus::Error(e)     => return backend::StartDecodeStatus::Error(e),
+                        }
                     }
                 }
         
-                backend::StartDecodeStatus::Fini(())
+                Self::after_items(format, reader, cx)
+            }
+
+            fn after_item<R>(format: &F, reader: &mut R, len: Len, idx: Idx, data: &mut PartialData<T $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                if F::INDEFINITE_LENGTHS && len == *SequenceLen::INDEFINITE {
+                    F::DecodeCollectionNext::start_decode(format, reader, cx)
+                    .and_then(
+                        |more| Self::after_next(format, reader, more, idx, data, cx),
+                        |dec| Self::Next(idx, dec),
+                    )
+                } else {
+                    Self::items_from(format, reader, len, idx + 1, data, cx)
+                }
+            }
+
+            fn after_next<R>(format: &F, reader: &mut R, more: bool, idx: Idx, data: &mut PartialData<T $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                if more {
+                    Self::items_from(format, reader, *SequenceLen::INDEFINITE, idx + 1, data, cx)
+                } else {
+                    Self::after_items(format, reader, cx)
+                }
+            }
+
+            fn after_items<R>(format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                F::DecodeCollectionEnd::start_decode(format, reader, cx)
+                .bimap(|()| (), Self::End)
             }
         }
 
@@ -261,6 +343,24 @@ macro_rules! seq_collection_def {
             state: Option<DecodeState<F, T $(, $s)?>>,
         }
 
+        impl<F, T $(, $s)?> Decoder<F, T $(, $s)?>
+        where
+            F: backend::FormatDecode,
+            T: backend::Decodable $(+ $t_bound $(+ $t_bounds)*)?,
+            $($s: $s_bound $(+ $s_bounds)*,)?
+        {
+            fn start_decode_with<R>(format: &F, reader: &mut R, mut data: PartialData<T $(, $s)?>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Data<T $(, $s)?>, Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                match backend::limits::start_nested(format, || DecodeCursor::after_init(format, reader, &mut data, cx)) {
+                    backend::StartDecodeStatus::Fini(())        => backend::StartDecodeStatus::Fini(data.into_data()),
+                    backend::StartDecodeStatus::Pending(cursor) => backend::StartDecodeStatus::Pending(Self { state: Some(DecodeState { data, cursor }) }),
+                    backend::StartDecodeStatus::Error(e)        => backend::StartDecodeStatus::Error(e),
+                }
+            }
+        }
+
         impl<F, T $(, $s)?> backend::Decode for Decoder<F, T $(, $s)?>
         where
             F: backend::FormatDecode,
@@ -278,12 +378,21 @@ macro_rules! seq_collection_def {
             where
                 R: io::AsyncBufRead + Unpin,
             {
-                let mut data = PartialData::new();
-                match DecodeCursor::after_init(format, reader, &mut data, cx) {
-                    backend::StartDecodeStatus::Fini(())        => backend::StartDecodeStatus::Fini(data.into_data()),
-                    backend::StartDecodeStatus::Pending(cursor) => backend::StartDecodeStatus::Pending(Self { state: Some(DecodeState { data, cursor }) }),
-                    backend::StartDecodeStatus::Error(e)        => backend::StartDecodeStatus::Error(e),
-                }
+                Self::start_decode_with(format, reader, PartialData::new(), cx)
+            }
+
+            // The items are decoded anew, but into the emptied storage of the existing collection
+            fn start_decode_into<R>(format: &F, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut seed = core::mem::replace(data, <Self::Data as SeqApi<T>>::new());
+                <Self::Data as SeqApi<T>>::clear(&mut seed);
+                Self::start_decode_with(format, reader, PartialData(seed), cx)
+                .and_then(
+                    |d| { *data = d; backend::StartDecodeStatus::Fini(()) },
+                    |dec| dec,
+                )
             }
 
             fn poll_decode<R>(&mut self, format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <F as backend::Format>::Error>
@@ -291,7 +400,7 @@ macro_rules! seq_collection_def {
                 R: io::AsyncBufRead + Unpin,
             {
                 if let Some(state) = &mut self.state {
-                    match &mut state.cursor {
+                    backend::limits::poll_nested(format, || match &mut state.cursor {
                         DecodeCursor::Init => decode_chain!(state.cursor, DecodeCursor, DecodeCursor::after_init(format, reader, &mut state.data, cx)),
                         DecodeCursor::Len(dec) =>
                             decode_poll_chain!(
@@ -299,21 +408,29 @@ macro_rules! seq_collection_def {
                                 DecodeCursor,
                                 dec.poll_decode(format, reader, cx),
                                 |len: SequenceLen| {
-                                    DecodeCursor::after_len(format, reader, *len, &mut state.data, cx)
+                                    DecodeCursor::after_len(format, reader, len, &mut state.data, cx)
                                 }
                             ),
                         DecodeCursor::Cur(len, idx, dec) =>
                             decode_poll_chain!(
                                 state.cursor,
                                 DecodeCursor,
-                                dec.poll_decode(format, reader, cx),
+                                dec.poll_decode(format, reader, cx).map_err(|e| F::nested_err(e, PathSegment::Index(*idx))),
                                 |d| {
                                     <Self::Data as SeqApi<T>>::append(&mut state.data, d);
-                                    DecodeCursor::items_from(format, reader, *len, *idx + 1, &mut state.data, cx)
+                                    DecodeCursor::after_item(format, reader, *len, *idx, &mut state.data, cx)
                                 }
                             ),
-                        _ => return backend::PollDecodeStatus::Error(F::invalid_input_err()),
-                    }
+                        DecodeCursor::Next(idx, dec) =>
+                            decode_poll_chain!(
+                                state.cursor,
+                                DecodeCursor,
+                                dec.poll_decode(format, reader, cx),
+                                |more| DecodeCursor::after_next(format, reader, more, *idx, &mut state.data, cx)
+                            ),
+                        DecodeCursor::End(dec) => decode_poll_fini!(state.cursor, DecodeCursor, dec.poll_decode(format, reader, cx), |()| ()),
+                        _ => backend::PollDecodeStatus::Error(F::invalid_input_err()),
+                    })
                     // SAFETY:
                     // The only way this code gets executed is if the outer state existed and reached
                     // the DecodeCursor::Fini state as a result of this call.  That cursor state is
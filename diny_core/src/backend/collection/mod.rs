@@ -17,6 +17,10 @@ mod vec {
             Self::new()
         }
 
+        fn clear(&mut self) {
+            self.clear();
+        }
+
         fn reserve(&mut self, len: usize) {
             self.reserve_exact(len);
         }
@@ -48,6 +52,10 @@ mod vec_deque {
             Self::new()
         }
 
+        fn clear(&mut self) {
+            self.clear();
+        }
+
         fn reserve(&mut self, len: usize) {
             self.reserve_exact(len);
         }
@@ -79,6 +87,10 @@ mod linked_list {
             Self::new()
         }
 
+        fn clear(&mut self) {
+            self.clear();
+        }
+
         fn reserve(&mut self, _len: usize) {
         }
 
@@ -112,6 +124,10 @@ mod binary_heap {
             Self::new()
         }
 
+        fn clear(&mut self) {
+            self.clear();
+        }
+
         fn reserve(&mut self, _len: usize) {
         }
 
@@ -145,6 +161,10 @@ mod btree_set {
             Self::new()
         }
 
+        fn clear(&mut self) {
+            self.clear();
+        }
+
         fn reserve(&mut self, _len: usize) {
         }
 
@@ -182,6 +202,10 @@ mod hash_set {
             Self::with_hasher(S::default())
         }
 
+        fn clear(&mut self) {
+            self.clear();
+        }
+
         fn reserve(&mut self, len: usize) {
             self.reserve(len)
         }
@@ -217,6 +241,10 @@ mod btree_map {
             Self::new()
         }
 
+        fn clear(&mut self) {
+            self.clear();
+        }
+
         fn reserve(&mut self, _len: usize) {
         }
 
@@ -254,6 +282,10 @@ mod hash_map {
             Self::with_hasher(S::default())
         }
 
+        fn clear(&mut self) {
+            self.clear();
+        }
+
         fn reserve(&mut self, len: usize) {
             self.reserve(len)
         }
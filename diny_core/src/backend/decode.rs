@@ -48,6 +48,19 @@ impl<Dta, Dec, Err> StartDecodeStatus<Dta, Dec, Err> {
             Self::Error  (err) => StartDecodeStatus::Error    (err),
         }
     }
+
+    /// Maps any returned error to the error returned by applying `f`
+    #[inline(always)]
+    pub fn map_err<F, E>(self, f: F) -> StartDecodeStatus<Dta, Dec, E>
+    where
+        F: FnOnce(Err) -> E
+    {
+        match self {
+            Self::Fini   (dta) => StartDecodeStatus::Fini   (dta),
+            Self::Pending(dec) => StartDecodeStatus::Pending(dec),
+            Self::Error  (err) => StartDecodeStatus::Error  (f(err)),
+        }
+    }
 }
 
 impl<Dta, Dec, Err> From<Dta> for StartDecodeStatus<Dta, Dec, Err> {
@@ -198,4 +211,36 @@ pub trait Decode: Sized {
     where
         R: io::AsyncBufRead + Unpin,
     ;
+
+    /// Begin decoding into an existing value, reusing any storage it already
+    /// owns (e.g. the capacity of a `Vec` or `String`) in place of allocating anew.
+    ///
+    /// Once finished, `data` holds the decoded value.  While [pending](Poll),
+    /// and after an error, its contents are unspecified.
+    ///
+    /// By default the value is decoded with `start_decode` and then assigned to `data`.
+    ///
+    /// # Implementation
+    /// Implementations must ensure that `start_decode_into` is semantically equivalent to
+    /// calling `init` followed by `poll_decode_into`, other than in which storage is reused.
+    fn start_decode_into<R>(format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> StartDecodeStatus<(), Self, <<Self as Decode>::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        Self::start_decode(format, reader, cx)
+        .and_then(
+            |d| { *data = d; StartDecodeStatus::Fini(()) },
+            |dec| dec,
+        )
+    }
+
+    /// Continue a [pending](Poll) [start_decode_into](Decode::start_decode_into) operation,
+    /// which must be passed the same `data` each time.
+    fn poll_decode_into<R>(&mut self, format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> PollDecodeStatus<(), <<Self as Decode>::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.poll_decode(format, reader, cx)
+        .map(|d| *data = d)
+    }
 }
\ No newline at end of file
@@ -80,6 +80,10 @@ pub trait Encode: Sized {
     /// The concrete data structure to encode.
     type Data: ?Sized;
 
+    /// The largest number of bytes that encoding any value of `Data` can produce,
+    /// or `None` if the encoded length is unbounded (e.g. for collections).
+    const MAX_ENCODED_LEN: Option<usize> = None;
+
     /// Initialize the internal state of the encoder.
     fn init(data: &Self::Data) -> Self;
 
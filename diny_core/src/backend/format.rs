@@ -1,13 +1,13 @@
 use core::future::Future;
-use crate::backend::{Decode, Encode, internal::{VariantIdx, SequenceLen}};
-use crate::io;
+use crate::backend::{Decode, DecodeLimits, Encode, LimitExceeded, internal::{EnumDef, MapLen, Nothing, OptionTag, Rewrapped, SequenceLen, StructDef, Suffixed, VariantIdx}, limits::UNLIMITED};
+use crate::{error::PathSegment, io, schema::FingerprintMismatch};
 
 /// Base trait common to all formatters.
 ///
 /// Provides the minimal required support for handling the
 /// errors encountered during [encoding](Encode) and 
 /// [decoding](Decode) operations.
-pub trait Format {
+pub trait Format: Sized {
     /// The type of errors that can occur during serialization and deserialization
     type Error: From<io::Error>;
 
@@ -16,10 +16,42 @@ pub trait Format {
 
     /// The error to return when a data contract has been violated
     fn invalid_data_err () -> Self::Error;
+
+    /// The error to return when one of the [decode limits](DecodeLimits) has been exceeded
+    fn limit_exceeded_err(kind: LimitExceeded) -> Self::Error {
+        io::error::limit_exceeded(kind).into()
+    }
+
+    /// The error to return when a peer's [schema fingerprint](crate::schema::Schema::fingerprint)
+    /// does not match the local one
+    fn fingerprint_mismatch_err(mismatch: FingerprintMismatch) -> Self::Error {
+        io::error::fingerprint_mismatch(mismatch).into()
+    }
+
+    /// The error to return when decoding failed within the given
+    /// [segment](PathSegment) of the value, such as one of its fields.
+    ///
+    /// Called for each enclosing segment as `err` propagates outwards.  By
+    /// default `err` is returned unchanged, while formats whose errors are
+    /// [io errors](io::Error) may record the path with [within](io::error::within).
+    fn nested_err(err: Self::Error, segment: PathSegment) -> Self::Error {
+        let _ = segment;
+        err
+    }
+
+    /// The error to return when decoding failed after `offset` bytes of the
+    /// value had been consumed.
+    ///
+    /// By default `err` is returned unchanged, while formats whose errors are
+    /// [io errors](io::Error) may record the offset with [at_offset](io::error::at_offset).
+    fn offset_err(err: Self::Error, offset: usize) -> Self::Error {
+        let _ = offset;
+        err
+    }
 }
 
 /// Define the primitive [encoders](Encode) utilized by a [formatter](Format)
-pub trait FormatEncode: Format {
+pub trait FormatEncode: Format + FormatEncodeHooks {
     type EncodeUnit: Encode<Data=()  , Format=Self>;
     type EncodeBool: Encode<Data=bool, Format=Self>;
 
@@ -47,6 +79,87 @@ pub trait FormatEncode: Format {
 
     type EncodeVariantIdx : Encode<Data=VariantIdx , Format=Self>;
     type EncodeSequenceLen: Encode<Data=SequenceLen, Format=Self>;
+
+    /// The most bytes a value that must be buffered in full before it is
+    /// written, such as a [serde](crate::serde) value, may encode to.  A longer
+    /// encoding fails with a [BytesLen](LimitExceeded::BytesLen) limit error.
+    ///
+    /// Defaults to [MAX_BUFFERED_BYTES](crate::backend::limits::MAX_BUFFERED_BYTES).
+    fn max_buffered_len(&self) -> usize {
+        crate::backend::limits::MAX_BUFFERED_BYTES
+    }
+}
+
+/// Define the structural [encoders](Encode) with which a [formatter](Format)
+/// marks the boundaries of values and names their fields and variants.
+///
+/// Formats which encode values purely by position need not implement this
+/// themselves, but may instead take the defaults noted on each hook by
+/// implementing [DefaultHooks].
+pub trait FormatEncodeHooks: Format {
+    /// Written before the members of a derived struct, tuple or fixed size array.
+    ///
+    /// This and the other markers below default to [Nothing].
+    type EncodeStructBegin<S: StructDef>: Encode<Data=(), Format=Self>;
+
+    /// Encodes the field at position `IDX` of a derived struct with `E`, the
+    /// encoder of the field itself.
+    ///
+    /// Defaults to `E`, writing nothing but the field.
+    type EncodeStructField<S: StructDef, const IDX: usize, E: Encode<Format=Self>>: Encode<Data=E::Data, Format=Self>;
+
+    /// Written after the members of a derived struct, tuple or fixed size array.
+    type EncodeStructEnd<S: StructDef>: Encode<Data=(), Format=Self>;
+
+    /// Encodes an entire derived struct with `E`, which writes its members
+    /// along with the begin, field and end markers above.
+    ///
+    /// Defaults to `E`, for formats that need not know the encoded struct in advance.
+    type EncodeStruct<S: StructDef, E: Encode<Format=Self>>: Encode<Data=E::Data, Format=Self>;
+
+    /// Written between the members of a tuple, fixed size array or collection,
+    /// and between the key and value of a map entry.
+    type EncodeItemSeparator: Encode<Data=(), Format=Self>;
+
+    /// Written before the key of each map entry.
+    type EncodeMapEntryBegin: Encode<Data=(), Format=Self>;
+
+    /// Written after the value of each map entry.
+    type EncodeMapEntryEnd: Encode<Data=(), Format=Self>;
+
+    /// Written after the items of a sequence or the entries of a map.
+    type EncodeCollectionEnd: Encode<Data=(), Format=Self>;
+
+    /// Written before the entries of a map.
+    ///
+    /// Defaults to the [sequence length](FormatEncode::EncodeSequenceLen) encoder.
+    type EncodeMapLen: Encode<Data=MapLen, Format=Self>;
+
+    /// Encodes an entire map with `E`, which writes its length and entries
+    /// along with the entry markers above.
+    ///
+    /// Defaults to `E`, writing the entries in the iteration order of the map.
+    type EncodeMap<E: Encode<Format=Self>>: Encode<Data=E::Data, Format=Self>;
+
+    /// Written in place of the [variant index](VariantIdx) of a derived enum or a [Result].
+    ///
+    /// Defaults to the variant index itself, for formats that do not name variants.
+    type EncodeVariant<E: EnumDef>: Encode<Data=VariantIdx, Format=Self>;
+
+    /// Written after the payload of a variant.
+    type EncodeVariantEnd<E: EnumDef>: Encode<Data=(), Format=Self>;
+
+    /// Written in place of the variant index of an [Option].
+    ///
+    /// Defaults to the [variant index](FormatEncode::EncodeVariantIdx) encoder.
+    type EncodeOptionTag: Encode<Data=OptionTag, Format=Self>;
+
+    /// Writes a value already encoded with this format's own encoders, such as
+    /// the scratch buffer of a `diny::serde::Serde` value.
+    ///
+    /// Defaults to the byte slice encoder, so that the value is written as opaque
+    /// bytes.  Formats whose values delimit themselves may write it as it is.
+    type EncodeRaw: Encode<Data=[u8], Format=Self>;
 }
 
 /// Define the primitive serialization methods and the concrete [futures](Future) they return.
@@ -110,7 +223,7 @@ pub trait FormatSerialize: FormatEncode {
 }
 
 /// Define the primitive [decoders](Decode) utilized by a [formatter](Format)
-pub trait FormatDecode: Format {
+pub trait FormatDecode: Format + FormatDecodeHooks {
     type DecodeUnit: Decode<Data=()  , Format=Self>;
     type DecodeBool: Decode<Data=bool, Format=Self>;
 
@@ -129,6 +242,7 @@ pub trait FormatDecode: Format {
     type DecodeF32 : Decode<Data=f32 , Format=Self>;
     type DecodeF64 : Decode<Data=f64 , Format=Self>;
 
+    #[cfg(any(feature = "std", feature = "alloc"))]
     type DecodeByteVec: Decode<Data=Vec<u8>, Format=Self>;
 
     type DecodeChar  : Decode<Data=char  , Format=Self>;
@@ -137,8 +251,107 @@ pub trait FormatDecode: Format {
 
     type DecodeVariantIdx : Decode<Data=VariantIdx , Format=Self>;
     type DecodeSequenceLen: Decode<Data=SequenceLen, Format=Self>;
+
+    /// Whether the sequence and map lengths this format decodes may be
+    /// [indefinite](SequenceLen::INDEFINITE).
+    ///
+    /// Defaults to `false`, so that a hostile length of `usize::MAX` is never
+    /// mistaken for one.
+    const INDEFINITE_LENGTHS: bool = false;
+
+    /// The [limits](DecodeLimits) to enforce while decoding untrusted input.
+    ///
+    /// Defaults to no limits at all.
+    fn decode_limits(&self) -> &DecodeLimits {
+        &UNLIMITED
+    }
+
+    /// Whether the bytes left over once a value has been decoded from the
+    /// front of a [slice](crate::from_slice) may be ignored.
+    ///
+    /// Defaults to permitting no bytes at all.
+    fn accepts_trailing(&self, bytes: &[u8]) -> bool {
+        bytes.is_empty()
+    }
 } 
 
+/// Define the structural [decoders](Decode) with which a [formatter](Format)
+/// reads the markers written by its [encode hooks](FormatEncodeHooks).
+///
+/// Formats which encode values purely by position need not implement this
+/// themselves, but may instead take the defaults noted on each hook by
+/// implementing [DefaultHooks].
+pub trait FormatDecodeHooks: Format {
+    /// Read before the members of a derived struct, tuple or fixed size array.
+    ///
+    /// This and the other markers below default to [Nothing].
+    type DecodeStructBegin<S: StructDef>: Decode<Data=(), Format=Self>;
+
+    /// Decodes the field at position `IDX` of a derived struct with `D`, the
+    /// decoder of the field itself.
+    ///
+    /// Defaults to `D`, reading nothing but the field.
+    type DecodeStructField<S: StructDef, const IDX: usize, D: Decode<Format=Self>>: Decode<Data=D::Data, Format=Self>;
+
+    /// Read after the members of a derived struct, tuple or fixed size array.
+    type DecodeStructEnd<S: StructDef>: Decode<Data=(), Format=Self>;
+
+    /// Decodes an entire derived struct with `D`, which reads its members
+    /// along with the begin, field and end markers above.
+    ///
+    /// Defaults to `D`.
+    type DecodeStruct<S: StructDef, D: Decode<Format=Self>>: Decode<Data=D::Data, Format=Self>;
+
+    /// Read between the members of a tuple, fixed size array or collection of
+    /// known length, and between the key and value of a map entry.
+    type DecodeItemSeparator: Decode<Data=(), Format=Self>;
+
+    /// Read before the key of each map entry.
+    type DecodeMapEntryBegin: Decode<Data=(), Format=Self>;
+
+    /// Read after the value of each map entry.
+    type DecodeMapEntryEnd: Decode<Data=(), Format=Self>;
+
+    /// Read after each item of a collection whose length was decoded as
+    /// [indefinite](SequenceLen::INDEFINITE), reporting whether another follows.
+    type DecodeCollectionNext: Decode<Data=bool, Format=Self>;
+
+    /// Read after the items of a sequence or the entries of a map.
+    type DecodeCollectionEnd: Decode<Data=(), Format=Self>;
+
+    /// Read before the entries of a map.
+    ///
+    /// Defaults to the [sequence length](FormatDecode::DecodeSequenceLen) decoder.
+    type DecodeMapLen: Decode<Data=MapLen, Format=Self>;
+
+    /// Read in place of the [variant index](VariantIdx) of a derived enum or a [Result].
+    ///
+    /// Defaults to the variant index itself, for formats that do not name variants.
+    type DecodeVariant<E: EnumDef>: Decode<Data=VariantIdx, Format=Self>;
+
+    /// Read after the payload of a variant.
+    type DecodeVariantEnd<E: EnumDef>: Decode<Data=(), Format=Self>;
+
+    /// Read in place of the variant index of an [Option].
+    ///
+    /// Defaults to the [variant index](FormatDecode::DecodeVariantIdx) decoder.
+    type DecodeOptionTag: Decode<Data=OptionTag, Format=Self>;
+
+    /// Read after the [variant](FormatDecodeHooks::DecodeVariant) of a derived enum
+    /// that has no payload.
+    ///
+    /// Defaults to a [unit](FormatDecode::DecodeUnit) followed by the
+    /// [end of the variant](FormatDecodeHooks::DecodeVariantEnd), as they are written.
+    type DecodeUnitVariant<E: EnumDef>: Decode<Data=(), Format=Self>;
+
+    /// Reads a value written by [EncodeRaw](FormatEncodeHooks::EncodeRaw), returning
+    /// its encoding.
+    ///
+    /// Defaults to the byte vector decoder.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    type DecodeRaw: Decode<Data=Vec<u8>, Format=Self>;
+}
+
 /// Define the primitive deserialization methods and the concrete [futures](Future) they return.
 pub trait FormatDeserialize: FormatDecode {
     type DeserializeUnit<'r, R>: Future<Output=Result<()  , Self::Error>> + Unpin where Self: 'r, R: 'r + io::AsyncBufRead + Unpin;
@@ -190,4 +403,51 @@ pub trait FormatDeserialize: FormatDecode {
 
     fn deserialize_variant_idx <'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeVariantIdx <'r, R> where R: io::AsyncBufRead + Unpin;
     fn deserialize_sequence_len<'r, R>(&'r self, reader: &'r mut R) -> Self::DeserializeSequenceLen<'r, R> where R: io::AsyncBufRead + Unpin;
-}
\ No newline at end of file
+}
+
+/// Opt a [formatter](Format) into the default [encode](FormatEncodeHooks)
+/// and [decode](FormatDecodeHooks) hooks, which write and read nothing but
+/// the values themselves.
+pub trait DefaultHooks: Format {}
+
+impl<F> FormatEncodeHooks for F
+where
+    F: FormatEncode + DefaultHooks,
+{
+    type EncodeStructBegin<S: StructDef> = Nothing<F>;
+    type EncodeStructField<S: StructDef, const IDX: usize, E: Encode<Format=F>> = E;
+    type EncodeStructEnd<S: StructDef> = Nothing<F>;
+    type EncodeStruct<S: StructDef, E: Encode<Format=F>> = E;
+    type EncodeItemSeparator = Nothing<F>;
+    type EncodeMapEntryBegin = Nothing<F>;
+    type EncodeMapEntryEnd   = Nothing<F>;
+    type EncodeCollectionEnd = Nothing<F>;
+    type EncodeMapLen        = Rewrapped<MapLen, F::EncodeSequenceLen>;
+    type EncodeMap<E: Encode<Format=F>> = E;
+    type EncodeVariant<E: EnumDef> = F::EncodeVariantIdx;
+    type EncodeVariantEnd<E: EnumDef> = Nothing<F>;
+    type EncodeOptionTag = Rewrapped<OptionTag, F::EncodeVariantIdx>;
+    type EncodeRaw = F::EncodeByteSlice;
+}
+
+impl<F> FormatDecodeHooks for F
+where
+    F: FormatDecode + DefaultHooks,
+{
+    type DecodeStructBegin<S: StructDef> = Nothing<F>;
+    type DecodeStructField<S: StructDef, const IDX: usize, D: Decode<Format=F>> = D;
+    type DecodeStructEnd<S: StructDef> = Nothing<F>;
+    type DecodeStruct<S: StructDef, D: Decode<Format=F>> = D;
+    type DecodeItemSeparator  = Nothing<F>;
+    type DecodeMapEntryBegin  = Nothing<F>;
+    type DecodeMapEntryEnd    = Nothing<F>;
+    type DecodeCollectionNext = Nothing<F, bool>;
+    type DecodeCollectionEnd  = Nothing<F>;
+    type DecodeMapLen         = Rewrapped<MapLen, F::DecodeSequenceLen>;
+    type DecodeVariant<E: EnumDef> = F::DecodeVariantIdx;
+    type DecodeVariantEnd<E: EnumDef> = Nothing<F>;
+    type DecodeOptionTag = Rewrapped<OptionTag, F::DecodeVariantIdx>;
+    type DecodeUnitVariant<E: EnumDef> = Suffixed<F::DecodeUnit, Nothing<F>, ()>;
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    type DecodeRaw = F::DecodeByteVec;
+}
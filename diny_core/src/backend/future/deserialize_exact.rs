@@ -1,5 +1,5 @@
 use core::{marker::PhantomData, pin::Pin, task::{Context, Poll}};
-use crate::backend::{Decode, FormatDeserialize};
+use crate::backend::{Decode, FormatDeserialize, limits::LimitReader};
 use crate::io;
 
 
@@ -10,6 +10,7 @@ pub struct DeserializeExact<'r, F, R, Dta, Dec> {
     reader: &'r mut R,
     data: PhantomData<*const Dta>,
     decoder: Dec,
+    consumed: usize,
 }
 
 impl<'r, F, R, Dta, Dec> DeserializeExact<'r, F, R, Dta, Dec> {
@@ -19,6 +20,7 @@ impl<'r, F, R, Dta, Dec> DeserializeExact<'r, F, R, Dta, Dec> {
             reader,
             data: PhantomData,
             decoder,
+            consumed: 0,
         }
     }
 }
@@ -35,6 +37,12 @@ where
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = &mut *self;
-        this.decoder.poll_decode(this.format, this.reader, cx).into()
+        let max = this.format.decode_limits().max_total_bytes();
+        let mut reader = LimitReader::new(this.reader, &mut this.consumed, max);
+        let status = this.decoder.poll_decode(this.format, &mut reader, cx);
+        status
+        .map_err(|e| reader.limit_err::<F>(e))
+        .map_err(|e| F::offset_err(e, this.consumed))
+        .into()
     }
 }
\ No newline at end of file
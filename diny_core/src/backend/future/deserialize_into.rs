@@ -0,0 +1,58 @@
+use core::{pin::Pin, task::{Context, Poll}};
+use crate::backend::{Decode, FormatDecode, PollDecodeStatus, StartDecodeStatus, limits::LimitReader};
+use crate::io;
+
+
+/// Implements a future that [decodes](Decode) into an existing value,
+/// reusing any storage it already owns.
+pub struct DeserializeInto<'r, F, R, Dta, Dec> {
+    format: &'r F,
+    reader: &'r mut R,
+    data: &'r mut Dta,
+    decoder: Option<Dec>,
+    consumed: usize,
+}
+
+impl<'r, F, R, Dta, Dec> DeserializeInto<'r, F, R, Dta, Dec> {
+    pub fn new(format: &'r F, reader: &'r mut R, data: &'r mut Dta) -> Self {
+        Self {
+            format,
+            reader,
+            data,
+            decoder: None,
+            consumed: 0,
+        }
+    }
+}
+
+impl<'r, F, R, Dta, Dec> Unpin for DeserializeInto<'r, F, R, Dta, Dec> {}
+
+impl<'r, F, R, Dta, Dec> core::future::Future for DeserializeInto<'r, F, R, Dta, Dec>
+where
+    F: FormatDecode,
+    R: io::AsyncBufRead + Unpin,
+    Dec: Decode<Format=F, Data=Dta>,
+{
+    type Output = Result<(), F::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let max = this.format.decode_limits().max_total_bytes();
+        let mut reader = LimitReader::new(&mut *this.reader, &mut this.consumed, max);
+        let status = match &mut this.decoder {
+            None => match Dec::start_decode_into(this.format, &mut reader, this.data, cx) {
+                StartDecodeStatus::Fini(())      => PollDecodeStatus::Fini(()),
+                StartDecodeStatus::Pending(dec) => {
+                    this.decoder = Some(dec);
+                    PollDecodeStatus::Pending
+                }
+                StartDecodeStatus::Error(e)      => PollDecodeStatus::Error(e),
+            },
+            Some(dec) => dec.poll_decode_into(this.format, &mut reader, this.data, cx),
+        };
+        status
+        .map_err(|e| reader.limit_err::<F>(e))
+        .map_err(|e| F::offset_err(e, this.consumed))
+        .into()
+    }
+}
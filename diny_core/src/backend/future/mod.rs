@@ -1,2 +1,3 @@
 #[doc(hidden)] pub mod deserialize_exact;
+#[doc(hidden)] pub mod deserialize_into;
 #[doc(hidden)] pub mod serialize_all;
\ No newline at end of file
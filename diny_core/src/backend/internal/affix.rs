@@ -0,0 +1,257 @@
+use core::task::Context;
+use crate::backend::{self, Decode, Encode, Format};
+use crate::io;
+
+/// Encodes or decodes a value preceded by a marker that carries no data,
+/// such as the separator between the members of a tuple or the name of a
+/// struct field.
+pub enum Prefixed<P, T> {
+    Prefix(P),
+    Value(T),
+    Fini,
+}
+
+impl<P, E> Prefixed<P, E>
+where
+    E: Encode,
+    P: Encode<Data=(), Format=E::Format>,
+{
+    /// Start encoding the value without its prefix, as for the first of a
+    /// run of members that are only separated from one another.
+    pub fn start_encode_value<W>(format: &E::Format, writer: &mut W, data: &E::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <E::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        E::start_encode(format, writer, data, cx)
+        .map_pending(Self::Value)
+    }
+}
+
+impl<P, E> Encode for Prefixed<P, E>
+where
+    E: Encode,
+    P: Encode<Data=(), Format=E::Format>,
+{
+    type Format = E::Format;
+    type Data = E::Data;
+
+    const MAX_ENCODED_LEN: Option<usize> = backend::size::sum(P::MAX_ENCODED_LEN, E::MAX_ENCODED_LEN);
+
+    fn init(_data: &Self::Data) -> Self {
+        Self::Prefix(P::init(&()))
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <Self::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match P::start_encode(format, writer, &(), cx) {
+            backend::StartEncodeStatus::Fini         => Self::start_encode_value(format, writer, data, cx),
+            backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Prefix(enc)),
+            backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e),
+        }
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<<Self::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Prefix(enc) => encode_poll_chain!(*self, enc.poll_encode(format, writer, &(), cx), Self::start_encode_value(format, writer, data, cx)),
+            Self::Value(enc)  => encode_poll_fini!(*self, enc.poll_encode(format, writer, data, cx)),
+            Self::Fini        => backend::PollEncodeStatus::Error(<Self::Format as Format>::invalid_input_err()),
+        }
+    }
+}
+
+impl<P, D> Prefixed<P, D>
+where
+    D: Decode,
+    P: Decode<Data=(), Format=D::Format>,
+{
+    /// Start decoding the value without its prefix, as for the first of a
+    /// run of members that are only separated from one another.
+    pub fn start_decode_value<R>(format: &D::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<D::Data, Self, <D::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        D::start_decode(format, reader, cx)
+        .bimap(|d| d, Self::Value)
+    }
+}
+
+impl<P, D> Decode for Prefixed<P, D>
+where
+    D: Decode,
+    P: Decode<Data=(), Format=D::Format>,
+{
+    type Format = D::Format;
+    type Data = D::Data;
+
+    fn init() -> Self {
+        Self::Prefix(P::init())
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        P::start_decode(format, reader, cx)
+        .and_then(
+            |()| Self::start_decode_value(format, reader, cx),
+            Self::Prefix,
+        )
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match self {
+            Self::Prefix(dec) => decode_poll_chain!(*self, Self, dec.poll_decode(format, reader, cx), |()| Self::start_decode_value(format, reader, cx)),
+            Self::Value(dec)  => decode_poll_fini!(*self, Self, dec.poll_decode(format, reader, cx), |d| d),
+            Self::Fini        => backend::PollDecodeStatus::Error(<Self::Format as Format>::invalid_input_err()),
+        }
+    }
+
+    fn start_decode_into<R>(format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        P::start_decode(format, reader, cx)
+        .and_then(
+            |()| D::start_decode_into(format, reader, data, cx).bimap(|()| (), Self::Value),
+            Self::Prefix,
+        )
+    }
+
+    fn poll_decode_into<R>(&mut self, format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match self {
+            Self::Prefix(dec) => decode_poll_chain!(*self, Self, dec.poll_decode(format, reader, cx), |()| D::start_decode_into(format, reader, data, cx).bimap(|()| (), Self::Value)),
+            Self::Value(dec)  => decode_poll_fini!(*self, Self, dec.poll_decode_into(format, reader, data, cx), |()| ()),
+            Self::Fini        => backend::PollDecodeStatus::Error(<Self::Format as Format>::invalid_input_err()),
+        }
+    }
+}
+
+/// Encodes or decodes a value followed by a marker that carries no data,
+/// such as the end of an enum variant.
+pub enum Suffixed<T, S, V = ()> {
+    Value(T),
+    Suffix(Option<V>, S),
+    Fini,
+}
+
+impl<E, S> Suffixed<E, S>
+where
+    E: Encode,
+    S: Encode<Data=(), Format=E::Format>,
+{
+    fn start_encode_suffix<W>(format: &E::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <E::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        S::start_encode(format, writer, &(), cx)
+        .map_pending(|enc| Self::Suffix(None, enc))
+    }
+}
+
+impl<E, S> Encode for Suffixed<E, S>
+where
+    E: Encode,
+    S: Encode<Data=(), Format=E::Format>,
+{
+    type Format = E::Format;
+    type Data = E::Data;
+
+    const MAX_ENCODED_LEN: Option<usize> = backend::size::sum(E::MAX_ENCODED_LEN, S::MAX_ENCODED_LEN);
+
+    fn init(data: &Self::Data) -> Self {
+        Self::Value(E::init(data))
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <Self::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match E::start_encode(format, writer, data, cx) {
+            backend::StartEncodeStatus::Fini         => Self::start_encode_suffix(format, writer, cx),
+            backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Value(enc)),
+            backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e),
+        }
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<<Self::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Value(enc)     => encode_poll_chain!(*self, enc.poll_encode(format, writer, data, cx), Self::start_encode_suffix(format, writer, cx)),
+            Self::Suffix(_, enc) => encode_poll_fini!(*self, enc.poll_encode(format, writer, &(), cx)),
+            Self::Fini           => backend::PollEncodeStatus::Error(<Self::Format as Format>::invalid_input_err()),
+        }
+    }
+}
+
+impl<D, S> Suffixed<D, S, D::Data>
+where
+    D: Decode,
+    S: Decode<Data=(), Format=D::Format>,
+{
+    fn start_decode_suffix<R>(format: &D::Format, reader: &mut R, data: D::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<D::Data, Self, <D::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match S::start_decode(format, reader, cx) {
+            backend::StartDecodeStatus::Fini(())     => backend::StartDecodeStatus::Fini(data),
+            backend::StartDecodeStatus::Pending(dec) => backend::StartDecodeStatus::Pending(Self::Suffix(Some(data), dec)),
+            backend::StartDecodeStatus::Error(e)     => backend::StartDecodeStatus::Error(e),
+        }
+    }
+}
+
+impl<D, S> Decode for Suffixed<D, S, D::Data>
+where
+    D: Decode,
+    S: Decode<Data=(), Format=D::Format>,
+{
+    type Format = D::Format;
+    type Data = D::Data;
+
+    fn init() -> Self {
+        Self::Value(D::init())
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        D::start_decode(format, reader, cx)
+        .and_then(
+            |d| Self::start_decode_suffix(format, reader, d, cx),
+            Self::Value,
+        )
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match self {
+            Self::Value(dec)        => decode_poll_chain!(*self, Self, dec.poll_decode(format, reader, cx), |d| Self::start_decode_suffix(format, reader, d, cx)),
+            Self::Suffix(data, dec) =>
+                match dec.poll_decode(format, reader, cx) {
+                    backend::PollDecodeStatus::Fini(()) => match data.take() {
+                        Some(d) => { *self = Self::Fini; backend::PollDecodeStatus::Fini(d) },
+                        None    => { *self = Self::Fini; backend::PollDecodeStatus::Error(<Self::Format as Format>::invalid_input_err()) },
+                    },
+                    backend::PollDecodeStatus::Pending  => backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => { *self = Self::Fini; backend::PollDecodeStatus::Error(e) },
+                },
+            Self::Fini              => backend::PollDecodeStatus::Error(<Self::Format as Format>::invalid_input_err()),
+        }
+    }
+}
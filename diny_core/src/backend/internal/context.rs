@@ -0,0 +1,115 @@
+use core::{marker::PhantomData, task::Context};
+use crate::backend::{self, internal::{EnumDef, StructDef}, Decode, Format};
+use crate::{error::PathSegment, io};
+
+/// A [path segment](PathSegment) known when the decoder is instantiated.
+pub trait Segment {
+    /// The segment to record when decoding fails within it
+    const SEGMENT: PathSegment;
+}
+
+/// The field at position `POS` of the struct described by `S`
+pub struct FieldOf<S, const POS: usize>(PhantomData<fn() -> S>);
+
+impl<S, const POS: usize> Segment for FieldOf<S, POS>
+where
+    S: StructDef,
+{
+    const SEGMENT: PathSegment = PathSegment::Field {
+        ty: S::NAME,
+        name: match S::FIELD_NAMES {
+            Some(names) => Some(names[POS]),
+            None        => None,
+        },
+        pos: POS,
+    };
+}
+
+/// The payload of the variant with index `IDX` of the enum described by `E`
+pub struct VariantOf<E, const IDX: usize>(PhantomData<fn() -> E>);
+
+impl<E, const IDX: usize> Segment for VariantOf<E, IDX>
+where
+    E: EnumDef,
+{
+    const SEGMENT: PathSegment = PathSegment::Variant {
+        ty: E::NAME,
+        name: variant_name(E::VARIANTS, IDX),
+    };
+}
+
+const fn variant_name(variants: &'static [(usize, &'static str)], idx: usize) -> &'static str {
+    let mut i = 0;
+    while i < variants.len() {
+        if variants[i].0 == idx {
+            return variants[i].1;
+        }
+        i += 1;
+    }
+    ""
+}
+
+/// Decodes with `D`, passing any error through the format's
+/// [nested_err](Format::nested_err) along with the segment `C`.
+pub struct Within<C, D>(D, PhantomData<fn() -> C>);
+
+impl<C, D> Within<C, D>
+where
+    C: Segment,
+    D: Decode,
+{
+    fn nested_err(err: <D::Format as Format>::Error) -> <D::Format as Format>::Error {
+        <D::Format as Format>::nested_err(err, C::SEGMENT)
+    }
+
+    fn new(dec: D) -> Self {
+        Self(dec, PhantomData)
+    }
+}
+
+impl<C, D> Decode for Within<C, D>
+where
+    C: Segment,
+    D: Decode,
+{
+    type Format = D::Format;
+    type Data = D::Data;
+
+    fn init() -> Self {
+        Self::new(D::init())
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        D::start_decode(format, reader, cx)
+        .bimap(|d| d, Self::new)
+        .map_err(Self::nested_err)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.poll_decode(format, reader, cx)
+        .map_err(Self::nested_err)
+    }
+
+    fn start_decode_into<R>(format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        D::start_decode_into(format, reader, data, cx)
+        .bimap(|()| (), Self::new)
+        .map_err(Self::nested_err)
+    }
+
+    fn poll_decode_into<R>(&mut self, format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.poll_decode_into(format, reader, data, cx)
+        .map_err(Self::nested_err)
+    }
+}
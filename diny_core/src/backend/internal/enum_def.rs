@@ -0,0 +1,40 @@
+use crate::backend::internal::VariantIdx;
+
+/// Describes the variants of an enum to the formats that encode them by name.
+///
+/// An implementation is generated alongside the encoders and decoders of
+/// every derived enum, and one is provided for [Result](ResultDef).
+pub trait EnumDef {
+    /// The name of the enum
+    const NAME: &'static str;
+
+    /// The index and name of every variant, in declaration order
+    const VARIANTS: &'static [(usize, &'static str)];
+
+    /// The index of every variant without a payload
+    const UNIT_VARIANTS: &'static [usize];
+
+    /// The name of the variant with the given index, if there is one
+    fn variant_name(idx: VariantIdx) -> Option<&'static str> {
+        Self::VARIANTS.iter().find(|(i, _)| *i == *idx).map(|(_, name)| *name)
+    }
+
+    /// The index of the variant with the given name, if there is one
+    fn variant_idx(name: &str) -> Option<VariantIdx> {
+        Self::VARIANTS.iter().find(|(_, n)| *n == name).map(|(i, _)| (*i).into())
+    }
+
+    /// Whether the variant with the given index has no payload
+    fn is_unit(idx: VariantIdx) -> bool {
+        Self::UNIT_VARIANTS.contains(&*idx)
+    }
+}
+
+/// Describes the `Ok` and `Err` variants of a [Result]
+pub struct ResultDef;
+
+impl EnumDef for ResultDef {
+    const NAME: &'static str = "Result";
+    const VARIANTS: &'static [(usize, &'static str)] = &[(0, "Ok"), (1, "Err")];
+    const UNIT_VARIANTS: &'static [usize] = &[];
+}
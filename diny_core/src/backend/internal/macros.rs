@@ -1,14 +1,11 @@
-macro_rules! usize_wrapper_def {
-    ($t:ident, $ser_fn:ident, $ser_enc: ident, $ser_fut: ident, $deser_fn: ident, $deser_dec: ident, $deser_fut: ident) => {
-        use crate::backend::{Decodable, AsyncDeserialize, Encodable, FormatDecode, FormatDeserialize, FormatEncode, FormatSerialize, AsyncSerialize};
-        use crate::io;
-
+macro_rules! usize_wrapper_struct_def {
+    ($t:ident) => {
         #[repr(transparent)]
         #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
         pub struct $t(usize);
         
         impl $t {
-            pub fn new(v: usize) -> Self {
+            pub const fn new(v: usize) -> Self {
                 Self(v)
             }
         }
@@ -38,7 +35,16 @@ macro_rules! usize_wrapper_def {
                 &mut self.0
             }
         }
-        
+    };
+}
+
+macro_rules! usize_wrapper_def {
+    ($t:ident, $ser_fn:ident, $ser_enc: ident, $ser_fut: ident, $deser_fn: ident, $deser_dec: ident, $deser_fut: ident) => {
+        use crate::backend::{Decodable, AsyncDeserialize, Encodable, FormatDecode, FormatDeserialize, FormatEncode, FormatSerialize, AsyncSerialize};
+        use crate::io;
+
+        usize_wrapper_struct_def!($t);
+
         impl Encodable for $t {
             type Encoder<F>
             where
@@ -85,4 +91,58 @@ macro_rules! usize_wrapper_def {
             }
         }
     };
-}
\ No newline at end of file
+
+    // A wrapper whose coders are format hooks, serialized with the generic futures
+    ($t:ident, $ser_enc: ident, $deser_dec: ident) => {
+        use crate::backend::{self, Decodable, AsyncDeserialize, Encodable, FormatDecode, FormatDeserialize, FormatEncode, FormatSerialize, AsyncSerialize};
+        use crate::io;
+
+        usize_wrapper_struct_def!($t);
+
+        impl Encodable for $t {
+            type Encoder<F>
+            where
+                F: FormatEncode,
+            = F::$ser_enc;
+        }
+
+        impl AsyncSerialize for $t {
+            type Future<'w, F, W>
+            where
+                F: 'w + FormatSerialize,
+                W: 'w + io::AsyncWrite + Unpin,
+            = backend::SerializeAll<'w, F, W, Self, Self::Encoder<F>>;
+
+            fn serialize<'w, F, W>(&'w self, format: &'w F, writer: &'w mut W) -> Self::Future<'w, F, W>
+            where
+                F: FormatSerialize,
+                W: io::AsyncWrite + Unpin,
+            {
+                backend::SerializeAll::new(format, writer, self, <Self::Encoder::<F> as backend::Encode>::init(self))
+            }
+        }
+
+        impl Decodable for $t {
+            type Decoder<F>
+            where
+                F: FormatDecode,
+            = F::$deser_dec;
+        }
+
+        impl AsyncDeserialize for $t {
+            type Future<'r, F, R>
+            where
+                F: 'r + FormatDeserialize,
+                R: 'r + io::AsyncBufRead + Unpin,
+            = backend::DeserializeExact<'r, F, R, Self, Self::Decoder<F>>;
+
+            fn deserialize<'r, F, R>(format: &'r F, reader: &'r mut R) -> Self::Future<'r, F, R>
+            where
+                F: FormatDeserialize,
+                R: io::AsyncBufRead + Unpin,
+            {
+                backend::DeserializeExact::new(format, reader, <Self::Decoder::<F> as backend::Decode>::init())
+            }
+        }
+    };
+}
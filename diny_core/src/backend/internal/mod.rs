@@ -2,7 +2,51 @@
 mod macros;
 
 #[doc(hidden)] pub mod variant_idx  { usize_wrapper_def!(VariantIdx , serialize_variant_idx , EncodeVariantIdx , SerializeVariantIdx , deserialize_variant_idx , DecodeVariantIdx , DeserializeVariantIdx ); }
+#[doc(hidden)] pub mod option_tag   { usize_wrapper_def!(OptionTag  , EncodeOptionTag  , DecodeOptionTag  ); }
 #[doc(hidden)] pub mod sequence_len { usize_wrapper_def!(SequenceLen, serialize_sequence_len, EncodeSequenceLen, SerializeSequenceLen, deserialize_sequence_len, DecodeSequenceLen, DeserializeSequenceLen); }
+#[doc(hidden)] pub mod map_len      { usize_wrapper_def!(MapLen     , EncodeMapLen     , DecodeMapLen     ); }
+
+#[doc(hidden)] pub mod struct_def;
+#[doc(hidden)] pub mod enum_def;
+#[doc(hidden)] pub mod affix;
+#[doc(hidden)] pub mod versioned;
+#[doc(hidden)] pub mod context;
+#[doc(hidden)] pub mod rewrap;
 
 #[doc(inline)] pub use variant_idx::VariantIdx;
-#[doc(inline)] pub use sequence_len::SequenceLen;
\ No newline at end of file
+#[doc(inline)] pub use option_tag::OptionTag;
+#[doc(inline)] pub use sequence_len::SequenceLen;
+#[doc(inline)] pub use map_len::MapLen;
+#[doc(inline)] pub use struct_def::{Arity, Nothing, StructDef};
+#[doc(inline)] pub use enum_def::{EnumDef, ResultDef};
+#[doc(inline)] pub use affix::{Prefixed, Suffixed};
+#[doc(inline)] pub use context::{FieldOf, Segment, VariantOf, Within};
+#[doc(inline)] pub use rewrap::Rewrapped;
+
+impl SequenceLen {
+    /// The length decoded by formats that only discover the end of a sequence
+    /// by reading it, see [CollectionNext](crate::backend::FormatDecodeHooks::DecodeCollectionNext).
+    ///
+    /// At least one item follows an indefinite length, and it is only treated as
+    /// such by formats that declare [INDEFINITE_LENGTHS](crate::backend::FormatDecode::INDEFINITE_LENGTHS).
+    pub const INDEFINITE: Self = Self::new(usize::MAX);
+
+    /// Whether the length is only discovered while decoding
+    pub fn is_indefinite(&self) -> bool {
+        *self == Self::INDEFINITE
+    }
+}
+
+impl MapLen {
+    /// The length decoded by formats that only discover the end of a map
+    /// by reading it, see [CollectionNext](crate::backend::FormatDecodeHooks::DecodeCollectionNext).
+    ///
+    /// At least one entry follows an indefinite length, and it is only treated as
+    /// such by formats that declare [INDEFINITE_LENGTHS](crate::backend::FormatDecode::INDEFINITE_LENGTHS).
+    pub const INDEFINITE: Self = Self::new(usize::MAX);
+
+    /// Whether the length is only discovered while decoding
+    pub fn is_indefinite(&self) -> bool {
+        *self == Self::INDEFINITE
+    }
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use core::{marker::PhantomData, task::Context};
+use crate::backend::{self, Decode, Encode, Format};
+use crate::io;
+
+/// Encodes and decodes the `usize` wrapper `T` with `C`, the coder of another
+/// such wrapper.
+///
+/// Used by the default [hooks](crate::backend::DefaultHooks) to write an
+/// [OptionTag](super::OptionTag) as a [VariantIdx](super::VariantIdx), so
+/// that an [Option] is encoded like any other enum with two variants, and a
+/// [MapLen](super::MapLen) as a [SequenceLen](super::SequenceLen).
+pub struct Rewrapped<T, C>(C, PhantomData<fn() -> T>);
+
+fn rewrap<A, B>(a: A) -> B
+where
+    A: Into<usize>,
+    B: From<usize>,
+{
+    B::from(a.into())
+}
+
+impl<T, C> Encode for Rewrapped<T, C>
+where
+    T: Copy + Into<usize>,
+    C: Encode,
+    C::Data: Sized + From<usize>,
+{
+    type Format = C::Format;
+    type Data = T;
+
+    const MAX_ENCODED_LEN: Option<usize> = C::MAX_ENCODED_LEN;
+
+    fn init(data: &Self::Data) -> Self {
+        Self(C::init(&rewrap(*data)), PhantomData)
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <Self::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        C::start_encode(format, writer, &rewrap(*data), cx)
+        .map_pending(|enc| Self(enc, PhantomData))
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<<Self::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.poll_encode(format, writer, &rewrap(*data), cx)
+    }
+}
+
+impl<T, C> Decode for Rewrapped<T, C>
+where
+    T: From<usize>,
+    C: Decode,
+    C::Data: Into<usize>,
+{
+    type Format = C::Format;
+    type Data = T;
+
+    fn init() -> Self {
+        Self(C::init(), PhantomData)
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        C::start_decode(format, reader, cx)
+        .bimap(rewrap, |dec| Self(dec, PhantomData))
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.poll_decode(format, reader, cx)
+        .map(rewrap)
+    }
+}
@@ -0,0 +1,99 @@
+use core::{marker::PhantomData, task::Context};
+use crate::backend::{self, Decode, Encode, FormatDecode, FormatEncode};
+use crate::io;
+
+/// Describes the shape of a struct, tuple or fixed size array to the formats
+/// that encode it.
+///
+/// An implementation is generated alongside the encoders and decoders of
+/// every derived struct that has at least one encoded field.
+pub trait StructDef {
+    /// The name of the struct, or `None` for tuples and fixed size arrays
+    const NAME: Option<&'static str>;
+
+    /// The number of fields that are encoded, excluding any that are skipped
+    const FIELD_COUNT: usize;
+
+    /// The names of the encoded fields in order, or `None` when the fields
+    /// are positional, as with tuple structs, tuples and arrays
+    const FIELD_NAMES: Option<&'static [&'static str]>;
+
+    /// The numeric tags of the encoded fields in order, or `None` for tuples
+    /// and arrays.
+    ///
+    /// Each tag is taken from the field's `#[diny(tag = N)]` attribute, or is
+    /// otherwise one more than that of the preceding field, starting from one.
+    /// Tags increase strictly from one field to the next.
+    const FIELD_TAGS: Option<&'static [u32]>;
+}
+
+/// Describes a tuple or fixed size array of `N` members
+pub struct Arity<const N: usize>;
+
+impl<const N: usize> StructDef for Arity<N> {
+    const NAME: Option<&'static str> = None;
+    const FIELD_COUNT: usize = N;
+    const FIELD_NAMES: Option<&'static [&'static str]> = None;
+    const FIELD_TAGS: Option<&'static [u32]> = None;
+}
+
+/// Encodes and decodes nothing at all, decoding the default value of `D`.
+///
+/// Used by formats that have no need to mark the boundaries of a struct,
+/// name its fields or delimit the members of a collection.
+pub struct Nothing<F, D = ()>(PhantomData<fn() -> (F, D)>);
+
+impl<F, D> Encode for Nothing<F, D>
+where
+    F: FormatEncode,
+{
+    type Format = F;
+    type Data = D;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(0);
+
+    fn init(_data: &Self::Data) -> Self {
+        Self(PhantomData)
+    }
+
+    fn start_encode<W>(_format: &F, _writer: &mut W, _data: &Self::Data, _cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        backend::StartEncodeStatus::Fini
+    }
+
+    fn poll_encode<W>(&mut self, _format: &F, _writer: &mut W, _data: &Self::Data, _cx: &mut Context<'_>) -> backend::PollEncodeStatus<<F as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        backend::PollEncodeStatus::Fini
+    }
+}
+
+impl<F, D> Decode for Nothing<F, D>
+where
+    F: FormatDecode,
+    D: Default,
+{
+    type Format = F;
+    type Data = D;
+
+    fn init() -> Self {
+        Self(PhantomData)
+    }
+
+    fn start_decode<R>(_format: &F, _reader: &mut R, _cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, <F as backend::Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        backend::StartDecodeStatus::Fini(D::default())
+    }
+
+    fn poll_decode<R>(&mut self, _format: &F, _reader: &mut R, _cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <F as backend::Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        backend::PollDecodeStatus::Fini(D::default())
+    }
+}
@@ -0,0 +1,328 @@
+use core::{cmp::min, pin::Pin, task::{Context, Poll}};
+use crate::backend::{self, internal::SequenceLen, Decodable, Decode, Encodable, Encode, Format};
+use crate::{framing::FrameReader, io};
+
+/// Encodes a value preceded by the length of its encoding, as used by
+/// structs marked `#[diny(versioned)]`.
+///
+/// The length is written with the format's [sequence length](crate::backend::FormatEncode::EncodeSequenceLen)
+/// encoding, which permits a reader to skip any trailing bytes it does not understand.
+///
+/// The value is measured before it is encoded, and while an enclosing versioned
+/// value is itself being [measured](backend::size::measuring), the value stands
+/// in for its encoding with as many padding bytes, so that each level of nesting
+/// adds a single measurement rather than doubling the work of those beneath it.
+pub enum Encoder<E>
+where
+    E: Encode,
+{
+    Init,
+    Len(SequenceLen, <SequenceLen as Encodable>::Encoder<E::Format>),
+    Body(E),
+    Pad(usize),
+    Fini,
+}
+
+const PADDING: [u8; 64] = [0; 64];
+
+fn poll_pad<W>(writer: &mut W, remaining: &mut usize, cx: &mut Context<'_>) -> Poll<io::Result<()>>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    while *remaining > 0 {
+        match futures::ready!(Pin::new(&mut *writer).poll_write(cx, &PADDING[..min(PADDING.len(), *remaining)])) {
+            Ok(0)  => return Poll::Ready(Err(io::error::write_zero())),
+            Ok(n)  => *remaining -= n,
+            Err(e) => return Poll::Ready(Err(e)),
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+impl<E> Encoder<E>
+where
+    E: Encode,
+{
+    fn start_encode_body<W>(format: &E::Format, writer: &mut W, data: &E::Data, len: SequenceLen, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <E::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        if backend::size::measuring(cx) {
+            return Self::start_pad(writer, usize::from(len), cx);
+        }
+
+        E::start_encode(format, writer, data, cx)
+        .map_pending(Self::Body)
+    }
+
+    fn start_pad<W>(writer: &mut W, mut remaining: usize, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <E::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match poll_pad(writer, &mut remaining, cx) {
+            Poll::Pending       => backend::StartEncodeStatus::Pending(Self::Pad(remaining)),
+            Poll::Ready(Ok(())) => backend::StartEncodeStatus::Fini,
+            Poll::Ready(Err(e)) => backend::StartEncodeStatus::Error(e.into()),
+        }
+    }
+}
+
+impl<E> Encode for Encoder<E>
+where
+    E: Encode,
+{
+    type Format = E::Format;
+    type Data = E::Data;
+
+    const MAX_ENCODED_LEN: Option<usize> = backend::size::sum(
+        <<SequenceLen as Encodable>::Encoder<E::Format> as Encode>::MAX_ENCODED_LEN,
+        E::MAX_ENCODED_LEN,
+    );
+
+    fn init(_data: &Self::Data) -> Self {
+        Self::Init
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <Self::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let len = match backend::size::encoder_len::<E>(format, data) {
+            Ok(len) => SequenceLen::new(len),
+            Err(e)  => return backend::StartEncodeStatus::Error(e),
+        };
+
+        match <<SequenceLen as Encodable>::Encoder<E::Format> as Encode>::start_encode(format, writer, &len, cx) {
+            backend::StartEncodeStatus::Fini         => Self::start_encode_body(format, writer, data, len, cx),
+            backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Len(len, enc)),
+            backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e),
+        }
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<<Self::Format as Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Init          => encode_chain!(*self, Self::start_encode(format, writer, data, cx)),
+            Self::Len(len, enc) => {
+                let len = *len;
+                encode_poll_chain!(*self, enc.poll_encode(format, writer, &len, cx), Self::start_encode_body(format, writer, data, len, cx))
+            }
+            Self::Body(enc)     => encode_poll_fini!(*self, enc.poll_encode(format, writer, data, cx)),
+            Self::Pad(remaining) => match poll_pad(writer, remaining, cx) {
+                Poll::Pending       => backend::PollEncodeStatus::Pending,
+                Poll::Ready(Ok(())) => { *self = Self::Fini; backend::PollEncodeStatus::Fini }
+                Poll::Ready(Err(e)) => { *self = Self::Fini; backend::PollEncodeStatus::Error(e.into()) }
+            },
+            Self::Fini          => backend::PollEncodeStatus::Error(<Self::Format as Format>::invalid_input_err()),
+        }
+    }
+}
+
+/// Decodes a value preceded by the length of its encoding, as used by
+/// structs marked `#[diny(versioned)]`.
+///
+/// The value is decoded from a reader limited to that length, so that
+/// [trailing fields](Trailing) missing from an older encoding are observed
+/// at its end, and any bytes left over once the value has been decoded
+/// (e.g. fields added by a newer encoding) are skipped.
+pub enum Decoder<D>
+where
+    D: Decode,
+{
+    Init,
+    Len(<SequenceLen as Decodable>::Decoder<D::Format>),
+    Body(D, usize),
+    Skip(Option<D::Data>, usize),
+    Fini,
+}
+
+fn poll_skip<R>(reader: &mut R, remaining: &mut usize, cx: &mut Context<'_>) -> Poll<io::Result<()>>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    while *remaining > 0 {
+        match futures::ready!(Pin::new(&mut *reader).poll_fill_buf(cx)) {
+            Ok([])   => return Poll::Ready(Err(io::error::unexpected_eof())),
+            Ok(buf)  => {
+                let n = min(buf.len(), *remaining);
+                Pin::new(&mut *reader).consume(n);
+                *remaining -= n;
+            }
+            Err(e)   => return Poll::Ready(Err(e)),
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+impl<D> Decoder<D>
+where
+    D: Decode,
+{
+    fn start_skip<R>(reader: &mut R, data: Option<D::Data>, mut remaining: usize, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Option<D::Data>, Self, <D::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match poll_skip(reader, &mut remaining, cx) {
+            Poll::Pending          => backend::StartDecodeStatus::Pending(Self::Skip(data, remaining)),
+            Poll::Ready(Ok(()))    => backend::StartDecodeStatus::Fini(data),
+            Poll::Ready(Err(e))    => backend::StartDecodeStatus::Error(e.into()),
+        }
+    }
+
+    fn start_decode_body<R>(format: &D::Format, reader: &mut R, len: SequenceLen, cx: &mut Context<'_>) -> backend::StartDecodeStatus<D::Data, Self, <D::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut remaining = usize::from(len);
+        let status = D::start_decode(format, &mut FrameReader::new(reader, &mut remaining), cx);
+        status.and_then(
+            |d| Self::start_skip(reader, Some(d), remaining, cx).and_then(Self::expect_data, |dec| dec),
+            |dec| Self::Body(dec, remaining),
+        )
+    }
+
+    fn start_decode_body_into<R>(format: &D::Format, reader: &mut R, data: &mut D::Data, len: SequenceLen, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <D::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut remaining = usize::from(len);
+        let status = D::start_decode_into(format, &mut FrameReader::new(reader, &mut remaining), data, cx);
+        status.and_then(
+            |()| Self::start_skip(reader, None, remaining, cx).bimap(|_| (), |dec| dec),
+            |dec| Self::Body(dec, remaining),
+        )
+    }
+
+    fn expect_data(data: Option<D::Data>) -> backend::StartDecodeStatus<D::Data, Self, <D::Format as Format>::Error> {
+        data.ok_or_else(<D::Format as Format>::invalid_input_err).into()
+    }
+}
+
+impl<D> Decode for Decoder<D>
+where
+    D: Decode,
+{
+    type Format = D::Format;
+    type Data = D::Data;
+
+    fn init() -> Self {
+        Self::Init
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        <<SequenceLen as Decodable>::Decoder<D::Format> as Decode>::start_decode(format, reader, cx)
+        .and_then(
+            |len| Self::start_decode_body(format, reader, len, cx),
+            Self::Len,
+        )
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match self {
+            Self::Init                => decode_chain!(*self, Self, Self::start_decode(format, reader, cx)),
+            Self::Len(dec)            => decode_poll_chain!(*self, Self, dec.poll_decode(format, reader, cx), |len| Self::start_decode_body(format, reader, len, cx)),
+            Self::Body(dec, remaining) => {
+                let status = dec.poll_decode(format, &mut FrameReader::new(reader, remaining), cx);
+                let remaining = *remaining;
+                decode_poll_chain!(*self, Self, status, |d| Self::start_skip(reader, Some(d), remaining, cx).and_then(Self::expect_data, |dec| dec))
+            }
+            Self::Skip(data, remaining) => match poll_skip(reader, remaining, cx) {
+                Poll::Pending       => backend::PollDecodeStatus::Pending,
+                Poll::Ready(Ok(())) => {
+                    let data = data.take();
+                    *self = Self::Fini;
+                    data.map_or_else(|| backend::PollDecodeStatus::Error(<Self::Format as Format>::invalid_input_err()), backend::PollDecodeStatus::Fini)
+                }
+                Poll::Ready(Err(e)) => { *self = Self::Fini; backend::PollDecodeStatus::Error(e.into()) }
+            }
+            Self::Fini                => backend::PollDecodeStatus::Error(<Self::Format as Format>::invalid_input_err()),
+        }
+    }
+
+    fn start_decode_into<R>(format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        <<SequenceLen as Decodable>::Decoder<D::Format> as Decode>::start_decode(format, reader, cx)
+        .and_then(
+            |len| Self::start_decode_body_into(format, reader, data, len, cx),
+            Self::Len,
+        )
+    }
+
+    fn poll_decode_into<R>(&mut self, format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match self {
+            Self::Init                => decode_chain!(*self, Self, Self::start_decode_into(format, reader, data, cx)),
+            Self::Len(dec)            => decode_poll_chain!(*self, Self, dec.poll_decode(format, reader, cx), |len| Self::start_decode_body_into(format, reader, data, len, cx)),
+            Self::Body(dec, remaining) => {
+                let status = dec.poll_decode_into(format, &mut FrameReader::new(reader, remaining), data, cx);
+                let remaining = *remaining;
+                decode_poll_chain!(*self, Self, status, |()| Self::start_skip(reader, None, remaining, cx).bimap(|_| (), |dec| dec))
+            }
+            Self::Skip(_, remaining)  => match poll_skip(reader, remaining, cx) {
+                Poll::Pending       => backend::PollDecodeStatus::Pending,
+                Poll::Ready(Ok(())) => { *self = Self::Fini; backend::PollDecodeStatus::Fini(()) }
+                Poll::Ready(Err(e)) => { *self = Self::Fini; backend::PollDecodeStatus::Error(e.into()) }
+            }
+            Self::Fini                => backend::PollDecodeStatus::Error(<Self::Format as Format>::invalid_input_err()),
+        }
+    }
+}
+
+/// Decodes a field added to a versioned struct after its first version,
+/// yielding `None` if the (length limited) encoding ends before it.
+///
+/// Only meaningful for the trailing fields of a value decoded by a
+/// [versioned decoder](Decoder), and for formats that do not mark the end
+/// of a struct.
+pub enum Trailing<D> {
+    Init,
+    Present(D),
+    Fini,
+}
+
+impl<D> Decode for Trailing<D>
+where
+    D: Decode,
+{
+    type Format = D::Format;
+    type Data = Option<D::Data>;
+
+    fn init() -> Self {
+        Self::Init
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match Pin::new(&mut *reader).poll_fill_buf(cx) {
+            Poll::Pending       => backend::StartDecodeStatus::Pending(Self::Init),
+            Poll::Ready(Ok([])) => backend::StartDecodeStatus::Fini(None),
+            Poll::Ready(Ok(_))  => D::start_decode(format, reader, cx).bimap(Some, Self::Present),
+            Poll::Ready(Err(e)) => backend::StartDecodeStatus::Error(e.into()),
+        }
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <Self::Format as Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match self {
+            Self::Init         => decode_chain!(*self, Self, Self::start_decode(format, reader, cx)),
+            Self::Present(dec) => decode_poll_fini!(*self, Self, dec.poll_decode(format, reader, cx), Some),
+            Self::Fini         => backend::PollDecodeStatus::Error(<Self::Format as Format>::invalid_input_err()),
+        }
+    }
+}
@@ -1,17 +1,22 @@
 use core::task::Context;
-use crate::backend::{self, Encode as _, Decode as _};
-use crate::io;
+use crate::backend::{self, internal::{Arity, Prefixed}, Encode as _, Decode as _};
+use crate::{error::PathSegment, io};
 
 
 type Data<T, const L: usize> = [T; L];
 
+type EncodeItem<F, T> = Prefixed<<F as backend::FormatEncodeHooks>::EncodeItemSeparator, <T as backend::Encodable>::Encoder<F>>;
+type DecodeItem<F, T> = Prefixed<<F as backend::FormatDecodeHooks>::DecodeItemSeparator, <T as backend::Decodable>::Decoder<F>>;
+
 pub enum Encoder<F, T, const L: usize>
 where
     F: backend::FormatEncode,
     T: backend::Encodable,
 {
     Init,
-    Cur(usize, <T as backend::Encodable>::Encoder<F>),
+    Begin(F::EncodeStructBegin<Arity<L>>),
+    Cur(usize, EncodeItem<F, T>),
+    End(F::EncodeStructEnd<Arity<L>>),
     Fini,
 }
 
@@ -24,7 +29,11 @@ where
     where
         W: io::AsyncWrite + Unpin,
     {
-        Self::fields_from(format, writer, 0, data, cx)
+        match F::EncodeStructBegin::<Arity<L>>::start_encode(format, writer, &(), cx) {
+            backend::StartEncodeStatus::Fini         => Self::fields_from(format, writer, 0, data, cx),
+            backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Begin(enc)),
+            backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e),
+        }
     }
 
     fn fields_from<W>(format: &F, writer: &mut W, idx: usize, data: &Data<T, L>, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
@@ -32,14 +41,27 @@ where
         W: io::AsyncWrite + Unpin,
     {
         for (i, d) in data.iter().enumerate().skip(idx) {
-            match <T as backend::Encodable>::Encoder::<F>::start_encode(format, writer, d, cx) {
+            let status = if i == 0 {
+                EncodeItem::<F, T>::start_encode_value(format, writer, d, cx)
+            } else {
+                EncodeItem::<F, T>::start_encode(format, writer, d, cx)
+            };
+            match status {
                 backend::StartEncodeStatus::Fini         => continue,
                 backend::StartEncodeStatus::Pending(enc) => return backend::StartEncodeStatus::Pending(Self::Cur(i, enc)),
                 backend::StartEncodeStatus::Error(e)     => return backend::StartEncodeStatus::Error(e),
             }
         }
 
-        backend::StartEncodeStatus::Fini
+        Self::after_fields(format, writer, cx)
+    }
+
+    fn after_fields<W>(format: &F, writer: &mut W, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        F::EncodeStructEnd::<Arity<L>>::start_encode(format, writer, &(), cx)
+        .map_pending(Self::End)
     }
 }
 
@@ -51,6 +73,14 @@ where
     type Data = Data<T, L>;
     type Format = F;
 
+    const MAX_ENCODED_LEN: Option<usize> = backend::size::sum(
+        backend::size::sum(
+            <F::EncodeStructBegin<Arity<L>> as backend::Encode>::MAX_ENCODED_LEN,
+            <F::EncodeStructEnd<Arity<L>> as backend::Encode>::MAX_ENCODED_LEN,
+        ),
+        backend::size::mul(<EncodeItem<F, T> as backend::Encode>::MAX_ENCODED_LEN, L),
+    );
+
     fn init(_data: &Self::Data) -> Self {
         Self::Init
     }
@@ -68,7 +98,9 @@ where
     {
         match self {
             Self::Init          => encode_chain!(*self, Self::start_encode(format, writer, data, cx)),
+            Self::Begin(enc)    => encode_poll_chain!(*self, enc.poll_encode(format, writer, &(), cx), Self::fields_from(format, writer, 0, data, cx)),
             Self::Cur(idx, enc) => encode_poll_chain!(*self, enc.poll_encode(format, writer, &data[*idx], cx), Self::fields_from(format, writer, *idx + 1, data, cx)),
+            Self::End(enc)      => encode_poll_fini!(*self, enc.poll_encode(format, writer, &(), cx)),
             Self::Fini          => backend::PollEncodeStatus::Error(F::invalid_input_err()),
         }
     }
@@ -136,7 +168,9 @@ where
     T: backend::Decodable,
 {
     Init,
-    Cur(usize, <T as backend::Decodable>::Decoder<F>),
+    Begin(F::DecodeStructBegin<Arity<L>>),
+    Cur(usize, DecodeItem<F, T>),
+    End(F::DecodeStructEnd<Arity<L>>),
     Fini,
 }
 
@@ -171,7 +205,11 @@ where
     where
         R: io::AsyncBufRead + Unpin,
     {
-        Self::fields_from(format, reader, 0, data, cx)
+        F::DecodeStructBegin::<Arity<L>>::start_decode(format, reader, cx)
+        .and_then(
+            |()| Self::fields_from(format, reader, 0, data, cx),
+            Self::Begin,
+        )
     }
 
     fn fields_from<R>(format: &F, reader: &mut R, idx: usize, data: &mut PartialData<T, L>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
@@ -179,14 +217,27 @@ where
         R: io::AsyncBufRead + Unpin,
     {
         for i in idx..L {
-            match <T as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx) {
+            let status = if i == 0 {
+                DecodeItem::<F, T>::start_decode_value(format, reader, cx)
+            } else {
+                DecodeItem::<F, T>::start_decode(format, reader, cx)
+            };
+            match status {
                 backend::StartDecodeStatus::Fini(d) => { data[i] = Some(d); continue },
                 backend::StartDecodeStatus::Pending(dec) => return backend::StartDecodeStatus::Pending(Self::Cur(i, dec)),
-                backend::StartDecodeStatus::Error(e) => return backend::StartDecodeStatus::Error(e),
+                backend::StartDecodeStatus::Error(e) => return backend::StartDecodeStatus::Error(F::nested_err(e, PathSegment::Index(i))),
             }
         }
 
-        backend::StartDecodeStatus::Fini(())
+        Self::after_fields(format, reader, cx)
+    }
+
+    fn after_fields<R>(format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        F::DecodeStructEnd::<Arity<L>>::start_decode(format, reader, cx)
+        .bimap(|()| (), Self::End)
     }
 }
 
@@ -229,16 +280,24 @@ where
         if let Some(state) = &mut self.state {
             match &mut state.cursor {
                 DecodeCursor::Init => decode_chain!(state.cursor, DecodeCursor, DecodeCursor::after_init(format, reader, &mut state.data, cx)),
-                DecodeCursor::Cur(idx, dec) =>
+                DecodeCursor::Begin(dec) =>
                     decode_poll_chain!(
                         state.cursor,
                         DecodeCursor,
                         dec.poll_decode(format, reader, cx),
+                        |()| DecodeCursor::fields_from(format, reader, 0, &mut state.data, cx)
+                    ),
+                DecodeCursor::Cur(idx, dec) =>
+                    decode_poll_chain!(
+                        state.cursor,
+                        DecodeCursor,
+                        dec.poll_decode(format, reader, cx).map_err(|e| F::nested_err(e, PathSegment::Index(*idx))),
                         |d| {
                             state.data[*idx] = Some(d);
                             DecodeCursor::fields_from(format, reader, *idx + 1, &mut state.data, cx)
                         }
                     ),
+                DecodeCursor::End(dec) => decode_poll_fini!(state.cursor, DecodeCursor, dec.poll_decode(format, reader, cx), |()| ()),
                 DecodeCursor::Fini => return backend::PollDecodeStatus::Error(F::invalid_input_err()),
             }
             // SAFETY:
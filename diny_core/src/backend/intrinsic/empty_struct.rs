@@ -28,6 +28,8 @@ where
     type Format = F;
     type Data = Dta;
 
+    const MAX_ENCODED_LEN: Option<usize> = <F::EncodeUnit as Encode>::MAX_ENCODED_LEN;
+
     fn init(_data: &Self::Data) -> Self {
         Self::Init(PhantomData, PhantomData)
     }
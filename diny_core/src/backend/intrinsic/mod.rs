@@ -1,4 +1,5 @@
 #[doc(hidden)] pub mod empty_struct;
 #[doc(hidden)] pub mod option;
 #[doc(hidden)] pub mod result;
-#[doc(hidden)] pub mod array;
\ No newline at end of file
+#[doc(hidden)] pub mod array;
+#[doc(hidden)] pub mod tuple;
\ No newline at end of file
@@ -1,7 +1,7 @@
 use core::task::Context;
 use crate::backend::{self, Encode as _, Decode as _};
 use crate::io;
-use backend::internal::VariantIdx;
+use backend::internal::OptionTag;
 
 
 type Data<T> = Option<T>;
@@ -12,7 +12,7 @@ where
     T: backend::Encodable,
 {
     Init,
-    Index(VariantIdx, <VariantIdx as backend::Encodable>::Encoder<F>),
+    Index(OptionTag, <OptionTag as backend::Encodable>::Encoder<F>),
     V0(<() as backend::Encodable>::Encoder<F>),
     V1(<T as backend::Encodable>::Encoder<F>),
     Fini,
@@ -23,7 +23,7 @@ where
     F: backend::FormatEncode,
     T: backend::Encodable,
 {
-    fn variant_index(data: &Data<T>) -> VariantIdx {
+    fn variant_index(data: &Data<T>) -> OptionTag {
         match data {
             None    => 0,
             Some(_) => 1,
@@ -35,7 +35,7 @@ where
         W: io::AsyncWrite + Unpin,
     {
         let index = Self::variant_index(data);
-        match <VariantIdx as backend::Encodable>::Encoder::<F>::start_encode(format, writer, &index, cx) {
+        match <OptionTag as backend::Encodable>::Encoder::<F>::start_encode(format, writer, &index, cx) {
             backend::StartEncodeStatus::Fini         => Self::after_index(format, writer, data, cx),
             backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Index(index, enc)),
             backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e)
@@ -79,6 +79,14 @@ where
     type Data = Data<T>;
     type Format = F;
 
+    const MAX_ENCODED_LEN: Option<usize> = backend::size::sum(
+        <<OptionTag as backend::Encodable>::Encoder<F> as backend::Encode>::MAX_ENCODED_LEN,
+        backend::size::max(
+            <<() as backend::Encodable>::Encoder<F> as backend::Encode>::MAX_ENCODED_LEN,
+            <T::Encoder<F> as backend::Encode>::MAX_ENCODED_LEN,
+        ),
+    );
+
     fn init(_data: &Self::Data) -> Self {
         Self::Init
     }
@@ -147,7 +155,7 @@ where
     T: backend::Decodable,
 {
     Init,
-    Index(<VariantIdx as backend::Decodable>::Decoder<F>),
+    Index(<OptionTag as backend::Decodable>::Decoder<F>),
     None(<() as backend::Decodable>::Decoder<F>),
     Some(<T as backend::Decodable>::Decoder<F>),
     Fini,
@@ -162,14 +170,14 @@ where
     where
         R: io::AsyncBufRead + Unpin,
     {
-        <VariantIdx as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx)
+        <OptionTag as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx)
         .and_then(
             |idx| Self::after_index(idx, format, reader, cx),
             Self::Index,
         )
     }
 
-    fn after_index<R>(index: VariantIdx, format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Data<T>, Self, <F as backend::Format>::Error>
+    fn after_index<R>(index: OptionTag, format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Data<T>, Self, <F as backend::Format>::Error>
     where
         R: io::AsyncBufRead + Unpin,
     {
@@ -1,10 +1,15 @@
 use core::task::Context;
-use crate::backend::{self, internal::VariantIdx, Encode as _, Decode as _};
+use crate::backend::{self, internal::{ResultDef, Suffixed, VariantIdx}, Encode as _, Decode as _};
 use crate::io;
 
 
 type Data<O, E> = Result<O, E>;
 
+type EncodeVariant<F> = <F as backend::FormatEncodeHooks>::EncodeVariant<ResultDef>;
+type EncodePayload<F, T> = Suffixed<<T as backend::Encodable>::Encoder<F>, <F as backend::FormatEncodeHooks>::EncodeVariantEnd<ResultDef>>;
+type DecodeVariant<F> = <F as backend::FormatDecodeHooks>::DecodeVariant<ResultDef>;
+type DecodePayload<F, T> = Suffixed<<T as backend::Decodable>::Decoder<F>, <F as backend::FormatDecodeHooks>::DecodeVariantEnd<ResultDef>, T>;
+
 pub enum Encoder<F, O, E>
 where
     F: backend::FormatEncode,
@@ -12,9 +17,9 @@ where
     E: backend::Encodable,
 {
     Init,
-    Index(VariantIdx, <VariantIdx as backend::Encodable>::Encoder<F>),
-    V0(<O as backend::Encodable>::Encoder<F>),
-    V1(<E as backend::Encodable>::Encoder<F>),
+    Index(VariantIdx, EncodeVariant<F>),
+    V0(EncodePayload<F, O>),
+    V1(EncodePayload<F, E>),
     Fini,
 }
 
@@ -36,7 +41,7 @@ where
         W: io::AsyncWrite + Unpin,
     {
         let index = Self::variant_index(data);
-        match <VariantIdx as backend::Encodable>::Encoder::<F>::start_encode(format, writer, &index, cx) {
+        match EncodeVariant::<F>::start_encode(format, writer, &index, cx) {
             backend::StartEncodeStatus::Fini         => Self::after_index(format, writer, data, cx),
             backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Index(index, enc)),
             backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e)
@@ -57,7 +62,7 @@ where
     where
         W: io::AsyncWrite + Unpin,
     {
-        <O as backend::Encodable>::Encoder::<F>::start_encode(format, writer, data, cx)
+        EncodePayload::<F, O>::start_encode(format, writer, data, cx)
         .map_pending(Self::V0)
     }
 
@@ -65,7 +70,7 @@ where
     where
         W: io::AsyncWrite + Unpin,
     {
-        <E as backend::Encodable>::Encoder::<F>::start_encode(format, writer, data, cx)
+        EncodePayload::<F, E>::start_encode(format, writer, data, cx)
         .map_pending(Self::V1)
     }
 }
@@ -79,6 +84,14 @@ where
     type Data = Data<O, E>;
     type Format = F;
 
+    const MAX_ENCODED_LEN: Option<usize> = backend::size::sum(
+        <EncodeVariant<F> as backend::Encode>::MAX_ENCODED_LEN,
+        backend::size::max(
+            <EncodePayload<F, O> as backend::Encode>::MAX_ENCODED_LEN,
+            <EncodePayload<F, E> as backend::Encode>::MAX_ENCODED_LEN,
+        ),
+    );
+
     fn init(_data: &Self::Data) -> Self {
         Self::Init
     }
@@ -158,9 +171,9 @@ where
     E: backend::Decodable,
 {
     Init,
-    Index(<VariantIdx as backend::Decodable>::Decoder<F>),
-    Ok(<O as backend::Decodable>::Decoder<F>),
-    Err(<E as backend::Decodable>::Decoder<F>),
+    Index(DecodeVariant<F>),
+    Ok(DecodePayload<F, O>),
+    Err(DecodePayload<F, E>),
     Fini,
 }
 
@@ -174,7 +187,7 @@ where
     where
         R: io::AsyncBufRead + Unpin,
     {
-        <VariantIdx as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx)
+        DecodeVariant::<F>::start_decode(format, reader, cx)
         .and_then(
             |idx| Self::after_index(idx, format, reader, cx),
             Self::Index,
@@ -196,7 +209,7 @@ where
     where
         R: io::AsyncBufRead + Unpin,
     {
-        <O as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx)
+        DecodePayload::<F, O>::start_decode(format, reader, cx)
         .and_then(
             |o| backend::StartDecodeStatus::Fini(Data::Ok(o)),
             Self::Ok,
@@ -207,7 +220,7 @@ where
     where
         R: io::AsyncBufRead + Unpin,
     {
-        <E as backend::Decodable>::Decoder::<F>::start_decode(format, reader, cx)
+        DecodePayload::<F, E>::start_decode(format, reader, cx)
         .and_then(
             |e| backend::StartDecodeStatus::Fini(Data::Err(e)),
             Self::Err,
@@ -0,0 +1,325 @@
+macro_rules! tuple_def {
+    ($($idx: tt $T: ident $Cur: ident),+) => {
+        use core::task::Context;
+        use crate::backend::{self, internal::{Arity, Prefixed}, Encode as _, Decode as _};
+        use crate::io;
+
+
+        type Data<$($T),+> = ($($T,)+);
+
+        const ARITY: usize = [$($idx),+].len();
+
+        type EncodeItem<F, T> = Prefixed<<F as backend::FormatEncodeHooks>::EncodeItemSeparator, <T as backend::Encodable>::Encoder<F>>;
+        type DecodeItem<F, T> = Prefixed<<F as backend::FormatDecodeHooks>::DecodeItemSeparator, <T as backend::Decodable>::Decoder<F>>;
+
+        pub enum Encoder<F, $($T),+>
+        where
+            F: backend::FormatEncode,
+            $($T: backend::Encodable,)+
+        {
+            Init,
+            Begin(F::EncodeStructBegin<Arity<ARITY>>),
+            $($Cur(EncodeItem<F, $T>),)+
+            End(F::EncodeStructEnd<Arity<ARITY>>),
+            Fini,
+        }
+
+        impl<F, $($T),+> Encoder<F, $($T),+>
+        where
+            F: backend::FormatEncode,
+            $($T: backend::Encodable,)+
+        {
+            fn after_init<W>(format: &F, writer: &mut W, data: &Data<$($T),+>, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                match F::EncodeStructBegin::<Arity<ARITY>>::start_encode(format, writer, &(), cx) {
+                    backend::StartEncodeStatus::Fini         => Self::fields_from(format, writer, 0, data, cx),
+                    backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Begin(enc)),
+                    backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e),
+                }
+            }
+
+            #[allow(clippy::absurd_extreme_comparisons)]
+            fn fields_from<W>(format: &F, writer: &mut W, idx: usize, data: &Data<$($T),+>, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                $(
+                    if idx <= $idx {
+                        let status = if $idx > 0 {
+                            EncodeItem::<F, $T>::start_encode(format, writer, &data.$idx, cx)
+                        } else {
+                            EncodeItem::<F, $T>::start_encode_value(format, writer, &data.$idx, cx)
+                        };
+                        match status {
+                            backend::StartEncodeStatus::Fini         => {},
+                            backend::StartEncodeStatus::Pending(enc) => return backend::StartEncodeStatus::Pending(Self::$Cur(enc)),
+                            backend::StartEncodeStatus::Error(e)     => return backend::StartEncodeStatus::Error(e),
+                        }
+                    }
+                )+
+
+                Self::after_fields(format, writer, cx)
+            }
+
+            fn after_fields<W>(format: &F, writer: &mut W, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                F::EncodeStructEnd::<Arity<ARITY>>::start_encode(format, writer, &(), cx)
+                .map_pending(Self::End)
+            }
+        }
+
+        impl<F, $($T),+> backend::Encode for Encoder<F, $($T),+>
+        where
+            F: backend::FormatEncode,
+            $($T: backend::Encodable,)+
+        {
+            type Data = Data<$($T),+>;
+            type Format = F;
+
+            const MAX_ENCODED_LEN: Option<usize> = {
+                let len = <F::EncodeStructBegin<Arity<ARITY>> as backend::Encode>::MAX_ENCODED_LEN;
+                $(let len = backend::size::sum(len, <EncodeItem<F, $T> as backend::Encode>::MAX_ENCODED_LEN);)+
+                backend::size::sum(len, <F::EncodeStructEnd<Arity<ARITY>> as backend::Encode>::MAX_ENCODED_LEN)
+            };
+
+            fn init(_data: &Self::Data) -> Self {
+                Self::Init
+            }
+
+            fn start_encode<W>(format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <F as backend::Format>::Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                Self::after_init(format, writer, data, cx)
+            }
+
+            fn poll_encode<W>(&mut self, format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<<F as backend::Format>::Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                match self {
+                    Self::Init     => encode_chain!(*self, Self::start_encode(format, writer, data, cx)),
+                    Self::Begin(enc) => encode_poll_chain!(*self, enc.poll_encode(format, writer, &(), cx), Self::fields_from(format, writer, 0, data, cx)),
+                    $(Self::$Cur(enc) => encode_poll_chain!(*self, enc.poll_encode(format, writer, &data.$idx, cx), Self::fields_from(format, writer, $idx + 1, data, cx)),)+
+                    Self::End(enc) => encode_poll_fini!(*self, enc.poll_encode(format, writer, &(), cx)),
+                    Self::Fini     => backend::PollEncodeStatus::Error(F::invalid_input_err()),
+                }
+            }
+        }
+
+        impl<$($T),+> backend::Encodable for Data<$($T),+>
+        where
+            $($T: backend::Encodable,)+
+        {
+            type Encoder<F: backend::FormatEncode> = Encoder<F, $($T),+>;
+        }
+
+        impl<$($T),+> backend::AsyncSerialize for Data<$($T),+>
+        where
+            $($T: backend::AsyncSerialize,)+
+        {
+            type Future<'w, F, W>
+            where
+                Self: 'w,
+                F: 'w + backend::FormatSerialize,
+                W: 'w + io::AsyncWrite + Unpin,
+            = backend::SerializeAll<'w, F, W, Self, Self::Encoder<F>>;
+
+            fn serialize<'w, F, W>(&'w self, format: &'w F, writer: &'w mut W) -> Self::Future<'w, F, W>
+            where
+                F: backend::FormatSerialize,
+                W: io::AsyncWrite + Unpin,
+            {
+                backend::SerializeAll::new(format, writer, self, <Self::Encoder::<F> as backend::Encode>::init(self))
+            }
+        }
+
+        type PartialData<$($T),+> = ($(Option<$T>,)+);
+
+        fn into_data<$($T),+>(data: PartialData<$($T),+>) -> Data<$($T),+> {
+            ($(data.$idx.unwrap(),)+)
+        }
+
+        enum DecodeCursor<F, $($T),+>
+        where
+            F: backend::FormatDecode,
+            $($T: backend::Decodable,)+
+        {
+            Init,
+            Begin(F::DecodeStructBegin<Arity<ARITY>>),
+            $($Cur(DecodeItem<F, $T>),)+
+            End(F::DecodeStructEnd<Arity<ARITY>>),
+            Fini,
+        }
+
+        struct DecodeState<F, $($T),+>
+        where
+            F: backend::FormatDecode,
+            $($T: backend::Decodable,)+
+        {
+            data: PartialData<$($T),+>,
+            cursor: DecodeCursor<F, $($T),+>,
+        }
+
+        impl<F, $($T),+> DecodeState<F, $($T),+>
+        where
+            F: backend::FormatDecode,
+            $($T: backend::Decodable,)+
+        {
+            pub fn new() -> Self {
+                Self {
+                    data: ($(Option::<$T>::None,)+),
+                    cursor: DecodeCursor::Init,
+                }
+            }
+        }
+
+        impl<F, $($T),+> DecodeCursor<F, $($T),+>
+        where
+            F: backend::FormatDecode,
+            $($T: backend::Decodable,)+
+        {
+            fn after_init<R>(format: &F, reader: &mut R, data: &mut PartialData<$($T),+>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                F::DecodeStructBegin::<Arity<ARITY>>::start_decode(format, reader, cx)
+                .and_then(
+                    |()| Self::fields_from(format, reader, 0, data, cx),
+                    Self::Begin,
+                )
+            }
+
+            #[allow(clippy::absurd_extreme_comparisons)]
+            fn fields_from<R>(format: &F, reader: &mut R, idx: usize, data: &mut PartialData<$($T),+>, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                $(
+                    if idx <= $idx {
+                        let status = if $idx > 0 {
+                            DecodeItem::<F, $T>::start_decode(format, reader, cx)
+                        } else {
+                            DecodeItem::<F, $T>::start_decode_value(format, reader, cx)
+                        };
+                        match status {
+                            backend::StartDecodeStatus::Fini(d)      => data.$idx = Some(d),
+                            backend::StartDecodeStatus::Pending(dec) => return backend::StartDecodeStatus::Pending(Self::$Cur(dec)),
+                            backend::StartDecodeStatus::Error(e)     => return backend::StartDecodeStatus::Error(e),
+                        }
+                    }
+                )+
+
+                Self::after_fields(format, reader, cx)
+            }
+
+            fn after_fields<R>(format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                F::DecodeStructEnd::<Arity<ARITY>>::start_decode(format, reader, cx)
+                .bimap(|()| (), Self::End)
+            }
+        }
+
+        pub struct Decoder<F, $($T),+>
+        where
+            F: backend::FormatDecode,
+            $($T: backend::Decodable,)+
+        {
+            state: Option<DecodeState<F, $($T),+>>,
+        }
+
+        impl<F, $($T),+> backend::Decode for Decoder<F, $($T),+>
+        where
+            F: backend::FormatDecode,
+            $($T: backend::Decodable,)+
+        {
+            type Data = Data<$($T),+>;
+            type Format = F;
+
+            fn init() -> Self {
+                Self { state: Some(DecodeState::new()) }
+            }
+
+            fn start_decode<R>(format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut state = DecodeState::new();
+                match DecodeCursor::after_init(format, reader, &mut state.data, cx) {
+                    backend::StartDecodeStatus::Fini(())        => backend::StartDecodeStatus::Fini(into_data(state.data)),
+                    backend::StartDecodeStatus::Pending(cursor) => backend::StartDecodeStatus::Pending(Self { state: Some(DecodeState { cursor, ..state }) }),
+                    backend::StartDecodeStatus::Error(e)        => backend::StartDecodeStatus::Error(e),
+                }
+            }
+
+            fn poll_decode<R>(&mut self, format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <F as backend::Format>::Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                if let Some(state) = &mut self.state {
+                    match &mut state.cursor {
+                        DecodeCursor::Init => decode_chain!(state.cursor, DecodeCursor, DecodeCursor::after_init(format, reader, &mut state.data, cx)),
+                        DecodeCursor::Begin(dec) =>
+                            decode_poll_chain!(
+                                state.cursor,
+                                DecodeCursor,
+                                dec.poll_decode(format, reader, cx),
+                                |()| DecodeCursor::fields_from(format, reader, 0, &mut state.data, cx)
+                            ),
+                        $(
+                            DecodeCursor::$Cur(dec) =>
+                                decode_poll_chain!(
+                                    state.cursor,
+                                    DecodeCursor,
+                                    dec.poll_decode(format, reader, cx),
+                                    |d| {
+                                        state.data.$idx = Some(d);
+                                        DecodeCursor::fields_from(format, reader, $idx + 1, &mut state.data, cx)
+                                    }
+                                ),
+                        )+
+                        DecodeCursor::End(dec) => decode_poll_fini!(state.cursor, DecodeCursor, dec.poll_decode(format, reader, cx), |()| ()),
+                        DecodeCursor::Fini => return backend::PollDecodeStatus::Error(F::invalid_input_err()),
+                    }
+                    // The Fini cursor state is only reached once every element has been
+                    // decoded, so all of the partial data is present when it is consumed here.
+                    .map(|()| into_data(self.state.take().unwrap().data))
+                } else {
+                    backend::PollDecodeStatus::Error(F::invalid_input_err())
+                }
+            }
+        }
+
+        impl<$($T),+> backend::Decodable for Data<$($T),+>
+        where
+            $($T: backend::Decodable,)+
+        {
+            type Decoder<F: backend::FormatDecode> = Decoder<F, $($T),+>;
+        }
+
+        impl<$($T),+> backend::AsyncDeserialize for Data<$($T),+>
+        where
+            $($T: backend::AsyncDeserialize,)+
+        {
+            type Future<'r, F, R>
+            where
+                F: 'r + backend::FormatDeserialize,
+                R: 'r + io::AsyncBufRead + Unpin,
+            = backend::DeserializeExact<'r, F, R, Self, Self::Decoder<F>>;
+
+            fn deserialize<'r, F, R>(format: &'r F, reader: &'r mut R) -> Self::Future<'r, F, R>
+            where
+                F: backend::FormatDeserialize,
+                R: io::AsyncBufRead + Unpin,
+            {
+                backend::DeserializeExact::new(format, reader, <Self::Decoder::<F> as backend::Decode>::init())
+            }
+        }
+    };
+}
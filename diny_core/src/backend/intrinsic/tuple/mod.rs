@@ -0,0 +1,19 @@
+#[macro_use]
+mod macros;
+
+#[doc(hidden)] pub mod tuple1 { tuple_def!(0 T0 Cur0); }
+#[doc(hidden)] pub mod tuple2 { tuple_def!(0 T0 Cur0, 1 T1 Cur1); }
+#[doc(hidden)] pub mod tuple3 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2); }
+#[doc(hidden)] pub mod tuple4 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3); }
+#[doc(hidden)] pub mod tuple5 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4); }
+#[doc(hidden)] pub mod tuple6 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5); }
+#[doc(hidden)] pub mod tuple7 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6); }
+#[doc(hidden)] pub mod tuple8 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6, 7 T7 Cur7); }
+#[doc(hidden)] pub mod tuple9 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6, 7 T7 Cur7, 8 T8 Cur8); }
+#[doc(hidden)] pub mod tuple10 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6, 7 T7 Cur7, 8 T8 Cur8, 9 T9 Cur9); }
+#[doc(hidden)] pub mod tuple11 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6, 7 T7 Cur7, 8 T8 Cur8, 9 T9 Cur9, 10 T10 Cur10); }
+#[doc(hidden)] pub mod tuple12 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6, 7 T7 Cur7, 8 T8 Cur8, 9 T9 Cur9, 10 T10 Cur10, 11 T11 Cur11); }
+#[doc(hidden)] pub mod tuple13 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6, 7 T7 Cur7, 8 T8 Cur8, 9 T9 Cur9, 10 T10 Cur10, 11 T11 Cur11, 12 T12 Cur12); }
+#[doc(hidden)] pub mod tuple14 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6, 7 T7 Cur7, 8 T8 Cur8, 9 T9 Cur9, 10 T10 Cur10, 11 T11 Cur11, 12 T12 Cur12, 13 T13 Cur13); }
+#[doc(hidden)] pub mod tuple15 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6, 7 T7 Cur7, 8 T8 Cur8, 9 T9 Cur9, 10 T10 Cur10, 11 T11 Cur11, 12 T12 Cur12, 13 T13 Cur13, 14 T14 Cur14); }
+#[doc(hidden)] pub mod tuple16 { tuple_def!(0 T0 Cur0, 1 T1 Cur1, 2 T2 Cur2, 3 T3 Cur3, 4 T4 Cur4, 5 T5 Cur5, 6 T6 Cur6, 7 T7 Cur7, 8 T8 Cur8, 9 T9 Cur9, 10 T10 Cur10, 11 T11 Cur11, 12 T12 Cur12, 13 T13 Cur13, 14 T14 Cur14, 15 T15 Cur15); }
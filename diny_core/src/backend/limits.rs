@@ -0,0 +1,331 @@
+use core::{cmp::{max, min}, fmt, mem, pin::Pin, task::{Context, Poll}};
+use crate::{backend, io};
+
+
+/// Identifies which of the [decode limits](DecodeLimits) has been exceeded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// A sequence or map length prefix was larger than permitted
+    SequenceLen,
+    /// A string or byte vector length prefix was larger than permitted
+    BytesLen,
+    /// More bytes were required to decode a single object than permitted
+    TotalBytes,
+    /// Containers were nested more deeply than permitted
+    Depth,
+    /// A [frame](crate::framing) was longer than permitted
+    FrameLen,
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SequenceLen => f.write_str("maximum sequence length exceeded"),
+            Self::BytesLen    => f.write_str("maximum byte length exceeded"),
+            Self::TotalBytes  => f.write_str("maximum total bytes exceeded"),
+            Self::Depth       => f.write_str("maximum nesting depth exceeded"),
+            Self::FrameLen    => f.write_str("maximum frame length exceeded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LimitExceeded {}
+
+/// Limits enforced while decoding, to guard against hostile input.
+///
+/// A [format](super::FormatDecode) exposes its limits via the
+/// [decode_limits](super::FormatDecode::decode_limits) method, and
+/// they are consulted by every collection, string and byte decoder
+/// before any memory is reserved.  By default, nothing is limited.
+///
+/// The limits hold no state of their own, so a single format instance
+/// may be shared between any number of concurrent deserializations.
+#[derive(Clone, Debug)]
+pub struct DecodeLimits {
+    max_sequence_len: usize,
+    max_bytes_len: usize,
+    max_total_bytes: usize,
+    max_depth: usize,
+}
+
+/// Limits that permit anything the platform can represent.
+pub static UNLIMITED: DecodeLimits = DecodeLimits::unlimited();
+
+impl DecodeLimits {
+    /// Instantiate limits that permit anything the platform can represent.
+    pub const fn unlimited() -> Self {
+        Self {
+            max_sequence_len: usize::MAX,
+            max_bytes_len: usize::MAX,
+            max_total_bytes: usize::MAX,
+            max_depth: usize::MAX,
+        }
+    }
+
+    /// Limit the number of items in any decoded sequence or map.
+    pub const fn with_max_sequence_len(mut self, n: usize) -> Self {
+        self.max_sequence_len = n;
+        self
+    }
+
+    /// Limit the number of bytes in any decoded string or byte vector.
+    pub const fn with_max_bytes_len(mut self, n: usize) -> Self {
+        self.max_bytes_len = n;
+        self
+    }
+
+    /// Limit the number of bytes read while decoding a single object.
+    pub const fn with_max_total_bytes(mut self, n: usize) -> Self {
+        self.max_total_bytes = n;
+        self
+    }
+
+    /// Limit how deeply collections and boxed values may be nested.
+    pub const fn with_max_depth(mut self, n: usize) -> Self {
+        self.max_depth = n;
+        self
+    }
+
+    /// The maximum number of items in any decoded sequence or map.
+    pub fn max_sequence_len(&self) -> usize {
+        self.max_sequence_len
+    }
+
+    /// The maximum number of bytes in any decoded string or byte vector.
+    pub fn max_bytes_len(&self) -> usize {
+        self.max_bytes_len
+    }
+
+    /// The maximum number of bytes read while decoding a single object.
+    pub fn max_total_bytes(&self) -> usize {
+        self.max_total_bytes
+    }
+
+    /// The maximum nesting depth of collections and boxed values.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Verify that a decoded sequence length is permitted.
+    pub fn check_sequence_len(&self, len: usize) -> Result<usize, LimitExceeded> {
+        if len > self.max_sequence_len {
+            Err(LimitExceeded::SequenceLen)
+        } else {
+            Ok(len)
+        }
+    }
+
+    /// Verify that a decoded string or byte vector length is permitted.
+    pub fn check_bytes_len(&self, len: usize) -> Result<usize, LimitExceeded> {
+        if len > self.max_bytes_len {
+            Err(LimitExceeded::BytesLen)
+        } else {
+            Ok(len)
+        }
+    }
+
+    /// Invoke `f` one nesting level deeper than the caller.
+    ///
+    /// Intended to wrap every call a container's decoder makes into the
+    /// decoders of its contents, so that the current depth reflects the
+    /// chain of nested decoders being driven at any point in time.
+    pub fn nest<T>(&self, f: impl FnOnce() -> T) -> Result<T, LimitExceeded> {
+        if self.max_depth == usize::MAX {
+            return Ok(f());
+        }
+
+        let _level = depth::Level::enter(self.max_depth)?;
+        Ok(f())
+    }
+}
+
+/// The nesting depth of the decoders being driven.
+///
+/// Decoders only ever nest within a single poll, and every level is left again
+/// before the poll returns, so the depth is a property of the call stack rather
+/// than of any one deserialization.  It is counted per thread, so that neither
+/// concurrent deserializations nor those interleaved on one thread observe each
+/// other's depth.
+#[cfg(feature = "std")]
+mod depth {
+    use std::cell::Cell;
+    use super::LimitExceeded;
+
+    std::thread_local! {
+        static DEPTH: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub struct Level;
+
+    impl Level {
+        pub fn enter(max: usize) -> Result<Self, LimitExceeded> {
+            DEPTH.with(|depth| {
+                if depth.get() >= max {
+                    Err(LimitExceeded::Depth)
+                } else {
+                    depth.set(depth.get() + 1);
+                    Ok(Level)
+                }
+            })
+        }
+    }
+
+    impl Drop for Level {
+        fn drop(&mut self) {
+            DEPTH.with(|depth| depth.set(depth.get() - 1));
+        }
+    }
+}
+
+// Without thread locals, a single count is shared by the whole program.
+#[cfg(not(feature = "std"))]
+mod depth {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use super::LimitExceeded;
+
+    static DEPTH: AtomicUsize = AtomicUsize::new(0);
+
+    pub struct Level;
+
+    impl Level {
+        pub fn enter(max: usize) -> Result<Self, LimitExceeded> {
+            if DEPTH.fetch_add(1, Ordering::Relaxed) >= max {
+                DEPTH.fetch_sub(1, Ordering::Relaxed);
+                Err(LimitExceeded::Depth)
+            } else {
+                Ok(Level)
+            }
+        }
+    }
+
+    impl Drop for Level {
+        fn drop(&mut self) {
+            DEPTH.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The most memory reserved up front for a decoded collection, string or byte
+/// vector, however long its encoding claims it to be.  Anything beyond it is
+/// only allocated as the data actually arrives, so a hostile length prefix
+/// cannot exhaust memory even when its [limit](DecodeLimits) is not restricted.
+pub const MAX_PREALLOC_BYTES: usize = 64 * 1024;
+
+/// The most bytes a value that must be buffered in full before it is written,
+/// such as a [serde](crate::serde) value, may encode to by default.
+pub const MAX_BUFFERED_BYTES: usize = 16 * 1024 * 1024;
+
+/// The number of `T`s to reserve ahead of decoding `len` of them.
+pub fn prealloc_len<T>(len: usize) -> usize {
+    min(len, MAX_PREALLOC_BYTES / max(mem::size_of::<T>(), 1))
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+/// Start decoding one nesting level deeper, as determined by the format's [decode limits](DecodeLimits).
+pub fn start_nested<F, D, S>(format: &F, f: impl FnOnce() -> backend::StartDecodeStatus<D, S, F::Error>) -> backend::StartDecodeStatus<D, S, F::Error>
+where
+    F: backend::FormatDecode,
+{
+    match format.decode_limits().nest(f) {
+        Ok(status) => status,
+        Err(kind)  => backend::StartDecodeStatus::Error(F::limit_exceeded_err(kind)),
+    }
+}
+
+/// Continue decoding one nesting level deeper, as determined by the format's [decode limits](DecodeLimits).
+pub fn poll_nested<F, D>(format: &F, f: impl FnOnce() -> backend::PollDecodeStatus<D, F::Error>) -> backend::PollDecodeStatus<D, F::Error>
+where
+    F: backend::FormatDecode,
+{
+    match format.decode_limits().nest(f) {
+        Ok(status) => status,
+        Err(kind)  => backend::PollDecodeStatus::Error(F::limit_exceeded_err(kind)),
+    }
+}
+
+/// Restricts the number of bytes that may be read from the underlying reader
+/// to the [maximum total bytes](DecodeLimits::max_total_bytes).
+///
+/// Once the limit is reached, the buffer appears to end without the underlying
+/// reader being polled, so that a decoder peeking past the end of a value that
+/// fits exactly is unaffected.  A decoder that needed more bytes instead fails,
+/// and its error is then attributed to the limit by [limit_err](LimitReader::limit_err).
+pub(crate) struct LimitReader<'r, R> {
+    reader: &'r mut R,
+    consumed: &'r mut usize,
+    max: usize,
+    exhausted: bool,
+}
+
+impl<'r, R> LimitReader<'r, R> {
+    pub(crate) fn new(reader: &'r mut R, consumed: &'r mut usize, max: usize) -> Self {
+        Self {
+            reader,
+            consumed,
+            max,
+            exhausted: false,
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.max - *self.consumed
+    }
+
+    /// Replace a decoding error with a [TotalBytes](LimitExceeded::TotalBytes)
+    /// error if the decoder asked for bytes beyond the limit.
+    pub(crate) fn limit_err<F: backend::Format>(&self, err: F::Error) -> F::Error {
+        if self.exhausted {
+            F::limit_exceeded_err(LimitExceeded::TotalBytes)
+        } else {
+            err
+        }
+    }
+}
+
+impl<R> io::AsyncRead for LimitReader<'_, R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.remaining() == 0 && !buf.is_empty() {
+            return Poll::Ready(Err(io::error::limit_exceeded(LimitExceeded::TotalBytes)));
+        }
+
+        let n = min(buf.len(), this.remaining());
+        let ret = Pin::new(&mut *this.reader).poll_read(cx, &mut buf[..n]);
+        if let Poll::Ready(Ok(n)) = ret {
+            *this.consumed += n;
+        }
+        ret
+    }
+}
+
+impl<R> io::AsyncBufRead for LimitReader<'_, R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        let remaining = this.remaining();
+        if remaining == 0 {
+            this.exhausted = true;
+            return Poll::Ready(Ok(&[]));
+        }
+
+        Pin::new(&mut *this.reader).poll_fill_buf(cx)
+        .map_ok(|buf| &buf[..min(buf.len(), remaining)])
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        *this.consumed += amt;
+        Pin::new(&mut *this.reader).consume(amt)
+    }
+}
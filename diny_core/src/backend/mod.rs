@@ -2,9 +2,11 @@
 #[doc(hidden)] pub mod encode;
 #[doc(hidden)] pub mod format;
 #[doc(hidden)] pub mod future;
+#[doc(hidden)] pub mod limits;
 #[doc(hidden)] pub mod collection;
 #[doc(hidden)] pub mod intrinsic;
 #[doc(hidden)] pub mod primitive;
+#[doc(hidden)] pub mod size;
 #[doc(hidden)] pub mod wrapper;
 
 /// Types used to support structural serialization
@@ -26,16 +28,28 @@ pub use self::{
         StartEncodeStatus,
     },
     format::{
+        DefaultHooks,
         Format,
         FormatDecode,
+        FormatDecodeHooks,
         FormatDeserialize,
         FormatEncode,
+        FormatEncodeHooks,
         FormatSerialize
     },
     future::{
         deserialize_exact::DeserializeExact,
+        deserialize_into::DeserializeInto,
         serialize_all::SerializeAll,
-    }
+    },
+    limits::{
+        DecodeLimits,
+        LimitExceeded,
+    },
+    size::{
+        encoded_len,
+        max_encoded_len,
+    },
 };
 
 /// Define the [encoder](Encode) to use for serializing the data type.
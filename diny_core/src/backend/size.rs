@@ -0,0 +1,115 @@
+use core::task::Context;
+use crate::backend::{Encodable, Encode, Format, FormatEncode, StartEncodeStatus};
+use crate::util::AsyncCountingWriter;
+
+
+/// Compute the exact number of bytes `data` encodes to in the indicated
+/// [format](FormatEncode), without retaining the encoded bytes.
+pub fn encoded_len<F, T>(format: &F, data: &T) -> Result<usize, F::Error>
+where
+    F: FormatEncode,
+    T: Encodable + ?Sized,
+{
+    encoder_len::<T::Encoder<F>>(format, data)
+}
+
+/// Compute the exact number of bytes the encoder `E` writes for `data`,
+/// without retaining the encoded bytes.
+///
+/// Useful to formats that must write the length of a value ahead of it.
+pub fn encoder_len<E>(format: &E::Format, data: &E::Data) -> Result<usize, <E::Format as Format>::Error>
+where
+    E: Encode,
+{
+    let mut writer = AsyncCountingWriter::new();
+    let mut cx = Context::from_waker(measure::waker());
+    match E::start_encode(format, &mut writer, data, &mut cx) {
+        StartEncodeStatus::Fini       => Ok(writer.bytes_written()),
+        // The counting writer never pends, so neither should a well behaved encoder
+        StartEncodeStatus::Pending(_) => Err(<E::Format as Format>::invalid_input_err()),
+        StartEncodeStatus::Error(e)   => Err(e),
+    }
+}
+
+/// Whether the encoder polled with `cx` is being driven by [encoder_len], so
+/// that only the number of bytes it writes matters, and not what they are.
+///
+/// An encoder that has already measured part of its encoding, in order to
+/// write its length ahead of it, may then write as many arbitrary bytes in
+/// its place rather than encoding it once more.
+pub fn measuring(cx: &Context<'_>) -> bool {
+    measure::is_measuring(cx.waker())
+}
+
+// A measurement is told apart from any other encoding by the waker of the
+// context it is driven with, which never has to wake anything.
+#[cfg(feature = "std")]
+mod measure {
+    use std::{sync::{Arc, LazyLock}, task::{Wake, Waker}};
+
+    struct Measure;
+
+    // Unlike the shared no-op waker, this one can be told apart from others
+    #[allow(clippy::manual_noop_waker)]
+    impl Wake for Measure {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    static WAKER: LazyLock<Waker> = LazyLock::new(|| Waker::from(Arc::new(Measure)));
+
+    pub fn waker() -> &'static Waker {
+        &WAKER
+    }
+
+    pub fn is_measuring(waker: &Waker) -> bool {
+        waker.will_wake(&WAKER)
+    }
+}
+
+// Without std, measurements are driven like any other encoding.
+#[cfg(not(feature = "std"))]
+mod measure {
+    use core::task::Waker;
+
+    pub fn waker() -> &'static Waker {
+        futures::task::noop_waker_ref()
+    }
+
+    pub fn is_measuring(_waker: &Waker) -> bool {
+        false
+    }
+}
+
+/// The largest number of bytes any value of `T` can encode to in the
+/// indicated [format](FormatEncode), or `None` if it is unbounded.
+pub const fn max_encoded_len<F, T>() -> Option<usize>
+where
+    F: FormatEncode,
+    T: Encodable + ?Sized,
+{
+    <T::Encoder<F> as Encode>::MAX_ENCODED_LEN
+}
+
+/// The maximum length of two consecutive encodings
+pub const fn sum(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => a.checked_add(b),
+        _                  => None,
+    }
+}
+
+/// The maximum length of either of two alternative encodings
+pub const fn max(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if a > b { a } else { b }),
+        _                  => None,
+    }
+}
+
+/// The maximum length of `n` consecutive encodings
+pub const fn mul(a: Option<usize>, n: usize) -> Option<usize> {
+    match a {
+        Some(a) => a.checked_mul(n),
+        None    => None,
+    }
+}
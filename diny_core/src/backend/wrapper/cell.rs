@@ -20,6 +20,8 @@ where
     type Format = F;
     type Data = Data<T>;
 
+    const MAX_ENCODED_LEN: Option<usize> = <T::Encoder<F> as backend::Encode>::MAX_ENCODED_LEN;
+
     fn init(data: &Self::Data) -> Self {
         Self(T::Encoder::<F>::init(&data.get()))
     }
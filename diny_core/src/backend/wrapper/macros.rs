@@ -18,6 +18,8 @@ macro_rules! wrapper_encode_impl_deref {
         {
             type Format = F;
             type Data = Data<T>;
+
+            const MAX_ENCODED_LEN: Option<usize> = <T::Encoder<F> as backend::Encode>::MAX_ENCODED_LEN;
         
             fn init(data: &Self::Data) -> Self {
                 Self(T::Encoder::<F>::init(data))
@@ -107,7 +109,7 @@ macro_rules! wrapper_decode_impl {
             where
                 R: io::AsyncBufRead + Unpin,
             {
-                T::Decoder::<F>::start_decode(format, reader, cx)
+                backend::limits::start_nested(format, || T::Decoder::<F>::start_decode(format, reader, cx))
                 .bimap(
                     Data::<T>::new,
                     |s| Self(s, PhantomData),
@@ -118,7 +120,7 @@ macro_rules! wrapper_decode_impl {
             where
                 R: io::AsyncBufRead + Unpin,
              {
-                self.0.poll_decode(format, reader, cx)
+                backend::limits::poll_nested(format, || self.0.poll_decode(format, reader, cx))
                 .map(Data::<T>::new)
             }
         }
@@ -23,6 +23,8 @@ where
     type Format = F;
     type Data = Data<T>;
 
+    const MAX_ENCODED_LEN: Option<usize> = <<() as backend::Encodable>::Encoder<F> as backend::Encode>::MAX_ENCODED_LEN;
+
     fn init(_data: &Self::Data) -> Self {
         Self(<() as backend::Encodable>::Encoder::<F>::init(&()), PhantomData)
     }
@@ -27,6 +27,8 @@ where
     type Format = F;
     type Data = Data<T>;
 
+    const MAX_ENCODED_LEN: Option<usize> = <T::Encoder<F> as backend::Encode>::MAX_ENCODED_LEN;
+
     fn init(data: &Self::Data) -> Self {
         match data.try_borrow() {
             Ok(ref d) => Self(Some(T::Encoder::<F>::init(d))),
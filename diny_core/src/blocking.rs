@@ -0,0 +1,156 @@
+use core::{pin::Pin, task::{Context, Poll}};
+use crate::backend::{Decodable, Decode, Encodable, Encode, Format, FormatDecode, FormatEncode, StartDecodeStatus, StartEncodeStatus, limits::LimitReader};
+use crate::io;
+use crate::util::AsyncSliceReader;
+
+/// Serializes `data` in the indicated [format](FormatEncode) into a new vector of bytes.
+pub fn to_vec<F, T>(format: &F, data: &T) -> Result<Vec<u8>, F::Error>
+where
+    F: FormatEncode,
+    T: Encodable + ?Sized,
+{
+    let mut bytes = Vec::new();
+    encode::<T::Encoder<F>, _>(format, &mut bytes, data)?;
+    Ok(bytes)
+}
+
+/// Serializes `data` in the indicated [format](FormatEncode) to a blocking
+/// [writer](std::io::Write), flushing it once every byte has been written.
+///
+/// The writer is not buffered, so a [BufWriter](std::io::BufWriter) should be
+/// used wherever individual writes are expensive, e.g. for a file or socket.
+pub fn to_writer<F, W, T>(format: &F, writer: W, data: &T) -> Result<(), F::Error>
+where
+    F: FormatEncode,
+    W: std::io::Write,
+    T: Encodable + ?Sized,
+{
+    let mut writer = Blocking(writer);
+    encode::<T::Encoder<F>, _>(format, &mut writer, data)?;
+    writer.0.flush().map_err(Into::into)
+}
+
+/// Deserializes a `T` in the indicated [format](FormatDecode) from a slice of bytes.
+///
+/// The encoding must span the whole of the slice; any bytes left over once
+/// the `T` has been decoded are [invalid data](Format::invalid_data_err),
+/// unless the format [accepts](FormatDecode::accepts_trailing) them.
+/// Use [from_reader] to decode a `T` from the front of a longer input.
+pub fn from_slice<F, T>(format: &F, bytes: &[u8]) -> Result<T, F::Error>
+where
+    F: FormatDecode,
+    T: Decodable,
+{
+    let mut reader = AsyncSliceReader::new(bytes);
+    let data = decode::<T::Decoder<F>, _>(format, &mut reader)?;
+    if format.accepts_trailing(&bytes[reader.bytes_read()..]) {
+        Ok(data)
+    } else {
+        Err(F::offset_err(F::invalid_data_err(), reader.bytes_read()))
+    }
+}
+
+/// Deserializes a `T` in the indicated [format](FormatDecode) from a blocking
+/// [reader](std::io::BufRead).
+///
+/// Only the bytes that make up the `T` are consumed, so that further objects
+/// can be read from the same `reader` afterwards.
+pub fn from_reader<F, R, T>(format: &F, reader: R) -> Result<T, F::Error>
+where
+    F: FormatDecode,
+    R: std::io::BufRead,
+    T: Decodable,
+{
+    decode::<T::Decoder<F>, _>(format, &mut Blocking(reader))
+}
+
+// Readers and writers that never pend leave a well behaved coder nothing to
+// wait for, so a pending status is reported as a broken contract rather than
+// polled again with a waker that would never be woken.
+pub(crate) fn encode<E, W>(format: &E::Format, writer: &mut W, data: &E::Data) -> Result<(), <E::Format as Format>::Error>
+where
+    E: Encode,
+    W: io::AsyncWrite + Unpin,
+{
+    let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+    match E::start_encode(format, writer, data, &mut cx) {
+        StartEncodeStatus::Fini       => Ok(()),
+        StartEncodeStatus::Pending(_) => Err(<E::Format as Format>::invalid_input_err()),
+        StartEncodeStatus::Error(e)   => Err(e),
+    }
+}
+
+pub(crate) fn decode<D, R>(format: &D::Format, reader: &mut R) -> Result<D::Data, <D::Format as Format>::Error>
+where
+    D: Decode,
+    R: io::AsyncBufRead + Unpin,
+{
+    let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+    let max = format.decode_limits().max_total_bytes();
+    let mut consumed = 0;
+    let mut reader = LimitReader::new(reader, &mut consumed, max);
+    match D::start_decode(format, &mut reader, &mut cx) {
+        StartDecodeStatus::Fini(data) => Ok(data),
+        StartDecodeStatus::Pending(_) => Err(<D::Format as Format>::invalid_input_err()),
+        StartDecodeStatus::Error(e)   => {
+            let e = reader.limit_err::<D::Format>(e);
+            Err(<D::Format as Format>::offset_err(e, consumed))
+        }
+    }
+}
+
+// Presents a blocking reader or writer as an asynchronous one that is always ready.
+struct Blocking<T>(T);
+
+impl<T> Unpin for Blocking<T> {}
+
+impl<W: std::io::Write> io::AsyncWrite for Blocking<W> {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match this.0.write(buf) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                ret => return Poll::Ready(ret),
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().0.flush())
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().0.flush())
+    }
+}
+
+impl<R: std::io::BufRead> io::AsyncRead for Blocking<R> {
+    fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match this.0.read(buf) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                ret => return Poll::Ready(ret),
+            }
+        }
+    }
+}
+
+impl<R: std::io::BufRead> io::AsyncBufRead for Blocking<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        // Retry interruptions first, as the buffer cannot be returned from within the loop
+        loop {
+            match this.0.fill_buf() {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Poll::Ready(Err(e)),
+                Ok(_)  => break,
+            }
+        }
+        Poll::Ready(this.0.fill_buf())
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().0.consume(amt)
+    }
+}
@@ -8,6 +8,9 @@ pub trait BufferEncode: Sized {
     type Format: FormatEncode;
     type Data;
 
+    /// See [Encode::MAX_ENCODED_LEN]
+    const MAX_ENCODED_LEN: Option<usize> = None;
+
     fn init_buffer(data: &Self::Data) -> Self;
 
     fn start_encode_buffer<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <<Self as BufferEncode>::Format as Format>::Error>
@@ -25,6 +28,8 @@ impl<T> Encode for T where T: BufferEncode {
     type Format = T::Format;
     type Data = T::Data;
 
+    const MAX_ENCODED_LEN: Option<usize> = <T as BufferEncode>::MAX_ENCODED_LEN;
+
     fn init(data: &Self::Data) -> Self {
         Self::init_buffer(data)
     }
@@ -2,7 +2,7 @@ use core::{
     pin::Pin,
     task::{Poll, Context}
 };
-use crate::{backend::{self, Decode, PollDecodeStatus, StartDecodeStatus}, io};
+use crate::{backend::{self, Decode, PollDecodeStatus, StartDecodeStatus, limits::LimitReader}, framing::{FramedDeserialize, FramedTryDeserialize, Framing}, io, schema::{self, Describe, FingerprintMismatch}};
 
 /// Creates a new [Deserializer] from the specified [format](backend::FormatDecode)
 /// and [reader](io::AsyncBufRead)
@@ -28,6 +28,9 @@ impl<F, R> Deserializer<F, R> {
     }
 
     /// Converts the [Deserializer] into a stream of `D`'s
+    ///
+    /// Any decoding error terminates the stream.  Use [into_try_stream](Self::into_try_stream)
+    /// to observe the error itself.
     pub fn into_stream<D>(self) -> Deserialize<F, R, D>
     where
         F: backend::FormatDecode,
@@ -37,6 +40,41 @@ impl<F, R> Deserializer<F, R> {
         Deserialize::new(self)
     }
 
+    /// Converts the [Deserializer] into a stream of `Result<D, F::Error>`'s
+    pub fn into_try_stream<D>(self) -> TryDeserialize<F, R, D>
+    where
+        F: backend::FormatDecode,
+        R: io::AsyncBufRead + Unpin,
+        D: backend::Decodable,
+    {
+        TryDeserialize::new(self)
+    }
+
+    /// Converts the [Deserializer] into a stream of length-delimited `D`'s, as
+    /// configured by `framing`
+    ///
+    /// Frames that fail to decode are skipped.  Use
+    /// [into_framed_try_stream](Self::into_framed_try_stream) to observe the errors.
+    pub fn into_framed_stream<D>(self, framing: Framing) -> FramedDeserialize<F, R, D>
+    where
+        F: backend::FormatDecode,
+        R: io::AsyncBufRead + Unpin,
+        D: backend::Decodable,
+    {
+        FramedDeserialize::new(self, framing)
+    }
+
+    /// Converts the [Deserializer] into a stream of length-delimited
+    /// `Result<D, F::Error>`'s, as configured by `framing`
+    pub fn into_framed_try_stream<D>(self, framing: Framing) -> FramedTryDeserialize<F, R, D>
+    where
+        F: backend::FormatDecode,
+        R: io::AsyncBufRead + Unpin,
+        D: backend::Decodable,
+    {
+        FramedTryDeserialize::new(self, framing)
+    }
+
     /// Deserializes a single object of type `D`
     pub fn deserialize<D>(&mut self) -> D::Future<'_, F, R>
     where
@@ -46,6 +84,46 @@ impl<F, R> Deserializer<F, R> {
     {
         D::deserialize(&self.format, &mut self.reader)
     }
+
+    /// Reads a [fingerprint](schema::Schema::fingerprint) written by a peer's
+    /// [Serializer](crate::Serializer::write_fingerprint), and verifies that it
+    /// matches that of `D`'s schema
+    ///
+    /// Should the fingerprints differ, the format's
+    /// [fingerprint mismatch error](backend::Format::fingerprint_mismatch_err) is
+    /// returned, and no `D`'s should be read, since the peer would encode them
+    /// differently.
+    pub fn verify_fingerprint<D>(&mut self) -> impl '_ + futures::Future<Output=Result<(), <F as backend::Format>::Error>>
+    where
+        F: backend::FormatDeserialize,
+        R: io::AsyncBufRead + Unpin,
+        D: Describe + ?Sized,
+    {
+        use futures::{future, TryFutureExt};
+
+        let expected = schema::fingerprint::<D>();
+        <u64 as backend::AsyncDeserialize>::deserialize(&self.format, &mut self.reader)
+        .and_then(move |found| future::ready(
+            if found == expected {
+                Ok(())
+            } else {
+                Err(F::fingerprint_mismatch_err(FingerprintMismatch { expected, found }))
+            }
+        ))
+    }
+
+    /// Deserializes a single object of type `D` into `data`, reusing any
+    /// storage it already owns (e.g. the capacity of a `Vec` or `String`)
+    ///
+    /// Should deserialization fail, the contents of `data` are unspecified.
+    pub fn deserialize_into<'r, D>(&'r mut self, data: &'r mut D) -> backend::DeserializeInto<'r, F, R, D, D::Decoder<F>>
+    where
+        F: backend::FormatDecode,
+        R: io::AsyncBufRead + Unpin,
+        D: backend::Decodable,
+    {
+        backend::DeserializeInto::new(&self.format, &mut self.reader, data)
+    }
 }
 
 enum State<F, D>
@@ -58,26 +136,37 @@ where
     Error,
 }
 
-/// Implements the [Stream](futures::Stream) trait
-pub struct Deserialize<F, R, D>
+/// Implements the [Stream](futures::Stream) trait, yielding each decoded item or the
+/// error that prevented it from being decoded.
+///
+/// The stream ends cleanly (i.e. returns `None`) if the reader is exhausted on a message
+/// boundary.  If the reader is exhausted part way through a message, the stream instead
+/// yields the format's [UnexpectedEof](io::ErrorKind::UnexpectedEof) error.  After any
+/// error has been yielded, the stream is terminated.
+///
+/// The format's [maximum total bytes](backend::DecodeLimits::max_total_bytes) is applied
+/// to each item individually.
+pub struct TryDeserialize<F, R, D>
 where
     F: backend::FormatDecode,
     D: backend::Decodable,
 {
     deserializer: Deserializer<F, R>,
     state: State<F, D>,
+    consumed: usize,
 }
 
-impl<F, R, D> Deserialize<F, R, D>
+impl<F, R, D> TryDeserialize<F, R, D>
 where
     F: backend::FormatDecode,
     D: backend::Decodable,
 {
-    /// Instantiates a new [Deserializer] for the data type `D` from the given `format` and `reader`
+    /// Instantiates a new [TryDeserialize] stream for the data type `D` from the given `deserializer`
     pub fn new(deserializer: Deserializer<F, R>) -> Self {
         Self {
             deserializer,
             state: State::Ready,
+            consumed: 0,
         }
     }
 
@@ -99,50 +188,132 @@ where
     }
 }
 
-impl<F, R, D> Unpin for Deserialize<F, R, D>
+impl<F, R, D> Unpin for TryDeserialize<F, R, D>
 where
     F: backend::FormatDecode,
     D: backend::Decodable,
 {}
 
-impl<F, R, D> futures::Stream for Deserialize<F, R, D>
+impl<F, R, D> futures::Stream for TryDeserialize<F, R, D>
 where
     F: backend::FormatDecode,
     R: io::AsyncBufRead + Unpin,
     D: backend::Decodable,
 {
-    type Item = D;
+    type Item = Result<D, <F as backend::Format>::Error>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let Self {
             deserializer,
             state,
+            consumed,
         } = &mut *self;
+        let max = deserializer.format.decode_limits().max_total_bytes();
 
         match state {
-            State::Ready => match D::Decoder::start_decode(&deserializer.format, &mut deserializer.reader, cx) {
-                StartDecodeStatus::Fini(d) => Poll::Ready(Some(d)),
-                StartDecodeStatus::Pending(dec) => {
-                    *state = State::Pending(dec);
-                    Poll::Pending
-                }
-                StartDecodeStatus::Error(_) => {
-                    *state = State::Error;
-                    Poll::Ready(None)
+            State::Ready => {
+                // Peek at the reader to distinguish a clean end of stream from a truncated message
+                let at_eof = match Pin::new(&mut deserializer.reader).poll_fill_buf(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(buf)) => buf.is_empty(),
+                    Poll::Ready(Err(e)) => {
+                        *state = State::Error;
+                        return Poll::Ready(Some(Err(e.into())));
+                    }
+                };
+
+                // Zero sized data types still decode successfully at the end of the stream
+                *consumed = 0;
+                let mut reader = LimitReader::new(&mut deserializer.reader, consumed, max);
+                match D::Decoder::start_decode(&deserializer.format, &mut reader, cx) {
+                    StartDecodeStatus::Fini(d) => Poll::Ready(Some(Ok(d))),
+                    StartDecodeStatus::Pending(dec) => {
+                        *state = State::Pending(dec);
+                        Poll::Pending
+                    }
+                    StartDecodeStatus::Error(_) if at_eof => Poll::Ready(None),
+                    StartDecodeStatus::Error(e) => {
+                        let e = reader.limit_err::<F>(e);
+                        *state = State::Error;
+                        Poll::Ready(Some(Err(F::offset_err(e, *consumed))))
+                    }
                 }
             }
-            State::Pending(p) => match p.poll_decode(&deserializer.format, &mut deserializer.reader, cx) {
-                PollDecodeStatus::Fini(d) => {
-                    *state = State::Ready;
-                    Poll::Ready(Some(d))
-                }
-                PollDecodeStatus::Pending => Poll::Pending,
-                PollDecodeStatus::Error(_) => {
-                    *state = State::Error;
-                    Poll::Ready(None)
+            State::Pending(p) => {
+                let mut reader = LimitReader::new(&mut deserializer.reader, consumed, max);
+                match p.poll_decode(&deserializer.format, &mut reader, cx) {
+                    PollDecodeStatus::Fini(d) => {
+                        *state = State::Ready;
+                        Poll::Ready(Some(Ok(d)))
+                    }
+                    PollDecodeStatus::Pending => Poll::Pending,
+                    PollDecodeStatus::Error(e) => {
+                        let e = reader.limit_err::<F>(e);
+                        *state = State::Error;
+                        Poll::Ready(Some(Err(F::offset_err(e, *consumed))))
+                    }
                 }
             }
             State::Error => Poll::Ready(None)
         }
     }
-}
\ No newline at end of file
+}
+
+/// Implements the [Stream](futures::Stream) trait
+///
+/// Decoding errors are not reported, and simply terminate the stream.  See
+/// [TryDeserialize] for a stream that surfaces them.
+pub struct Deserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{
+    inner: TryDeserialize<F, R, D>,
+}
+
+impl<F, R, D> Deserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{
+    /// Instantiates a new [Deserializer] for the data type `D` from the given `format` and `reader`
+    pub fn new(deserializer: Deserializer<F, R>) -> Self {
+        Self {
+            inner: TryDeserialize::new(deserializer),
+        }
+    }
+
+    /// Returns `true` if the stream is ready to receive another item, `false` if not
+    pub fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    /// Consumes the stream and attempts to return the underlying decoding `format` and `reader`.
+    /// 
+    /// If the stream is not in the `Ready` state, the underlying reader will be consumed, and
+    /// the underlying `format` will be returned as an error.
+    pub fn try_into_inner(self) -> Result<Deserializer<F, R>, F> {
+        self.inner.try_into_inner()
+    }
+}
+
+impl<F, R, D> Unpin for Deserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{}
+
+impl<F, R, D> futures::Stream for Deserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    R: io::AsyncBufRead + Unpin,
+    D: backend::Decodable,
+{
+    type Item = D;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner)
+        .poll_next(cx)
+        .map(|o| o.and_then(Result::ok))
+    }
+}
@@ -0,0 +1,179 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use crate::io;
+
+
+/// One step along the path from the value being decoded to the point at
+/// which decoding failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A field of a derived struct, or of the payload of a variant
+    Field {
+        /// The name of the struct, if it has one
+        ty: Option<&'static str>,
+        /// The name of the field, or `None` for the fields of a tuple struct
+        name: Option<&'static str>,
+        /// The position of the field amongst those that are encoded
+        pos: usize,
+    },
+    /// The position of an item within a sequence, or of an entry within a map
+    Index(usize),
+    /// The payload of a variant of a derived enum
+    Variant {
+        /// The name of the enum
+        ty: &'static str,
+        /// The name of the variant
+        name: &'static str,
+    },
+}
+
+/// Displays a sequence of [path segments](PathSegment), outermost first,
+/// in the form `Order.lines[3].sku`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Path<'a>(&'a [PathSegment]);
+
+impl<'a> Path<'a> {
+    /// Wraps segments ordered from the outermost to the innermost.
+    pub fn new(segments: &'a [PathSegment]) -> Self {
+        Self(segments)
+    }
+
+    /// The segments of the path, outermost first
+    pub fn segments(&self) -> &'a [PathSegment] {
+        self.0
+    }
+
+    /// Whether the path has no segments at all
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only the outermost segment is qualified by the name of its type,
+        // as those within are implied by the fields leading to them
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field { ty, name, pos } => {
+                    if let (0, Some(ty)) = (i, ty) {
+                        f.write_str(ty)?;
+                    }
+                    match name {
+                        Some(name) => write!(f, ".{}", name)?,
+                        None       => write!(f, ".{}", pos)?,
+                    }
+                }
+                PathSegment::Index(idx) => write!(f, "[{}]", idx)?,
+                PathSegment::Variant { ty, name } => {
+                    if i == 0 {
+                        f.write_str(ty)?;
+                    }
+                    write!(f, "::{}", name)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An [io error](io::Error) annotated with where in the input decoding failed.
+///
+/// Formats whose errors are [io errors](io::Error) may opt in to this context
+/// by overriding [nested_err](crate::backend::Format::nested_err) and
+/// [offset_err](crate::backend::Format::offset_err) with the
+/// [within](io::error::within) and [at_offset](io::error::at_offset) helpers,
+/// as each of the bundled formats does, after which the errors they return carry a `DecodeError` of the
+/// same [kind](io::ErrorKind), displayed for example as
+/// `invalid data at byte 42 in Order.lines[3].sku`.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct DecodeError {
+    error: io::Error,
+    offset: Option<usize>,
+    path: Vec<PathSegment>,
+}
+
+#[cfg(feature = "std")]
+impl DecodeError {
+    /// The kind of the underlying error
+    pub fn kind(&self) -> io::ErrorKind {
+        self.error.kind()
+    }
+
+    /// The underlying error, without any context
+    pub fn get_ref(&self) -> &io::Error {
+        &self.error
+    }
+
+    /// The number of bytes of the value consumed before the failure was detected,
+    /// if known
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// The path from the value being decoded to the point at which decoding failed
+    pub fn path(&self) -> Path<'_> {
+        Path::new(&self.path)
+    }
+
+    /// The context attached to an [io error](io::Error), if there is any
+    pub fn find(err: &io::Error) -> Option<&DecodeError> {
+        err.get_ref().and_then(|e| e.downcast_ref::<DecodeError>())
+    }
+
+    pub(crate) fn annotate(mut err: io::Error, f: impl FnOnce(&mut DecodeError)) -> io::Error {
+        if let Some(context) = err.get_mut().and_then(|e| e.downcast_mut::<DecodeError>()) {
+            f(context);
+            return err;
+        }
+
+        let mut context = DecodeError {
+            error: err,
+            offset: None,
+            path: Vec::new(),
+        };
+        f(&mut context);
+        io::Error::new(context.kind(), context)
+    }
+
+    // Segments are added while the error propagates outwards
+    pub(crate) fn push_outer(&mut self, segment: PathSegment) {
+        self.path.insert(0, segment);
+    }
+
+    pub(crate) fn set_offset(&mut self, offset: usize) {
+        // The innermost offset is the one relative to the value being decoded
+        self.offset.get_or_insert(offset);
+    }
+}
+
+/// The underlying error of an [io error](io::Error), looking through any
+/// [context](DecodeError) attached to it.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub fn underlying(err: &io::Error) -> &io::Error {
+    DecodeError::find(err).map_or(err, DecodeError::get_ref)
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)?;
+        if let Some(offset) = self.offset {
+            write!(f, " at byte {}", offset)?;
+        }
+        if !self.path.is_empty() {
+            write!(f, " in {}", self.path())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -0,0 +1,616 @@
+use core::{
+    cmp::min,
+    pin::Pin,
+    task::{Poll, Context}
+};
+use crate::{
+    backend::{self, internal::SequenceLen, Decode, Encode, LimitExceeded, PollDecodeStatus, PollEncodeStatus},
+    deserializer::Deserializer,
+    io,
+    serializer::Serializer,
+};
+
+const FIXED_HEADER_LEN: usize = 4;
+
+/// The encoding of the length prefixed to each frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameHeader {
+    /// The format's own [sequence length](backend::FormatEncode::EncodeSequenceLen) encoding
+    SequenceLen,
+    /// A four byte, big endian, unsigned integer, regardless of the format
+    FixedU32,
+}
+
+/// What a receiver does with a frame that is larger than the
+/// [maximum frame length](Framing::with_max_frame_len).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Oversized {
+    /// Yield a [FrameLen](LimitExceeded::FrameLen) error, and terminate the stream
+    Reject,
+    /// Discard the frame without decoding it, and continue with the next one
+    Skip,
+}
+
+/// Configures length-delimited framing for a [Serializer] sink or
+/// [Deserializer] stream.
+///
+/// Each message is prefixed with the length of its encoding, which permits
+/// a receiver to skip the remainder of any frame it could not fully decode
+/// (e.g. one sent by a newer version of a peer) without losing its place in
+/// the stream.  Both peers must use the same [FrameHeader].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Framing {
+    header: FrameHeader,
+    max_frame_len: usize,
+    oversized: Oversized,
+}
+
+impl Default for Framing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Framing {
+    /// Framing with a [SequenceLen](FrameHeader::SequenceLen) header, and no
+    /// maximum frame length.
+    pub const fn new() -> Self {
+        Self {
+            header: FrameHeader::SequenceLen,
+            max_frame_len: usize::MAX,
+            oversized: Oversized::Reject,
+        }
+    }
+
+    /// Use the indicated encoding for the length header
+    pub const fn with_header(self, header: FrameHeader) -> Self {
+        Self { header, ..self }
+    }
+
+    /// Limit the length of any frame that is sent or received
+    pub const fn with_max_frame_len(self, max_frame_len: usize) -> Self {
+        Self { max_frame_len, ..self }
+    }
+
+    /// Determine how a receiver handles a frame larger than the maximum frame length
+    pub const fn with_oversized(self, oversized: Oversized) -> Self {
+        Self { oversized, ..self }
+    }
+
+    /// The encoding of the length header
+    pub const fn header(&self) -> FrameHeader {
+        self.header
+    }
+
+    /// The maximum length of any frame, excluding its header
+    pub const fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// How a receiver handles a frame larger than the maximum frame length
+    pub const fn oversized(&self) -> Oversized {
+        self.oversized
+    }
+}
+
+
+enum HeaderEncoder<F>
+where
+    F: backend::FormatEncode,
+{
+    SequenceLen(SequenceLen, <SequenceLen as backend::Encodable>::Encoder<F>),
+    Fixed([u8; FIXED_HEADER_LEN], usize),
+}
+
+impl<F> HeaderEncoder<F>
+where
+    F: backend::FormatEncode,
+{
+    fn new(framing: &Framing, len: usize) -> Result<Self, F::Error> {
+        match framing.header {
+            FrameHeader::SequenceLen => {
+                let len = SequenceLen::new(len);
+                Ok(Self::SequenceLen(len, Encode::init(&len)))
+            }
+            FrameHeader::FixedU32 => u32::try_from(len)
+                .map(|n| Self::Fixed(n.to_be_bytes(), 0))
+                .map_err(|_| F::limit_exceeded_err(LimitExceeded::FrameLen)),
+        }
+    }
+
+    fn poll_encode<W>(&mut self, format: &F, writer: &mut W, cx: &mut Context<'_>) -> PollEncodeStatus<F::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::SequenceLen(len, enc) => enc.poll_encode(format, writer, len, cx),
+            Self::Fixed(buf, pos) => {
+                while *pos < buf.len() {
+                    match Pin::new(&mut *writer).poll_write(cx, &buf[*pos..]) {
+                        Poll::Pending           => return PollEncodeStatus::Pending,
+                        Poll::Ready(Ok(0))      => return PollEncodeStatus::Error(io::Error::from(io::ErrorKind::WriteZero).into()),
+                        Poll::Ready(Ok(n))      => *pos += n,
+                        Poll::Ready(Err(e))     => return PollEncodeStatus::Error(e.into()),
+                    }
+                }
+                PollEncodeStatus::Fini
+            }
+        }
+    }
+}
+
+enum SendState<F, D>
+where
+    F: backend::FormatEncode,
+    D: backend::Encodable,
+{
+    Ready,
+    Header(HeaderEncoder<F>, D),
+    Body(D::Encoder<F>, D),
+    Error,
+    Closed,
+}
+
+/// Implements the [Sink](futures::Sink) trait, prefixing each item with the
+/// length of its encoding.
+///
+/// Sending an item whose encoding is longer than the
+/// [maximum frame length](Framing::with_max_frame_len) fails with a
+/// [FrameLen](LimitExceeded::FrameLen) error, without writing anything.
+pub struct FramedSerialize<F, W, D>
+where
+    F: backend::FormatEncode,
+    D: backend::Encodable,
+{
+    serializer: Serializer<F, W>,
+    framing: Framing,
+    state: SendState<F, D>,
+}
+
+impl<F, W, D> FramedSerialize<F, W, D>
+where
+    F: backend::FormatEncode,
+    W: io::AsyncWrite + Unpin,
+    D: backend::Encodable,
+{
+    /// Instantiates a new [FramedSerialize] sink for the data type `D` from the given `serializer`
+    pub fn new(serializer: Serializer<F, W>, framing: Framing) -> Self {
+        Self {
+            serializer,
+            framing,
+            state: SendState::Ready,
+        }
+    }
+
+    /// Returns `true` if the sink is ready to send another item, `false` if not
+    pub fn is_ready(&self) -> bool {
+        matches!(self.state, SendState::Ready)
+    }
+
+    /// Consumes the sink and attempts to return the underlying encoding `format` and `writer`.
+    ///
+    /// If the sink is not in the `Ready` state, the underlying writer will be consumed, and
+    /// the underlying `format` will be returned as an error.
+    pub fn try_into_inner(self) -> Result<Serializer<F, W>, F> {
+        if let SendState::Ready = self.state {
+            Ok(self.serializer)
+        } else {
+            Err(self.serializer.format)
+        }
+    }
+}
+
+impl<F, W, D> Unpin for FramedSerialize<F, W, D>
+where
+    F: backend::FormatEncode,
+    D: backend::Encodable,
+{}
+
+impl<F, W, D> futures::Sink<D> for FramedSerialize<F, W, D>
+where
+    F: backend::FormatEncode,
+    W: io::AsyncWrite + Unpin,
+    D: backend::Encodable,
+{
+    type Error = F::Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let Self {
+            serializer,
+            state,
+            ..
+        } = &mut *self;
+
+        loop {
+            let status = match state {
+                SendState::Ready => return Poll::Ready(Ok(())),
+                SendState::Header(enc, _) => enc.poll_encode(&serializer.format, &mut serializer.writer, cx),
+                SendState::Body(enc, data) => enc.poll_encode(&serializer.format, &mut serializer.writer, data, cx),
+                SendState::Error => return Poll::Ready(Err(<F as backend::Format>::invalid_input_err())),
+                SendState::Closed => return Poll::Ready(Ok(())),
+            };
+
+            match status {
+                PollEncodeStatus::Fini => {
+                    *state = match core::mem::replace(state, SendState::Error) {
+                        SendState::Header(_, data) => SendState::Body(<D::Encoder<F> as Encode>::init(&data), data),
+                        _                          => SendState::Ready,
+                    };
+                }
+                PollEncodeStatus::Pending => return Poll::Pending,
+                PollEncodeStatus::Error(e) => {
+                    *state = SendState::Error;
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: D) -> Result<(), Self::Error> {
+        if !self.is_ready() {
+            return Err(<F as backend::Format>::invalid_input_err());
+        }
+
+        let len = backend::encoded_len(&self.serializer.format, &item)?;
+        if len > self.framing.max_frame_len {
+            return Err(F::limit_exceeded_err(LimitExceeded::FrameLen));
+        }
+
+        self.state = SendState::Header(HeaderEncoder::<F>::new(&self.framing, len)?, item);
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        futures::ready!(Pin::new(&mut *self).poll_ready(cx))?;
+        match futures::ready!(Pin::new(&mut self.serializer.writer).poll_flush(cx)) {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(e) => {
+                self.state = SendState::Error;
+                Poll::Ready(Err(e.into()))
+            }
+        }
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        futures::ready!(Pin::new(&mut *self).poll_ready(cx))?;
+        match futures::ready!(Pin::new(&mut self.serializer.writer).poll_close(cx)) {
+            Ok(()) => {
+                self.state = SendState::Closed;
+                Poll::Ready(Ok(()))
+            }
+            Err(e) => {
+                self.state = SendState::Error;
+                Poll::Ready(Err(e.into()))
+            }
+        }
+    }
+}
+
+
+/// Restricts an inner reader to the remainder of a single frame, which
+/// it reports as the end of the stream.
+pub(crate) struct FrameReader<'r, R> {
+    reader: &'r mut R,
+    remaining: &'r mut usize,
+}
+
+impl<'r, R> FrameReader<'r, R> {
+    pub(crate) fn new(reader: &'r mut R, remaining: &'r mut usize) -> Self {
+        Self { reader, remaining }
+    }
+}
+
+impl<R> io::AsyncRead for FrameReader<'_, R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = min(buf.len(), *this.remaining);
+        let result = futures::ready!(Pin::new(&mut *this.reader).poll_read(cx, &mut buf[..n]));
+        if let Ok(n) = result {
+            *this.remaining -= n;
+        }
+        Poll::Ready(result)
+    }
+}
+
+impl<R> io::AsyncBufRead for FrameReader<'_, R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        let remaining = *this.remaining;
+        Pin::new(&mut *this.reader).poll_fill_buf(cx)
+        .map_ok(|buf| &buf[..min(buf.len(), remaining)])
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        let amt = min(amt, *this.remaining);
+        *this.remaining -= amt;
+        Pin::new(&mut *this.reader).consume(amt);
+    }
+}
+
+enum HeaderDecoder<F>
+where
+    F: backend::FormatDecode,
+{
+    SequenceLen(<SequenceLen as backend::Decodable>::Decoder<F>),
+    Fixed([u8; FIXED_HEADER_LEN], usize),
+}
+
+impl<F> HeaderDecoder<F>
+where
+    F: backend::FormatDecode,
+{
+    fn new(framing: &Framing) -> Self {
+        match framing.header {
+            FrameHeader::SequenceLen => Self::SequenceLen(Decode::init()),
+            FrameHeader::FixedU32    => Self::Fixed([0u8; FIXED_HEADER_LEN], 0),
+        }
+    }
+
+    fn poll_decode<R>(&mut self, format: &F, reader: &mut R, cx: &mut Context<'_>) -> PollDecodeStatus<usize, F::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match self {
+            Self::SequenceLen(dec) => dec.poll_decode(format, reader, cx).map(usize::from),
+            Self::Fixed(buf, pos) => {
+                while *pos < buf.len() {
+                    match Pin::new(&mut *reader).poll_fill_buf(cx) {
+                        Poll::Pending                          => return PollDecodeStatus::Pending,
+                        Poll::Ready(Err(e))                    => return PollDecodeStatus::Error(e.into()),
+                        Poll::Ready(Ok([]))                    => return PollDecodeStatus::Error(io::error::unexpected_eof().into()),
+                        Poll::Ready(Ok(bytes)) => {
+                            let n = min(bytes.len(), buf.len() - *pos);
+                            buf[*pos..*pos + n].copy_from_slice(&bytes[..n]);
+                            *pos += n;
+                            Pin::new(&mut *reader).consume(n);
+                        }
+                    }
+                }
+                match usize::try_from(u32::from_be_bytes(*buf)) {
+                    Ok(len) => PollDecodeStatus::Fini(len),
+                    Err(_)  => PollDecodeStatus::Error(F::limit_exceeded_err(LimitExceeded::FrameLen)),
+                }
+            }
+        }
+    }
+}
+
+enum RecvState<F, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{
+    Ready,
+    Header(HeaderDecoder<F>),
+    Body(usize, D::Decoder<F>),
+    Skip { report_eof: bool },
+    Error,
+}
+
+/// Implements the [Stream](futures::Stream) trait, yielding each length-delimited
+/// item or the error that prevented it from being decoded.
+///
+/// Errors confined to the body of a single frame do not terminate the stream:
+/// the remainder of the frame is skipped, and decoding continues with the next
+/// frame.  Bytes left over in a frame after an item has been decoded are skipped
+/// in the same way.  Errors reading a frame header, and (unless configured to be
+/// [skipped](Oversized::Skip)) frames longer than the maximum frame length,
+/// terminate the stream.
+///
+/// The stream ends cleanly (i.e. returns `None`) if the reader is exhausted on a
+/// frame boundary.
+pub struct FramedTryDeserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{
+    deserializer: Deserializer<F, R>,
+    framing: Framing,
+    state: RecvState<F, D>,
+    remaining: usize,
+}
+
+impl<F, R, D> FramedTryDeserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{
+    /// Instantiates a new [FramedTryDeserialize] stream for the data type `D` from the given `deserializer`
+    pub fn new(deserializer: Deserializer<F, R>, framing: Framing) -> Self {
+        Self {
+            deserializer,
+            framing,
+            state: RecvState::Ready,
+            remaining: 0,
+        }
+    }
+
+    /// Returns `true` if the stream is positioned on a frame boundary, `false` if not
+    pub fn is_ready(&self) -> bool {
+        matches!(self.state, RecvState::Ready)
+    }
+
+    /// Consumes the stream and attempts to return the underlying decoding `format` and `reader`.
+    ///
+    /// If the stream is not in the `Ready` state, the underlying reader will be consumed, and
+    /// the underlying `format` will be returned as an error.
+    pub fn try_into_inner(self) -> Result<Deserializer<F, R>, F> {
+        if let RecvState::Ready = self.state {
+            Ok(self.deserializer)
+        } else {
+            Err(self.deserializer.format)
+        }
+    }
+
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, RecvState::Error)
+    }
+}
+
+impl<F, R, D> Unpin for FramedTryDeserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{}
+
+impl<F, R, D> futures::Stream for FramedTryDeserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    R: io::AsyncBufRead + Unpin,
+    D: backend::Decodable,
+{
+    type Item = Result<D, <F as backend::Format>::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Self {
+            deserializer,
+            framing,
+            state,
+            remaining,
+        } = &mut *self;
+
+        loop {
+            match state {
+                RecvState::Ready => {
+                    match Pin::new(&mut deserializer.reader).poll_fill_buf(cx) {
+                        Poll::Pending => return Poll::Pending,
+                        Poll::Ready(Ok([])) => return Poll::Ready(None),
+                        Poll::Ready(Ok(_)) => *state = RecvState::Header(HeaderDecoder::new(framing)),
+                        Poll::Ready(Err(e)) => {
+                            *state = RecvState::Error;
+                            return Poll::Ready(Some(Err(e.into())));
+                        }
+                    }
+                }
+                RecvState::Header(dec) => match dec.poll_decode(&deserializer.format, &mut deserializer.reader, cx) {
+                    PollDecodeStatus::Fini(len) => {
+                        *remaining = len;
+                        if len <= framing.max_frame_len {
+                            *state = RecvState::Body(len, Decode::init());
+                        } else if let Oversized::Skip = framing.oversized {
+                            *state = RecvState::Skip { report_eof: true };
+                        } else {
+                            *state = RecvState::Error;
+                            return Poll::Ready(Some(Err(F::limit_exceeded_err(LimitExceeded::FrameLen))));
+                        }
+                    }
+                    PollDecodeStatus::Pending => return Poll::Pending,
+                    PollDecodeStatus::Error(e) => {
+                        *state = RecvState::Error;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+                RecvState::Body(len, dec) => {
+                    let mut reader = FrameReader::new(&mut deserializer.reader, remaining);
+                    match dec.poll_decode(&deserializer.format, &mut reader, cx) {
+                        PollDecodeStatus::Fini(d) => {
+                            *state = RecvState::Skip { report_eof: true };
+                            return Poll::Ready(Some(Ok(d)));
+                        }
+                        PollDecodeStatus::Pending => return Poll::Pending,
+                        PollDecodeStatus::Error(e) => {
+                            // A truncated frame has already been reported by the decoder
+                            let offset = *len - *remaining;
+                            *state = RecvState::Skip { report_eof: false };
+                            return Poll::Ready(Some(Err(F::offset_err(e, offset))));
+                        }
+                    }
+                }
+                RecvState::Skip { report_eof } => {
+                    while *remaining > 0 {
+                        match Pin::new(&mut deserializer.reader).poll_fill_buf(cx) {
+                            Poll::Pending => return Poll::Pending,
+                            Poll::Ready(Ok([])) => {
+                                let report = *report_eof;
+                                *state = RecvState::Error;
+                                return Poll::Ready(report.then(|| Err(io::error::unexpected_eof().into())));
+                            }
+                            Poll::Ready(Ok(buf)) => {
+                                let n = min(buf.len(), *remaining);
+                                Pin::new(&mut deserializer.reader).consume(n);
+                                *remaining -= n;
+                            }
+                            Poll::Ready(Err(e)) => {
+                                *state = RecvState::Error;
+                                return Poll::Ready(Some(Err(e.into())));
+                            }
+                        }
+                    }
+                    *state = RecvState::Ready;
+                }
+                RecvState::Error => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Implements the [Stream](futures::Stream) trait, yielding each length-delimited item.
+///
+/// Frames that cannot be decoded (e.g. messages unknown to this version of a peer)
+/// are silently skipped.  Errors that leave the stream unable to locate the next
+/// frame terminate it.  See [FramedTryDeserialize] for a stream that surfaces errors.
+pub struct FramedDeserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{
+    inner: FramedTryDeserialize<F, R, D>,
+}
+
+impl<F, R, D> FramedDeserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{
+    /// Instantiates a new [FramedDeserialize] stream for the data type `D` from the given `deserializer`
+    pub fn new(deserializer: Deserializer<F, R>, framing: Framing) -> Self {
+        Self {
+            inner: FramedTryDeserialize::new(deserializer, framing),
+        }
+    }
+
+    /// Returns `true` if the stream is positioned on a frame boundary, `false` if not
+    pub fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    /// Consumes the stream and attempts to return the underlying decoding `format` and `reader`.
+    ///
+    /// If the stream is not in the `Ready` state, the underlying reader will be consumed, and
+    /// the underlying `format` will be returned as an error.
+    pub fn try_into_inner(self) -> Result<Deserializer<F, R>, F> {
+        self.inner.try_into_inner()
+    }
+}
+
+impl<F, R, D> Unpin for FramedDeserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    D: backend::Decodable,
+{}
+
+impl<F, R, D> futures::Stream for FramedDeserialize<F, R, D>
+where
+    F: backend::FormatDecode,
+    R: io::AsyncBufRead + Unpin,
+    D: backend::Decodable,
+{
+    type Item = D;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match futures::ready!(Pin::new(&mut self.inner).poll_next(cx)) {
+                Some(Ok(d))                        => return Poll::Ready(Some(d)),
+                Some(Err(_)) if !self.inner.is_terminated() => continue,
+                _                                  => return Poll::Ready(None),
+            }
+        }
+    }
+}
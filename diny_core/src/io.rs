@@ -28,6 +28,46 @@ pub mod error {
         io::ErrorKind::UnexpectedEof.into()
     }
     
+    /// Helper method to instantiate an [InvalidData](futures::io::ErrorKind::InvalidData) [error](futures::io::Error)
+    /// identifying the [decode limit](crate::backend::DecodeLimits) that was exceeded
+    #[inline(always)]
+    pub fn limit_exceeded(kind: crate::backend::LimitExceeded) -> io::Error {
+        #[cfg(feature = "std")]
+        return io::Error::new(io::ErrorKind::InvalidData, kind);
+        #[cfg(not(feature = "std"))]
+        return { let _ = kind; invalid_data() };
+    }
+
+    /// Helper method to instantiate an [InvalidData](futures::io::ErrorKind::InvalidData) [error](futures::io::Error)
+    /// carrying the [mismatched fingerprints](crate::schema::FingerprintMismatch)
+    #[inline(always)]
+    pub fn fingerprint_mismatch(mismatch: crate::schema::FingerprintMismatch) -> io::Error {
+        #[cfg(feature = "std")]
+        return io::Error::new(io::ErrorKind::InvalidData, mismatch);
+        #[cfg(not(feature = "std"))]
+        return { let _ = mismatch; invalid_data() };
+    }
+
+    /// Helper method to annotate an [error](futures::io::Error) with the [path segment](crate::error::PathSegment)
+    /// within which decoding failed, see [DecodeError](crate::error::DecodeError)
+    #[inline(always)]
+    pub fn within(err: io::Error, segment: crate::error::PathSegment) -> io::Error {
+        #[cfg(feature = "std")]
+        return crate::error::DecodeError::annotate(err, |context| context.push_outer(segment));
+        #[cfg(not(feature = "std"))]
+        return { let _ = segment; err };
+    }
+
+    /// Helper method to annotate an [error](futures::io::Error) with the number of bytes
+    /// consumed before decoding failed, see [DecodeError](crate::error::DecodeError)
+    #[inline(always)]
+    pub fn at_offset(err: io::Error, offset: usize) -> io::Error {
+        #[cfg(feature = "std")]
+        return crate::error::DecodeError::annotate(err, |context| context.set_offset(offset));
+        #[cfg(not(feature = "std"))]
+        return { let _ = offset; err };
+    }
+
      /// Helper method to instantiate an [WriteZero](futures::io::ErrorKind::WriteZero) [error](futures::io::Error)
      #[inline(always)]
     pub fn write_zero() -> io::Error {
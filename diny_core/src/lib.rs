@@ -22,18 +22,37 @@ mod macros;
 /// Types and traits implemented by backend [formatters](backend::Format)
 pub mod backend;
 
+/// Synchronous serialization to and from byte slices and blocking io
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub mod blocking;
+
 /// Helper modules for implementing buffered serialization primitives
 pub mod buffer;
 
 /// Types used to support deserialization streams
 pub mod deserializer;
 
+/// Context describing where in the input decoding failed
+pub mod error;
+
+/// Types used to support length-delimited framing of sinks and streams
+pub mod framing;
+
 /// Re-export of io related structures
 pub mod io;
 
 /// Types used to suport serialization sinks
 pub mod serializer;
 
+/// Static descriptions of the wire shape of serializable types
+pub mod schema;
+
+/// Serialization of types implementing serde's traits
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+pub mod serde;
+
 /// Helper modules that may be externally useful
 pub mod util;
 
@@ -54,7 +73,15 @@ pub use backend::{
     AsyncDeserialize,
     AsyncSerialize,
     AsyncSerialization,
+    encoded_len,
+    max_encoded_len,
 };
 
-pub use deserializer::{deserializer, Deserialize, Deserializer};
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use blocking::{from_reader, from_slice, to_vec, to_writer};
+
+pub use deserializer::{deserializer, Deserialize, Deserializer, TryDeserialize};
+pub use framing::{FramedDeserialize, FramedSerialize, FramedTryDeserialize, Framing};
+pub use schema::Describe;
 pub use serializer::{serializer, Serialize, Serializer};
@@ -0,0 +1,708 @@
+//! A static description of the wire shape of serializable types.
+//!
+//! Every type that diny knows how to serialize implements [Describe], whose
+//! [SCHEMA](Describe::SCHEMA) captures how the type is laid out by any
+//! format: primitives, options and results, sequences and maps along with
+//! the types of their items, arrays with their length, and for derived
+//! structs and enums the names, tags and types of their encoded fields and
+//! the indices of their variants.
+//!
+//! Nested types are referred to via a [SchemaRef], which is only resolved
+//! on demand, so that recursive types may be described.  Structs and enums
+//! are identified by name, along with the type arguments of generic ones,
+//! when nested, and their [definitions](Schema::definitions) may be collected
+//! separately, for instance to document a protocol.
+//!
+//! Each schema also has a 64-bit [fingerprint](Schema::fingerprint), which a
+//! [Serializer](crate::Serializer) may [write](crate::Serializer::write_fingerprint)
+//! ahead of its items, for a [Deserializer](crate::Deserializer) to
+//! [verify](crate::Deserializer::verify_fingerprint) before decoding them.
+//!
+//! The schema of a type is displayed as it would be referred to by another:
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use diny_core::schema::Describe;
+//!
+//! assert_eq!(<HashMap<String, Vec<[u8; 4]>>>::SCHEMA.to_string(), "{string: [[u8; 4]]}");
+//! ```
+
+use core::fmt;
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+
+/// A type whose shape on the wire can be described statically.
+///
+/// An implementation is generated alongside the encoder of every derived
+/// struct and enum.
+pub trait Describe {
+    /// The shape of the type on the wire
+    const SCHEMA: Schema;
+}
+
+/// The shape of a type on the wire.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Schema {
+    /// The unit type, `()`
+    Unit,
+    /// A `bool`
+    Bool,
+    /// An `i8`
+    I8,
+    /// An `i16`
+    I16,
+    /// An `i32`
+    I32,
+    /// An `i64`
+    I64,
+    /// An `i128`
+    I128,
+    /// A `u8`
+    U8,
+    /// A `u16`
+    U16,
+    /// A `u32`
+    U32,
+    /// A `u64`
+    U64,
+    /// A `u128`
+    U128,
+    /// An `f32`
+    F32,
+    /// An `f64`
+    F64,
+    /// A `char`
+    Char,
+    /// A UTF-8 string
+    String,
+    /// An opaque slice of bytes
+    Bytes,
+    /// An optional value
+    Option(SchemaRef),
+    /// Either an `Ok` or an `Err` value
+    Result(SchemaRef, SchemaRef),
+    /// A variable length sequence of items
+    Seq(SchemaRef),
+    /// A variable length sequence of keys and their values
+    Map(SchemaRef, SchemaRef),
+    /// A fixed length array of items
+    Array(SchemaRef, usize),
+    /// A tuple of positional members
+    Tuple(&'static [SchemaRef]),
+    /// A derived struct
+    Struct(&'static StructSchema),
+    /// A derived enum
+    Enum(&'static EnumSchema),
+    /// A field encoded `with` another module, identified by the module's path
+    Custom(&'static str),
+}
+
+/// A lazily resolved reference to the [Schema] of a nested type.
+#[derive(Copy, Clone)]
+pub struct SchemaRef(fn() -> Schema);
+
+/// The encoded fields of a derived struct.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StructSchema {
+    /// The name of the struct
+    pub name: &'static str,
+    /// The type arguments of a generic struct, in order
+    pub params: &'static [SchemaRef],
+    /// The encoded fields in order, excluding any that are skipped
+    pub fields: &'static [FieldSchema],
+    /// Whether each encoding is preceded by its length, as for a struct
+    /// marked `#[diny(versioned)]`
+    pub versioned: bool,
+}
+
+/// The variants of a derived enum.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EnumSchema {
+    /// The name of the enum
+    pub name: &'static str,
+    /// The type arguments of a generic enum, in order
+    pub params: &'static [SchemaRef],
+    /// Every variant, in declaration order
+    pub variants: &'static [VariantSchema],
+}
+
+/// A single variant of a derived enum.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VariantSchema {
+    /// The index that identifies the variant on the wire
+    pub index: usize,
+    /// The name of the variant
+    pub name: &'static str,
+    /// The encoded fields in order, excluding any that are skipped
+    pub fields: &'static [FieldSchema],
+}
+
+/// A single encoded field of a derived struct or enum variant.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// The name of the field, or `None` for positional fields
+    pub name: Option<&'static str>,
+    /// The numeric tag of the field, as used by formats such as protobuf
+    pub tag: u32,
+    /// The shape of the field
+    pub schema: SchemaRef,
+    /// The version of a versioned struct in which the field was added, or
+    /// `None` for an original field
+    pub since: Option<u32>,
+}
+
+fn schema_of<T: Describe + ?Sized>() -> Schema {
+    T::SCHEMA
+}
+
+impl SchemaRef {
+    /// Refer to the schema of the type `T`.
+    pub const fn of<T: Describe + ?Sized>() -> Self {
+        Self(schema_of::<T>)
+    }
+
+    /// Refer to the schema returned by `f`.
+    pub const fn new(f: fn() -> Schema) -> Self {
+        Self(f)
+    }
+
+    /// Resolve the schema being referred to.
+    pub fn get(&self) -> Schema {
+        (self.0)()
+    }
+}
+
+/// Structs and enums are compared by name and type arguments, so that
+/// recursive types may be compared without recursing indefinitely.
+impl PartialEq for SchemaRef {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.get(), other.get());
+        match a.name() {
+            Some(_) => a.is_same_definition(&b),
+            None    => a == b,
+        }
+    }
+}
+
+impl Eq for SchemaRef {}
+
+impl fmt::Debug for SchemaRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.get(), f)
+    }
+}
+
+impl fmt::Display for SchemaRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.get(), f)
+    }
+}
+
+/// The [fingerprint](Schema::fingerprint) of the schema of `T`.
+pub fn fingerprint<T: Describe + ?Sized>() -> u64 {
+    T::SCHEMA.fingerprint()
+}
+
+/// A fingerprint read from a peer did not match that of the local schema.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FingerprintMismatch {
+    /// The fingerprint of the local schema
+    pub expected: u64,
+    /// The fingerprint read from the peer
+    pub found: u64,
+}
+
+impl fmt::Display for FingerprintMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "schema fingerprint mismatch: expected {:016x}, found {:016x}", self.expected, self.found)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FingerprintMismatch {}
+
+impl Schema {
+    /// The name of the struct or enum being described, if it is one
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Self::Struct(s) => Some(s.name),
+            Self::Enum(e)   => Some(e.name),
+            _               => None,
+        }
+    }
+
+    /// The type arguments of a generic struct or enum, which are empty for
+    /// anything else
+    pub fn params(&self) -> &'static [SchemaRef] {
+        match self {
+            Self::Struct(s) => s.params,
+            Self::Enum(e)   => e.params,
+            _               => &[],
+        }
+    }
+
+    /// Whether both describe the same struct or enum, having the same name
+    /// and type arguments, without comparing their definitions.
+    pub fn is_same_definition(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Struct(a), Self::Struct(b)) => a.name == b.name && a.params == b.params,
+            (Self::Enum  (a), Self::Enum  (b)) => a.name == b.name && a.params == b.params,
+            _                                  => false,
+        }
+    }
+
+    /// Display the definition of a struct or enum, rather than just its name.
+    pub fn definition(&self) -> impl fmt::Display {
+        struct Definition(Schema);
+
+        impl fmt::Display for Definition {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match &self.0 {
+                    Schema::Struct(s) => fmt::Display::fmt(s, f),
+                    Schema::Enum(e)   => fmt::Display::fmt(e, f),
+                    other             => fmt::Display::fmt(other, f),
+                }
+            }
+        }
+
+        Definition(*self)
+    }
+
+    /// Invoke `f` with a reference to every type nested directly within this one.
+    pub fn for_each_nested(&self, mut f: impl FnMut(SchemaRef)) {
+        match self {
+            Self::Option(t) | Self::Seq(t) | Self::Array(t, _) => f(*t),
+            Self::Result(a, b) | Self::Map(a, b)               => { f(*a); f(*b) }
+            Self::Tuple(ts)                                    => ts.iter().copied().for_each(f),
+            Self::Struct(s)                                    => s.fields.iter().for_each(|field| f(field.schema)),
+            Self::Enum(e)                                      => e.variants.iter().flat_map(|v| v.fields).for_each(|field| f(field.schema)),
+            _                                                  => {}
+        }
+    }
+
+    /// A 64-bit hash of the structure of the schema, that is stable across
+    /// builds and platforms.
+    ///
+    /// Any change to the shape of the type, or any type nested within it,
+    /// changes the fingerprint: adding, removing or reordering fields, renaming
+    /// them or changing their tags, or changing the names or indices of enum
+    /// variants.  The names of structs and enums themselves are not included,
+    /// so renaming a type leaves its fingerprint unchanged.  Nor are the fields
+    /// of a versioned struct that are marked `since` a later version, so that
+    /// its versions, which can read each other's encodings, agree.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hash_schema(*self, &mut hasher, None);
+        hasher.0
+    }
+
+    /// Collect the schemas of this and every nested struct and enum, each one
+    /// once, in the order they are first encountered.
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn definitions(&self) -> Vec<Schema> {
+        fn visit(schema: Schema, defs: &mut Vec<Schema>) {
+            if schema.name().is_some() {
+                if defs.iter().any(|d| d.is_same_definition(&schema)) {
+                    return;
+                }
+                defs.push(schema);
+            }
+            schema.for_each_nested(|nested| visit(nested.get(), defs));
+        }
+
+        let mut defs = Vec::new();
+        visit(*self, &mut defs);
+        defs
+    }
+}
+
+/// Formats the type as it would be referred to from another, naming structs
+/// and enums rather than defining them.
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unit         => f.write_str("()"),
+            Self::Bool         => f.write_str("bool"),
+            Self::I8           => f.write_str("i8"),
+            Self::I16          => f.write_str("i16"),
+            Self::I32          => f.write_str("i32"),
+            Self::I64          => f.write_str("i64"),
+            Self::I128         => f.write_str("i128"),
+            Self::U8           => f.write_str("u8"),
+            Self::U16          => f.write_str("u16"),
+            Self::U32          => f.write_str("u32"),
+            Self::U64          => f.write_str("u64"),
+            Self::U128         => f.write_str("u128"),
+            Self::F32          => f.write_str("f32"),
+            Self::F64          => f.write_str("f64"),
+            Self::Char         => f.write_str("char"),
+            Self::String       => f.write_str("string"),
+            Self::Bytes        => f.write_str("bytes"),
+            Self::Option(t)    => write!(f, "Option<{}>", t),
+            Self::Result(o, e) => write!(f, "Result<{}, {}>", o, e),
+            Self::Seq(t)       => write!(f, "[{}]", t),
+            Self::Map(k, v)    => write!(f, "{{{}: {}}}", k, v),
+            Self::Array(t, n)  => write!(f, "[{}; {}]", t, n),
+            Self::Tuple([t])   => write!(f, "({},)", t),
+            Self::Tuple(ts)    => {
+                f.write_str("(")?;
+                write_separated(f, ts.iter(), |f, t| write!(f, "{}", t))?;
+                f.write_str(")")
+            },
+            Self::Struct(s)    => write_name(f, s.name, s.params),
+            Self::Enum(e)      => write_name(f, e.name, e.params),
+            Self::Custom(path) => write!(f, "custom({})", path),
+        }
+    }
+}
+
+/// Formats the definition of the struct, its fields preceded by their tags.
+impl fmt::Display for StructSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.versioned {
+            f.write_str("versioned ")?;
+        }
+        f.write_str("struct ")?;
+        write_name(f, self.name, self.params)?;
+        write_fields(f, self.fields)?;
+        if !matches!(self.fields.first(), Some(FieldSchema { name: Some(_), .. })) {
+            f.write_str(";")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the definition of the enum, each variant followed by its index.
+impl fmt::Display for EnumSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("enum ")?;
+        write_name(f, self.name, self.params)?;
+        f.write_str(" {")?;
+        if !self.variants.is_empty() {
+            f.write_str(" ")?;
+            write_separated(f, self.variants.iter(), |f, v| {
+                f.write_str(v.name)?;
+                write_fields(f, v.fields)?;
+                write!(f, " = {}", v.index)
+            })?;
+            f.write_str(" ")?;
+        }
+        f.write_str("}")
+    }
+}
+
+fn write_name(f: &mut fmt::Formatter<'_>, name: &str, params: &[SchemaRef]) -> fmt::Result {
+    f.write_str(name)?;
+    if !params.is_empty() {
+        f.write_str("<")?;
+        write_separated(f, params.iter(), |f, t| write!(f, "{}", t))?;
+        f.write_str(">")?;
+    }
+    Ok(())
+}
+
+fn write_fields(f: &mut fmt::Formatter<'_>, fields: &[FieldSchema]) -> fmt::Result {
+    let (open, close) = match fields.first() {
+        None                                      => return Ok(()),
+        Some(FieldSchema { name: Some(_), .. })   => (" { ", " }"),
+        Some(FieldSchema { name: None, .. })      => ("(", ")"),
+    };
+
+    f.write_str(open)?;
+    write_separated(f, fields.iter(), |f, field| {
+        write!(f, "#{} ", field.tag)?;
+        if let Some(name) = field.name {
+            write!(f, "{}: ", name)?;
+        }
+        write!(f, "{}", field.schema)?;
+        if let Some(since) = field.since {
+            write!(f, " (since {})", since)?;
+        }
+        Ok(())
+    })?;
+    f.write_str(close)
+}
+
+fn write_separated<T>(f: &mut fmt::Formatter<'_>, items: impl Iterator<Item = T>, mut write: impl FnMut(&mut fmt::Formatter<'_>, T) -> fmt::Result) -> fmt::Result {
+    for (idx, item) in items.enumerate() {
+        if idx > 0 {
+            f.write_str(", ")?;
+        }
+        write(f, item)?;
+    }
+    Ok(())
+}
+
+/// The 64-bit FNV-1a hash, chosen for being fully specified and trivial to reproduce.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= u64::from(*b);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.write(&[n])
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes())
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.write_u64(s.len() as u64);
+        self.write(s.as_bytes())
+    }
+}
+
+/// The chain of structs and enums enclosing the schema being hashed, by which
+/// recursive references are recognised. Each generic instantiation is a
+/// distinct definition, so `Wrapper<Wrapper<u8>>` is not mistaken for a
+/// reference back to the outer `Wrapper`.
+struct Enclosing<'a> {
+    schema: Schema,
+    outer: Option<&'a Enclosing<'a>>,
+}
+
+fn hash_schema(schema: Schema, h: &mut Fnv1a, enclosing: Option<&Enclosing<'_>>) {
+    if schema.name().is_some() {
+        let mut depth = 0u64;
+        let mut cur = enclosing;
+        while let Some(e) = cur {
+            if e.schema.is_same_definition(&schema) {
+                h.write_u8(0xff);
+                h.write_u64(depth);
+                return;
+            }
+            depth += 1;
+            cur = e.outer;
+        }
+    }
+
+    match schema {
+        Schema::Unit         => h.write_u8(0),
+        Schema::Bool         => h.write_u8(1),
+        Schema::I8           => h.write_u8(2),
+        Schema::I16          => h.write_u8(3),
+        Schema::I32          => h.write_u8(4),
+        Schema::I64          => h.write_u8(5),
+        Schema::I128         => h.write_u8(6),
+        Schema::U8           => h.write_u8(7),
+        Schema::U16          => h.write_u8(8),
+        Schema::U32          => h.write_u8(9),
+        Schema::U64          => h.write_u8(10),
+        Schema::U128         => h.write_u8(11),
+        Schema::F32          => h.write_u8(12),
+        Schema::F64          => h.write_u8(13),
+        Schema::Char         => h.write_u8(14),
+        Schema::String       => h.write_u8(15),
+        Schema::Bytes        => h.write_u8(16),
+        Schema::Option(t)    => {
+            h.write_u8(17);
+            hash_schema(t.get(), h, enclosing);
+        },
+        Schema::Result(o, e) => {
+            h.write_u8(18);
+            hash_schema(o.get(), h, enclosing);
+            hash_schema(e.get(), h, enclosing);
+        },
+        Schema::Seq(t)       => {
+            h.write_u8(19);
+            hash_schema(t.get(), h, enclosing);
+        },
+        Schema::Map(k, v)    => {
+            h.write_u8(20);
+            hash_schema(k.get(), h, enclosing);
+            hash_schema(v.get(), h, enclosing);
+        },
+        Schema::Array(t, n)  => {
+            h.write_u8(21);
+            h.write_u64(n as u64);
+            hash_schema(t.get(), h, enclosing);
+        },
+        Schema::Tuple(ts)    => {
+            h.write_u8(22);
+            h.write_u64(ts.len() as u64);
+            ts.iter().for_each(|t| hash_schema(t.get(), h, enclosing));
+        },
+        Schema::Struct(s)    => {
+            h.write_u8(if s.versioned { 26 } else { 23 });
+            hash_fields(s.fields, s.versioned, h, &Enclosing { schema, outer: enclosing });
+        },
+        Schema::Enum(e)      => {
+            h.write_u8(24);
+            h.write_u64(e.variants.len() as u64);
+            let enclosing = Enclosing { schema, outer: enclosing };
+            for v in e.variants {
+                h.write_u64(v.index as u64);
+                h.write_str(v.name);
+                hash_fields(v.fields, false, h, &enclosing);
+            }
+        },
+        Schema::Custom(path) => {
+            h.write_u8(25);
+            h.write_str(path);
+        },
+    }
+}
+
+// The fields a versioned struct gains in later versions are left out, since
+// its old and new versions each read the other's encoding.
+fn hash_fields(fields: &[FieldSchema], versioned: bool, h: &mut Fnv1a, enclosing: &Enclosing<'_>) {
+    let hashed = || fields.iter().filter(move |field| !(versioned && field.since.is_some()));
+    h.write_u64(hashed().count() as u64);
+    for field in hashed() {
+        match field.name {
+            None       => h.write_u8(0),
+            Some(name) => {
+                h.write_u8(1);
+                h.write_str(name);
+            },
+        }
+        h.write_u64(u64::from(field.tag));
+        hash_schema(field.schema.get(), h, Some(enclosing));
+    }
+}
+
+macro_rules! describe_as {
+    ($schema: expr => $($t: ty),+ $(,)?) => {
+        $(
+            impl Describe for $t {
+                const SCHEMA: Schema = $schema;
+            }
+        )+
+    };
+}
+
+describe_as!(Schema::Unit   => ());
+describe_as!(Schema::Bool   => bool);
+describe_as!(Schema::I8     => i8);
+describe_as!(Schema::I16    => i16);
+describe_as!(Schema::I32    => i32);
+describe_as!(Schema::I64    => i64);
+describe_as!(Schema::I128   => i128);
+describe_as!(Schema::U8     => u8);
+describe_as!(Schema::U16    => u16);
+describe_as!(Schema::U32    => u32);
+describe_as!(Schema::U64    => u64);
+describe_as!(Schema::U128   => u128);
+describe_as!(Schema::F32    => f32);
+describe_as!(Schema::F64    => f64);
+describe_as!(Schema::Char   => char);
+describe_as!(Schema::String => str);
+describe_as!(Schema::Bytes  => [u8]);
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+describe_as!(Schema::String => String);
+
+impl<T: Describe> Describe for Option<T> {
+    const SCHEMA: Schema = Schema::Option(SchemaRef::of::<T>());
+}
+
+impl<O: Describe, E: Describe> Describe for Result<O, E> {
+    const SCHEMA: Schema = Schema::Result(SchemaRef::of::<O>(), SchemaRef::of::<E>());
+}
+
+impl<T: Describe, const L: usize> Describe for [T; L] {
+    const SCHEMA: Schema = Schema::Array(SchemaRef::of::<T>(), L);
+}
+
+impl<T: ?Sized> Describe for core::marker::PhantomData<T> {
+    const SCHEMA: Schema = Schema::Unit;
+}
+
+macro_rules! describe_tuple {
+    ($($T: ident),+) => {
+        impl<$($T: Describe),+> Describe for ($($T,)+) {
+            const SCHEMA: Schema = Schema::Tuple(&[$(SchemaRef::of::<$T>()),+]);
+        }
+    };
+}
+
+describe_tuple!(T0);
+describe_tuple!(T0, T1);
+describe_tuple!(T0, T1, T2);
+describe_tuple!(T0, T1, T2, T3);
+describe_tuple!(T0, T1, T2, T3, T4);
+describe_tuple!(T0, T1, T2, T3, T4, T5);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6, T7);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+describe_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
+
+/// Wrappers are encoded exactly as the value they wrap.
+macro_rules! describe_wrapper {
+    ($($t: ty),+ $(,)?) => {
+        $(
+            impl<T: Describe> Describe for $t {
+                const SCHEMA: Schema = T::SCHEMA;
+            }
+        )+
+    };
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+describe_wrapper!(Box<T>);
+
+#[cfg(feature = "std")]
+describe_wrapper!(
+    std::cell::Cell<T>,
+    std::cell::RefCell<T>,
+    std::rc::Rc<T>,
+    std::sync::Arc<T>,
+);
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<T: Describe> Describe for Vec<T> {
+    const SCHEMA: Schema = Schema::Seq(SchemaRef::of::<T>());
+}
+
+#[cfg(feature = "std")]
+mod collections {
+    use std::collections::{BinaryHeap, BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+    use super::{Describe, Schema, SchemaRef};
+
+    impl<T: Describe> Describe for VecDeque<T> {
+        const SCHEMA: Schema = Schema::Seq(SchemaRef::of::<T>());
+    }
+
+    impl<T: Describe> Describe for LinkedList<T> {
+        const SCHEMA: Schema = Schema::Seq(SchemaRef::of::<T>());
+    }
+
+    impl<T: Describe> Describe for BinaryHeap<T> {
+        const SCHEMA: Schema = Schema::Seq(SchemaRef::of::<T>());
+    }
+
+    impl<T: Describe> Describe for BTreeSet<T> {
+        const SCHEMA: Schema = Schema::Seq(SchemaRef::of::<T>());
+    }
+
+    impl<T: Describe, S> Describe for HashSet<T, S> {
+        const SCHEMA: Schema = Schema::Seq(SchemaRef::of::<T>());
+    }
+
+    impl<K: Describe, V: Describe> Describe for BTreeMap<K, V> {
+        const SCHEMA: Schema = Schema::Map(SchemaRef::of::<K>(), SchemaRef::of::<V>());
+    }
+
+    impl<K: Describe, V: Describe, S> Describe for HashMap<K, V, S> {
+        const SCHEMA: Schema = Schema::Map(SchemaRef::of::<K>(), SchemaRef::of::<V>());
+    }
+}
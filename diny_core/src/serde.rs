@@ -0,0 +1,906 @@
+//! Serialization of any type implementing [serde's](::serde) traits with the
+//! primitives of a diny [format](crate::backend::Format).
+//!
+//! Since serde is synchronous, a value is first serialized into a scratch
+//! buffer, which is then written with the format's [raw value](crate::backend::FormatEncodeHooks::EncodeRaw)
+//! encoder.  By default, that writes the buffer as a byte vector, i.e. preceded
+//! by its length in whatever manner the format writes one, but a self-delimiting
+//! format may embed it as it is, as `diny_json` does.  Decoding reads the whole
+//! of the value, bounded by the format's [maximum bytes length](crate::backend::DecodeLimits::max_bytes_len),
+//! before deserializing it.  Likewise, the scratch buffer may grow no longer than the format's
+//! [maximum buffered length](crate::backend::FormatEncode::max_buffered_len).
+//!
+//! Within the scratch buffer, serde's data model is written with the format's own
+//! encoders:
+//!
+//! * Primitives, strings and bytes are written as their diny counterparts, and
+//!   options as diny writes an [Option].
+//! * Sequences, tuples and structs are all written as sequences, the fields of a
+//!   struct by position rather than by name.
+//! * Maps are written as diny writes a map.
+//! * An enum variant is written as a sequence of its index followed by its fields,
+//!   if any.
+//!
+//! Diny formats are not self-describing, so types that rely upon
+//! [deserialize_any](::serde::Deserializer::deserialize_any), e.g. untagged enums,
+//! or that skip fields conditionally, cannot be deserialized.
+//!
+//! Wrap a value in [Serde] to serialize it directly, or apply `#[diny(serde)]` to
+//! a field of a derived struct or enum:
+//!
+//! ```ignore
+//! #[derive(diny::AsyncSerialization)]
+//! struct Session {
+//!     #[diny(serde)]
+//!     id: uuid::Uuid,
+//!     started: diny::serde::Serde<chrono::DateTime<chrono::Utc>>,
+//! }
+//! ```
+
+use core::{fmt, marker::PhantomData, task::Context};
+use ::serde::{de::{self, IntoDeserializer}, ser};
+use crate::backend::{self, Decode, Encode, FormatDecode, FormatEncode, internal::{MapLen, OptionTag, SequenceLen, VariantIdx}};
+use crate::blocking::{decode, encode};
+use crate::{io, util::AsyncSliceReader};
+
+
+/// Wraps any [Serialize](::serde::Serialize) or [Deserialize](::serde::Deserialize)
+/// type, so that it may be serialized with a diny format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Serde<T>(pub T);
+
+impl<T> Serde<T> {
+    /// Unwraps the value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Serde<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> core::ops::Deref for Serde<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> core::ops::DerefMut for Serde<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: ser::Serialize> ser::Serialize for Serde<T> {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: de::Deserialize<'de>> de::Deserialize<'de> for Serde<T> {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+/// Serializes `data` with the encoders of the indicated [format](FormatEncode),
+/// as written within the scratch buffer of a [Serde] value.
+pub fn to_vec<F, T>(format: &F, data: &T) -> Result<Vec<u8>, F::Error>
+where
+    F: FormatEncode,
+    T: ser::Serialize + ?Sized,
+{
+    let mut scratch = Scratch::new(format.max_buffered_len());
+    data.serialize(Serializer { format, out: &mut scratch })
+    .map_err(|e| e.into_format(io::ErrorKind::InvalidInput))?;
+    scratch.finish(format)
+}
+
+/// Deserializes a `T` with the decoders of the indicated [format](FormatDecode),
+/// as read from the scratch buffer of a [Serde] value.
+///
+/// The encoding must span the whole of the slice; any bytes left over once
+/// the `T` has been decoded are [invalid data](crate::backend::Format::invalid_data_err).
+pub fn from_slice<F, T>(format: &F, bytes: &[u8]) -> Result<T, F::Error>
+where
+    F: FormatDecode,
+    T: de::DeserializeOwned,
+{
+    let mut deserializer = Deserializer { format, reader: AsyncSliceReader::new(bytes) };
+    let data = T::deserialize(&mut deserializer).map_err(|e| e.into_format(io::ErrorKind::InvalidData))?;
+    if deserializer.reader.bytes_read() == bytes.len() {
+        Ok(data)
+    } else {
+        Err(F::invalid_data_err())
+    }
+}
+
+
+/// Encodes any [Serialize](::serde::Serialize) type, for use with `#[diny(serde)]`
+pub struct Encoder<F, T>(EncodeState<F>, PhantomData<fn(&T)>)
+where
+    F: FormatEncode,
+    T: ?Sized,
+;
+
+enum EncodeState<F: FormatEncode> {
+    Init,
+    Bytes(Vec<u8>, F::EncodeRaw),
+    Fini,
+}
+
+impl<F, T> Encode for Encoder<F, T>
+where
+    F: FormatEncode,
+    T: ser::Serialize + ?Sized,
+{
+    type Data = T;
+    type Format = F;
+
+    fn init(_data: &Self::Data) -> Self {
+        Self(EncodeState::Init, PhantomData)
+    }
+
+    fn start_encode<W>(format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, F::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match to_vec(format, data) {
+            Ok(bytes) => match F::EncodeRaw::start_encode(format, writer, &bytes, cx) {
+                backend::StartEncodeStatus::Fini         => backend::StartEncodeStatus::Fini,
+                backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self(EncodeState::Bytes(bytes, enc), PhantomData)),
+                backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e),
+            },
+            Err(e) => backend::StartEncodeStatus::Error(e),
+        }
+    }
+
+    fn poll_encode<W>(&mut self, format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<F::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let status = match &mut self.0 {
+            EncodeState::Init => match Self::start_encode(format, writer, data, cx) {
+                backend::StartEncodeStatus::Fini         => backend::PollEncodeStatus::Fini,
+                backend::StartEncodeStatus::Pending(enc) => { *self = enc; return backend::PollEncodeStatus::Pending; }
+                backend::StartEncodeStatus::Error(e)     => backend::PollEncodeStatus::Error(e),
+            },
+            EncodeState::Bytes(bytes, enc) => enc.poll_encode(format, writer, bytes, cx),
+            EncodeState::Fini => backend::PollEncodeStatus::Error(F::invalid_input_err()),
+        };
+        if !matches!(status, backend::PollEncodeStatus::Pending) {
+            self.0 = EncodeState::Fini;
+        }
+        status
+    }
+}
+
+/// Decodes any [Deserialize](::serde::Deserialize) type, for use with `#[diny(serde)]`
+pub struct Decoder<F, T>(F::DecodeRaw, PhantomData<fn() -> T>)
+where
+    F: FormatDecode,
+;
+
+impl<F, T> Decode for Decoder<F, T>
+where
+    F: FormatDecode,
+    T: de::DeserializeOwned,
+{
+    type Data = T;
+    type Format = F;
+
+    fn init() -> Self {
+        Self(F::DecodeRaw::init(), PhantomData)
+    }
+
+    fn start_decode<R>(format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, F::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        F::DecodeRaw::start_decode(format, reader, cx)
+        .and_then(
+            |bytes| from_slice(format, &bytes).into(),
+            |dec| Self(dec, PhantomData),
+        )
+    }
+
+    fn poll_decode<R>(&mut self, format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, F::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.poll_decode(format, reader, cx)
+        .and_then(|bytes| from_slice(format, &bytes).into())
+    }
+}
+
+impl<T> backend::Encodable for Serde<T>
+where
+    T: ser::Serialize,
+{
+    type Encoder<F: FormatEncode> = Encoder<F, Self>;
+}
+
+impl<T> backend::Decodable for Serde<T>
+where
+    T: de::DeserializeOwned,
+{
+    type Decoder<F: FormatDecode> = Decoder<F, Self>;
+}
+
+/// Serde values are opaque to diny, and described only as raw bytes.
+impl<T> crate::schema::Describe for Serde<T> {
+    const SCHEMA: crate::schema::Schema = crate::schema::Schema::Bytes;
+}
+
+impl<T> backend::AsyncSerialize for Serde<T>
+where
+    T: ser::Serialize,
+{
+    type Future<'w, F, W>
+    where
+        Self: 'w,
+        F: 'w + backend::FormatSerialize,
+        W: 'w + io::AsyncWrite + Unpin,
+    = backend::SerializeAll<'w, F, W, Self, Self::Encoder<F>>;
+
+    fn serialize<'w, F, W>(&'w self, format: &'w F, writer: &'w mut W) -> Self::Future<'w, F, W>
+    where
+        F: backend::FormatSerialize,
+        W: io::AsyncWrite + Unpin,
+    {
+        backend::SerializeAll::new(format, writer, self, <Self::Encoder::<F> as Encode>::init(self))
+    }
+}
+
+impl<T> backend::AsyncDeserialize for Serde<T>
+where
+    T: de::DeserializeOwned,
+{
+    type Future<'r, F, R>
+    where
+        F: 'r + backend::FormatDeserialize,
+        R: 'r + io::AsyncBufRead + Unpin,
+    = backend::DeserializeExact<'r, F, R, Self, Self::Decoder<F>>;
+
+    fn deserialize<'r, F, R>(format: &'r F, reader: &'r mut R) -> Self::Future<'r, F, R>
+    where
+        F: backend::FormatDeserialize,
+        R: io::AsyncBufRead + Unpin,
+    {
+        backend::DeserializeExact::new(format, reader, <Self::Decoder::<F> as Decode>::init())
+    }
+}
+
+
+/// Either an error reported by the format, or a message from serde itself
+enum Error<E> {
+    Format(E),
+    Custom(String),
+}
+
+impl<E: From<io::Error>> Error<E> {
+    fn into_format(self, kind: io::ErrorKind) -> E {
+        match self {
+            Self::Format(e)   => e,
+            Self::Custom(msg) => io::Error::new(kind, msg).into(),
+        }
+    }
+}
+
+impl<E> fmt::Debug for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<E> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Format(_)   => f.write_str("format error"),
+            Self::Custom(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl<E> std::error::Error for Error<E> {}
+
+impl<E> ser::Error for Error<E> {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl<E> de::Error for Error<E> {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+
+// The length of a compound precedes its items, but is only known once they
+// have all been serialized, since serde need not know it in advance.  So the
+// items are written straight into the scratch buffer, and each length is
+// encoded in its place when the buffer is finished.  The buffer may grow no
+// longer than the format's maximum buffered length.
+struct Scratch {
+    bytes: Vec<u8>,
+    lens: Vec<Len>,
+    max: usize,
+}
+
+struct Len {
+    at: usize,
+    count: usize,
+    map: bool,
+}
+
+impl Scratch {
+    fn new(max: usize) -> Self {
+        Self { bytes: Vec::new(), lens: Vec::new(), max }
+    }
+
+    fn check_len<F: FormatEncode>(len: usize, max: usize) -> Result<(), F::Error> {
+        if len > max {
+            Err(F::limit_exceeded_err(backend::LimitExceeded::BytesLen))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn encode<E, F>(&mut self, format: &F, data: &E::Data) -> Result<(), Error<F::Error>>
+    where
+        E: Encode<Format=F>,
+        F: FormatEncode,
+    {
+        encode::<E, _>(format, &mut self.bytes, data)
+        .and_then(|()| Self::check_len::<F>(self.bytes.len(), self.max))
+        .map_err(Error::Format)
+    }
+
+    fn finish<F: FormatEncode>(self, format: &F) -> Result<Vec<u8>, F::Error> {
+        let mut out = Vec::with_capacity(self.bytes.len() + self.lens.len());
+        let mut from = 0;
+        for len in self.lens {
+            out.extend_from_slice(&self.bytes[from..len.at]);
+            from = len.at;
+            if len.map {
+                encode::<<MapLen as backend::Encodable>::Encoder<F>, _>(format, &mut out, &len.count.into())?;
+            } else {
+                encode::<<SequenceLen as backend::Encodable>::Encoder<F>, _>(format, &mut out, &len.count.into())?;
+            }
+        }
+        out.extend_from_slice(&self.bytes[from..]);
+        Self::check_len::<F>(out.len(), self.max)?;
+        Ok(out)
+    }
+}
+
+struct Serializer<'a, F> {
+    format: &'a F,
+    out: &'a mut Scratch,
+}
+
+impl<'a, F: FormatEncode> Serializer<'a, F> {
+    fn put<E>(self, data: &E::Data) -> Result<(), Error<F::Error>>
+    where
+        E: Encode<Format=F>,
+    {
+        self.out.encode::<E, F>(self.format, data)
+    }
+
+    fn compound(self) -> Compound<'a, F> {
+        let idx = self.out.lens.len();
+        self.out.lens.push(Len { at: self.out.bytes.len(), count: 0, map: false });
+        Compound { format: self.format, out: self.out, idx }
+    }
+
+    fn variant(self, variant_index: u32) -> Result<Compound<'a, F>, Error<F::Error>> {
+        let mut compound = self.compound();
+        compound.item()?.put::<F::EncodeVariantIdx>(&VariantIdx::from(variant_index as usize))?;
+        Ok(compound)
+    }
+}
+
+// The items of every compound are counted as they are serialized
+struct Compound<'a, F> {
+    format: &'a F,
+    out: &'a mut Scratch,
+    idx: usize,
+}
+
+impl<'a, F: FormatEncode> Compound<'a, F> {
+    fn len(&mut self) -> &mut Len {
+        &mut self.out.lens[self.idx]
+    }
+
+    fn separator(&mut self) -> Result<(), Error<F::Error>> {
+        self.out.encode::<F::EncodeItemSeparator, F>(self.format, &())
+    }
+
+    fn item(&mut self) -> Result<Serializer<'_, F>, Error<F::Error>> {
+        if self.len().count > 0 {
+            self.separator()?;
+        }
+        self.len().count += 1;
+        Ok(Serializer { format: self.format, out: self.out })
+    }
+
+    fn entry_begin(&mut self) -> Result<Serializer<'_, F>, Error<F::Error>> {
+        if self.len().count > 0 {
+            self.separator()?;
+        }
+        self.out.encode::<F::EncodeMapEntryBegin, F>(self.format, &())?;
+        Ok(Serializer { format: self.format, out: self.out })
+    }
+
+    fn entry_value<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error<F::Error>> {
+        self.separator()?;
+        value.serialize(Serializer { format: self.format, out: self.out })?;
+        self.out.encode::<F::EncodeMapEntryEnd, F>(self.format, &())?;
+        self.len().count += 1;
+        Ok(())
+    }
+
+    fn end_map(mut self) -> Result<(), Error<F::Error>> {
+        self.len().map = true;
+        self.end_items()
+    }
+
+    fn end_items(self) -> Result<(), Error<F::Error>> {
+        self.out.encode::<F::EncodeCollectionEnd, F>(self.format, &())
+    }
+}
+
+macro_rules! serialize_primitive {
+    ($($method: ident($t: ty) => $enc: ident;)*) => {
+        $(
+            fn $method(self, v: $t) -> Result<(), Self::Error> {
+                self.put::<F::$enc>(&v)
+            }
+        )*
+    };
+}
+
+impl<'a, F: FormatEncode> ser::Serializer for Serializer<'a, F> {
+    type Ok = ();
+    type Error = Error<F::Error>;
+
+    type SerializeSeq = Compound<'a, F>;
+    type SerializeTuple = Compound<'a, F>;
+    type SerializeTupleStruct = Compound<'a, F>;
+    type SerializeTupleVariant = Compound<'a, F>;
+    type SerializeMap = Compound<'a, F>;
+    type SerializeStruct = Compound<'a, F>;
+    type SerializeStructVariant = Compound<'a, F>;
+
+    serialize_primitive! {
+        serialize_bool(bool) => EncodeBool;
+        serialize_i8  (i8  ) => EncodeI8;
+        serialize_i16 (i16 ) => EncodeI16;
+        serialize_i32 (i32 ) => EncodeI32;
+        serialize_i64 (i64 ) => EncodeI64;
+        serialize_i128(i128) => EncodeI128;
+        serialize_u8  (u8  ) => EncodeU8;
+        serialize_u16 (u16 ) => EncodeU16;
+        serialize_u32 (u32 ) => EncodeU32;
+        serialize_u64 (u64 ) => EncodeU64;
+        serialize_u128(u128) => EncodeU128;
+        serialize_f32 (f32 ) => EncodeF32;
+        serialize_f64 (f64 ) => EncodeF64;
+        serialize_char(char) => EncodeChar;
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Self::Error> {
+        self.put::<F::EncodeStr>(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> {
+        self.put::<F::EncodeByteSlice>(v)
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        self.out.encode::<<OptionTag as backend::Encodable>::Encoder<F>, F>(self.format, &0.into())?;
+        self.put::<F::EncodeUnit>(&())
+    }
+
+    fn serialize_some<T: ser::Serialize + ?Sized>(self, value: &T) -> Result<(), Self::Error> {
+        self.out.encode::<<OptionTag as backend::Encodable>::Encoder<F>, F>(self.format, &1.into())?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        self.put::<F::EncodeUnit>(&())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str) -> Result<(), Self::Error> {
+        self.variant(variant_index)?.end_items()
+    }
+
+    fn serialize_newtype_struct<T: ser::Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ser::Serialize + ?Sized>(self, _name: &'static str, variant_index: u32, _variant: &'static str, value: &T) -> Result<(), Self::Error> {
+        let mut compound = self.variant(variant_index)?;
+        value.serialize(compound.item()?)?;
+        compound.end_items()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self.compound())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self.compound())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self.compound())
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.variant(variant_index)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self.compound())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self.compound())
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.variant(variant_index)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+macro_rules! serialize_items {
+    ($($t: ident :: $method: ident;)*) => {
+        $(
+            impl<'a, F: FormatEncode> ser::$t for Compound<'a, F> {
+                type Ok = ();
+                type Error = Error<F::Error>;
+
+                fn $method<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+                    value.serialize(self.item()?)
+                }
+
+                fn end(self) -> Result<(), Self::Error> {
+                    self.end_items()
+                }
+            }
+        )*
+    };
+}
+
+serialize_items! {
+    SerializeSeq::serialize_element;
+    SerializeTuple::serialize_element;
+    SerializeTupleStruct::serialize_field;
+    SerializeTupleVariant::serialize_field;
+}
+
+impl<'a, F: FormatEncode> ser::SerializeStruct for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error<F::Error>;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self.item()?)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.end_items()
+    }
+}
+
+impl<'a, F: FormatEncode> ser::SerializeStructVariant for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error<F::Error>;
+
+    fn serialize_field<T: ser::Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self.item()?)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.end_items()
+    }
+}
+
+impl<'a, F: FormatEncode> ser::SerializeMap for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error<F::Error>;
+
+    fn serialize_key<T: ser::Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        key.serialize(self.entry_begin()?)
+    }
+
+    fn serialize_value<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.entry_value(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.end_map()
+    }
+}
+
+
+struct Deserializer<'a, F> {
+    format: &'a F,
+    reader: AsyncSliceReader<'a>,
+}
+
+impl<'a, F: FormatDecode> Deserializer<'a, F> {
+    fn take<D>(&mut self) -> Result<D::Data, Error<F::Error>>
+    where
+        D: Decode<Format=F>,
+    {
+        decode::<D, _>(self.format, &mut self.reader).map_err(Error::Format)
+    }
+
+    fn limit_exceeded(kind: backend::LimitExceeded) -> Error<F::Error> {
+        Error::Format(F::limit_exceeded_err(kind))
+    }
+
+    // Decodes the items of a compound with `f`, and then ensures that all of them were read
+    fn items<T>(&mut self, len: usize, map: bool, f: impl FnOnce(&mut Items<'_, 'a, F>) -> Result<T, Error<F::Error>>) -> Result<T, Error<F::Error>> {
+        let indefinite = F::INDEFINITE_LENGTHS && len == usize::MAX;
+        if !indefinite {
+            self.format.decode_limits().check_sequence_len(len).map_err(Self::limit_exceeded)?;
+        }
+
+        let format = self.format;
+        format.decode_limits().nest(|| {
+            let mut items = Items { de: self, len, idx: 0, indefinite, map, done: len == 0 };
+            let data = f(&mut items)?;
+            items.finish()?;
+            self.take::<F::DecodeCollectionEnd>()?;
+            Ok(data)
+        })
+        .map_err(Self::limit_exceeded)?
+    }
+
+    fn seq<T>(&mut self, expected: Option<usize>, f: impl FnOnce(&mut Items<'_, 'a, F>) -> Result<T, Error<F::Error>>) -> Result<T, Error<F::Error>> {
+        let len = *self.take::<<SequenceLen as backend::Decodable>::Decoder<F>>()?;
+        let indefinite = F::INDEFINITE_LENGTHS && len == *SequenceLen::INDEFINITE;
+        if let Some(n) = expected {
+            if !indefinite && n != len {
+                return Err(Error::Format(F::invalid_data_err()));
+            }
+        }
+        self.items(len, false, f)
+    }
+}
+
+// The items of a sequence, or the entries of a map, as they are decoded
+struct Items<'d, 'a, F> {
+    de: &'d mut Deserializer<'a, F>,
+    len: usize,
+    idx: usize,
+    indefinite: bool,
+    map: bool,
+    done: bool,
+}
+
+impl<'d, 'a, F: FormatDecode> Items<'d, 'a, F> {
+    // Positions the reader at the next item, or reports that there are no more
+    fn next(&mut self) -> Result<bool, Error<F::Error>> {
+        if self.done {
+            return Ok(false);
+        }
+        if self.indefinite {
+            if self.idx > 0 && !self.de.take::<F::DecodeCollectionNext>()? {
+                self.done = true;
+                return Ok(false);
+            }
+            self.de.format.decode_limits().check_sequence_len(self.idx + 1).map_err(Deserializer::<F>::limit_exceeded)?;
+        } else if self.idx == self.len {
+            self.done = true;
+            return Ok(false);
+        } else if self.idx > 0 {
+            self.de.take::<F::DecodeItemSeparator>()?;
+        }
+        if self.map {
+            self.de.take::<F::DecodeMapEntryBegin>()?;
+        }
+        self.idx += 1;
+        Ok(true)
+    }
+
+    fn finish(&mut self) -> Result<(), Error<F::Error>> {
+        if self.next()? {
+            Err(Error::Format(F::invalid_data_err()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        if self.indefinite { None } else { Some(self.len - self.idx) }
+    }
+}
+
+impl<'de, 'd, 'a, F: FormatDecode> de::SeqAccess<'de> for Items<'d, 'a, F> {
+    type Error = Error<F::Error>;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        if self.next()? {
+            seed.deserialize(&mut *self.de).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining()
+    }
+}
+
+impl<'de, 'd, 'a, F: FormatDecode> de::MapAccess<'de> for Items<'d, 'a, F> {
+    type Error = Error<F::Error>;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        if self.next()? {
+            seed.deserialize(&mut *self.de).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        self.de.take::<F::DecodeItemSeparator>()?;
+        let value = seed.deserialize(&mut *self.de)?;
+        self.de.take::<F::DecodeMapEntryEnd>()?;
+        Ok(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining()
+    }
+}
+
+impl<'de, 'i, 'd, 'a, F: FormatDecode> de::EnumAccess<'de> for &'i mut Items<'d, 'a, F> {
+    type Error = Error<F::Error>;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Self::Error> {
+        if !self.next()? {
+            return Err(Error::Format(F::invalid_data_err()));
+        }
+        let idx = *self.de.take::<F::DecodeVariantIdx>()?;
+        let idx = u32::try_from(idx).map_err(|_| Error::Format(F::invalid_data_err()))?;
+        let value = seed.deserialize(idx.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, 'i, 'd, 'a, F: FormatDecode> de::VariantAccess<'de> for &'i mut Items<'d, 'a, F> {
+    type Error = Error<F::Error>;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+        de::SeqAccess::next_element_seed(self, seed)?
+        .ok_or(Error::Format(F::invalid_data_err()))
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(self)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(self)
+    }
+}
+
+macro_rules! deserialize_primitive {
+    ($($method: ident => $dec: ident, $visit: ident;)*) => {
+        $(
+            fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.take::<F::$dec>()?)
+            }
+        )*
+    };
+}
+
+impl<'de, 'r, 'a, F: FormatDecode> de::Deserializer<'de> for &'r mut Deserializer<'a, F> {
+    type Error = Error<F::Error>;
+
+    deserialize_primitive! {
+        deserialize_bool    => DecodeBool, visit_bool;
+        deserialize_i8      => DecodeI8  , visit_i8;
+        deserialize_i16     => DecodeI16 , visit_i16;
+        deserialize_i32     => DecodeI32 , visit_i32;
+        deserialize_i64     => DecodeI64 , visit_i64;
+        deserialize_i128    => DecodeI128, visit_i128;
+        deserialize_u8      => DecodeU8  , visit_u8;
+        deserialize_u16     => DecodeU16 , visit_u16;
+        deserialize_u32     => DecodeU32 , visit_u32;
+        deserialize_u64     => DecodeU64 , visit_u64;
+        deserialize_u128    => DecodeU128, visit_u128;
+        deserialize_f32     => DecodeF32 , visit_f32;
+        deserialize_f64     => DecodeF64 , visit_f64;
+        deserialize_char    => DecodeChar, visit_char;
+        deserialize_str     => DecodeString , visit_string;
+        deserialize_string  => DecodeString , visit_string;
+        deserialize_bytes   => DecodeByteVec, visit_byte_buf;
+        deserialize_byte_buf=> DecodeByteVec, visit_byte_buf;
+    }
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("diny formats are not self-describing"))
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("diny formats are not self-describing"))
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("diny formats do not encode identifiers"))
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match *self.take::<<OptionTag as backend::Decodable>::Decoder<F>>()? {
+            0 => { self.take::<F::DecodeUnit>()?; visitor.visit_none() }
+            1 => visitor.visit_some(self),
+            _ => Err(Error::Format(F::invalid_data_err())),
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.take::<F::DecodeUnit>()?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.seq(None, |items| visitor.visit_seq(items))
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.seq(Some(len), |items| visitor.visit_seq(items))
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(self, _name: &'static str, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.seq(Some(len), |items| visitor.visit_seq(items))
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let len = *self.take::<<MapLen as backend::Decodable>::Decoder<F>>()?;
+        self.items(len, true, |items| visitor.visit_map(items))
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        self.seq(Some(fields.len()), |items| visitor.visit_seq(items))
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        self.seq(None, |items| visitor.visit_enum(items))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
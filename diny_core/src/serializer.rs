@@ -2,7 +2,7 @@ use core::{
     pin::Pin,
     task::{Poll, Context}
 };
-use crate::{backend::{self, Encode, PollEncodeStatus}, io};
+use crate::{backend::{self, Encode, PollEncodeStatus}, framing::{FramedSerialize, Framing}, io, schema::{self, Describe}};
 
 /// Creates a new [Serializer] from the specified [format](backend::FormatEncode)
 /// and [writer](io::AsyncWrite)
@@ -37,6 +37,17 @@ impl<F, W> Serializer<F, W> {
         Serialize::new(self)
     }
 
+    /// Converts the [Serializer] into a sink for `D`'s, prefixing each with its
+    /// encoded length as configured by `framing`
+    pub fn into_framed_sink<D>(self, framing: Framing) -> FramedSerialize<F, W, D>
+    where
+        F: backend::FormatEncode,
+        W: io::AsyncWrite + Unpin,
+        D: backend::Encodable,
+    {
+        FramedSerialize::new(self, framing)
+    }
+
     /// Serializes a single object of type `D`
     pub fn serialize<'w, D>(&'w mut self, data: &'w D) -> D::Future<'w, F, W>
     where
@@ -47,6 +58,24 @@ impl<F, W> Serializer<F, W> {
         D::serialize(data, &self.format, &mut self.writer)
     }
 
+    /// Writes the [fingerprint](schema::Schema::fingerprint) of `D`'s schema, for
+    /// a peer to [verify](crate::Deserializer::verify_fingerprint) before it
+    /// reads any `D`'s
+    ///
+    /// The fingerprint is encoded as a `u64` by the format, and is intended to be
+    /// written once, ahead of the first item.
+    pub fn write_fingerprint<D>(&mut self) -> impl '_ + futures::Future<Output=Result<(), <F as backend::Format>::Error>>
+    where
+        F: backend::FormatSerialize,
+        W: io::AsyncWrite + Unpin,
+        D: Describe + ?Sized,
+    {
+        let fingerprint = schema::fingerprint::<D>();
+        async move {
+            backend::AsyncSerialize::serialize(&fingerprint, &self.format, &mut self.writer).await
+        }
+    }
+
     /// Flushes the underlying `writer`
     pub fn flush(&mut self) -> impl '_ + futures::Future<Output=Result<(), <F as backend::Format>::Error>>
     where
@@ -0,0 +1,47 @@
+use core::{pin::Pin, task::{Context, Poll}};
+use crate::io;
+
+/// Accepts and discards every byte written to it asynchronously,
+/// counting the number of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{AsyncWriteExt, executor};
+/// use diny_core::util::AsyncCountingWriter;
+///
+/// let mut writer = AsyncCountingWriter::new();
+/// executor::block_on(writer.write_all(&[7u8; 5])).unwrap();
+/// assert_eq!(writer.bytes_written(), 5);
+/// ```
+#[derive(Debug, Default)]
+pub struct AsyncCountingWriter {
+    count: usize,
+}
+
+impl AsyncCountingWriter {
+    /// Instantiates a new counting writer that implements [AsyncWrite](io::AsyncWrite)
+    pub fn new() -> Self {
+        Self { count: 0 }
+    }
+
+    /// Returns the number of bytes that have been written so far
+    pub fn bytes_written(&self) -> usize {
+        self.count
+    }
+}
+
+impl io::AsyncWrite for AsyncCountingWriter {
+    fn poll_write(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.count += buf.len();
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -1,6 +1,8 @@
+#[doc(hidden)] pub mod async_counting_writer;
 #[doc(hidden)] pub mod async_slice_reader;
 #[doc(hidden)] pub mod async_slice_writer;
 
 
+#[doc(inline)] pub use async_counting_writer::AsyncCountingWriter;
 #[doc(inline)] pub use async_slice_reader::AsyncSliceReader;
-#[doc(inline)] pub use async_slice_writer::AsyncSliceWriter;
\ No newline at end of file
+#[doc(inline)] pub use async_slice_writer::AsyncSliceWriter;
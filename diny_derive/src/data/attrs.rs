@@ -0,0 +1,337 @@
+use super::{Errors, Result};
+
+/// The `#[diny(...)]` attributes that may be applied to a field.
+#[derive(Default)]
+pub struct FieldAttrs {
+    pub skip: Option<syn::Path>,
+    pub default: Option<syn::Path>,
+    pub with: Option<syn::Path>,
+    pub serde: Option<syn::Path>,
+    pub tag: Option<FieldTag>,
+    pub since: Option<FieldSince>,
+}
+
+/// An explicit field tag, along with the literal it was parsed from.
+pub struct FieldTag {
+    pub value: u32,
+    pub lit: syn::LitInt,
+}
+
+/// The version of a versioned struct that a field was added in, along with
+/// the literal it was parsed from.
+pub struct FieldSince {
+    pub value: u32,
+    pub lit: syn::LitInt,
+}
+
+/// The largest field tag, which is also the largest permitted by protobuf.
+pub const MAX_FIELD_TAG: u32 = (1 << 29) - 1;
+
+impl FieldAttrs {
+    pub fn is_skipped(&self) -> bool {
+        self.skip.is_some()
+    }
+
+    pub fn parse_input(attrs: &[syn::Attribute], errors: &mut Errors) -> Result<Self> {
+        let mut field_attrs = Self::default();
+        let mut has_default = None;
+        let mut ok = true;
+
+        let metas = attrs
+            .iter()
+            .filter(|a| a.path.is_ident("diny"))
+            .flat_map(|a| diny_metas(a, errors, &mut ok))
+            .collect::<Vec<_>>();
+
+        for meta in metas {
+            match &meta {
+                syn::Meta::Path(p) if p.is_ident("skip") => {
+                    if field_attrs.skip.is_some() {
+                        errors.add_spanned_error(p, "Duplicate diny attribute `skip`");
+                        ok = false;
+                    }
+                    field_attrs.skip = Some(p.clone());
+                }
+                syn::Meta::Path(p) if p.is_ident("default") => {
+                    if has_default.is_some() {
+                        errors.add_spanned_error(p, "Duplicate diny attribute `default`");
+                        ok = false;
+                    }
+                    has_default = Some(meta.clone());
+                }
+                syn::Meta::NameValue(nv) if nv.path.is_ident("default") => {
+                    if has_default.is_some() {
+                        errors.add_spanned_error(&nv.path, "Duplicate diny attribute `default`");
+                        ok = false;
+                    }
+                    has_default = Some(meta.clone());
+                    field_attrs.default = parse_path(nv, errors, &mut ok);
+                }
+                syn::Meta::NameValue(nv) if nv.path.is_ident("with") => {
+                    if field_attrs.with.is_some() {
+                        errors.add_spanned_error(&nv.path, "Duplicate diny attribute `with`");
+                        ok = false;
+                    }
+                    field_attrs.with = parse_path(nv, errors, &mut ok);
+                }
+                syn::Meta::Path(p) if p.is_ident("serde") => {
+                    if field_attrs.serde.is_some() {
+                        errors.add_spanned_error(p, "Duplicate diny attribute `serde`");
+                        ok = false;
+                    }
+                    field_attrs.serde = Some(p.clone());
+                }
+                syn::Meta::NameValue(nv) if nv.path.is_ident("tag") => {
+                    if field_attrs.tag.is_some() {
+                        errors.add_spanned_error(&nv.path, "Duplicate diny attribute `tag`");
+                        ok = false;
+                    }
+                    field_attrs.tag = parse_tag(&nv.lit, errors, &mut ok);
+                }
+                syn::Meta::NameValue(nv) if nv.path.is_ident("since") => {
+                    if field_attrs.since.is_some() {
+                        errors.add_spanned_error(&nv.path, "Duplicate diny attribute `since`");
+                        ok = false;
+                    }
+                    field_attrs.since = parse_since(&nv.lit, errors, &mut ok);
+                }
+                _ => {
+                    errors.add_spanned_error(&meta, "Unknown diny field attribute, expected one of `skip`, `default`, `with = \"...\"`, `serde`, `tag = N`, or `since = N`");
+                    ok = false;
+                }
+            }
+        }
+
+        if let Some(meta) = &has_default {
+            if field_attrs.skip.is_none() && field_attrs.since.is_none() {
+                errors.add_spanned_error(meta, "The diny attribute `default` is only valid on a field that is also marked `skip` or `since = N`");
+                ok = false;
+            }
+        }
+
+        if let (None, Some(since)) = (&has_default, &field_attrs.since) {
+            errors.add_spanned_error(&since.lit, "A field added `since` a version must also specify `default`, which fills it when decoding an older encoding");
+            ok = false;
+        }
+
+        if let (Some(_), Some(since)) = (&field_attrs.skip, &field_attrs.since) {
+            errors.add_spanned_error(&since.lit, "A field marked `skip` is never encoded, so it cannot also specify `since`");
+            ok = false;
+        }
+
+        if let (Some(_), Some(with)) = (&field_attrs.skip, &field_attrs.with) {
+            errors.add_spanned_error(with, "A field marked `skip` is never encoded, so it cannot also specify `with`");
+            ok = false;
+        }
+
+        if let (Some(_), Some(serde)) = (&field_attrs.skip, &field_attrs.serde) {
+            errors.add_spanned_error(serde, "A field marked `skip` is never encoded, so it cannot also specify `serde`");
+            ok = false;
+        }
+
+        if let (Some(_), Some(serde)) = (&field_attrs.with, &field_attrs.serde) {
+            errors.add_spanned_error(serde, "A field encoded `with` another module cannot also specify `serde`");
+            ok = false;
+        }
+
+        if let (Some(_), Some(tag)) = (&field_attrs.skip, &field_attrs.tag) {
+            errors.add_spanned_error(&tag.lit, "A field marked `skip` is never encoded, so it cannot also specify `tag`");
+            ok = false;
+        }
+
+        if ok {
+            Ok(field_attrs)
+        } else {
+            Err(())
+        }
+    }
+}
+
+fn diny_metas(attr: &syn::Attribute, errors: &mut Errors, ok: &mut bool) -> Vec<syn::Meta> {
+    match attr.parse_meta() {
+        Ok(syn::Meta::List(list)) =>
+            list.nested
+            .into_iter()
+            .filter_map(|nested| match nested {
+                syn::NestedMeta::Meta(meta) => Some(meta),
+                syn::NestedMeta::Lit(lit) => {
+                    errors.add_spanned_error(&lit, "Expected a diny attribute, found a literal");
+                    *ok = false;
+                    None
+                }
+            })
+            .collect(),
+        Ok(meta) => {
+            errors.add_spanned_error(&meta, "Expected a list of diny attributes, e.g. `#[diny(skip)]`");
+            *ok = false;
+            Vec::new()
+        }
+        Err(e) => {
+            errors.add_syn_error(e);
+            *ok = false;
+            Vec::new()
+        }
+    }
+}
+
+fn parse_path(nv: &syn::MetaNameValue, errors: &mut Errors, ok: &mut bool) -> Option<syn::Path> {
+    match &nv.lit {
+        syn::Lit::Str(s) => match s.parse::<syn::Path>() {
+            Ok(path) => Some(path),
+            Err(e) => {
+                errors.add_syn_error(e);
+                *ok = false;
+                None
+            }
+        },
+        lit => {
+            errors.add_spanned_error(lit, "Expected a string literal containing a path");
+            *ok = false;
+            None
+        }
+    }
+}
+
+/// Parse a field tag, which must lie within the range that protobuf permits.
+fn parse_tag(lit: &syn::Lit, errors: &mut Errors, ok: &mut bool) -> Option<FieldTag> {
+    match lit {
+        syn::Lit::Int(i) => match i.base10_parse::<u32>() {
+            Ok(n) if (1..=MAX_FIELD_TAG).contains(&n) => Some(FieldTag { value: n, lit: i.clone() }),
+            _ => {
+                errors.add_spanned_error(i, format!("A field tag must be an integer from 1 to {}", MAX_FIELD_TAG));
+                *ok = false;
+                None
+            }
+        },
+        lit => {
+            errors.add_spanned_error(lit, "Expected an integer literal");
+            *ok = false;
+            None
+        }
+    }
+}
+
+/// Parse the version a field was added in, which must be at least one (the
+/// version of a struct's original fields).
+fn parse_since(lit: &syn::Lit, errors: &mut Errors, ok: &mut bool) -> Option<FieldSince> {
+    match lit {
+        syn::Lit::Int(i) => match i.base10_parse::<u32>() {
+            Ok(n) if n >= 1 => Some(FieldSince { value: n, lit: i.clone() }),
+            _ => {
+                errors.add_spanned_error(i, "A version must be an integer from 1 to `u32::MAX`");
+                *ok = false;
+                None
+            }
+        },
+        lit => {
+            errors.add_spanned_error(lit, "Expected an integer literal");
+            *ok = false;
+            None
+        }
+    }
+}
+
+/// The `#[diny(...)]` attributes that may be applied to a struct or enum.
+#[derive(Default)]
+pub struct ContainerAttrs {
+    pub versioned: Option<syn::Path>,
+}
+
+impl ContainerAttrs {
+    pub fn is_versioned(&self) -> bool {
+        self.versioned.is_some()
+    }
+
+    pub fn parse_input(attrs: &[syn::Attribute], errors: &mut Errors) -> Result<Self> {
+        let mut container_attrs = Self::default();
+        let mut ok = true;
+
+        let metas = attrs
+            .iter()
+            .filter(|a| a.path.is_ident("diny"))
+            .flat_map(|a| diny_metas(a, errors, &mut ok))
+            .collect::<Vec<_>>();
+
+        for meta in metas {
+            match &meta {
+                syn::Meta::Path(p) if p.is_ident("versioned") => {
+                    if container_attrs.versioned.is_some() {
+                        errors.add_spanned_error(p, "Duplicate diny attribute `versioned`");
+                        ok = false;
+                    }
+                    container_attrs.versioned = Some(p.clone());
+                }
+                _ => {
+                    errors.add_spanned_error(&meta, "Unknown diny attribute, expected `versioned`");
+                    ok = false;
+                }
+            }
+        }
+
+        if ok {
+            Ok(container_attrs)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// The `#[diny(...)]` attributes that may be applied to an enum variant.
+#[derive(Default)]
+pub struct VariantAttrs {
+    pub index: Option<u32>,
+}
+
+impl VariantAttrs {
+    pub fn parse_input(attrs: &[syn::Attribute], errors: &mut Errors) -> Result<Self> {
+        let mut variant_attrs = Self::default();
+        let mut ok = true;
+
+        let metas = attrs
+            .iter()
+            .filter(|a| a.path.is_ident("diny"))
+            .flat_map(|a| diny_metas(a, errors, &mut ok))
+            .collect::<Vec<_>>();
+
+        for meta in metas {
+            match &meta {
+                syn::Meta::NameValue(nv) if nv.path.is_ident("index") => {
+                    if variant_attrs.index.is_some() {
+                        errors.add_spanned_error(&nv.path, "Duplicate diny attribute `index`");
+                        ok = false;
+                    }
+                    variant_attrs.index = parse_index(&nv.lit, errors, &mut ok);
+                }
+                _ => {
+                    errors.add_spanned_error(&meta, "Unknown diny variant attribute, expected `index = N`");
+                    ok = false;
+                }
+            }
+        }
+
+        if ok {
+            Ok(variant_attrs)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Parse a variant index, which must be representable by every format (i.e. a `u32`).
+pub fn parse_index(lit: &syn::Lit, errors: &mut Errors, ok: &mut bool) -> Option<u32> {
+    match lit {
+        syn::Lit::Int(i) => match i.base10_parse::<u32>() {
+            Ok(n) => Some(n),
+            Err(_) => {
+                errors.add_spanned_error(i, "A variant index must be an unsigned integer no larger than `u32::MAX`");
+                *ok = false;
+                None
+            }
+        },
+        lit => {
+            errors.add_spanned_error(lit, "Expected an integer literal");
+            *ok = false;
+            None
+        }
+    }
+}
@@ -1,18 +1,26 @@
+pub mod attrs;
 pub mod errors;
 
+pub use attrs::{ContainerAttrs, FieldAttrs, VariantAttrs};
 pub use errors::Errors;
 
+use std::collections::HashSet;
+use quote::ToTokens;
+
 pub type Result<T> = core::result::Result<T, ()>;
 
 newtype!(pub Input<'a>(&syn::DeriveInput));
 
 pub struct Def<'a> {
     pub name: TypeName<'a>,
+    pub generics: Generics<'a>,
     pub shape: Shape<'a>,
+    pub attrs: ContainerAttrs,
     pub input: Input<'a>,
 }
 
 newtype!(pub TypeName<'a>(&syn::Ident));
+newtype!(pub Generics<'a>(&syn::Generics));
 newtype!(pub TypeRef<'a>(&syn::Type));
 
 pub enum Shape<'a> {
@@ -23,11 +31,6 @@ pub enum Shape<'a> {
 newtype!(pub Variants<'a>(Vec<Variant<'a>>));
 newtype!(pub Fields<'a>(Vec<Field<'a>>));
 
-pub enum FieldsStyle {
-    Named,
-    Unnamed,
-}
-
 pub struct Variant<'a> {
     pub ctor: VariantCtor<'a>,
     pub index: VariantIndex,
@@ -41,6 +44,7 @@ newtype!(pub VariantIndex(syn::Index));
 pub struct Field<'a> {
     pub id: FieldId<'a>,
     pub type_ref: TypeRef<'a>,
+    pub attrs: FieldAttrs,
 }
 
 pub struct FieldId<'a> {
@@ -54,14 +58,23 @@ newtype!(pub FieldName<'a>(&syn::Ident));
 
 impl<'a> Def<'a> {
     pub fn parse_input(input: &'a syn::DeriveInput, errors: &mut Errors) -> Result<Self> {
+        let attrs = ContainerAttrs::parse_input(&input.attrs, errors);
         let shape = Shape::parse_input(&input.data, input, errors)?;
+        let attrs = shape.check_versions(attrs?, errors)?;
 
         Ok(Self {
             name: (&input.ident).into(),
+            generics: (&input.generics).into(),
             shape,
+            attrs,
             input: input.into(),
         })
     }
+
+    /// Whether the struct is encoded preceded by its length, as requested by `#[diny(versioned)]`.
+    pub fn is_versioned(&self) -> bool {
+        self.attrs.is_versioned()
+    }
 }
 
 impl<'a> Shape<'a> {
@@ -73,8 +86,9 @@ impl<'a> Shape<'a> {
                     syn::Fields::Unnamed(fields) => Shape::Struct(Fields::parse_input(fields.unnamed.iter(), errors)?),
                     syn::Fields::Unit            => Shape::Struct(Fields::unit()),
                 }
+                .check_tags(errors)?
             },
-            syn::Data::Enum(e) => Shape::Enum(Variants::parse_input(e.variants.iter().enumerate(), errors)?),
+            syn::Data::Enum(e) => Shape::Enum(Variants::parse_input(e.variants.iter(), errors)?),
             syn::Data::Union(_) => {
                 errors.add_spanned_error(input, "Diny does not support derivation of unions");
                 return Err(());
@@ -85,17 +99,169 @@ impl<'a> Shape<'a> {
     }
 }
 
+impl Shape<'_> {
+    /// Whether any field opts out of the default encoding of its type.
+    pub fn has_field_attrs(&self) -> bool {
+        match self {
+            Shape::Enum(vs)   => vs.iter().any(|v| v.fields.has_attrs()),
+            Shape::Struct(fs) => fs.has_attrs(),
+        }
+    }
+
+    /// Verify that the field tags of a struct increase strictly, and do not exceed the maximum.
+    fn check_tags(self, errors: &mut Errors) -> Result<Self> {
+        let mut ok = true;
+        if let Shape::Struct(fs) = &self {
+            let mut prev = 0;
+            for field in fs.encoded() {
+                match &field.attrs.tag {
+                    Some(tag) if tag.value <= prev => {
+                        errors.add_spanned_error(&tag.lit, format!("Field tag {} must be larger than the tag {} of the preceding field", tag.value, prev));
+                        ok = false;
+                    }
+                    Some(tag) => prev = tag.value,
+                    None if prev == attrs::MAX_FIELD_TAG => {
+                        errors.add_spanned_error(&field.type_ref, format!("The implicit field tag is larger than {}", attrs::MAX_FIELD_TAG));
+                        ok = false;
+                    }
+                    None => prev += 1,
+                }
+            }
+        }
+
+        if ok {
+            Ok(self)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl Shape<'_> {
+    /// Verify that only versioned structs have fields added `since` a version, and that
+    /// such fields follow every original field, in the order of their versions.
+    fn check_versions(&self, attrs: ContainerAttrs, errors: &mut Errors) -> Result<ContainerAttrs> {
+        let mut ok = true;
+        match (self, &attrs.versioned) {
+            (Shape::Enum(_), Some(versioned)) => {
+                errors.add_spanned_error(versioned, "Only a struct may be marked `versioned`");
+                ok = false;
+            }
+            (Shape::Enum(_), None) => {}
+            (Shape::Struct(fs), None) => {
+                for since in fs.iter().filter_map(|f| f.attrs.since.as_ref()) {
+                    errors.add_spanned_error(&since.lit, "A field may only specify `since` within a struct marked `#[diny(versioned)]`");
+                    ok = false;
+                }
+            }
+            (Shape::Struct(fs), Some(_)) => {
+                let mut prev = None;
+                for field in fs.encoded() {
+                    match (&field.attrs.since, prev) {
+                        (Some(since), Some(p)) if since.value < p => {
+                            errors.add_spanned_error(&since.lit, format!("A field added since version {} must precede those added since version {}", since.value, p));
+                            ok = false;
+                        }
+                        (Some(since), _) => prev = Some(since.value),
+                        (None, Some(_)) => {
+                            errors.add_spanned_error(&field.type_ref, "A field without `since` must precede every field added `since` a version");
+                            ok = false;
+                        }
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+
+        if ok {
+            Ok(attrs)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl Generics<'_> {
+    /// Whether there are any type or lifetime parameters, which must be used by every generated type.
+    pub fn has_type_or_lifetime_params(&self) -> bool {
+        self.0.params.iter().any(|p| !matches!(p, syn::GenericParam::Const(_)))
+    }
+
+
+    /// Clone the generics, requiring every type parameter to satisfy the provided `bound`.
+    pub fn with_bound(&self, bound: &proc_macro2::TokenStream) -> syn::Generics {
+        let mut generics = self.0.clone();
+        let idents = generics.type_params().map(|p| p.ident.clone()).collect::<Vec<_>>();
+        let where_clause = generics.make_where_clause();
+        for ident in idents {
+            where_clause.predicates.push(parse_quote! { #ident: #bound });
+        }
+        generics
+    }
+
+    /// Clone the generics, requiring each type parameter named by the type of a field
+    /// that is encoded in its own right to satisfy the provided `bound`.  As with serde,
+    /// parameters named only by fields that are skipped, or encoded `with` another
+    /// module or through the serde bridge, are left for the data type to constrain.
+    pub fn with_field_bound(&self, shape: &Shape, bound: &proc_macro2::TokenStream) -> syn::Generics {
+        let fields = match shape {
+            Shape::Enum(vs)   => vs.iter().flat_map(|v| v.fields.encoded_natively()).collect::<Vec<_>>(),
+            Shape::Struct(fs) => fs.encoded_natively().collect::<Vec<_>>(),
+        };
+
+        let mut named = HashSet::new();
+        for field in fields {
+            collect_idents(field.type_ref.to_token_stream(), &mut named);
+        }
+
+        let mut generics = self.0.clone();
+        let idents = generics.type_params().map(|p| p.ident.clone()).filter(|i| named.contains(i)).collect::<Vec<_>>();
+        let where_clause = generics.make_where_clause();
+        for ident in idents {
+            where_clause.predicates.push(parse_quote! { #ident: #bound });
+        }
+        generics
+    }
+}
+
+/// Gather every identifier within `tokens`, including those nested within groups.
+fn collect_idents(tokens: proc_macro2::TokenStream, idents: &mut HashSet<proc_macro2::Ident>) {
+    for token in tokens {
+        match token {
+            proc_macro2::TokenTree::Ident(ident) => { idents.insert(ident); }
+            proc_macro2::TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
 impl<'a> Fields<'a> {
     pub fn unit() -> Self {
         Fields(Vec::new())
     }
 
-    pub fn style(&self) -> FieldsStyle {
-        if !self.0.is_empty() && self.0[0].id.name.is_some() {
-            FieldsStyle::Named
-        } else {
-            FieldsStyle::Unnamed
-        }
+    /// Iterate over the fields that are actually encoded, in order.
+    pub fn encoded(&self) -> impl Iterator<Item = &Field<'a>> {
+        self.0.iter().filter(|f| !f.attrs.is_skipped())
+    }
+
+    /// Iterate over the encoded fields whose own type provides their encoding.
+    pub fn encoded_natively(&self) -> impl Iterator<Item = &Field<'a>> {
+        self.encoded().filter(|f| f.attrs.with.is_none() && f.attrs.serde.is_none())
+    }
+
+    /// The tags of the encoded fields, in order, where a field without an explicit
+    /// tag follows on from its predecessor (starting from one).
+    pub fn tags(&self) -> impl Iterator<Item = u32> + '_ {
+        self.encoded().scan(0, |prev, field| {
+            *prev = field.attrs.tag.as_ref().map_or(*prev + 1, |tag| tag.value);
+            Some(*prev)
+        })
+    }
+
+    /// Whether any field opts out of the default encoding of its type.
+    pub fn has_attrs(&self) -> bool {
+        self.0.iter().any(|f| f.attrs.is_skipped() || f.attrs.with.is_some())
     }
 
     pub fn parse_input<Fs>(fields: Fs, errors: &mut Errors) -> Result<Self>
@@ -118,11 +284,11 @@ impl<'a> Fields<'a> {
                             AllSame::Unnamed
                         };
 
-                    Ok(Field::parse_input(idx, field))
+                    Field::parse_input(idx, field, errors)
                 }
                 AllSame::Named => {
                     if field.ident.is_some() {
-                        Ok(Field::parse_input(idx, field))
+                        Field::parse_input(idx, field, errors)
                     } else {
                         errors.add_spanned_error(&field.ident, "Named field found within unnamed field definition");
                         Err(())
@@ -130,7 +296,7 @@ impl<'a> Fields<'a> {
                 }
                 AllSame::Unnamed => {
                     if field.ident.is_none() {
-                        Ok(Field::parse_input(idx, field))
+                        Field::parse_input(idx, field, errors)
                     } else {
                         errors.add_spanned_error(&field.ident, "Unnamed field found within named field definition");
                         Err(())
@@ -138,17 +304,21 @@ impl<'a> Fields<'a> {
                 }
             })
         })
+        // Parse every field before failing, so that all errors are reported at once
+        .collect::<Vec<Result<Field>>>()
+        .into_iter()
         .collect::<Result<Vec<Field>>>()
         .map(|fs| fs.into())
     }
 }
 
 impl<'a> Field<'a> {
-    pub fn parse_input(idx: usize, field: &'a syn::Field) -> Self {
-        Field {
+    pub fn parse_input(idx: usize, field: &'a syn::Field, errors: &mut Errors) -> Result<Self> {
+        Ok(Field {
             id: FieldId::new(idx, &field.ident),
             type_ref: (&field.ty).into(),
-        }
+            attrs: FieldAttrs::parse_input(&field.attrs, errors)?,
+        })
     }
 }
 
@@ -170,7 +340,6 @@ impl FieldId<'_> {
         }
     }
 
-    #[allow(dead_code)]
     pub fn var_name(&self) -> proc_macro2::TokenStream {
         use quote::ToTokens;
         match &self.name {
@@ -196,25 +365,75 @@ impl From<usize> for FieldIndex {
 impl<'a> Variants<'a> {
     pub fn parse_input<Vs>(variants: Vs, errors: &mut Errors) -> Result<Self> 
     where
-        Vs: Iterator<Item = (usize, &'a syn::Variant)>,
+        Vs: Iterator<Item = &'a syn::Variant>,
     {
+        // As with discriminants, a variant without an explicit index follows on from its predecessor
+        let mut next = Some(0);
+        let mut used = Vec::<(u32, &syn::Ident)>::new();
+
         variants
-            .map(|v| Variant::parse_input(v.0, v.1, errors))
+            .map(|variant| {
+                let index = VariantIndex::parse_input(variant, next, errors)?;
+                next = index.checked_add(1);
+
+                if let Some((_, other)) = used.iter().find(|(i, _)| *i == index) {
+                    errors.add_spanned_error(&variant.ident, format!("Variant index {} is already used by `{}`", index, other));
+                    return Err(());
+                }
+                used.push((index, &variant.ident));
+
+                Variant::parse_input(index, variant, errors)
+            })
+            .collect::<Vec<Result<_>>>()
+            .into_iter()
             .collect::<Result<Vec<_>>>()
             .map(|vs| vs.into())
     }
 }
 
 impl<'a> Variant<'a> {
-    pub fn parse_input(idx: usize, variant: &'a syn::Variant, errors: &mut Errors) -> Result<Self> {
+    pub fn parse_input(idx: u32, variant: &'a syn::Variant, errors: &mut Errors) -> Result<Self> {
+        let fields = Fields::parse_input(variant.fields.iter(), errors)?;
+        if let Some(tag) = fields.iter().find_map(|f| f.attrs.tag.as_ref()) {
+            errors.add_spanned_error(&tag.lit, "The field of an enum variant cannot specify a `tag`");
+            return Err(());
+        }
+        if let Some(since) = fields.iter().find_map(|f| f.attrs.since.as_ref()) {
+            errors.add_spanned_error(&since.lit, "The field of an enum variant cannot specify `since`");
+            return Err(());
+        }
+
         Ok(Variant {
             ctor: (&variant.ident).into(),
-            index: VariantIndex(idx.into()),
-            fields: Fields::parse_input(variant.fields.iter(), errors)?,
+            index: VariantIndex(syn::Index { index: idx, span: variant.ident.span() }),
+            fields,
         })
     }
 }
 
+impl VariantIndex {
+    /// Determine the index of a variant, in order of precedence, from its `#[diny(index = N)]`
+    /// attribute, its explicit discriminant, or else the index following the previous variant.
+    pub fn parse_input(variant: &syn::Variant, next: Option<u32>, errors: &mut Errors) -> Result<u32> {
+        let attrs = VariantAttrs::parse_input(&variant.attrs, errors)?;
+        if let Some(index) = attrs.index {
+            return Ok(index);
+        }
+
+        match &variant.discriminant {
+            Some((_, syn::Expr::Lit(syn::ExprLit { lit, .. }))) => {
+                let mut ok = true;
+                attrs::parse_index(lit, errors, &mut ok).ok_or(())
+            }
+            Some((_, expr)) => {
+                errors.add_spanned_error(expr, "Diny requires discriminants to be integer literals, otherwise specify `#[diny(index = N)]`");
+                Err(())
+            }
+            None => next.ok_or_else(|| errors.add_spanned_error(&variant.ident, "The implicit variant index is larger than `u32::MAX`")),
+        }
+    }
+}
+
 
 impl quote::ToTokens for TypeName<'_> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
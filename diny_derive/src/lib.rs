@@ -14,7 +14,43 @@ mod serialize;
 use proc_macro2::TokenStream;
 
 /// Generate both async serialization and deserialization code
-#[proc_macro_derive(AsyncSerialization)]
+///
+/// Fields may be annotated with the following attributes:
+///
+/// - `#[diny(skip)]` leaves the field off the wire, and fills it with
+///   `Default::default()` when decoding.
+/// - `#[diny(skip, default = "path")]` instead fills a skipped field by
+///   calling the function at `path`.  The field of an enum variant may be skipped too,
+///   leaving the variant encoded as though it had no payload.
+/// - `#[diny(with = "module")]` encodes the field with `module::Encoder<F>`
+///   and decodes it with `module::Decoder<F>`, rather than the coders of
+///   the field's type.
+/// - `#[diny(tag = N)]` sets the numeric tag by which formats such as protobuf
+///   identify the field.  Fields are otherwise tagged one more than the preceding
+///   encoded field (starting from one), and tags must increase from field to field.
+///
+/// A struct marked `#[diny(versioned)]` is encoded preceded by the length of its
+/// encoding, so that fields may be appended to it without breaking compatibility:
+///
+/// - `#[diny(since = N, default)]` marks a field added in version `N` of the
+///   struct, which is filled with `Default::default()` (or by calling the function
+///   at `path`, given `default = "path"`) when decoding an older encoding that ends
+///   before it.  Such fields must follow every original field, in order of version.
+/// - Bytes left over once a versioned struct has been decoded, such as fields
+///   appended by a newer version, are skipped.
+///
+/// Versioning relies on an encoding ending where its last field does, so it is
+/// suited to formats that do not delimit struct fields (e.g. binary formats).
+///
+/// Enum variants are identified on the wire by their index which, in order of
+/// precedence, is taken from a `#[diny(index = N)]` attribute, an integer literal
+/// discriminant, or else is one more than the index of the preceding variant
+/// (starting from zero).  Indices must be unique, and no larger than `u32::MAX`.
+///
+/// The static schema of the type is described by an implementation of
+/// `diny::schema::Describe`, which requires every type parameter to also
+/// implement it.
+#[proc_macro_derive(AsyncSerialization, attributes(diny))]
 pub fn derive_diny_aysnc_serialization(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     parse_input(&parse_macro_input!(input as syn::DeriveInput))
     .map_or_else(
@@ -32,8 +68,8 @@ pub fn derive_diny_aysnc_serialization(input: proc_macro::TokenStream) -> proc_m
     .into()
 }
 
-/// Generate only async serialization code
-#[proc_macro_derive(AsyncSerialize)]
+/// Generate only async serialization code, along with the type's schema
+#[proc_macro_derive(AsyncSerialize, attributes(diny))]
 pub fn derive_diny_async_serialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     parse_input(&parse_macro_input!(input as syn::DeriveInput))
     .map_or_else(
@@ -44,7 +80,7 @@ pub fn derive_diny_async_serialize(input: proc_macro::TokenStream) -> proc_macro
 }
 
 /// Generate only async deserialization code
-#[proc_macro_derive(AsyncDeserialize)]
+#[proc_macro_derive(AsyncDeserialize, attributes(diny))]
 pub fn derive_diny_async_deserialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     parse_input(&parse_macro_input!(input as syn::DeriveInput))
     .map_or_else(
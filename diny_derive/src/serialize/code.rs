@@ -1,55 +1,62 @@
 use proc_macro2::TokenStream;
-use quote::ToTokens;
 use crate::data;
 use super::encoded_field_gen::*;
 use super::encoded_variant_gen::*;
+use super::generics_gen::*;
 
 
 pub fn generate_async_serialize(def: &data::Def) -> TokenStream {
-    let type_name = def.name.to_token_stream();
-    match &def.shape {
-        data::Shape::Enum  (vs) => gen_enum_serialize(&type_name, vs),
-        data::Shape::Struct(fs) => gen_struct_serialize(&type_name, fs),
+    let serialize = match &def.shape {
+        data::Shape::Enum  (vs) => gen_enum_serialize(def, vs),
+        data::Shape::Struct(fs) => gen_struct_serialize(def, fs),
+    };
+    let describe = gen_describe(def);
+
+    quote! {
+        #serialize
+        #describe
     }
 }
 
 pub fn generate_async_deserialize(def: &data::Def) -> TokenStream {
-    let type_name = def.name.to_token_stream();
     match &def.shape {
-        data::Shape::Enum  (vs) => gen_enum_deserialize(&type_name, vs),
-        data::Shape::Struct(fs) => gen_struct_deserialize(&type_name, fs),
+        data::Shape::Enum  (vs) => gen_enum_deserialize(def, vs),
+        data::Shape::Struct(fs) => gen_struct_deserialize(def, fs),
     }
 }
 
-fn gen_struct_serialize(type_name: &TokenStream, fs: &data::Fields) -> TokenStream {
+fn gen_struct_serialize(def: &data::Def, fs: &data::Fields) -> TokenStream {
+    let generics = GenericsGen::for_encode(def);
+
+    fn non_empty_fields(type_name: &data::TypeName, fs: &data::Fields, generics: &GenericsGen) -> TokenStream {
+        let GenericsGen { data_type, format_impl, format_type, format_where, .. } = generics;
 
-    fn non_empty_fields(fs: &data::Fields) -> TokenStream {
         let encoded_fields: EncodedFieldsGen = fs.into();
         let variants = encoded_fields.iter().map(|field| {
             let ctor = &field.ctor;
-            let type_ref = &field.field.type_ref;
-            
-            let err_src = syn::spanned::Spanned::span(&type_ref);
-            quote_spanned! {err_src=> #ctor(<#type_ref as ::diny::backend::Encodable>::Encoder::<__F>) }
+            let encoder = field.field_encoder();
+
+            let err_src = field_span(field.field);
+            quote_spanned! {err_src=> #ctor(#encoder) }
         });
 
         let methods = encoded_fields.iter().map(|field| {
             let ctor = &field.ctor;
             let field_name = &field.field.id.field_name();
-            let type_ref = &field.field.type_ref;
+            let encoder = field.field_encoder();
             let this_method = &field.this_method;
 
             let next = &field.next_method.as_ref().map_or_else(
-                ||  quote! { ::diny::backend::StartEncodeStatus::Fini },
+                ||  quote! { Self::after_fields(format, writer, cx) },
                 |n| quote! { Self::#n(format, writer, data, cx) },
             );
 
             quote! {
-                fn #this_method<__W>(format: &__F, writer: &mut __W, data: &__Data, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartEncodeStatus<Self, <__F as ::diny::backend::Format>::Error>
+                fn #this_method<__W>(format: &__F, writer: &mut __W, data: &#data_type, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartEncodeStatus<Self, <__F as ::diny::backend::Format>::Error>
                 where
                     __W: ::diny::io::AsyncWrite + ::core::marker::Unpin,
                 {
-                    match <<#type_ref as ::diny::backend::Encodable>::Encoder::<__F> as ::diny::backend::Encode>::start_encode(format, writer, &data.#field_name, cx) {
+                    match <#encoder as ::diny::backend::Encode>::start_encode(format, writer, &data.#field_name, cx) {
                         ::diny::backend::StartEncodeStatus::Fini         => #next,
                         ::diny::backend::StartEncodeStatus::Pending(enc) => ::diny::backend::StartEncodeStatus::Pending(Self::#ctor(enc)),
                         ::diny::backend::StartEncodeStatus::Error(err)   => ::diny::backend::StartEncodeStatus::Error(err),
@@ -58,7 +65,19 @@ fn gen_struct_serialize(type_name: &TokenStream, fs: &data::Fields) -> TokenStre
             }
         });
 
+        let struct_def = gen_struct_def(type_name, fs);
+        let begin_encoder = quote! { <__F as ::diny::backend::FormatEncodeHooks>::EncodeStructBegin::<__StructDef> };
+        let end_encoder = quote! { <__F as ::diny::backend::FormatEncodeHooks>::EncodeStructEnd::<__StructDef> };
+        let max_encoded_len = gen_max_encoded_len_sum(
+            core::iter::once(begin_encoder.clone())
+            .chain(encoded_fields.iter().map(|field| field.field_encoder()))
+            .chain(core::iter::once(end_encoder.clone()))
+        );
         let init_transition = gen_encode_chain(quote! { Self::start_encode(format, writer, data, cx) });
+        let begin_transition = gen_encode_poll_chain(quote! { enc.poll_encode(format, writer, &(), cx) }, quote! { Self::after_begin(format, writer, data, cx) });
+        let end_transition = gen_encode_poll_fini(quote! { enc.poll_encode(format, writer, &(), cx) });
+        let phantom_variant = generics.phantom_variant();
+        let phantom_arm = generics.phantom_arm(quote! { Self }, quote! { ::diny::backend::PollEncodeStatus::Error(__F::invalid_input_err()) });
 
         let transitions = encoded_fields.iter().map(|field| {
             let ctor = &field.ctor;
@@ -66,7 +85,7 @@ fn gen_struct_serialize(type_name: &TokenStream, fs: &data::Fields) -> TokenStre
             let poll = quote! { enc.poll_encode(format, writer, &data.#field_name, cx) };
 
             let transition = match &field.next_method {
-                None => gen_encode_poll_fini(poll),
+                None => gen_encode_poll_chain(poll, quote! { Self::after_fields(format, writer, cx) }),
                 Some(n) => gen_encode_poll_chain(poll, quote! { Self::#n(format, writer, data, cx) }),
             };
 
@@ -76,28 +95,51 @@ fn gen_struct_serialize(type_name: &TokenStream, fs: &data::Fields) -> TokenStre
         });
 
         quote! {
-            pub enum __Encoder<__F>
-            where
-                __F: ::diny::backend::FormatEncode,
+            #struct_def
+
+            pub enum __Encoder #format_impl
+            #format_where
             {
                 Init,
+                Begin(#begin_encoder),
                 #(#variants,)*
+                End(#end_encoder),
+                #phantom_variant
                 Fini,
             }
 
-            impl<__F> __Encoder<__F>
-            where
-                __F: ::diny::backend::FormatEncode,
+            impl #format_impl __Encoder #format_type
+            #format_where
             {
+                fn after_init<__W>(format: &__F, writer: &mut __W, data: &#data_type, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartEncodeStatus<Self, <__F as ::diny::backend::Format>::Error>
+                where
+                    __W: ::diny::io::AsyncWrite + ::core::marker::Unpin,
+                {
+                    match <#begin_encoder as ::diny::backend::Encode>::start_encode(format, writer, &(), cx) {
+                        ::diny::backend::StartEncodeStatus::Fini         => Self::after_begin(format, writer, data, cx),
+                        ::diny::backend::StartEncodeStatus::Pending(enc) => ::diny::backend::StartEncodeStatus::Pending(Self::Begin(enc)),
+                        ::diny::backend::StartEncodeStatus::Error(err)   => ::diny::backend::StartEncodeStatus::Error(err),
+                    }
+                }
+
                 #(#methods)*
+
+                fn after_fields<__W>(format: &__F, writer: &mut __W, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartEncodeStatus<Self, <__F as ::diny::backend::Format>::Error>
+                where
+                    __W: ::diny::io::AsyncWrite + ::core::marker::Unpin,
+                {
+                    <#end_encoder as ::diny::backend::Encode>::start_encode(format, writer, &(), cx)
+                    .map_pending(Self::End)
+                }
             }                
         
-            impl<__F> ::diny::backend::Encode for __Encoder<__F>
-            where
-                __F: ::diny::backend::FormatEncode,
+            impl #format_impl ::diny::backend::Encode for __Encoder #format_type
+            #format_where
             {
-                type Data = __Data;
+                type Data = #data_type;
                 type Format = __F;
+
+                #max_encoded_len
         
                 fn init(_data: &Self::Data) -> Self {
                     Self::Init
@@ -116,7 +158,10 @@ fn gen_struct_serialize(type_name: &TokenStream, fs: &data::Fields) -> TokenStre
                 {
                     match self {
                         Self::Init => #init_transition,
+                        Self::Begin(enc) => #begin_transition,
                         #(#transitions)*
+                        Self::End(enc) => #end_transition,
+                        #phantom_arm
                         Self::Fini => ::diny::backend::PollEncodeStatus::Error(__F::invalid_input_err())
                     }
                 }
@@ -129,11 +174,12 @@ fn gen_struct_serialize(type_name: &TokenStream, fs: &data::Fields) -> TokenStre
         encode_type,
         encode_init
     ) =
-        if fs.len() > 0 {
+        if fs.encoded().next().is_some() {
+            let format_type = &generics.format_type;
             (
-                non_empty_fields(fs),
-                quote! { __Encoder<__F> },
-                quote! { Self::Encoder::<__F>::Init }
+                non_empty_fields(&def.name, fs, &generics),
+                quote! { <__F as ::diny::backend::FormatEncodeHooks>::EncodeStruct<__StructDef, __Encoder #format_type> },
+                quote! { <Self::Encoder::<__F> as ::diny::backend::Encode>::init(self) }
             )
         } else {
             (
@@ -143,21 +189,36 @@ fn gen_struct_serialize(type_name: &TokenStream, fs: &data::Fields) -> TokenStre
             )
         };
 
-    quote! {
-        type __Data = #type_name;
+    let (encode_type, encode_init) =
+        if def.is_versioned() {
+            (
+                quote! { ::diny::backend::internal::versioned::Encoder<#encode_type> },
+                quote! { <Self::Encoder::<__F> as ::diny::backend::Encode>::init(self) },
+            )
+        } else {
+            (encode_type, encode_init)
+        };
 
+    let GenericsGen { data_type, data_impl, data_where, .. } = &generics;
+
+    quote! {
         #encode_impl
 
-        impl ::diny::backend::Encodable for __Data {
+        impl #data_impl ::diny::backend::Encodable for #data_type
+        #data_where
+        {
             type Encoder<__F>
             where
                 __F: ::diny::backend::FormatEncode,
             = #encode_type;
         }
     
-        impl ::diny::backend::AsyncSerialize for __Data {
+        impl #data_impl ::diny::backend::AsyncSerialize for #data_type
+        #data_where
+        {
             type Future<'w, __F, __W>
             where
+                Self: 'w,
                 __F: 'w + ::diny::backend::FormatSerialize,
                 __W: 'w + ::diny::io::AsyncWrite + ::core::marker::Unpin,
                 = ::diny::backend::SerializeAll<'w, __F, __W, Self, Self::Encoder<__F>>;
@@ -173,91 +234,184 @@ fn gen_struct_serialize(type_name: &TokenStream, fs: &data::Fields) -> TokenStre
     }
 }
 
-fn gen_struct_deserialize(type_name: &TokenStream, fs: &data::Fields) -> TokenStream {
+fn gen_struct_deserialize(def: &data::Def, fs: &data::Fields) -> TokenStream {
+    let generics = GenericsGen::for_decode(def);
+
+    fn empty_fields(type_name: &data::TypeName, fs: &data::Fields, generics: &GenericsGen) -> TokenStream {
+        let GenericsGen { data_type, data_impl, data_where, .. } = generics;
+
+        let skipped_field_assignments = fs.iter().map(|field| {
+            let name = &field.id.field_name();
+            let default = gen_field_default(field);
+
+            quote! { #name: #default }
+        });
 
-    fn empty_fields() -> TokenStream {
         quote! {
-            impl ::diny::backend::intrinsic::empty_struct::NewUnitStruct for __Data {
+            impl #data_impl ::diny::backend::intrinsic::empty_struct::NewUnitStruct for #data_type
+            #data_where
+            {
                 fn new_unit_struct() -> Self {
-                    __Data {}
+                    #type_name {
+                        #(#skipped_field_assignments,)*
+                    }
                 }
             }
         }
     }
 
-    fn non_empty_fields(fs: &data::Fields) -> TokenStream {
-        let encoded_fields: EncodedFieldsGen = fs.into();
-
-        let partial_named_fields = encoded_fields.iter().map(|field| {
-            let name = &field.field.id.field_name();
-            let type_ref = &field.field.type_ref;
+    fn non_empty_fields(type_name: &data::TypeName, fs: &data::Fields, generics: &GenericsGen) -> TokenStream {
+        let GenericsGen { data_type, data_generics, data_impl, data_where, format_impl, format_type, format_where, phantom } = generics;
 
-            quote! { #name: ::core::option::Option<#type_ref> }
-        });
+        let encoded_fields: EncodedFieldsGen = fs.into();
 
-        let partial_unnamed_fields = encoded_fields.iter().map(|field| {
+        // A field added `since` a version decodes to `None` when an older encoding ends before it
+        let partial_fields = encoded_fields.iter().map(|field| {
+            let var = &field.field.id.var_name();
             let type_ref = &field.field.type_ref;
 
-            quote! { ::core::option::Option<#type_ref> }
-        });
+            if field.field.attrs.since.is_some() {
+                quote! { #var: ::core::option::Option<::core::option::Option<#type_ref>> }
+            } else {
+                quote! { #var: ::core::option::Option<#type_ref> }
+            }
+        })
+        .chain(phantom.iter().map(|p| quote! { __phantom: #p }));
 
         let partial_field_defaults = encoded_fields.iter().map(|field| {
-            let name = &field.field.id.field_name();
-
-            quote! { #name: ::core::option::Option::None }
-        });
-
-        let partial_field_assignments = encoded_fields.iter().map(|field| {
-            let name = &field.field.id.field_name();
-
-            quote! { #name: self.#name? }
+            let var = &field.field.id.var_name();
+
+            quote! { #var: ::core::option::Option::None }
+        })
+        .chain(phantom.iter().map(|_| quote! { __phantom: ::core::marker::PhantomData }));
+
+        let partial_field_assignments = fs.iter().map(|field| {
+            let name = &field.id.field_name();
+
+            if field.attrs.is_skipped() {
+                let default = gen_field_default(field);
+                quote! { #name: #default }
+            } else if field.attrs.since.is_some() {
+                let var = &field.id.var_name();
+                let default = gen_field_default(field);
+                quote! { #name: self.#var?.unwrap_or_else(|| #default) }
+            } else {
+                let var = &field.id.var_name();
+                quote! { #name: self.#var? }
+            }
         });
 
         let variants = encoded_fields.iter().map(|field| {
             let ctor = &field.ctor;
-            let type_ref = &field.field.type_ref;
-            
-            let err_src = syn::spanned::Spanned::span(&type_ref);
-            quote_spanned! {err_src=> #ctor(<#type_ref as ::diny::backend::Decodable>::Decoder::<__F>) }
+            let decoder = field.field_decoder();
+
+            let err_src = field_span(field.field);
+            quote_spanned! {err_src=> #ctor(#decoder) }
         });
 
         let methods = encoded_fields.iter().map(|field| {
             let ctor = &field.ctor;
-            let field_name = &field.field.id.field_name();
-            let type_ref = &field.field.type_ref;
+            let var = &field.field.id.var_name();
+            let decoder = field.field_decoder();
             let this_method = &field.this_method;
 
             let next = &field.next_method.as_ref().map_or_else(
-                ||  quote! { ::diny::backend::StartDecodeStatus::Fini(()) },
+                ||  quote! { Self::after_fields(format, reader, cx) },
                 |n| quote! { Self::#n(format, reader, data, cx) },
             );
             
             quote! {
-                fn #this_method<__R>(format: &__F, reader: &mut __R, data: &mut __PartialData, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<(), Self, <__F as ::diny::backend::Format>::Error>
+                fn #this_method<__R>(format: &__F, reader: &mut __R, data: &mut __PartialData #data_generics, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<(), Self, <__F as ::diny::backend::Format>::Error>
                 where
                     __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
                 {
-                    <<#type_ref as ::diny::backend::Decodable>::Decoder::<__F> as ::diny::backend::Decode>::start_decode(format, reader, cx)
+                    <#decoder as ::diny::backend::Decode>::start_decode(format, reader, cx)
+                    .and_then(
+                        |d| { data.#var = ::core::option::Option::Some(d); #next },
+                        Self::#ctor,
+                    )
+                }
+            }
+        });
+
+        // Decoding into an existing value decodes each field into the field it replaces
+        let into_methods = encoded_fields.iter().map(|field| {
+            let ctor = &field.ctor;
+            let name = &field.field.id.field_name();
+            let decoder = field.field_decoder();
+            let this_method = format_ident!("{}_into", &field.this_method);
+
+            let next = &field.next_method.as_ref().map_or_else(
+                ||  quote! { Self::after_fields(format, reader, cx) },
+                |n| { let n = format_ident!("{}_into", n); quote! { Self::#n(format, reader, data, cx) } },
+            );
+
+            let start = if field.field.attrs.since.is_some() {
+                let default = gen_field_default(field.field);
+                quote! {
+                    <#decoder as ::diny::backend::Decode>::start_decode(format, reader, cx)
+                    .and_then(
+                        |d| { data.#name = d.unwrap_or_else(|| #default); #next },
+                        Self::#ctor,
+                    )
+                }
+            } else {
+                quote! {
+                    <#decoder as ::diny::backend::Decode>::start_decode_into(format, reader, &mut data.#name, cx)
                     .and_then(
-                        |d| { data.#field_name = ::core::option::Option::Some(d); #next },
+                        |()| #next,
                         Self::#ctor,
                     )
                 }
+            };
+
+            quote! {
+                fn #this_method<__R>(format: &__F, reader: &mut __R, data: &mut #data_type, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<(), Self, <__F as ::diny::backend::Format>::Error>
+                where
+                    __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
+                {
+                    #start
+                }
             }
         });
 
+        let skipped_field_resets = fs.iter().filter(|field| field.attrs.is_skipped()).map(|field| {
+            let name = &field.id.field_name();
+            let default = gen_field_default(field);
+
+            quote! { data.#name = #default; }
+        });
+
         let init_transition = gen_decode_chain(
             &quote! { state.cursor },
             &quote! { __DecodeCursor },
             quote! { __DecodeCursor::after_init(format, reader, &mut state.data, cx) },
         );
 
+        let begin_transition = gen_decode_poll_chain(
+            &quote! { state.cursor },
+            &quote! { __DecodeCursor },
+            quote! { dec.poll_decode(format, reader, cx) },
+            quote! { |()| __DecodeCursor::after_begin(format, reader, &mut state.data, cx) },
+        );
+
+        let end_transition = gen_decode_poll_fini(
+            &quote! { state.cursor },
+            &quote! { __DecodeCursor },
+            quote! { dec.poll_decode(format, reader, cx) },
+            quote! { |()| () },
+        );
+
+        let struct_def = gen_struct_def(type_name, fs);
+        let begin_decoder = quote! { <__F as ::diny::backend::FormatDecodeHooks>::DecodeStructBegin::<__StructDef> };
+        let end_decoder = quote! { <__F as ::diny::backend::FormatDecodeHooks>::DecodeStructEnd::<__StructDef> };
+
         let transitions = encoded_fields.iter().map(|field| {
             let ctor = &field.ctor;
-            let field_name = &field.field.id.field_name();
+            let var = &field.field.id.var_name();
 
             let next = &field.next_method.as_ref().map_or_else(
-                ||  quote! { ::diny::backend::StartDecodeStatus::Fini(()) },
+                ||  quote! { __DecodeCursor::after_fields(format, reader, cx) },
                 |n| quote! { __DecodeCursor::#n(format, reader, &mut state.data, cx) },
             );
 
@@ -267,7 +421,7 @@ fn gen_struct_deserialize(type_name: &TokenStream, fs: &data::Fields) -> TokenSt
                 quote! { dec.poll_decode(format, reader, cx) },
                 quote! {
                     |d| {
-                        state.data.#field_name = ::core::option::Option::Some(d);
+                        state.data.#var = ::core::option::Option::Some(d);
                         #next
                     }                    
                 }
@@ -280,55 +434,138 @@ fn gen_struct_deserialize(type_name: &TokenStream, fs: &data::Fields) -> TokenSt
             }
         });
 
-        let partial_field_def = match fs.style() {
-            data::FieldsStyle::Named => quote! { { #(#partial_named_fields,)* } },
-            data::FieldsStyle::Unnamed => quote! { ( #(#partial_unnamed_fields,)* ); },
-        };
+        let init_into_transition = gen_decode_chain(
+            &quote! { state.cursor },
+            &quote! { __DecodeCursor },
+            quote! { __DecodeCursor::after_init_into(format, reader, data, cx) },
+        );
+
+        let begin_into_transition = gen_decode_poll_chain(
+            &quote! { state.cursor },
+            &quote! { __DecodeCursor },
+            quote! { dec.poll_decode(format, reader, cx) },
+            quote! { |()| __DecodeCursor::after_begin_into(format, reader, data, cx) },
+        );
+
+        let into_transitions = encoded_fields.iter().map(|field| {
+            let ctor = &field.ctor;
+            let name = &field.field.id.field_name();
+
+            let next = &field.next_method.as_ref().map_or_else(
+                ||  quote! { __DecodeCursor::after_fields(format, reader, cx) },
+                |n| { let n = format_ident!("{}_into", n); quote! { __DecodeCursor::#n(format, reader, data, cx) } },
+            );
+
+            let poll_chain = if field.field.attrs.since.is_some() {
+                let default = gen_field_default(field.field);
+                gen_decode_poll_chain(
+                    &quote! { state.cursor },
+                    &quote! { __DecodeCursor },
+                    quote! { dec.poll_decode(format, reader, cx) },
+                    quote! { |d: ::core::option::Option<_>| { data.#name = d.unwrap_or_else(|| #default); #next } },
+                )
+            } else {
+                gen_decode_poll_chain(
+                    &quote! { state.cursor },
+                    &quote! { __DecodeCursor },
+                    quote! { dec.poll_decode_into(format, reader, &mut data.#name, cx) },
+                    quote! { |()| #next },
+                )
+            };
+
+            quote! {
+                __DecodeCursor::#ctor(dec) => {
+                    #poll_chain
+                }
+            }
+        });
+
+        let phantom_variant = generics.phantom_variant();
+        let phantom_arm = generics.phantom_arm(quote! { __DecodeCursor }, quote! { return ::diny::backend::PollDecodeStatus::Error(__F::invalid_input_err()) });
 
         quote! {
-            struct __PartialData #partial_field_def
+            struct __PartialData #data_impl
+            #data_where
+            {
+                #(#partial_fields,)*
+            }
 
-            impl __PartialData {
+            impl #data_impl __PartialData #data_generics
+            #data_where
+            {
                 fn new() -> Self {
                     Self {
                         #(#partial_field_defaults,)*
                     }
                 }
 
-                fn into_data(self) -> ::core::option::Option<__Data> {
-                    ::core::option::Option::Some(__Data {
+                fn into_data(self) -> ::core::option::Option<#data_type> {
+                    ::core::option::Option::Some(#type_name {
                         #(#partial_field_assignments,)*
                     })
                 }
             }
 
-            enum __DecodeCursor<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            #struct_def
+
+            enum __DecodeCursor #format_impl
+            #format_where
             {
                 Init,
+                Begin(#begin_decoder),
                 #(#variants,)*
+                End(#end_decoder),
+                #phantom_variant
                 Fini,
             }
 
-            impl<__F> __DecodeCursor<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            impl #format_impl __DecodeCursor #format_type
+            #format_where
             {
+                fn after_init<__R>(format: &__F, reader: &mut __R, data: &mut __PartialData #data_generics, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<(), Self, <__F as ::diny::backend::Format>::Error>
+                where
+                    __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
+                {
+                    <#begin_decoder as ::diny::backend::Decode>::start_decode(format, reader, cx)
+                    .and_then(
+                        |()| Self::after_begin(format, reader, data, cx),
+                        Self::Begin,
+                    )
+                }
+
                 #(#methods)*
+
+                fn after_init_into<__R>(format: &__F, reader: &mut __R, data: &mut #data_type, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<(), Self, <__F as ::diny::backend::Format>::Error>
+                where
+                    __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
+                {
+                    <#begin_decoder as ::diny::backend::Decode>::start_decode(format, reader, cx)
+                    .and_then(
+                        |()| Self::after_begin_into(format, reader, data, cx),
+                        Self::Begin,
+                    )
+                }
+
+                #(#into_methods)*
+
+                fn after_fields<__R>(format: &__F, reader: &mut __R, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<(), Self, <__F as ::diny::backend::Format>::Error>
+                where
+                    __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
+                {
+                    <#end_decoder as ::diny::backend::Decode>::start_decode(format, reader, cx)
+                    .bimap(|()| (), Self::End)
+                }
             }                
         
-            struct __DecodeState<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            struct __DecodeState #format_impl
+            #format_where
             {
-                data: __PartialData,
-                cursor: __DecodeCursor<__F>,
+                data: __PartialData #data_generics,
+                cursor: __DecodeCursor #format_type,
             }
 
-            impl<__F> __DecodeState<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            impl #format_impl __DecodeState #format_type
+            #format_where
             {
                 fn new() -> Self {
                     Self {
@@ -338,29 +575,32 @@ fn gen_struct_deserialize(type_name: &TokenStream, fs: &data::Fields) -> TokenSt
                 }
             }
 
-            pub struct __Decoder<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            pub struct __Decoder #format_impl
+            #format_where
             {
-                state: ::core::option::Option<__DecodeState<__F>>,
+                state: ::core::option::Option<__DecodeState #format_type>,
             }
 
-            impl<__F> __Decoder<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            impl #format_impl __Decoder #format_type
+            #format_where
             {
                 fn new() -> Self {
                     Self {
                         state: ::core::option::Option::Some(__DecodeState::new()),
                     }
                 }
+
+                // Skipped fields take their defaults, just as when decoding a new value
+                #[allow(unused_variables)]
+                fn reset_skipped(data: &mut #data_type) {
+                    #(#skipped_field_resets)*
+                }
             }
 
-            impl<__F> ::diny::backend::Decode for __Decoder<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            impl #format_impl ::diny::backend::Decode for __Decoder #format_type
+            #format_where
             {
-                type Data = __Data;
+                type Data = #data_type;
                 type Format = __F;
 
                 fn init() -> Self {
@@ -393,7 +633,14 @@ fn gen_struct_deserialize(type_name: &TokenStream, fs: &data::Fields) -> TokenSt
                             __DecodeCursor::Init => {
                                 #init_transition
                             }
+                            __DecodeCursor::Begin(dec) => {
+                                #begin_transition
+                            }
                             #(#transitions)*
+                            __DecodeCursor::End(dec) => {
+                                #end_transition
+                            }
+                            #phantom_arm
                             __DecodeCursor::Fini => return ::diny::backend::PollDecodeStatus::Error(__F::invalid_input_err()),
                         }
                         .and_then(|()| match self.state.take().unwrap().data.into_data() {
@@ -404,6 +651,45 @@ fn gen_struct_deserialize(type_name: &TokenStream, fs: &data::Fields) -> TokenSt
                         ::diny::backend::PollDecodeStatus::Error(__F::invalid_input_err())
                     }
                 }
+
+                fn start_decode_into<__R>(format: &__F, reader: &mut __R, data: &mut Self::Data, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<(), Self, <__F as ::diny::backend::Format>::Error>
+                where
+                    __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
+                {
+                    match __DecodeCursor::after_init_into(format, reader, data, cx) {
+                        ::diny::backend::StartDecodeStatus::Fini(()) => {
+                            Self::reset_skipped(data);
+                            ::diny::backend::StartDecodeStatus::Fini(())
+                        }
+                        ::diny::backend::StartDecodeStatus::Pending(cursor) => ::diny::backend::StartDecodeStatus::Pending(Self { state: ::core::option::Option::Some(__DecodeState { data: __PartialData::new(), cursor }) }),
+                        ::diny::backend::StartDecodeStatus::Error(e) => ::diny::backend::StartDecodeStatus::Error(e),
+                    }
+                }
+
+                fn poll_decode_into<__R>(&mut self, format: &__F, reader: &mut __R, data: &mut Self::Data, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::PollDecodeStatus<(), <__F as ::diny::backend::Format>::Error>
+                where
+                    __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
+                {
+                    if let Some(state) = &mut self.state {
+                        match &mut state.cursor {
+                            __DecodeCursor::Init => {
+                                #init_into_transition
+                            }
+                            __DecodeCursor::Begin(dec) => {
+                                #begin_into_transition
+                            }
+                            #(#into_transitions)*
+                            __DecodeCursor::End(dec) => {
+                                #end_transition
+                            }
+                            #phantom_arm
+                            __DecodeCursor::Fini => return ::diny::backend::PollDecodeStatus::Error(__F::invalid_input_err()),
+                        }
+                        .map(|()| Self::reset_skipped(data))
+                    } else {
+                        ::diny::backend::PollDecodeStatus::Error(__F::invalid_input_err())
+                    }
+                }
             }
         }
     }
@@ -413,33 +699,48 @@ fn gen_struct_deserialize(type_name: &TokenStream, fs: &data::Fields) -> TokenSt
         decode_type,
         decode_init
     ) =
-        if fs.len() > 0 {
+        if fs.encoded().next().is_some() {
+            let format_type = &generics.format_type;
             (
-                non_empty_fields(fs),
-                quote! { __Decoder<__F> },
+                non_empty_fields(&def.name, fs, &generics),
+                quote! { <__F as ::diny::backend::FormatDecodeHooks>::DecodeStruct<__StructDef, __Decoder #format_type> },
                 quote! { <Self::Decoder::<__F> as ::diny::backend::Decode>::init() }
             )
         } else {
             (
-                empty_fields(),
+                empty_fields(&def.name, fs, &generics),
                 quote! { ::diny::backend::intrinsic::empty_struct::Decoder::<__F, Self> },
                 quote! { <::diny::backend::intrinsic::empty_struct::Decoder::<__F, Self> as ::diny::backend::Decode>::init() }
             )
         };
 
-    quote! {
-        type __Data = #type_name;
+    let (decode_type, decode_init) =
+        if def.is_versioned() {
+            (
+                quote! { ::diny::backend::internal::versioned::Decoder<#decode_type> },
+                quote! { <Self::Decoder::<__F> as ::diny::backend::Decode>::init() },
+            )
+        } else {
+            (decode_type, decode_init)
+        };
+
+    let GenericsGen { data_type, data_impl, data_where, .. } = &generics;
 
+    quote! {
         #decode_impl
 
-        impl ::diny::backend::Decodable for __Data {
+        impl #data_impl ::diny::backend::Decodable for #data_type
+        #data_where
+        {
             type Decoder<__F>
             where
                 __F: ::diny::backend::FormatDecode,
             = #decode_type;
         }
     
-        impl ::diny::backend::AsyncDeserialize for __Data {
+        impl #data_impl ::diny::backend::AsyncDeserialize for #data_type
+        #data_where
+        {
             type Future<'r, __F, __R>
             where
                 __F: 'r + ::diny::backend::FormatDeserialize,
@@ -457,25 +758,29 @@ fn gen_struct_deserialize(type_name: &TokenStream, fs: &data::Fields) -> TokenSt
     }
 }
 
-fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStream {
+fn gen_enum_serialize(def: &data::Def, vs: &data::Variants) -> TokenStream {
+    let generics = GenericsGen::for_encode(def);
+
+    fn non_empty_variants(type_name: &data::TypeName, vs: &data::Variants, generics: &GenericsGen) -> TokenStream {
+        let GenericsGen { data_type, format_impl, format_type, format_where, .. } = generics;
 
-    fn non_empty_variants(vs: &data::Variants) -> TokenStream {
         let encoded_variants: EncodedVariantsGen = vs.into();
 
         let variants = encoded_variants.iter().map(|variant| {
             let encode_ctor = &variant.ctor;
-            let type_ref = variant.type_ref.to_token_stream();
+            let encoder = variant.payload_encoder();
 
-            let err_src = syn::spanned::Spanned::span(&type_ref);
-            quote_spanned! {err_src=> #encode_ctor(<#type_ref as ::diny::backend::Encodable>::Encoder::<__F>) }
+            let err_src = variant_span(variant);
+            quote_spanned! {err_src=> #encode_ctor(#encoder) }
         });
 
-        let variant_indices = encoded_variants.iter().enumerate().map(|(id, variant)| {
+        let variant_indices = encoded_variants.iter().map(|variant| {
             let data_ctor = &variant.variant.ctor;
+            let id = &variant.variant.index;
 
             match &variant.type_ref {
-                VariantType::Unit       => quote! { __Data::#data_ctor{}  => #id },
-                VariantType::TypeRef(_) => quote! { __Data::#data_ctor(_) => #id },
+                VariantType::Unit       => quote! { #type_name::#data_ctor{..} => #id },
+                VariantType::TypeRef(_) => quote! { #type_name::#data_ctor(_) => #id },
             }
         });
 
@@ -484,14 +789,15 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
             let this_method = &variant.this_method;
 
             match &variant.type_ref {
-                VariantType::Unit       => quote! { __Data::#data_ctor{}  => Self::#this_method(format, writer, &(), cx) },
-                VariantType::TypeRef(_) => quote! { __Data::#data_ctor(d) => Self::#this_method(format, writer, d  , cx) },
+                VariantType::Unit       => quote! { #type_name::#data_ctor{..} => Self::#this_method(format, writer, &(), cx) },
+                VariantType::TypeRef(_) => quote! { #type_name::#data_ctor(d) => Self::#this_method(format, writer, d  , cx) },
             }
         });
 
         let methods = encoded_variants.iter().map(|variant| {
             let ctor = &variant.ctor;
             let type_ref = &variant.type_ref.to_token_stream();
+            let encoder = variant.payload_encoder();
             let this_method = &variant.this_method;
 
             quote! {
@@ -499,7 +805,7 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
                 where
                     __W: ::diny::io::AsyncWrite + ::core::marker::Unpin,
                 {
-                    match <<#type_ref as ::diny::backend::Encodable>::Encoder::<__F> as ::diny::backend::Encode>::start_encode(format, writer, &data, cx) {
+                    match <#encoder as ::diny::backend::Encode>::start_encode(format, writer, data, cx) {
                         ::diny::backend::StartEncodeStatus::Fini         => ::diny::backend::StartEncodeStatus::Fini,
                         ::diny::backend::StartEncodeStatus::Pending(enc) => ::diny::backend::StartEncodeStatus::Pending(Self::#ctor(enc)),
                         ::diny::backend::StartEncodeStatus::Error(err)   => ::diny::backend::StartEncodeStatus::Error(err),        
@@ -508,8 +814,13 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
             }
         });
 
+        let enum_def = gen_enum_def(type_name, vs);
+        let index_encoder = quote! { <__F as ::diny::backend::FormatEncodeHooks>::EncodeVariant::<__EnumDef> };
+        let max_encoded_len = gen_max_encoded_len_variants(&index_encoder, encoded_variants.iter().map(|variant| variant.payload_encoder()));
         let init_transition = gen_encode_chain(quote! { Self::after_init(format, writer, data, cx) });
         let index_transition = gen_encode_poll_chain(quote! { enc.poll_encode(format, writer, &index, cx) }, quote! { Self::after_index(format, writer, data, cx) } );
+        let phantom_variant = generics.phantom_variant();
+        let phantom_arm = generics.phantom_arm(quote! { Self }, quote! { ::diny::backend::PollEncodeStatus::Error(__F::invalid_input_err()) });
 
         let transitions = encoded_variants.iter().map(|variant| {
             let encode_ctor = &variant.ctor;
@@ -518,11 +829,11 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
             let poll = match &variant.type_ref {
                 VariantType::Unit       => {
                     let poll_fini = gen_encode_poll_fini(quote! { enc.poll_encode(format, writer, &(), cx) });
-                    quote! { __Data::#data_ctor{}  => #poll_fini }
+                    quote! { #type_name::#data_ctor{..} => #poll_fini }
                 }
                 VariantType::TypeRef(_) => {
                     let poll_fini = gen_encode_poll_fini(quote! { enc.poll_encode(format, writer, d, cx) });
-                    quote! { __Data::#data_ctor(d) => #poll_fini }
+                    quote! { #type_name::#data_ctor(d) => #poll_fini }
                 }                
             };
 
@@ -537,39 +848,40 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
         });
 
         quote! {
-            pub enum __Encoder<__F>
-            where
-                __F: ::diny::backend::FormatEncode,
+            #enum_def
+
+            pub enum __Encoder #format_impl
+            #format_where
             {
                 Init,
-                Index(::diny::backend::internal::VariantIdx, <::diny::backend::internal::VariantIdx as ::diny::backend::Encodable>::Encoder<__F>),
+                Index(::diny::backend::internal::VariantIdx, #index_encoder),
                 #(#variants,)*
+                #phantom_variant
                 Fini,
             }
 
-            impl<__F> __Encoder<__F>
-            where
-                __F: ::diny::backend::FormatEncode,
+            impl #format_impl __Encoder #format_type
+            #format_where
             {
-                fn variant_index(data: &__Data) -> ::diny::backend::internal::VariantIdx {
-                    match data {
+                fn variant_index(data: &#data_type) -> ::diny::backend::internal::VariantIdx {
+                    ::diny::backend::internal::VariantIdx::new(match data {
                         #(#variant_indices,)*
-                    }.into()
+                    })
                 }
         
-                fn after_init<__W>(format: &__F, writer: &mut __W, data: &__Data, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartEncodeStatus<Self, <__F as ::diny::backend::Format>::Error>
+                fn after_init<__W>(format: &__F, writer: &mut __W, data: &#data_type, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartEncodeStatus<Self, <__F as ::diny::backend::Format>::Error>
                 where
                     __W: ::diny::io::AsyncWrite + ::core::marker::Unpin,
                 {
                     let index = Self::variant_index(data);
-                    match <<::diny::backend::internal::VariantIdx as ::diny::backend::Encodable>::Encoder::<__F> as ::diny::backend::Encode>::start_encode(format, writer, &index, cx) {
+                    match <#index_encoder as ::diny::backend::Encode>::start_encode(format, writer, &index, cx) {
                         ::diny::backend::StartEncodeStatus::Fini         => Self::after_index(format, writer, data, cx),
                         ::diny::backend::StartEncodeStatus::Pending(enc) => ::diny::backend::StartEncodeStatus::Pending(Self::Index(index, enc)),
                         ::diny::backend::StartEncodeStatus::Error(e)     => ::diny::backend::StartEncodeStatus::Error(e)            
                     }
                 }
                 
-                fn after_index<__W>(format: &__F, writer: &mut __W, data: &__Data, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartEncodeStatus<Self, <__F as ::diny::backend::Format>::Error>
+                fn after_index<__W>(format: &__F, writer: &mut __W, data: &#data_type, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartEncodeStatus<Self, <__F as ::diny::backend::Format>::Error>
                 where
                     __W: ::diny::io::AsyncWrite + ::core::marker::Unpin,
                 {
@@ -581,12 +893,13 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
                 #(#methods)*
             }                
         
-            impl<__F> ::diny::backend::Encode for __Encoder<__F>
-            where
-                __F: ::diny::backend::FormatEncode,
+            impl #format_impl ::diny::backend::Encode for __Encoder #format_type
+            #format_where
             {
-                type Data = __Data;
+                type Data = #data_type;
                 type Format = __F;
+
+                #max_encoded_len
         
                 fn init(_data: &Self::Data) -> Self {
                     Self::Init
@@ -611,6 +924,7 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
                             #index_transition
                         }
                         #(#transitions)*
+                        #phantom_arm
                         Self::Fini => {
                             debug_assert!(false);
                             ::diny::backend::PollEncodeStatus::Error(__F::invalid_input_err())
@@ -627,9 +941,10 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
         encode_init
     ) =
         if vs.len() > 0 {
+            let format_type = &generics.format_type;
             (
-                non_empty_variants(vs),
-                quote! { __Encoder<__F> },
+                non_empty_variants(&def.name, vs, &generics),
+                quote! { __Encoder #format_type },
                 quote! { Self::Encoder::<__F>::Init }
             )
         } else {
@@ -640,21 +955,26 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
             )
         };
 
-    quote! {
-        type __Data = #type_name;
+    let GenericsGen { data_type, data_impl, data_where, .. } = &generics;
 
+    quote! {
         #encode_impl
 
-        impl ::diny::backend::Encodable for __Data {
+        impl #data_impl ::diny::backend::Encodable for #data_type
+        #data_where
+        {
             type Encoder<__F>
             where
                 __F: ::diny::backend::FormatEncode,
             = #encode_type;
         }
     
-        impl ::diny::backend::AsyncSerialize for __Data {
+        impl #data_impl ::diny::backend::AsyncSerialize for #data_type
+        #data_where
+        {
             type Future<'w, __F, __W>
             where
+                Self: 'w,
                 __F: 'w + ::diny::backend::FormatSerialize,
                 __W: 'w + ::diny::io::AsyncWrite + ::core::marker::Unpin,
                 = ::diny::backend::SerializeAll<'w, __F, __W, Self, Self::Encoder<__F>>;
@@ -670,21 +990,25 @@ fn gen_enum_serialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStre
     }
 }
 
-fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenStream {
+fn gen_enum_deserialize(def: &data::Def, vs: &data::Variants) -> TokenStream {
+    let generics = GenericsGen::for_decode(def);
+
+    fn non_empty_fields(type_name: &data::TypeName, vs: &data::Variants, generics: &GenericsGen) -> TokenStream {
+        let GenericsGen { data_type, format_impl, format_type, format_where, .. } = generics;
 
-    fn non_empty_fields(vs: &data::Variants) -> TokenStream {
         let encoded_variants: EncodedVariantsGen = vs.into();
 
         let variants = encoded_variants.iter().map(|variant| {
             let decode_ctor = &variant.ctor;
-            let type_ref = &variant.type_ref.to_token_stream();
-            
-            let err_src = syn::spanned::Spanned::span(&type_ref);
-            quote_spanned! {err_src=> #decode_ctor(<#type_ref as ::diny::backend::Decodable>::Decoder::<__F>) }
+            let decoder = variant.payload_decoder();
+
+            let err_src = variant_span(variant);
+            quote_spanned! {err_src=> #decode_ctor(#decoder) }
         });
 
-        let dispatch = encoded_variants.iter().enumerate().map(|(id, variant)| {
+        let dispatch = encoded_variants.iter().map(|variant| {
             let this_method = &variant.this_method;
+            let id = &variant.variant.index;
 
             quote! { #id => Self::#this_method(format, reader, cx) }
         });
@@ -692,20 +1016,23 @@ fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenSt
         let methods = encoded_variants.iter().map(|variant| {
             let data_ctor = &variant.variant.ctor;
             let decode_ctor = &variant.ctor;
-            let type_ref = &variant.type_ref.to_token_stream();
+            let decoder = variant.payload_decoder();
             let this_method = &variant.this_method;
 
             let status_ctor = match &variant.type_ref {
-                VariantType::Unit => quote! { |_| ::diny::backend::StartDecodeStatus::Fini(__Data::#data_ctor{}) },
-                VariantType::TypeRef(_) => quote! { |d| ::diny::backend::StartDecodeStatus::Fini(__Data::#data_ctor(d)) },
+                VariantType::Unit => {
+                    let ctor = gen_unit_variant_ctor(variant);
+                    quote! { |_| ::diny::backend::StartDecodeStatus::Fini(#type_name::#ctor) }
+                }
+                VariantType::TypeRef(_) => quote! { |d| ::diny::backend::StartDecodeStatus::Fini(#type_name::#data_ctor(d)) },
             };
 
             quote! {
-                fn #this_method<__R>(format: &__F, reader: &mut __R, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<__Data, Self, <__F as ::diny::backend::Format>::Error>
+                fn #this_method<__R>(format: &__F, reader: &mut __R, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<#data_type, Self, <__F as ::diny::backend::Format>::Error>
                 where
                     __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
                 {
-                    <<#type_ref as ::diny::backend::Decodable>::Decoder::<__F> as ::diny::backend::Decode>::start_decode(format, reader, cx)
+                    <#decoder as ::diny::backend::Decode>::start_decode(format, reader, cx)
                     .and_then(
                         #status_ctor,
                         Self::#decode_ctor
@@ -726,12 +1053,14 @@ fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenSt
             quote! { dec.poll_decode(format, reader, cx) },
             quote! { |idx| Self::after_index(idx, format, reader, cx) }
         );
+        let phantom_variant = generics.phantom_variant();
+        let phantom_arm = generics.phantom_arm(quote! { Self }, quote! { ::diny::backend::PollDecodeStatus::Error(__F::invalid_input_err()) });
 
         let transitions = encoded_variants.iter().map(|variant| {
             let decode_ctor = &variant.ctor;
             let data_ctor = &variant.variant.ctor;
             let ctor = match &variant.type_ref {
-                VariantType::Unit => quote! { #data_ctor{} },
+                VariantType::Unit => gen_unit_variant_ctor(variant),
                 VariantType::TypeRef(_) => quote! { #data_ctor(_d) },
             };
 
@@ -739,7 +1068,7 @@ fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenSt
                 &quote! { *self },
                 &quote! { Self },
                 quote! { dec.poll_decode(format, reader, cx) },
-                quote! { |_d| __Data::#ctor }
+                quote! { |_d| #type_name::#ctor }
             );
 
             quote! {
@@ -749,33 +1078,37 @@ fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenSt
             }
         });
 
+        let enum_def = gen_enum_def(type_name, vs);
+        let index_decoder = quote! { <__F as ::diny::backend::FormatDecodeHooks>::DecodeVariant::<__EnumDef> };
+
         quote! {
-            pub enum __Decoder<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            #enum_def
+
+            pub enum __Decoder #format_impl
+            #format_where
             {
                 Init,
-                Index(<::diny::backend::internal::VariantIdx as ::diny::backend::Decodable>::Decoder<__F>),
+                Index(#index_decoder),
                 #(#variants,)*
+                #phantom_variant
                 Fini,
             }
 
-            impl<__F> __Decoder<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            impl #format_impl __Decoder #format_type
+            #format_where
             {
-                fn from_index<__R>(format: &__F, reader: &mut __R, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<__Data, Self, <__F as ::diny::backend::Format>::Error>
+                fn from_index<__R>(format: &__F, reader: &mut __R, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<#data_type, Self, <__F as ::diny::backend::Format>::Error>
                 where
                     __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
                 {
-                    <<diny::backend::internal::VariantIdx as ::diny::backend::Decodable>::Decoder::<__F> as ::diny::backend::Decode>::start_decode(format, reader, cx)
+                    <#index_decoder as ::diny::backend::Decode>::start_decode(format, reader, cx)
                     .and_then(
                         |idx| Self::after_index(idx, format, reader, cx),
                         Self::Index
                     )
                 }
         
-                fn after_index<__R>(index: ::diny::backend::internal::VariantIdx, format: &__F, reader: &mut __R, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<__Data, Self, <__F as ::diny::backend::Format>::Error>
+                fn after_index<__R>(index: ::diny::backend::internal::VariantIdx, format: &__F, reader: &mut __R, cx: &mut ::core::task::Context<'_>) -> ::diny::backend::StartDecodeStatus<#data_type, Self, <__F as ::diny::backend::Format>::Error>
                 where
                     __R: ::diny::io::AsyncBufRead + ::core::marker::Unpin,
                 {
@@ -788,11 +1121,10 @@ fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenSt
                 #(#methods)*
             }
 
-            impl<__F> ::diny::backend::Decode for __Decoder<__F>
-            where
-                __F: ::diny::backend::FormatDecode,
+            impl #format_impl ::diny::backend::Decode for __Decoder #format_type
+            #format_where
             {
-                type Data = __Data;
+                type Data = #data_type;
                 type Format = __F;
         
                 fn init() -> Self {
@@ -819,6 +1151,7 @@ fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenSt
                             #index_transition
                         }
                         #(#transitions)*
+                        #phantom_arm
                         Self::Fini => {
                             ::diny::backend::PollDecodeStatus::Error(__F::invalid_input_err())
                         }
@@ -834,9 +1167,10 @@ fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenSt
         decode_init
     ) =
         if vs.len() > 0 {
+            let format_type = &generics.format_type;
             (
-                non_empty_fields(vs),
-                quote! { __Decoder<__F> },
+                non_empty_fields(&def.name, vs, &generics),
+                quote! { __Decoder #format_type },
                 quote! { Self::Decoder::<__F>::Init }
             )
         } else {
@@ -847,19 +1181,23 @@ fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenSt
             )
         };
 
-    quote! {
-        type __Data = #type_name;
+    let GenericsGen { data_type, data_impl, data_where, .. } = &generics;
 
+    quote! {
         #decode_impl
 
-        impl ::diny::backend::Decodable for __Data {
+        impl #data_impl ::diny::backend::Decodable for #data_type
+        #data_where
+        {
             type Decoder<__F>
             where
                 __F: ::diny::backend::FormatDecode,
             = #decode_type;
         }
     
-        impl ::diny::backend::AsyncDeserialize for __Data {
+        impl #data_impl ::diny::backend::AsyncDeserialize for #data_type
+        #data_where
+        {
             type Future<'r, __F, __R>
             where
                 __F: 'r + ::diny::backend::FormatDeserialize,
@@ -878,6 +1216,198 @@ fn gen_enum_deserialize(type_name: &TokenStream, vs: &data::Variants) -> TokenSt
 }
 
 
+fn field_span(field: &data::Field) -> proc_macro2::Span {
+    field.attrs.with.as_ref().map_or_else(
+        || syn::spanned::Spanned::span(&field.type_ref),
+        syn::spanned::Spanned::span,
+    )
+}
+
+fn variant_span(variant: &EncodedVariantGen) -> proc_macro2::Span {
+    variant.variant.fields.first().map_or_else(
+        || syn::spanned::Spanned::span(&variant.type_ref.to_token_stream()),
+        field_span,
+    )
+}
+
+fn gen_field_default(field: &data::Field) -> TokenStream {
+    match &field.attrs.default {
+        None => {
+            let type_ref = &field.type_ref;
+            let err_src = syn::spanned::Spanned::span(type_ref);
+            quote_spanned! {err_src=> <#type_ref as ::core::default::Default>::default() }
+        }
+        Some(path) => quote! { #path() },
+    }
+}
+
+/// Construct a variant without an encoded payload, filling any skipped field with its default.
+fn gen_unit_variant_ctor(variant: &EncodedVariantGen) -> TokenStream {
+    let data_ctor = &variant.variant.ctor;
+    match variant.skipped {
+        Some(field) => {
+            let name = &field.id.field_name();
+            let default = gen_field_default(field);
+            quote! { #data_ctor { #name: #default } }
+        }
+        None => quote! { #data_ctor{} },
+    }
+}
+
+/// The static schema of the data type, generated alongside its encoder.
+fn gen_describe(def: &data::Def) -> TokenStream {
+    let type_name = &def.name;
+    let name = syn::ext::IdentExt::unraw(&**type_name).to_string();
+
+    let generics = def.generics.with_bound(&quote! { ::diny::schema::Describe });
+    let (data_impl, data_type, data_where) = generics.split_for_impl();
+    let params = generics.type_params().map(|p| &p.ident);
+    let params = quote! { &[#(::diny::schema::SchemaRef::of::<#params>()),*] };
+
+    let schema = match &def.shape {
+        data::Shape::Struct(fs) => {
+            let fields = gen_field_schemas(fs);
+            let versioned = def.is_versioned();
+            quote! {
+                ::diny::schema::Schema::Struct(&::diny::schema::StructSchema {
+                    name: #name,
+                    params: #params,
+                    fields: #fields,
+                    versioned: #versioned,
+                })
+            }
+        },
+        data::Shape::Enum(vs) => {
+            let variants = vs.iter().map(|variant| {
+                let index = variant.index.index as usize;
+                let name = syn::ext::IdentExt::unraw(&*variant.ctor).to_string();
+                let fields = gen_field_schemas(&variant.fields);
+                quote! {
+                    ::diny::schema::VariantSchema {
+                        index: #index,
+                        name: #name,
+                        fields: #fields,
+                    }
+                }
+            });
+            quote! {
+                ::diny::schema::Schema::Enum(&::diny::schema::EnumSchema {
+                    name: #name,
+                    params: #params,
+                    variants: &[#(#variants),*],
+                })
+            }
+        },
+    };
+
+    quote! {
+        impl #data_impl ::diny::schema::Describe for #type_name #data_type
+        #data_where
+        {
+            const SCHEMA: ::diny::schema::Schema = #schema;
+        }
+    }
+}
+
+fn gen_field_schemas(fs: &data::Fields) -> TokenStream {
+    let fields = fs.encoded().zip(fs.tags()).map(|(field, tag)| {
+        let name = match &field.id.name {
+            Some(name) => {
+                let name = syn::ext::IdentExt::unraw(&**name).to_string();
+                quote! { ::core::option::Option::Some(#name) }
+            },
+            None => quote! { ::core::option::Option::None },
+        };
+        let schema = schema_ref(&field.type_ref, &field.attrs);
+        let since = match &field.attrs.since {
+            Some(since) => {
+                let since = since.value;
+                quote! { ::core::option::Option::Some(#since) }
+            },
+            None => quote! { ::core::option::Option::None },
+        };
+
+        let err_src = field_span(field);
+        quote_spanned! {err_src=>
+            ::diny::schema::FieldSchema {
+                name: #name,
+                tag: #tag,
+                schema: #schema,
+                since: #since,
+            }
+        }
+    });
+
+    quote! { &[#(#fields),*] }
+}
+
+/// A description of the struct for the formats that encode its shape, counting
+/// only its encoded fields.
+fn gen_struct_def(type_name: &data::TypeName, fs: &data::Fields) -> TokenStream {
+    let name = syn::ext::IdentExt::unraw(&**type_name).to_string();
+    let field_count = fs.encoded().count();
+    let field_names = fs.encoded().map(|field| field.id.name.as_ref().map(|name| syn::ext::IdentExt::unraw(&**name).to_string())).collect::<Option<Vec<_>>>();
+    let field_names = match field_names {
+        Some(names) => quote! { ::core::option::Option::Some(&[#(#names),*]) },
+        None        => quote! { ::core::option::Option::None },
+    };
+    let field_tags = fs.tags();
+
+    quote! {
+        pub struct __StructDef;
+
+        impl ::diny::backend::internal::StructDef for __StructDef {
+            const NAME: ::core::option::Option<&'static str> = ::core::option::Option::Some(#name);
+            const FIELD_COUNT: usize = #field_count;
+            const FIELD_NAMES: ::core::option::Option<&'static [&'static str]> = #field_names;
+            const FIELD_TAGS: ::core::option::Option<&'static [u32]> = ::core::option::Option::Some(&[#(#field_tags),*]);
+        }
+    }
+}
+
+/// A description of the enum's variants for the formats that encode them by name.
+fn gen_enum_def(type_name: &data::TypeName, vs: &data::Variants) -> TokenStream {
+    let name = syn::ext::IdentExt::unraw(&**type_name).to_string();
+    let variants = vs.iter().map(|variant| {
+        let index = variant.index.index as usize;
+        let name = syn::ext::IdentExt::unraw(&*variant.ctor).to_string();
+        quote! { (#index, #name) }
+    });
+    let units = vs.iter()
+        .filter(|variant| variant.fields.encoded().next().is_none())
+        .map(|variant| variant.index.index as usize);
+
+    quote! {
+        struct __EnumDef;
+
+        impl ::diny::backend::internal::EnumDef for __EnumDef {
+            const NAME: &'static str = #name;
+            const VARIANTS: &'static [(usize, &'static str)] = &[#(#variants),*];
+            const UNIT_VARIANTS: &'static [usize] = &[#(#units),*];
+        }
+    }
+}
+
+fn gen_max_encoded_len_sum(encoders: impl Iterator<Item = TokenStream>) -> TokenStream {
+    quote! {
+        const MAX_ENCODED_LEN: ::core::option::Option<usize> = {
+            let len = ::core::option::Option::Some(0);
+            #(let len = ::diny::backend::size::sum(len, <#encoders as ::diny::backend::Encode>::MAX_ENCODED_LEN);)*
+            len
+        };
+    }
+}
+
+fn gen_max_encoded_len_variants(index_encoder: &TokenStream, encoders: impl Iterator<Item = TokenStream>) -> TokenStream {
+    quote! {
+        const MAX_ENCODED_LEN: ::core::option::Option<usize> = {
+            let len = ::core::option::Option::Some(0);
+            #(let len = ::diny::backend::size::max(len, <#encoders as ::diny::backend::Encode>::MAX_ENCODED_LEN);)*
+            ::diny::backend::size::sum(<#index_encoder as ::diny::backend::Encode>::MAX_ENCODED_LEN, len)
+        };
+    }
+}
+
 fn gen_encode_chain(start: TokenStream) -> TokenStream {
     quote! {
         match #start {
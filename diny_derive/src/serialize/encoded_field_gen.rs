@@ -1,49 +1,119 @@
+use proc_macro2::TokenStream;
 use crate::data;
 
 pub struct EncodedFieldGen<'a> {
     pub field: &'a data::Field<'a>,
+    pub pos: usize,
     pub ctor: syn::Ident,
     pub this_method: syn::Ident,
     pub next_method: Option<syn::Ident>,
 }
 
 impl<'a> EncodedFieldGen<'a> {
-    fn for_struct(field: &'a data::Field<'a>, is_last: bool) -> Self {
+    fn for_struct(field: &'a data::Field<'a>, pos: usize, is_last: bool) -> Self {
         let ctor = format_ident!("F{}", *field.id.index);
 
         let this_method =
-            if field.id.index.index == 0 {
-                format_ident!("after_init")
+            if pos == 0 {
+                format_ident!("after_begin")
             } else {
-                format_ident!("after_f{}", field.id.index.index - 1)
+                format_ident!("after_f{}", pos - 1)
             };
 
         let next_method =
             if is_last {
                 None
             } else {
-                Some(format_ident!("after_f{}", *field.id.index))
+                Some(format_ident!("after_f{}", pos))
             };
 
         Self {
             field,
+            pos,
             ctor,
             this_method,
             next_method,
         }
     }
+
+    pub fn encoder(&self) -> TokenStream {
+        encoder_type(&self.field.type_ref, Some(&self.field.attrs))
+    }
+
+    pub fn decoder(&self) -> TokenStream {
+        decoder_type(&self.field.type_ref, Some(&self.field.attrs))
+    }
+
+    /// The encoder for the field, as the format wraps it for the field's position
+    pub fn field_encoder(&self) -> TokenStream {
+        let pos = self.pos;
+        let encoder = self.encoder();
+        quote! { <__F as ::diny::backend::FormatEncodeHooks>::EncodeStructField<__StructDef, #pos, #encoder> }
+    }
+
+    /// The decoder for the field, as the format wraps it for the field's position,
+    /// which is optional if the field was added `since` a version, and which
+    /// reports the field as the context of any error
+    pub fn field_decoder(&self) -> TokenStream {
+        let pos = self.pos;
+        let decoder = self.decoder();
+        let field_decoder = quote! { <__F as ::diny::backend::FormatDecodeHooks>::DecodeStructField<__StructDef, #pos, #decoder> };
+        let field_decoder = if self.field.attrs.since.is_some() {
+            quote! { ::diny::backend::internal::versioned::Trailing<#field_decoder> }
+        } else {
+            field_decoder
+        };
+        quote! { ::diny::backend::internal::Within<::diny::backend::internal::FieldOf<__StructDef, #pos>, #field_decoder> }
+    }
+}
+
+/// The encoder for a field, either the one its type is `Encodable` with, the
+/// `Encoder` found within the module specified by `#[diny(with = "...")]`, or
+/// the serde bridge selected by `#[diny(serde)]`.
+pub fn encoder_type<T: quote::ToTokens>(type_ref: &T, attrs: Option<&data::FieldAttrs>) -> TokenStream {
+    match attrs {
+        Some(data::FieldAttrs { with: Some(m), .. }) => quote! { #m::Encoder::<__F> },
+        Some(data::FieldAttrs { serde: Some(_), .. }) => quote! { ::diny::serde::Encoder::<__F, #type_ref> },
+        _ => quote! { <#type_ref as ::diny::backend::Encodable>::Encoder::<__F> },
+    }
+}
+
+/// The decoder for a field, either the one its type is `Decodable` with, the
+/// `Decoder` found within the module specified by `#[diny(with = "...")]`, or
+/// the serde bridge selected by `#[diny(serde)]`.
+pub fn decoder_type<T: quote::ToTokens>(type_ref: &T, attrs: Option<&data::FieldAttrs>) -> TokenStream {
+    match attrs {
+        Some(data::FieldAttrs { with: Some(m), .. }) => quote! { #m::Decoder::<__F> },
+        Some(data::FieldAttrs { serde: Some(_), .. }) => quote! { ::diny::serde::Decoder::<__F, #type_ref> },
+        _ => quote! { <#type_ref as ::diny::backend::Decodable>::Decoder::<__F> },
+    }
+}
+
+/// A reference to the schema of a field, either that of its type, a custom
+/// schema naming the module specified by `#[diny(with = "...")]`, or the bytes
+/// written by the serde bridge selected by `#[diny(serde)]`.
+pub fn schema_ref<T: quote::ToTokens>(type_ref: &T, attrs: &data::FieldAttrs) -> TokenStream {
+    match attrs {
+        data::FieldAttrs { with: Some(m), .. } => {
+            let path = quote! { #m }.to_string().replace(' ', "");
+            quote! { ::diny::schema::SchemaRef::new(|| ::diny::schema::Schema::Custom(#path)) }
+        },
+        data::FieldAttrs { serde: Some(_), .. } => quote! { ::diny::schema::SchemaRef::of::<::diny::serde::Serde<#type_ref>>() },
+        _ => quote! { ::diny::schema::SchemaRef::of::<#type_ref>() },
+    }
 }
 
 newtype!(pub EncodedFieldsGen<'a>(Vec<EncodedFieldGen<'a>>));
 
 impl<'a> From<&'a data::Fields<'a>> for EncodedFieldsGen<'a> {
     fn from(fields: &'a data::Fields<'a>) -> Self {
-        let n = fields.len() as u32;
+        let n = fields.encoded().count();
         Self(
             fields
-            .iter()
-            .map(|field| EncodedFieldGen::for_struct(field, n-1 == field.id.index.index))
+            .encoded()
+            .enumerate()
+            .map(|(pos, field)| EncodedFieldGen::for_struct(field, pos, n-1 == pos))
             .collect::<Vec<_>>()
         )        
     }
-}
\ No newline at end of file
+}
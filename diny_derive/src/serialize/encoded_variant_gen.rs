@@ -1,6 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use crate::data;
+use super::encoded_field_gen::{decoder_type, encoder_type};
 
 pub enum VariantType<'a> {
     Unit,
@@ -20,22 +21,63 @@ pub struct EncodedVariantGen<'a> {
     pub variant: &'a data::Variant<'a>,
     pub ctor: syn::Ident,
     pub type_ref: VariantType<'a>,
+    pub attrs: Option<&'a data::FieldAttrs>,
+    /// The field of a variant whose payload is skipped, which is filled with its default
+    pub skipped: Option<&'a data::Field<'a>>,
     pub this_method: syn::Ident,
 }
 
 impl<'a> EncodedVariantGen<'a> {
     fn for_enum(variant: &'a data::Variant<'a>) -> Self {
         let ctor = format_ident!("V{}", *variant.index);
-        let type_ref = variant.fields.first().map_or_else(|| VariantType::Unit, |v| VariantType::TypeRef(&v.type_ref));
+        let encoded = variant.fields.encoded().next();
+        let type_ref = encoded.map_or_else(|| VariantType::Unit, |v| VariantType::TypeRef(&v.type_ref));
+        let attrs = encoded.map(|v| &v.attrs);
+        let skipped = variant.fields.first().filter(|v| v.attrs.is_skipped());
         let this_method = format_ident!("v{}", variant.index.index);
 
         Self {
             variant,
             ctor,
             type_ref,
+            attrs,
+            skipped,
             this_method,
         }
     }
+
+    pub fn encoder(&self) -> TokenStream {
+        encoder_type(&self.type_ref.to_token_stream(), self.attrs)
+    }
+
+    pub fn decoder(&self) -> TokenStream {
+        decoder_type(&self.type_ref.to_token_stream(), self.attrs)
+    }
+
+    /// The encoder for the payload, followed by whatever the format writes at the end of a variant
+    pub fn payload_encoder(&self) -> TokenStream {
+        let encoder = self.encoder();
+        quote! { ::diny::backend::internal::Suffixed<#encoder, <__F as ::diny::backend::FormatEncodeHooks>::EncodeVariantEnd<__EnumDef>> }
+    }
+
+    /// The decoder for the payload, followed by whatever the format reads at the end of a variant,
+    /// or the format's own decoder for the rest of a unit variant, and which reports the variant as the context of any error
+    pub fn payload_decoder(&self) -> TokenStream {
+        let payload = match &self.type_ref {
+            VariantType::Unit => quote! { <__F as ::diny::backend::FormatDecodeHooks>::DecodeUnitVariant<__EnumDef> },
+            VariantType::TypeRef(type_ref) => {
+                let decoder = self.decoder();
+                quote! { ::diny::backend::internal::Suffixed<#decoder, <__F as ::diny::backend::FormatDecodeHooks>::DecodeVariantEnd<__EnumDef>, #type_ref> }
+            }
+        };
+        let idx = self.variant.index.index as usize;
+        quote! {
+            ::diny::backend::internal::Within<
+                ::diny::backend::internal::VariantOf<__EnumDef, #idx>,
+                #payload,
+            >
+        }
+    }
 }
 
 newtype!(pub EncodedVariantsGen<'a>(Vec<EncodedVariantGen<'a>>));
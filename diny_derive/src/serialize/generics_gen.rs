@@ -0,0 +1,69 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use crate::data;
+
+/// The generic parameter lists required to emit both the impls on the
+/// data type itself, and the encoder/decoder types that are additionally
+/// parameterized over the format `__F`.
+pub struct GenericsGen {
+    pub data_type: TokenStream,
+    pub data_generics: TokenStream,
+    pub data_impl: TokenStream,
+    pub data_where: TokenStream,
+    pub format_impl: TokenStream,
+    pub format_type: TokenStream,
+    pub format_where: TokenStream,
+    pub phantom: Option<TokenStream>,
+}
+
+impl GenericsGen {
+    fn new(def: &data::Def, data_bound: TokenStream, format_bound: TokenStream) -> Self {
+        let type_name = &def.name;
+
+        let data_generics = def.generics.with_field_bound(&def.shape, &data_bound);
+        let (data_impl, data_type, data_where) = data_generics.split_for_impl();
+
+        let mut format_generics = data_generics.clone();
+        format_generics.params.push(parse_quote! { __F });
+        format_generics.make_where_clause().predicates.push(parse_quote! { __F: #format_bound });
+        let (format_impl, format_type, format_where) = format_generics.split_for_impl();
+
+        // Skipped fields, and those encoded `with` another module, do not name their type in
+        // the generated encoders and decoders, potentially leaving some parameters unused.
+        let phantom =
+            if def.shape.has_field_attrs() && def.generics.has_type_or_lifetime_params() {
+                Some(quote! { ::core::marker::PhantomData<fn() -> #type_name #data_type> })
+            } else {
+                None
+            };
+
+        Self {
+            data_type: quote! { #type_name #data_type },
+            data_generics: data_type.to_token_stream(),
+            data_impl: data_impl.to_token_stream(),
+            data_where: data_where.to_token_stream(),
+            format_impl: format_impl.to_token_stream(),
+            format_type: format_type.to_token_stream(),
+            format_where: format_where.to_token_stream(),
+            phantom,
+        }
+    }
+
+    /// An additional, never constructed, variant for the generated encoder and decoder enums
+    pub fn phantom_variant(&self) -> TokenStream {
+        self.phantom.as_ref().map_or_else(TokenStream::new, |p| quote! { #[allow(dead_code)] __Phantom(#p), })
+    }
+
+    /// The match arm that accompanies the [phantom_variant](Self::phantom_variant)
+    pub fn phantom_arm(&self, ty: TokenStream, on_match: TokenStream) -> TokenStream {
+        self.phantom.as_ref().map_or_else(TokenStream::new, |_| quote! { #ty::__Phantom(_) => #on_match, })
+    }
+
+    pub fn for_encode(def: &data::Def) -> Self {
+        Self::new(def, quote! { ::diny::backend::Encodable }, quote! { ::diny::backend::FormatEncode })
+    }
+
+    pub fn for_decode(def: &data::Def) -> Self {
+        Self::new(def, quote! { ::diny::backend::Decodable }, quote! { ::diny::backend::FormatDecode })
+    }
+}
@@ -1,3 +1,4 @@
 pub mod code;
 pub mod encoded_field_gen;
-pub mod encoded_variant_gen;
\ No newline at end of file
+pub mod encoded_variant_gen;
+pub mod generics_gen;
@@ -0,0 +1,92 @@
+use core::task::Context;
+use diny::{backend::{self, Format}, buffer, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+type Error = <ThisFormat as Format>::Error;
+type Data = bool;
+
+pub struct Encoder(lex::Emit<1>);
+
+impl buffer::BufferEncode for Encoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(5);
+
+    fn init_buffer(data: &Self::Data) -> Self {
+        Self(lex::Emit::new([if *data { b"true" as &[u8] } else { b"false" }]))
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        enc.0.write_remaining(writer, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.write_remaining(writer, cx)
+    }
+}
+
+// The first character determines which of the two literals must follow
+pub enum Decoder {
+    Init,
+    True(lex::Expect<1>),
+    False(lex::Expect<1>),
+}
+
+impl Decoder {
+    fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self {
+                Self::Init => match lex::skip_ws(reader, cx) {
+                    backend::PollDecodeStatus::Fini(b't') => *self = Self::True (lex::Expect::new([(false, b"true" )])),
+                    backend::PollDecodeStatus::Fini(b'f') => *self = Self::False(lex::Expect::new([(false, b"false")])),
+                    backend::PollDecodeStatus::Fini(_)    => return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+                    backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e)   => return backend::PollDecodeStatus::Error(e),
+                },
+                Self::True (dec) => return dec.read_remaining(reader, cx).map(|()| true ),
+                Self::False(dec) => return dec.read_remaining(reader, cx).map(|()| false),
+            }
+        }
+    }
+}
+
+impl backend::Decode for Decoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self::Init
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.read_remaining(reader, cx)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(reader, cx)
+    }
+}
+
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
@@ -0,0 +1,136 @@
+use core::{pin::Pin, task::{Context, Poll}};
+use diny::{backend::{self, Encode as _, Format}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+
+type Data = [u8];
+type Error = <ThisFormat as Format>::Error;
+
+// A comma, followed by at most three digits
+const MAX_ITEM_LEN: usize = 4;
+
+/// Writes the bytes as an array of numbers
+pub enum Encoder {
+    Open,
+    Item(usize, BufferState<MAX_ITEM_LEN>),
+    Close,
+    Fini,
+}
+
+impl Encoder {
+    // Prepare to write the byte at `idx`, or the closing bracket after the last one
+    fn item(data: &Data, idx: usize) -> Self {
+        match data.get(idx) {
+            None    => Self::Close,
+            Some(b) => {
+                let mut fmt = itoa::Buffer::new();
+                let digits = fmt.format(*b).as_bytes();
+                let start = usize::from(idx > 0);
+                let mut buf = [b','; MAX_ITEM_LEN];
+                buf[start..start + digits.len()].copy_from_slice(digits);
+                Self::Item(idx + 1, BufferState::with_partial_contents(buf, start + digits.len()))
+            }
+        }
+    }
+
+    fn write_remaining<W>(&mut self, writer: &mut W, data: &Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        loop {
+            match self {
+                Self::Open => match lex::poll_write(writer, b"[", cx) {
+                    Poll::Ready(Ok(_))  => *self = Self::item(data, 0),
+                    Poll::Ready(Err(e)) => return self.error(e),
+                    Poll::Pending       => return backend::PollEncodeStatus::Pending,
+                },
+                Self::Item(next, buf) => match buf.write_remaining(writer, cx) {
+                    backend::PollEncodeStatus::Fini     => *self = Self::item(data, *next),
+                    backend::PollEncodeStatus::Pending  => return backend::PollEncodeStatus::Pending,
+                    backend::PollEncodeStatus::Error(e) => return self.error(e),
+                },
+                Self::Close => match lex::poll_write(writer, b"]", cx) {
+                    Poll::Ready(Ok(_))  => { *self = Self::Fini; return backend::PollEncodeStatus::Fini; }
+                    Poll::Ready(Err(e)) => return self.error(e),
+                    Poll::Pending       => return backend::PollEncodeStatus::Pending,
+                },
+                Self::Fini => return backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+
+    fn error(&mut self, err: Error) -> backend::PollEncodeStatus<Error> {
+        *self = Self::Fini;
+        backend::PollEncodeStatus::Error(err)
+    }
+}
+
+impl backend::Encode for Encoder
+{
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init(_data: &Self::Data) -> Self {
+        Self::Open
+    }
+
+    fn start_encode<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init(data);
+        enc.write_remaining(writer, data, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode<W>(&mut self, _format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.write_remaining(writer, data, cx)
+    }
+}
+
+pub struct SerializeAll<'w, W> {
+    format: &'w ThisFormat,
+    writer: &'w mut W,
+    data: &'w Data,
+    encoder: Encoder,
+}
+
+pub(crate) fn serialize<'w, W>(format: &'w ThisFormat, writer: &'w mut W, data: &'w Data) -> SerializeAll<'w, W>
+where
+    W: ::diny::io::AsyncWrite + Unpin,
+{
+    SerializeAll::new(format, writer, data)
+}
+
+impl<'w, W> SerializeAll<'w, W> {
+    fn new(format: &'w ThisFormat, writer: &'w mut W, data: &'w Data) -> SerializeAll<'w, W>
+    where
+        W: ::diny::io::AsyncWrite + Unpin,
+    {
+        SerializeAll {
+            format,
+            writer,
+            data,
+            encoder: Encoder::init(data),
+        }
+    }
+}
+
+impl<'w, W> Unpin for SerializeAll<'w, W> {}
+
+impl<'w, W> core::future::Future for SerializeAll<'w, W>
+where
+    W: ::diny::io::AsyncWrite + Unpin,
+{
+    type Output = Result<(), <ThisFormat as backend::Format>::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        this.encoder.poll_encode(this.format, this.writer, this.data, cx).into()
+    }
+}
+
@@ -0,0 +1,159 @@
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+use core::task::Context;
+use core::convert::TryFrom;
+use diny::{backend::{self, Format}, io};
+use crate::Formatter as ThisFormat;
+use super::{lex, number};
+
+
+type Data = Vec<u8>;
+type Error = <ThisFormat as Format>::Error;
+type ByteEncoder = <ThisFormat as backend::FormatEncode>::EncodeByteSlice;
+
+pub struct Encoder(ByteEncoder);
+
+impl backend::Encode for Encoder
+{
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init(data: &Self::Data) -> Self {
+        Self(ByteEncoder::init(data))
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <Self::Format as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        ByteEncoder::start_encode(format, writer, data, cx)
+        .map_pending(Encoder)
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<<Self::Format as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.poll_encode(format, writer, data, cx)
+    }
+}
+
+enum State {
+    Open(lex::Expect<1>),
+    First,
+    Item(number::Decoder),
+    Next,
+    Fini,
+}
+
+/// Reads an array of numbers, each of which must fit within a byte
+pub struct Decoder {
+    data: Vec<u8>,
+    state: State,
+}
+
+fn push(data: &mut Data, format: &ThisFormat, token: &[u8]) -> Result<(), Error> {
+    let b = number::to_unsigned(token)
+        .and_then(|v| u8::try_from(v).ok())
+        .ok_or_else(ThisFormat::invalid_data_err)?;
+
+    backend::FormatDecode::decode_limits(format)
+    .check_bytes_len(data.len() + 1)
+    .map_err(ThisFormat::limit_exceeded_err)?;
+
+    data.push(b);
+    Ok(())
+}
+
+impl Decoder {
+    fn error(&mut self, err: Error) -> backend::PollDecodeStatus<Data, Error> {
+        self.state = State::Fini;
+        backend::PollDecodeStatus::Error(err)
+    }
+
+    fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match &mut self.state {
+                State::Open(dec) => match dec.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(())  => self.state = State::First,
+                    backend::PollDecodeStatus::Pending   => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e)  => return self.error(e),
+                },
+                State::First | State::Next => {
+                    let first = matches!(self.state, State::First);
+                    match lex::skip_ws(reader, cx) {
+                        backend::PollDecodeStatus::Fini(b']') => {
+                            lex::consume(reader, 1);
+                            self.state = State::Fini;
+                            return backend::PollDecodeStatus::Fini(core::mem::take(&mut self.data));
+                        }
+                        backend::PollDecodeStatus::Fini(b',') if !first => {
+                            lex::consume(reader, 1);
+                            self.state = State::Item(number::Decoder::new());
+                        }
+                        backend::PollDecodeStatus::Fini(_) if first => self.state = State::Item(number::Decoder::new()),
+                        backend::PollDecodeStatus::Fini(_)          => return self.error(ThisFormat::invalid_data_err()),
+                        backend::PollDecodeStatus::Pending          => return backend::PollDecodeStatus::Pending,
+                        backend::PollDecodeStatus::Error(e)         => return self.error(e),
+                    }
+                }
+                State::Item(dec) => match dec.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(()) => {
+                        if let Err(e) = push(&mut self.data, format, dec.token()) {
+                            return self.error(e);
+                        }
+                        self.state = State::Next;
+                    }
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => return self.error(e),
+                },
+                State::Fini => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+}
+
+impl backend::Decode for Decoder
+{
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self {
+            data: Vec::new(),
+            state: State::Open(lex::Expect::new([(true, b"[")])),
+        }
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.read_remaining(format, reader, cx)
+        .lift(dec)
+    }
+
+    // The bytes are read into the emptied storage of the existing vector
+    fn start_decode_into<R>(format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.data = core::mem::take(data);
+        dec.data.clear();
+        dec.read_remaining(format, reader, cx)
+        .map(|d| *data = d)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(format, reader, cx)
+    }
+}
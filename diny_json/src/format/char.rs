@@ -0,0 +1,126 @@
+use core::task::Context;
+use diny::{backend, buffer, io};
+use crate::Formatter as ThisFormat;
+use super::text;
+
+type Error = <ThisFormat as backend::Format>::Error;
+type Data = char;
+
+// The maximum number of bytes required to encode a char as UTF-8
+const MAX_UTF8_LEN: usize = 4;
+
+// The char is retained, since buffered encoders are not passed their data
+// once they have started.
+pub struct Encoder(Data, text::Encoder);
+
+impl Encoder {
+    fn write_remaining<W>(&mut self, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut buf = [0u8; MAX_UTF8_LEN];
+        self.1.write_remaining(writer, self.0.encode_utf8(&mut buf).as_bytes(), cx)
+    }
+}
+
+impl buffer::BufferEncode for Encoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    // A control character escaped as \u00XX, between quotes
+    const MAX_ENCODED_LEN: Option<usize> = Some(8);
+
+    fn init_buffer(data: &Self::Data) -> Self {
+        Self(*data, text::Encoder::Open)
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        enc.write_remaining(writer, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.write_remaining(writer, cx)
+    }
+}
+
+// Holds the UTF-8 encoding of a single char, rejecting anything longer
+struct CharBuf {
+    buf: [u8; MAX_UTF8_LEN],
+    len: usize,
+}
+
+impl text::Sink for CharBuf {
+    fn push(&mut self, _format: &ThisFormat, bytes: &[u8]) -> Result<(), Error> {
+        if self.len + bytes.len() > MAX_UTF8_LEN {
+            return Err(<ThisFormat as backend::Format>::invalid_data_err());
+        }
+        self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+}
+
+fn to_char(bytes: &[u8]) -> Result<Data, Error> {
+    let mut chars = core::str::from_utf8(bytes)
+        .map_err(|_| <ThisFormat as backend::Format>::invalid_data_err())?
+        .chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _               => Err(<ThisFormat as backend::Format>::invalid_data_err()),
+    }
+}
+
+pub struct Decoder {
+    data: CharBuf,
+    text: text::Decoder,
+}
+
+impl Decoder {
+    fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.text.read_remaining(format, reader, &mut self.data, cx)
+        .and_then(|()| to_char(&self.data.buf[..self.data.len]).into())
+    }
+}
+
+impl backend::Decode for Decoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self {
+            data: CharBuf { buf: [0u8; MAX_UTF8_LEN], len: 0 },
+            text: text::Decoder::new(),
+        }
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.read_remaining(format, reader, cx)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(format, reader, cx)
+    }
+}
+
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
@@ -0,0 +1,48 @@
+use core::task::Context;
+use diny::{backend::{self, Format}, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+type Error = <ThisFormat as Format>::Error;
+
+// A comma precedes another item, and is read.  A closing bracket ends the
+// collection, and is left unread for the decoder of the collection's end.
+pub struct Decoder;
+
+impl Decoder {
+    fn read_remaining<R>(reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<bool, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        lex::skip_ws(reader, cx)
+        .and_then(|b| match b {
+            b',' => { lex::consume(reader, 1); backend::PollDecodeStatus::Fini(true) }
+            b']' => backend::PollDecodeStatus::Fini(false),
+            _    => backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+        })
+    }
+}
+
+impl backend::Decode for Decoder {
+    type Data = bool;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        Self::read_remaining(reader, cx)
+        .lift(Self)
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        Self::read_remaining(reader, cx)
+    }
+}
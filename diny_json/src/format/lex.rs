@@ -0,0 +1,164 @@
+use core::{pin::Pin, task::{Context, Poll}};
+use diny::{backend::{self, Format}, io};
+use crate::Formatter as ThisFormat;
+
+type Error = <ThisFormat as Format>::Error;
+
+/// Whether `b` is one of the four whitespace characters permitted between tokens
+#[inline(always)]
+pub fn is_ws(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+/// Write as much of `buf` as the writer currently accepts, treating a
+/// zero length write as an error.
+pub fn poll_write<W>(writer: &mut W, buf: &[u8], cx: &mut Context<'_>) -> Poll<Result<usize, Error>>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    match Pin::new(writer).poll_write(cx, buf) {
+        Poll::Ready(Ok(0)) => Poll::Ready(Err(io::error::write_zero())),
+        other              => other,
+    }
+}
+
+/// The bytes currently buffered by `reader`, which are only empty at the end of the stream
+pub fn fill_buf<'r, R>(reader: &'r mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<&'r [u8], Error>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    match Pin::new(reader).poll_fill_buf(cx) {
+        Poll::Ready(Ok(buf)) => backend::PollDecodeStatus::Fini(buf),
+        Poll::Ready(Err(e))  => backend::PollDecodeStatus::Error(e),
+        Poll::Pending        => backend::PollDecodeStatus::Pending,
+    }
+}
+
+/// Mark the first `n` buffered bytes as read
+#[inline(always)]
+pub fn consume<R>(reader: &mut R, n: usize)
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    Pin::new(reader).consume(n)
+}
+
+/// Peek at the next byte without reading it, failing at the end of the stream
+pub fn peek<R>(reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<u8, Error>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    fill_buf(reader, cx)
+    .and_then(|buf| match buf.first() {
+        Some(b) => backend::PollDecodeStatus::Fini(*b),
+        None    => backend::PollDecodeStatus::Error(io::error::unexpected_eof()),
+    })
+}
+
+/// Skip any whitespace, then peek at the byte that follows it without reading it
+pub fn skip_ws<R>(reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<u8, Error>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    loop {
+        let (n, next) = match fill_buf(reader, cx) {
+            backend::PollDecodeStatus::Fini(buf) => {
+                if buf.is_empty() {
+                    return backend::PollDecodeStatus::Error(io::error::unexpected_eof());
+                }
+                let n = buf.iter().take_while(|b| is_ws(**b)).count();
+                (n, buf.get(n).copied())
+            }
+            backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+            backend::PollDecodeStatus::Error(e) => return backend::PollDecodeStatus::Error(e),
+        };
+
+        consume(reader, n);
+        if let Some(b) = next {
+            return backend::PollDecodeStatus::Fini(b);
+        }
+    }
+}
+
+/// Writes a run of literal tokens, any of which may be empty
+pub struct Emit<const N: usize> {
+    parts: [&'static [u8]; N],
+    part: usize,
+    offset: usize,
+}
+
+impl<const N: usize> Emit<N> {
+    pub const fn new(parts: [&'static [u8]; N]) -> Self {
+        Self { parts, part: 0, offset: 0 }
+    }
+
+    pub fn write_remaining<W>(&mut self, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        while self.part < N {
+            let lit = self.parts[self.part];
+            if self.offset == lit.len() {
+                self.part += 1;
+                self.offset = 0;
+                continue;
+            }
+
+            match poll_write(writer, &lit[self.offset..], cx) {
+                Poll::Ready(Ok(n))  => self.offset += n,
+                Poll::Ready(Err(e)) => return backend::PollEncodeStatus::Error(e),
+                Poll::Pending       => return backend::PollEncodeStatus::Pending,
+            }
+        }
+
+        backend::PollEncodeStatus::Fini
+    }
+}
+
+/// Reads a run of literal tokens, each of which may be preceded by whitespace
+/// when it is paired with `true`.
+pub struct Expect<const N: usize> {
+    parts: [(bool, &'static [u8]); N],
+    part: usize,
+    offset: usize,
+}
+
+impl<const N: usize> Expect<N> {
+    pub const fn new(parts: [(bool, &'static [u8]); N]) -> Self {
+        Self { parts, part: 0, offset: 0 }
+    }
+
+    pub fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        while self.part < N {
+            let (ws, lit) = self.parts[self.part];
+            if self.offset == lit.len() {
+                self.part += 1;
+                self.offset = 0;
+                continue;
+            }
+
+            let next = if ws && self.offset == 0 {
+                skip_ws(reader, cx)
+            } else {
+                peek(reader, cx)
+            };
+
+            match next {
+                backend::PollDecodeStatus::Fini(b) => {
+                    if b != lit[self.offset] {
+                        return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err());
+                    }
+                    consume(reader, 1);
+                    self.offset += 1;
+                }
+                backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(e) => return backend::PollDecodeStatus::Error(e),
+            }
+        }
+
+        backend::PollDecodeStatus::Fini(())
+    }
+}
@@ -0,0 +1,343 @@
+// A literal token that holds no data, such as null or the punctuation between
+// the members of arrays and objects.  Whitespace is permitted before it.
+macro_rules! literal_def {
+    ($lit: expr) => {
+        use core::task::Context;
+        use diny::{backend::{self, Format}, buffer, io};
+        use crate::Formatter as ThisFormat;
+        use super::lex;
+
+        type Error = <ThisFormat as Format>::Error;
+        type Data = ();
+
+        const LITERAL: &[u8] = $lit;
+
+        pub struct Encoder(lex::Emit<1>);
+
+        impl buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(LITERAL.len());
+
+            fn init_buffer(_data: &Self::Data) -> Self {
+                Self(lex::Emit::new([LITERAL]))
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                enc.0.write_remaining(writer, cx)
+                .lift(enc)
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                self.0.write_remaining(writer, cx)
+            }
+        }
+
+        pub struct Decoder(lex::Expect<1>);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(lex::Expect::new([(true, LITERAL)]))
+            }
+
+            fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.0.read_remaining(reader, cx)
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.0.read_remaining(reader, cx)
+            }
+        }
+    };
+}
+
+macro_rules! int_def {
+    ($t:ty, $to_int:ident) => {
+        use core::{convert::TryFrom, task::Context};
+        use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+        use crate::Formatter as ThisFormat;
+        use super::number;
+
+        type Error = <ThisFormat as Format>::Error;
+        type Data = $t;
+
+        pub struct Encoder(BufferState<{ number::MAX_INT_LEN }>);
+
+        impl diny::buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(number::MAX_INT_LEN);
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(number::encode_int(*data))
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                enc.0.start_write(writer, cx)
+                .lift(enc)
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                self.0.write_remaining(writer, cx)
+            }
+        }
+
+        #[inline(always)]
+        fn from_token(t: &[u8]) -> Result<Data, Error> {
+            number::$to_int(t)
+            .and_then(|v| Data::try_from(v).ok())
+            .ok_or_else(ThisFormat::invalid_data_err)
+        }
+
+        pub struct Decoder(number::Decoder);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(number::Decoder::new())
+            }
+
+            fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.0.read_remaining(reader, cx)
+                .and_then(|()| from_token(dec.0.token()).into())
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.0.read_remaining(reader, cx)
+                .and_then(|()| from_token(self.0.token()).into())
+            }
+        }
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! unsigned_def {
+    ($t:ty) => {
+        int_def!($t, to_unsigned);
+    };
+}
+
+macro_rules! signed_def {
+    ($t:ty) => {
+        int_def!($t, to_signed);
+    };
+}
+
+macro_rules! float_def {
+    ($t:ty) => {
+        use core::task::Context;
+        use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+        use crate::Formatter as ThisFormat;
+        use super::number;
+
+        type Error = <ThisFormat as Format>::Error;
+        type Data = $t;
+
+        // Infinities and NaN cannot be written, and are rejected as invalid data
+        pub struct Encoder(Option<BufferState<{ number::MAX_FLOAT_LEN }>>);
+
+        impl diny::buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(number::MAX_FLOAT_LEN);
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(number::encode_float(*data))
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                match &mut enc.0 {
+                    Some(buf) => buf.start_write(writer, cx).lift(enc),
+                    None      => backend::StartEncodeStatus::Error(ThisFormat::invalid_data_err()),
+                }
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                match &mut self.0 {
+                    Some(buf) => buf.write_remaining(writer, cx),
+                    None      => backend::PollEncodeStatus::Error(ThisFormat::invalid_data_err()),
+                }
+            }
+        }
+
+        #[inline(always)]
+        fn from_token(t: &[u8]) -> Result<Data, Error> {
+            number::to_float(t)
+            .ok_or_else(ThisFormat::invalid_data_err)
+        }
+
+        pub struct Decoder(number::Decoder);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(number::Decoder::new())
+            }
+
+            fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.0.read_remaining(reader, cx)
+                .and_then(|()| from_token(dec.0.token()).into())
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.0.read_remaining(reader, cx)
+                .and_then(|()| from_token(self.0.token()).into())
+            }
+        }
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+// The length of a sequence or map is never written.  Instead, its opening bracket
+// is, and the decoded length is either zero, when the closing bracket follows
+// immediately, or indefinite, leaving the collection to find its own end.
+macro_rules! len_def {
+    ($t: ty) => {
+        use core::task::Context;
+        use diny::{backend::{self, Format}, buffer, io};
+        use crate::Formatter as ThisFormat;
+        use super::lex;
+
+        pub type Data = $t;
+        pub type Error = <ThisFormat as Format>::Error;
+
+        pub struct Encoder(lex::Emit<1>);
+
+        impl buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(1);
+
+            fn init_buffer(_data: &Self::Data) -> Self {
+                Self(lex::Emit::new([b"["]))
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                enc.0.write_remaining(writer, cx)
+                .lift(enc)
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                self.0.write_remaining(writer, cx)
+            }
+        }
+
+        pub enum Decoder {
+            Open(lex::Expect<1>),
+            Peek,
+        }
+
+        impl Decoder {
+            fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                if let Self::Open(dec) = self {
+                    match dec.read_remaining(reader, cx) {
+                        backend::PollDecodeStatus::Fini(())  => *self = Self::Peek,
+                        backend::PollDecodeStatus::Pending   => return backend::PollDecodeStatus::Pending,
+                        backend::PollDecodeStatus::Error(e)  => return backend::PollDecodeStatus::Error(e),
+                    }
+                }
+
+                lex::skip_ws(reader, cx)
+                .map(|b| if b == b']' { Data::new(0) } else { Data::INDEFINITE })
+            }
+        }
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self::Open(lex::Expect::new([(true, b"[")]))
+            }
+
+            fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.read_remaining(reader, cx)
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.read_remaining(reader, cx)
+            }
+        }
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
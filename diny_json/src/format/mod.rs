@@ -0,0 +1,47 @@
+#[macro_use]
+mod macros;
+
+pub(crate) mod lex;
+mod number;
+mod text;
+
+pub mod unit;
+pub mod bool;
+pub mod char;
+pub mod str;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod string;
+pub mod byte_slice;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod byte_vec;
+pub mod raw;
+
+pub mod i8   { signed_def!(i8  ); }
+pub mod i16  { signed_def!(i16 ); }
+pub mod i32  { signed_def!(i32 ); }
+pub mod i64  { signed_def!(i64 ); }
+pub mod i128 { signed_def!(i128); }
+
+pub mod u8   { unsigned_def!(u8  ); }
+pub mod u16  { unsigned_def!(u16 ); }
+pub mod u32  { unsigned_def!(u32 ); }
+pub mod u64  { unsigned_def!(u64 ); }
+pub mod u128 { unsigned_def!(u128); }
+
+pub mod f32  { float_def!(f32); }
+pub mod f64  { float_def!(f64); }
+
+pub mod variant_idx;
+pub mod option_tag;
+pub mod sequence_len { len_def!(diny::backend::internal::SequenceLen); }
+
+pub mod struct_begin;
+pub mod struct_field;
+pub mod struct_end;
+pub mod item_separator  { literal_def!(b","); }
+pub mod map_entry_begin { literal_def!(b"["); }
+pub mod map_entry_end   { literal_def!(b"]"); }
+pub mod collection_next;
+pub mod collection_end  { literal_def!(b"]"); }
+pub mod variant;
+pub mod variant_end     { literal_def!(b"}"); }
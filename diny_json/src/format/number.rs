@@ -0,0 +1,185 @@
+use core::task::Context;
+use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+type Error = <ThisFormat as Format>::Error;
+
+// Long enough for any integer, and for any float written by a typical encoder
+// with all of its significant digits.
+pub const MAX_TOKEN_LEN: usize = 64;
+
+// The longest outputs of itoa (i128::MIN) and ryu (an f64 such as -2.2250738585072014e-308)
+pub const MAX_INT_LEN  : usize = 40;
+pub const MAX_FLOAT_LEN: usize = 24;
+
+#[inline(always)]
+fn is_token_byte(b: u8) -> bool {
+    matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')
+}
+
+// The length of the run of digits at the start of `t`
+#[inline(always)]
+fn digits(t: &[u8]) -> usize {
+    t.iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+// The length of `-?(0|[1-9][0-9]*)` at the start of `t`, if it is present
+fn int_len(t: &[u8]) -> Option<usize> {
+    let sign = usize::from(t.first() == Some(&b'-'));
+    match digits(&t[sign..]) {
+        0 => None,
+        n if n > 1 && t[sign] == b'0' => None,
+        n => Some(sign + n),
+    }
+}
+
+// Whether `t` is an integer, optionally followed by a fraction and an exponent,
+// as given by the grammar of RFC 8259 section 6.
+fn is_number(t: &[u8]) -> bool {
+    let mut i = match int_len(t) {
+        Some(n) => n,
+        None    => return false,
+    };
+
+    if t.get(i) == Some(&b'.') {
+        match digits(&t[i + 1..]) {
+            0 => return false,
+            n => i += 1 + n,
+        }
+    }
+
+    if matches!(t.get(i), Some(b'e') | Some(b'E')) {
+        i += 1;
+        if matches!(t.get(i), Some(b'-') | Some(b'+')) {
+            i += 1;
+        }
+        match digits(&t[i..]) {
+            0 => return false,
+            n => i += n,
+        }
+    }
+
+    i == t.len()
+}
+
+/// Interpret a token as a signed integer, without any fraction or exponent
+pub fn to_signed(t: &[u8]) -> Option<i128> {
+    if int_len(t) == Some(t.len()) {
+        core::str::from_utf8(t).ok()?.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Interpret a token as an unsigned integer, without any fraction or exponent
+pub fn to_unsigned(t: &[u8]) -> Option<u128> {
+    if int_len(t) == Some(t.len()) && t[0] != b'-' {
+        core::str::from_utf8(t).ok()?.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Interpret a token as a finite float of type `T`
+pub fn to_float<T>(t: &[u8]) -> Option<T>
+where
+    T: core::str::FromStr + Into<f64> + Copy,
+{
+    if is_number(t) {
+        core::str::from_utf8(t).ok()?
+        .parse()
+        .ok()
+        .filter(|v: &T| (*v).into().is_finite())
+    } else {
+        None
+    }
+}
+
+/// Write the decimal representation of an integer
+pub fn encode_int<T: itoa::Integer>(v: T) -> BufferState<MAX_INT_LEN> {
+    let mut fmt = itoa::Buffer::new();
+    let s = fmt.format(v).as_bytes();
+    let mut buf = [0u8; MAX_INT_LEN];
+    buf[..s.len()].copy_from_slice(s);
+    BufferState::with_partial_contents(buf, s.len())
+}
+
+/// Write the shortest representation of a float that reads back as the same
+/// value, or `None` if it is infinite or NaN, neither of which JSON can represent.
+pub fn encode_float<T: ryu::Float + Into<f64> + Copy>(v: T) -> Option<BufferState<MAX_FLOAT_LEN>> {
+    if v.into().is_finite() {
+        let mut fmt = ryu::Buffer::new();
+        let s = fmt.format_finite(v).as_bytes();
+        let mut buf = [0u8; MAX_FLOAT_LEN];
+        buf[..s.len()].copy_from_slice(s);
+        Some(BufferState::with_partial_contents(buf, s.len()))
+    } else {
+        None
+    }
+}
+
+/// Reads the characters of a number, after skipping any leading whitespace.
+///
+/// The token ends at the first byte that cannot be part of a number, which is
+/// left unread, or at the end of the stream.
+pub struct Decoder {
+    buf: [u8; MAX_TOKEN_LEN],
+    len: usize,
+    started: bool,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self { buf: [0u8; MAX_TOKEN_LEN], len: 0, started: false }
+    }
+
+    /// The characters read so far
+    pub fn token(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    pub fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        if !self.started {
+            match lex::skip_ws(reader, cx) {
+                backend::PollDecodeStatus::Fini(_)  => self.started = true,
+                backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(e) => return backend::PollDecodeStatus::Error(e),
+            }
+        }
+
+        loop {
+            let (n, done) = match lex::fill_buf(reader, cx) {
+                backend::PollDecodeStatus::Fini(buf) => {
+                    let n = buf.iter().take_while(|b| is_token_byte(**b)).count();
+                    if self.len + n > MAX_TOKEN_LEN {
+                        return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err());
+                    }
+                    self.buf[self.len..self.len + n].copy_from_slice(&buf[..n]);
+                    (n, n < buf.len() || buf.is_empty())
+                }
+                backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(e) => return backend::PollDecodeStatus::Error(e),
+            };
+
+            self.len += n;
+            lex::consume(reader, n);
+            if done {
+                return if self.len == 0 {
+                    backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err())
+                } else {
+                    backend::PollDecodeStatus::Fini(())
+                };
+            }
+        }
+    }
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,66 @@
+use core::task::Context;
+use diny::{backend::{self, Format, internal::OptionTag}, buffer, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+type Error = <ThisFormat as Format>::Error;
+type Data = OptionTag;
+
+// No tag is written, since `None` is written as the null of its unit payload,
+// and `Some` as its value alone.
+pub struct Encoder;
+
+impl buffer::BufferEncode for Encoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(0);
+
+    fn init_buffer(_data: &Self::Data) -> Self {
+        Self
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, _writer: &mut W, _data: &Self::Data, _cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        backend::StartEncodeStatus::Fini
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, _writer: &mut W, _cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        backend::PollEncodeStatus::Fini
+    }
+}
+
+// A null denotes `None`, and is left unread for the decoder of its unit payload.
+// Anything else is the value of `Some`.
+pub struct Decoder;
+
+impl backend::Decode for Decoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        lex::skip_ws(reader, cx)
+        .map(|b| Data::new(if b == b'n' { 0 } else { 1 }))
+        .lift(Self)
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        lex::skip_ws(reader, cx)
+        .map(|b| Data::new(if b == b'n' { 0 } else { 1 }))
+    }
+}
@@ -0,0 +1,218 @@
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+use core::task::Context;
+use diny::{backend::{self, Format, FormatDecode as _}, buffer, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+type Data = [u8];
+type Error = <ThisFormat as Format>::Error;
+
+// A value encoded in advance with this format's own encoders is already JSON
+// text, and is written as it is.
+pub enum Encoder {
+    Init,
+    Cur(buffer::BufferCursor),
+    Fini,
+}
+
+impl Encoder {
+    fn after_init<W>(writer: &mut W, data: &Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        if data.is_empty() {
+            return backend::StartEncodeStatus::Error(ThisFormat::invalid_input_err());
+        }
+
+        let mut cur = buffer::BufferCursor::new(data);
+        match cur.write_remaining(writer, data, cx) {
+            backend::PollEncodeStatus::Fini     => backend::StartEncodeStatus::Fini,
+            backend::PollEncodeStatus::Pending  => backend::StartEncodeStatus::Pending(Self::Cur(cur)),
+            backend::PollEncodeStatus::Error(e) => backend::StartEncodeStatus::Error(e),
+        }
+    }
+}
+
+impl backend::Encode for Encoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init(_data: &Self::Data) -> Self {
+        Self::Init
+    }
+
+    fn start_encode<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        Self::after_init(writer, data, cx)
+    }
+
+    fn poll_encode<W>(&mut self, _format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Init     => diny::encode_chain!(*self, Self::after_init(writer, data, cx)),
+            Self::Cur(cur) => diny::encode_poll_fini!(*self, cur.write_remaining(writer, data, cx)),
+            Self::Fini     => backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+enum State {
+    // Whitespace before the value
+    Ws,
+    // A number or literal, which ends at the first byte that cannot continue it
+    Token,
+    // Within `depth` arrays or objects, or a string when `string` is set
+    Nested { depth: usize, string: bool, escape: bool },
+    Fini,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+// Reads the text of a single value, tracking only enough of its structure to
+// find where it ends.  The value itself is validated when it is deserialized.
+pub struct Decoder {
+    data: Vec<u8>,
+    state: State,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Decoder {
+    // Advances the state over `b`, returning whether the value continues with it
+    fn scan(state: &mut State, b: u8) -> Result<bool, Error> {
+        match state {
+            State::Ws => {
+                *state = match b {
+                    b'[' | b'{' => State::Nested { depth: 1, string: false, escape: false },
+                    b'"'        => State::Nested { depth: 0, string: true , escape: false },
+                    b']' | b'}' | b',' | b':' => return Err(ThisFormat::invalid_data_err()),
+                    _           => State::Token,
+                };
+            }
+            State::Token => {
+                if lex::is_ws(b) || matches!(b, b']' | b'}' | b',' | b':' | b'[' | b'{' | b'"') {
+                    *state = State::Fini;
+                    return Ok(false);
+                }
+            }
+            State::Nested { depth, string, escape } => {
+                if *string {
+                    if *escape {
+                        *escape = false;
+                    } else if b == b'\\' {
+                        *escape = true;
+                    } else if b == b'"' {
+                        *string = false;
+                    }
+                } else {
+                    match b {
+                        b'"'        => *string = true,
+                        b'[' | b'{' => *depth += 1,
+                        b']' | b'}' => *depth -= 1,
+                        _           => {}
+                    }
+                }
+                if *depth == 0 && !*string {
+                    *state = State::Fini;
+                }
+            }
+            State::Fini => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Vec<u8>, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        if let State::Fini = self.state {
+            return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err());
+        }
+
+        loop {
+            if let State::Ws = self.state {
+                match lex::skip_ws(reader, cx) {
+                    backend::PollDecodeStatus::Fini(_)  => {}
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => return self.error(e),
+                }
+            }
+
+            let buf = match lex::fill_buf(reader, cx) {
+                backend::PollDecodeStatus::Fini(buf) => buf,
+                backend::PollDecodeStatus::Pending   => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(e)  => return self.error(e),
+            };
+
+            // Only a number or literal may be ended by the end of the input
+            if buf.is_empty() {
+                return match self.state {
+                    State::Token => self.fini(),
+                    _            => self.error(io::error::unexpected_eof()),
+                };
+            }
+
+            let mut n = 0;
+            for b in buf {
+                match Self::scan(&mut self.state, *b) {
+                    Ok(true)  => n += 1,
+                    Ok(false) => break,
+                    Err(e)    => return self.error(e),
+                }
+                if let State::Fini = self.state {
+                    break;
+                }
+            }
+
+            if let Err(kind) = format.decode_limits().check_bytes_len(self.data.len() + n) {
+                return self.error(ThisFormat::limit_exceeded_err(kind));
+            }
+            self.data.extend_from_slice(&buf[..n]);
+            lex::consume(reader, n);
+
+            if let State::Fini = self.state {
+                return backend::PollDecodeStatus::Fini(core::mem::take(&mut self.data));
+            }
+        }
+    }
+
+    fn fini(&mut self) -> backend::PollDecodeStatus<Vec<u8>, Error> {
+        self.state = State::Fini;
+        backend::PollDecodeStatus::Fini(core::mem::take(&mut self.data))
+    }
+
+    fn error(&mut self, err: Error) -> backend::PollDecodeStatus<Vec<u8>, Error> {
+        self.state = State::Fini;
+        backend::PollDecodeStatus::Error(err)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl backend::Decode for Decoder {
+    type Data = Vec<u8>;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self { data: Vec::new(), state: State::Ws }
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.read_remaining(format, reader, cx)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(format, reader, cx)
+    }
+}
@@ -0,0 +1,67 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend::{self, Format, internal::StructDef}, buffer, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+type Error = <ThisFormat as Format>::Error;
+
+// Closes the object or array opened by the beginning of the struct
+fn literal<S: StructDef>() -> &'static [u8] {
+    if S::FIELD_NAMES.is_some() { b"}" } else { b"]" }
+}
+
+pub struct Encoder<S>(lex::Emit<1>, PhantomData<fn() -> S>);
+
+impl<S: StructDef> buffer::BufferEncode for Encoder<S> {
+    type Data = ();
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(1);
+
+    fn init_buffer(_data: &Self::Data) -> Self {
+        Self(lex::Emit::new([literal::<S>()]), PhantomData)
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        enc.0.write_remaining(writer, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.write_remaining(writer, cx)
+    }
+}
+
+pub struct Decoder<S>(lex::Expect<1>, PhantomData<fn() -> S>);
+
+impl<S: StructDef> backend::Decode for Decoder<S> {
+    type Data = ();
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self(lex::Expect::new([(true, literal::<S>())]), PhantomData)
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.0.read_remaining(reader, cx)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.read_remaining(reader, cx)
+    }
+}
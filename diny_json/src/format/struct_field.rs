@@ -0,0 +1,88 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend::{self, Format, internal::StructDef}, buffer, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+type Error = <ThisFormat as Format>::Error;
+
+// Every field but the first is preceded by a comma, and the fields of structs
+// with named fields are also preceded by their quoted name and a colon.
+//
+// Names are matched exactly when decoding, so the fields of an object must
+// appear in the order they are declared in, and their names must not be escaped.
+fn comma<const IDX: usize>() -> &'static [u8] {
+    if IDX > 0 { b"," } else { b"" }
+}
+
+fn name<S: StructDef, const IDX: usize>() -> Option<&'static [u8]> {
+    S::FIELD_NAMES
+    .and_then(|names| names.get(IDX))
+    .map(|name| name.as_bytes())
+}
+
+pub struct Encoder<S, const IDX: usize>(lex::Emit<4>, PhantomData<fn() -> S>);
+
+impl<S: StructDef, const IDX: usize> buffer::BufferEncode for Encoder<S, IDX> {
+    type Data = ();
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = match S::FIELD_NAMES {
+        Some(names) if IDX < names.len() => Some(4 + names[IDX].len()),
+        _                                => Some(1),
+    };
+
+    fn init_buffer(_data: &Self::Data) -> Self {
+        let parts = match name::<S, IDX>() {
+            Some(name) => [comma::<IDX>(), b"\"", name, b"\":"],
+            None       => [comma::<IDX>(), b""  , b"", b""   ],
+        };
+        Self(lex::Emit::new(parts), PhantomData)
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        enc.0.write_remaining(writer, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.write_remaining(writer, cx)
+    }
+}
+
+pub struct Decoder<S, const IDX: usize>(lex::Expect<5>, PhantomData<fn() -> S>);
+
+impl<S: StructDef, const IDX: usize> backend::Decode for Decoder<S, IDX> {
+    type Data = ();
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        let parts = match name::<S, IDX>() {
+            Some(name) => [(true, comma::<IDX>()), (true , b"\""), (false, name), (false, b"\""), (true , b":")],
+            None       => [(true, comma::<IDX>()), (false, b""  ), (false, b"" ), (false, b""  ), (false, b"" )],
+        };
+        Self(lex::Expect::new(parts), PhantomData)
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.0.read_remaining(reader, cx)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.read_remaining(reader, cx)
+    }
+}
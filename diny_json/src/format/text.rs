@@ -0,0 +1,255 @@
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+use core::task::{Context, Poll};
+use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+type Error = <ThisFormat as Format>::Error;
+
+// The longest escape sequence, \u00XX, which is used for control characters
+// that have no shorter form.
+const MAX_ESCAPE_LEN: usize = 6;
+
+#[inline(always)]
+fn needs_escape(b: u8) -> bool {
+    b == b'"' || b == b'\\' || b < 0x20
+}
+
+fn escape(b: u8) -> BufferState<MAX_ESCAPE_LEN> {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let short = match b {
+        b'"'  => Some(b'"'),
+        b'\\' => Some(b'\\'),
+        b'\n' => Some(b'n'),
+        b'\r' => Some(b'r'),
+        b'\t' => Some(b't'),
+        0x08  => Some(b'b'),
+        0x0c  => Some(b'f'),
+        _     => None,
+    };
+
+    match short {
+        Some(c) => BufferState::with_partial_contents([b'\\', c, 0, 0, 0, 0], 2),
+        None    => BufferState::with_contents([b'\\', b'u', b'0', b'0', HEX[(b >> 4) as usize], HEX[(b & 0xf) as usize]]),
+    }
+}
+
+/// Writes a string between quotes, escaping the characters that RFC 8259
+/// section 7 requires to be escaped, and no others.
+///
+/// Runs of characters that need no escaping are written directly from the
+/// string, so that nothing is buffered beyond a single escape sequence.
+pub enum Encoder {
+    Open,
+    Body(usize),
+    Escape(usize, BufferState<MAX_ESCAPE_LEN>),
+    Close,
+    Fini,
+}
+
+impl Encoder {
+    pub fn write_remaining<W>(&mut self, writer: &mut W, data: &[u8], cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        loop {
+            match self {
+                Self::Open => match lex::poll_write(writer, b"\"", cx) {
+                    Poll::Ready(Ok(_))  => *self = Self::Body(0),
+                    Poll::Ready(Err(e)) => return self.error(e),
+                    Poll::Pending       => return backend::PollEncodeStatus::Pending,
+                },
+                Self::Body(pos) => {
+                    let start = *pos;
+                    let run = data[start..].iter().take_while(|b| !needs_escape(**b)).count();
+                    if run > 0 {
+                        match lex::poll_write(writer, &data[start..start + run], cx) {
+                            Poll::Ready(Ok(n))  => *pos += n,
+                            Poll::Ready(Err(e)) => return self.error(e),
+                            Poll::Pending       => return backend::PollEncodeStatus::Pending,
+                        }
+                    } else if start == data.len() {
+                        *self = Self::Close;
+                    } else {
+                        *self = Self::Escape(start + 1, escape(data[start]));
+                    }
+                }
+                Self::Escape(pos, buf) => match buf.write_remaining(writer, cx) {
+                    backend::PollEncodeStatus::Fini     => *self = Self::Body(*pos),
+                    backend::PollEncodeStatus::Pending  => return backend::PollEncodeStatus::Pending,
+                    backend::PollEncodeStatus::Error(e) => return self.error(e),
+                },
+                Self::Close => match lex::poll_write(writer, b"\"", cx) {
+                    Poll::Ready(Ok(_))  => { *self = Self::Fini; return backend::PollEncodeStatus::Fini; }
+                    Poll::Ready(Err(e)) => return self.error(e),
+                    Poll::Pending       => return backend::PollEncodeStatus::Pending,
+                },
+                Self::Fini => return backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+
+    fn error(&mut self, err: Error) -> backend::PollEncodeStatus<Error> {
+        *self = Self::Fini;
+        backend::PollEncodeStatus::Error(err)
+    }
+}
+
+/// Receives the unescaped bytes of a string as they are decoded
+pub trait Sink {
+    fn push(&mut self, format: &ThisFormat, bytes: &[u8]) -> Result<(), Error>;
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Sink for Vec<u8> {
+    fn push(&mut self, format: &ThisFormat, bytes: &[u8]) -> Result<(), Error> {
+        backend::FormatDecode::decode_limits(format)
+        .check_bytes_len(self.len() + bytes.len())
+        .map_err(ThisFormat::limit_exceeded_err)?;
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    Open,
+    Body,
+    Escape,
+    // The hex digits of a \u escape, following the high surrogate it completes if any
+    Unicode { high: Option<u16>, n: u8, v: u16 },
+    // A high surrogate, which must be followed by the \u escape of a low surrogate
+    Surrogate(u16),
+    SurrogateU(u16),
+    Fini,
+}
+
+/// Reads a quoted string, after skipping any leading whitespace, passing its
+/// unescaped contents to a [Sink].
+///
+/// The bytes are not checked to be valid UTF-8, which is left to the caller
+/// once the string is complete.
+pub struct Decoder(State);
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self(State::Open)
+    }
+
+    pub fn read_remaining<R, S>(&mut self, format: &ThisFormat, reader: &mut R, sink: &mut S, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+        S: Sink,
+    {
+        loop {
+            if let State::Open = self.0 {
+                match lex::skip_ws(reader, cx) {
+                    backend::PollDecodeStatus::Fini(b'"') => { lex::consume(reader, 1); self.0 = State::Body; }
+                    backend::PollDecodeStatus::Fini(_)    => return self.error(ThisFormat::invalid_data_err()),
+                    backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e)   => return self.error(e),
+                }
+            }
+
+            let n = match lex::fill_buf(reader, cx) {
+                backend::PollDecodeStatus::Fini(buf) => {
+                    if buf.is_empty() {
+                        return self.error(io::error::unexpected_eof());
+                    }
+                    match self.scan(format, buf, sink) {
+                        Ok(n)  => n,
+                        Err(e) => return self.error(e),
+                    }
+                }
+                backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(e) => return self.error(e),
+            };
+
+            lex::consume(reader, n);
+            if let State::Fini = self.0 {
+                return backend::PollDecodeStatus::Fini(());
+            }
+        }
+    }
+
+    fn error(&mut self, err: Error) -> backend::PollDecodeStatus<(), Error> {
+        self.0 = State::Fini;
+        backend::PollDecodeStatus::Error(err)
+    }
+
+    // Decode as much of `buf` as possible, returning the number of bytes used
+    fn scan<S: Sink>(&mut self, format: &ThisFormat, buf: &[u8], sink: &mut S) -> Result<usize, Error> {
+        let mut i = 0;
+        while i < buf.len() {
+            let b = buf[i];
+            match self.0 {
+                State::Body => {
+                    let run = buf[i..].iter().take_while(|b| !needs_escape(**b)).count();
+                    if run > 0 {
+                        sink.push(format, &buf[i..i + run])?;
+                        i += run;
+                        continue;
+                    }
+                    match b {
+                        b'"'  => { self.0 = State::Fini; return Ok(i + 1); }
+                        b'\\' => self.0 = State::Escape,
+                        _     => return Err(ThisFormat::invalid_data_err()),
+                    }
+                }
+                State::Escape => {
+                    let c = match b {
+                        b'"'  => b'"',
+                        b'\\' => b'\\',
+                        b'/'  => b'/',
+                        b'b'  => 0x08,
+                        b'f'  => 0x0c,
+                        b'n'  => b'\n',
+                        b'r'  => b'\r',
+                        b't'  => b'\t',
+                        b'u'  => { self.0 = State::Unicode { high: None, n: 0, v: 0 }; i += 1; continue; }
+                        _     => return Err(ThisFormat::invalid_data_err()),
+                    };
+                    sink.push(format, &[c])?;
+                    self.0 = State::Body;
+                }
+                State::Unicode { high, n, v } => {
+                    let d = (b as char).to_digit(16).ok_or_else(ThisFormat::invalid_data_err)?;
+                    let v = (v << 4) | d as u16;
+                    self.0 = if n < 3 {
+                        State::Unicode { high, n: n + 1, v }
+                    } else {
+                        Self::unicode(format, high, v, sink)?
+                    };
+                }
+                State::Surrogate(high) if b == b'\\' => self.0 = State::SurrogateU(high),
+                State::SurrogateU(high) if b == b'u' => self.0 = State::Unicode { high: Some(high), n: 0, v: 0 },
+                State::Surrogate(_) | State::SurrogateU(_) => return Err(ThisFormat::invalid_data_err()),
+                State::Open | State::Fini => return Err(ThisFormat::invalid_input_err()),
+            }
+            i += 1;
+        }
+        Ok(i)
+    }
+
+    // Resolve a complete \u escape, which may be the first or second half of a surrogate pair
+    fn unicode<S: Sink>(format: &ThisFormat, high: Option<u16>, v: u16, sink: &mut S) -> Result<State, Error> {
+        let c = match (high, v) {
+            (None, 0xd800..=0xdbff) => return Ok(State::Surrogate(v)),
+            (None, 0xdc00..=0xdfff) => None,
+            (None, _)               => char::from_u32(v as u32),
+            (Some(h), 0xdc00..=0xdfff) => char::from_u32(0x10000 + (((h - 0xd800) as u32) << 10) + (v - 0xdc00) as u32),
+            (Some(_), _)               => None,
+        };
+
+        let c = c.ok_or_else(ThisFormat::invalid_data_err)?;
+        sink.push(format, c.encode_utf8(&mut [0u8; 4]).as_bytes())?;
+        Ok(State::Body)
+    }
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
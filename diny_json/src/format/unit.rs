@@ -0,0 +1,3 @@
+literal_def!(b"null");
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
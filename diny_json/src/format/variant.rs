@@ -0,0 +1,202 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend::{self, Format, internal::{EnumDef, VariantIdx}}, buffer, io};
+use crate::Formatter as ThisFormat;
+use super::lex;
+
+type Error = <ThisFormat as Format>::Error;
+type Data = VariantIdx;
+
+const fn max_name_len(variants: &[(usize, &str)]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < variants.len() {
+        if variants[i].1.len() > max {
+            max = variants[i].1.len();
+        }
+        i += 1;
+    }
+    max
+}
+
+// A variant is written as an object with a single member, named after the
+// variant, whose value is the payload.  Only the opening of the object, up to
+// and including the colon, is written here.
+pub struct Encoder<E>(Option<lex::Emit<3>>, PhantomData<fn() -> E>);
+
+impl<E: EnumDef> buffer::BufferEncode for Encoder<E> {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(4 + max_name_len(E::VARIANTS));
+
+    fn init_buffer(data: &Self::Data) -> Self {
+        Self(
+            E::variant_name(*data).map(|name| lex::Emit::new([b"{\"", name.as_bytes(), b"\":"])),
+            PhantomData,
+        )
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        match &mut enc.0 {
+            Some(emit) => emit.write_remaining(writer, cx).lift(enc),
+            None       => backend::StartEncodeStatus::Error(ThisFormat::invalid_data_err()),
+        }
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match &mut self.0 {
+            Some(emit) => emit.write_remaining(writer, cx),
+            None       => backend::PollEncodeStatus::Error(ThisFormat::invalid_data_err()),
+        }
+    }
+}
+
+// The name is matched a byte at a time, without buffering it, by tracking the
+// first variant whose name begins with the bytes read so far.  Any later variant
+// that shares that prefix is found by searching forward from it.
+fn next_candidate<E: EnumDef>(cand: usize, len: usize, b: u8) -> Option<usize> {
+    let prefix = &E::VARIANTS.get(cand)?.1.as_bytes()[..len];
+    E::VARIANTS[cand..].iter()
+    .position(|(_, name)| {
+        let name = name.as_bytes();
+        name.len() > len && &name[..len] == prefix && name[len] == b
+    })
+    .map(|i| cand + i)
+}
+
+fn matched<E: EnumDef>(cand: usize, len: usize) -> Option<VariantIdx> {
+    let prefix = &E::VARIANTS.get(cand)?.1.as_bytes()[..len];
+    E::VARIANTS[cand..].iter()
+    .find(|(_, name)| name.as_bytes() == prefix)
+    .map(|(idx, _)| VariantIdx::new(*idx))
+}
+
+// A unit variant may also be written as a bare string naming it, and since
+// the format reads no further for a unit variant, its `null` payload and the
+// end of the object are read here too.
+pub enum Decoder<E> {
+    Open,
+    Quote(lex::Expect<1>),
+    Name { cand: usize, len: usize, bare: bool },
+    Colon(VariantIdx, lex::Expect<1>),
+    Unit(VariantIdx, lex::Expect<2>),
+    Fini(PhantomData<fn() -> E>),
+}
+
+impl<E: EnumDef> Decoder<E> {
+    fn error(&mut self, err: Error) -> backend::PollDecodeStatus<Data, Error> {
+        *self = Self::Fini(PhantomData);
+        backend::PollDecodeStatus::Error(err)
+    }
+
+    fn fini(&mut self, idx: VariantIdx) -> backend::PollDecodeStatus<Data, Error> {
+        *self = Self::Fini(PhantomData);
+        backend::PollDecodeStatus::Fini(idx)
+    }
+
+    fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self {
+                Self::Open => match lex::skip_ws(reader, cx) {
+                    backend::PollDecodeStatus::Fini(b'{') => {
+                        lex::consume(reader, 1);
+                        *self = Self::Quote(lex::Expect::new([(true, b"\"")]));
+                    }
+                    backend::PollDecodeStatus::Fini(b'"') => {
+                        lex::consume(reader, 1);
+                        *self = Self::Name { cand: 0, len: 0, bare: true };
+                    }
+                    backend::PollDecodeStatus::Fini(_)  => return self.error(ThisFormat::invalid_data_err()),
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => return self.error(e),
+                },
+                Self::Quote(dec) => match dec.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(()) => *self = Self::Name { cand: 0, len: 0, bare: false },
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => return self.error(e),
+                },
+                Self::Name { cand, len, bare } => {
+                    let (cand, len, bare) = (*cand, *len, *bare);
+                    match lex::peek(reader, cx) {
+                        backend::PollDecodeStatus::Fini(b'"') => match matched::<E>(cand, len) {
+                            Some(idx) if bare => {
+                                if !E::is_unit(idx) {
+                                    return self.error(ThisFormat::invalid_data_err());
+                                }
+                                lex::consume(reader, 1);
+                                return self.fini(idx);
+                            }
+                            Some(idx) => {
+                                lex::consume(reader, 1);
+                                *self = Self::Colon(idx, lex::Expect::new([(true, b":")]));
+                            }
+                            None => return self.error(ThisFormat::invalid_data_err()),
+                        },
+                        backend::PollDecodeStatus::Fini(b) => match next_candidate::<E>(cand, len, b) {
+                            Some(cand) => {
+                                lex::consume(reader, 1);
+                                *self = Self::Name { cand, len: len + 1, bare };
+                            }
+                            None => return self.error(ThisFormat::invalid_data_err()),
+                        },
+                        backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                        backend::PollDecodeStatus::Error(e) => return self.error(e),
+                    }
+                }
+                Self::Colon(idx, dec) => {
+                    let idx = *idx;
+                    match dec.read_remaining(reader, cx) {
+                        backend::PollDecodeStatus::Fini(()) if E::is_unit(idx) => *self = Self::Unit(idx, lex::Expect::new([(true, b"null"), (true, b"}")])),
+                        backend::PollDecodeStatus::Fini(()) => return self.fini(idx),
+                        backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                        backend::PollDecodeStatus::Error(e) => return self.error(e),
+                    }
+                }
+                Self::Unit(idx, dec) => {
+                    let idx = *idx;
+                    return match dec.read_remaining(reader, cx) {
+                        backend::PollDecodeStatus::Fini(()) => self.fini(idx),
+                        backend::PollDecodeStatus::Pending  => backend::PollDecodeStatus::Pending,
+                        backend::PollDecodeStatus::Error(e) => self.error(e),
+                    };
+                }
+                Self::Fini(_) => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+}
+
+impl<E: EnumDef> backend::Decode for Decoder<E> {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self::Open
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.read_remaining(reader, cx)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(reader, cx)
+    }
+}
@@ -0,0 +1,78 @@
+use core::{convert::TryFrom, task::Context};
+use diny::{backend::{self, Format, internal::VariantIdx}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::number;
+
+type Error = <ThisFormat as Format>::Error;
+type Data = VariantIdx;
+
+// Enums are written by the name of their variant, so the bare index is
+// only written when it is serialized on its own, as a number.
+pub struct Encoder(BufferState<{ number::MAX_INT_LEN }>);
+
+impl diny::buffer::BufferEncode for Encoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(number::MAX_INT_LEN);
+
+    fn init_buffer(data: &Self::Data) -> Self {
+        Encoder(number::encode_int(**data))
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        enc.0.start_write(writer, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.write_remaining(writer, cx)
+    }
+}
+
+#[inline(always)]
+fn from_token(t: &[u8]) -> Result<Data, Error> {
+    number::to_unsigned(t)
+    .and_then(|v| usize::try_from(v).ok())
+    .map(Data::new)
+    .ok_or_else(ThisFormat::invalid_data_err)
+}
+
+pub struct Decoder(number::Decoder);
+
+impl backend::Decode for Decoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self(number::Decoder::new())
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.0.read_remaining(reader, cx)
+        .and_then(|()| from_token(dec.0.token()).into())
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.read_remaining(reader, cx)
+        .and_then(|()| from_token(self.0.token()).into())
+    }
+}
+
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
@@ -0,0 +1,314 @@
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+//! The expected text in this module is compact RFC 8259 JSON, as written by the
+//! encoder, while the decoder is also given the whitespace a person might add.
+
+use core::fmt::Debug;
+use std::collections::BTreeMap;
+use diny::{backend::{DecodeLimits, LimitExceeded}, io, AsyncDeserialize, AsyncSerialization, AsyncSerialize, util::AsyncSliceReader};
+use diny_json::Formatter;
+use futures::executor::block_on;
+
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Rec {
+    id: u32,
+    name: String,
+    tag: Option<u8>,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub enum Kind {
+    A,
+    B(i64),
+    Bx,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Pair(u8, bool);
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Skipping {
+    a: u8,
+    #[diny(skip)]
+    b: u8,
+    c: bool,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Empty {}
+
+fn limits() -> DecodeLimits {
+    DecodeLimits::unlimited().with_max_bytes_len(1 << 20)
+}
+
+fn serialize<T: AsyncSerialize + ?Sized>(send: &T) -> Vec<u8> {
+    let mut tx = Vec::new();
+    block_on(send.serialize(&diny_json::format(), &mut tx)).expect("unable to serialize");
+    tx
+}
+
+fn deserialize_with<T: AsyncDeserialize>(format: &Formatter, text: &[u8]) -> io::Result<T> {
+    let mut rx = AsyncSliceReader::from(text);
+    let recv = block_on(T::deserialize(format, &mut rx));
+    if recv.is_ok() {
+        assert_eq!(rx.bytes_read(), text.len());
+    }
+    recv
+}
+
+fn deserialize<T: AsyncDeserialize>(text: &[u8]) -> io::Result<T> {
+    deserialize_with(&Formatter::with_limits(limits()), text)
+}
+
+fn test_encoding<T>(send: T, expected: &[u8])
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    let text = serialize(&send);
+    assert_eq!(core::str::from_utf8(&text).unwrap(), core::str::from_utf8(expected).unwrap());
+    assert_eq!(deserialize::<T>(&text).expect("unable to deserialize"), send);
+    diny_test::roundtrip_all_schedules(&diny_json::format(), &send);
+}
+
+fn assert_invalid_data<T: Debug>(recv: io::Result<T>) {
+    assert_eq!(recv.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+
+#[test]
+fn ints_are_decimal() {
+    test_encoding(0u8, b"0");
+    test_encoding(255u8, b"255");
+    test_encoding(-128i8, b"-128");
+    test_encoding(1000u16, b"1000");
+    test_encoding(-1_000_000i32, b"-1000000");
+    test_encoding(u64::MAX, b"18446744073709551615");
+    test_encoding(u128::MAX, b"340282366920938463463374607431768211455");
+    test_encoding(i128::MIN, b"-170141183460469231731687303715884105728");
+}
+
+#[test]
+fn floats_are_the_shortest_exact_decimal() {
+    test_encoding(1.5f32, b"1.5");
+    test_encoding(0.1f32, b"0.1");
+    test_encoding(-0.0f64, b"-0.0");
+    test_encoding(1.1f64, b"1.1");
+    test_encoding(1e20f64, b"1e20");
+    test_encoding(f64::MIN_POSITIVE, b"2.2250738585072014e-308");
+
+    assert_eq!(deserialize::<f64>(b"1").unwrap(), 1.0);
+    assert_eq!(deserialize::<f64>(b"2.5E+2").unwrap(), 250.0);
+    assert_eq!(deserialize::<f32>(b"-3e-1").unwrap(), -0.3);
+}
+
+#[test]
+fn non_finite_floats_are_not_encoded() {
+    let mut tx = Vec::new();
+    for v in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let err = block_on(v.serialize(&diny_json::format(), &mut tx)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+    assert_invalid_data(deserialize::<f32>(b"1e39"));
+}
+
+#[test]
+fn literals_are_encoded() {
+    test_encoding(false, b"false");
+    test_encoding(true, b"true");
+    test_encoding((), b"null");
+}
+
+#[test]
+fn chars_and_strings_are_quoted_and_escaped() {
+    test_encoding('a', b"\"a\"");
+    test_encoding('"', b"\"\\\"\"");
+    test_encoding('\u{1}', b"\"\\u0001\"");
+    test_encoding('€', "\"€\"".as_bytes());
+    test_encoding('\u{10151}', "\"\u{10151}\"".as_bytes());
+    test_encoding(String::new(), b"\"\"");
+    test_encoding(String::from("a\\b/c"), b"\"a\\\\b/c\"");
+    test_encoding(String::from("\t\r\n\u{8}\u{c}\u{1f}"), b"\"\\t\\r\\n\\b\\f\\u001f\"");
+    test_encoding("x".repeat(300), &[&b"\""[..], &[b'x'; 300], b"\""].concat());
+}
+
+#[test]
+fn any_escape_is_accepted() {
+    assert_eq!(deserialize::<String>(b"\"\\/\\u0041\\u00fc\\u20AC\"").unwrap(), "/A\u{fc}€");
+    assert_eq!(deserialize::<String>(b"\"\\ud800\\udd51\"").unwrap(), "\u{10151}");
+    assert_eq!(deserialize::<char>(b"\"\\ud83d\\ude00\"").unwrap(), '\u{1f600}');
+}
+
+#[test]
+fn byte_slices_are_arrays_of_numbers() {
+    assert_eq!(serialize::<[u8]>(&[]), b"[]");
+    assert_eq!(serialize::<[u8]>(&[0, 1, 255]), b"[0,1,255]");
+}
+
+#[test]
+fn sequences_and_maps_are_arrays() {
+    test_encoding(Vec::<u8>::new(), b"[]");
+    test_encoding(vec![1u8, 2, 3], b"[1,2,3]");
+    test_encoding(vec![String::from("a"), String::new()], b"[\"a\",\"\"]");
+    test_encoding(vec![Vec::<u8>::new(), vec![1]], b"[[],[1]]");
+    test_encoding(BTreeMap::<u8, bool>::new(), b"[]");
+    test_encoding(BTreeMap::from([(1u8, 2u8), (3u8, 4u8)]), b"[[1,2],[3,4]]");
+}
+
+#[test]
+fn structs_are_objects() {
+    test_encoding(
+        Rec { id: 300, name: String::from("ab"), tag: Some(2), kind: Kind::B(3) },
+        b"{\"id\":300,\"name\":\"ab\",\"tag\":2,\"kind\":{\"B\":3}}",
+    );
+    test_encoding(Skipping { a: 1, b: 0, c: true }, b"{\"a\":1,\"c\":true}");
+    test_encoding(Empty {}, b"null");
+}
+
+#[test]
+fn tuples_and_arrays_are_arrays() {
+    test_encoding(Pair(1, false), b"[1,false]");
+    test_encoding((1u8, String::from("a")), b"[1,\"a\"]");
+    test_encoding([1u8, 2, 3], b"[1,2,3]");
+    test_encoding([[0u8; 2]; 2], b"[[0,0],[0,0]]");
+}
+
+#[test]
+fn options_are_null_or_the_value() {
+    test_encoding(None::<u32>, b"null");
+    test_encoding(Some(300u32), b"300");
+    test_encoding(vec![Some(1u8), None], b"[1,null]");
+    test_encoding(Rec { id: 0, name: String::new(), tag: None, kind: Kind::A }, b"{\"id\":0,\"name\":\"\",\"tag\":null,\"kind\":{\"A\":null}}");
+}
+
+#[test]
+fn enums_are_objects_named_by_variant() {
+    test_encoding(Kind::A, b"{\"A\":null}");
+    test_encoding(Kind::B(-2), b"{\"B\":-2}");
+    test_encoding(Kind::Bx, b"{\"Bx\":null}");
+    test_encoding(Ok::<u8, String>(1), b"{\"Ok\":1}");
+    test_encoding(Err::<u8, String>(String::from("e")), b"{\"Err\":\"e\"}");
+}
+
+#[test]
+fn unit_variants_may_be_bare_strings() {
+    assert_eq!(deserialize::<Kind>(b"\"A\"").unwrap(), Kind::A);
+    assert_eq!(deserialize::<Kind>(b" \"Bx\"").unwrap(), Kind::Bx);
+    assert_eq!(deserialize::<Kind>(b"{ \"A\" : null }").unwrap(), Kind::A);
+    assert_eq!(
+        deserialize::<Vec<Kind>>(b"[\"A\",{\"B\":1},{\"Bx\":null},\"Bx\"]").unwrap(),
+        vec![Kind::A, Kind::B(1), Kind::Bx, Kind::Bx],
+    );
+    assert_invalid_data(deserialize::<Kind>(b"\"B\""));
+    assert_invalid_data(deserialize::<Kind>(b"\"C\""));
+    assert_invalid_data(deserialize::<Result<u8, u8>>(b"\"Ok\""));
+}
+
+#[test]
+fn from_slice_accepts_trailing_whitespace() {
+    let format = diny_json::format();
+    assert_eq!(diny::from_slice::<_, Kind>(&format, b"{\"B\":1}\n").unwrap(), Kind::B(1));
+    assert_eq!(diny::from_slice::<_, Kind>(&format, b"\"A\" \r\n\t").unwrap(), Kind::A);
+    assert_eq!(diny::from_slice::<_, u8>(&format, b"7\n").unwrap(), 7);
+    assert_eq!(diny::from_slice::<_, Vec<u8>>(&format, b" [1] ").unwrap(), vec![1]);
+    assert_eq!(diny::from_slice::<_, u8>(&format, b"7 8").unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(diny::from_slice::<_, Kind>(&format, b"\"A\"x").unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn whitespace_is_accepted_between_tokens() {
+    let text = b" {\n  \"id\" : 300 ,\r\n  \"name\":\t\"ab\",\n  \"tag\": null,\n  \"kind\": { \"B\" : [ 1 ,2 ] }\n}";
+    assert_invalid_data(deserialize::<Rec>(text));
+
+    let text = b" {\n  \"id\" : 300 ,\r\n  \"name\":\t\"ab\",\n  \"tag\": null,\n  \"kind\": { \"B\" : -7 }\n}";
+    assert_eq!(
+        deserialize::<Rec>(text).unwrap(),
+        Rec { id: 300, name: String::from("ab"), tag: None, kind: Kind::B(-7) },
+    );
+    assert_eq!(deserialize::<Vec<u8>>(b"[ ]").unwrap(), vec![]);
+    assert_eq!(deserialize::<Vec<u8>>(b" [ 1 , 2 ]").unwrap(), vec![1, 2]);
+    assert_eq!(deserialize::<BTreeMap<u8, u8>>(b"[ [1, 2] ]").unwrap(), BTreeMap::from([(1, 2)]));
+    assert_eq!(deserialize::<(bool, ())>(b"[ true , null ]").unwrap(), (true, ()));
+}
+
+#[test]
+fn rejects_mismatched_values() {
+    assert_invalid_data(deserialize::<u8>(b"256"));
+    assert_invalid_data(deserialize::<u8>(b"-1"));
+    assert_invalid_data(deserialize::<u8>(b"+1"));
+    assert_invalid_data(deserialize::<u8>(b"01"));
+    assert_invalid_data(deserialize::<u8>(b"1.0"));
+    assert_invalid_data(deserialize::<u8>(b"\"1\""));
+    assert_invalid_data(deserialize::<f64>(b".5"));
+    assert_invalid_data(deserialize::<f64>(b"1."));
+    assert_invalid_data(deserialize::<f64>(b"1e"));
+    assert_invalid_data(deserialize::<bool>(b"True"));
+    assert_invalid_data(deserialize::<()>(b"nil"));
+    assert_invalid_data(deserialize::<String>(b"abc"));
+    assert_invalid_data(deserialize::<String>(b"\"a\\x\""));
+    assert_invalid_data(deserialize::<String>(b"\"a\nb\""));
+    assert_invalid_data(deserialize::<String>(b"\"\\ud800\""));
+    assert_invalid_data(deserialize::<String>(b"\"\\ud800\\u0041\""));
+    assert_invalid_data(deserialize::<String>(b"\"\\udc00\""));
+    assert_invalid_data(deserialize::<String>(b"\"\xff\""));
+    assert_invalid_data(deserialize::<char>(b"\"ab\""));
+    assert_invalid_data(deserialize::<char>(b"\"\""));
+    assert_invalid_data(deserialize::<Vec<u8>>(b"[1;2]"));
+    assert_invalid_data(deserialize::<Vec<u8>>(b"[1,]"));
+    assert_invalid_data(deserialize::<Vec<u8>>(b"[256]"));
+    assert_invalid_data(deserialize::<(u8, u8)>(b"[1,2,3]"));
+    assert_invalid_data(deserialize::<(u8, u8)>(b"[1]"));
+    assert_invalid_data(deserialize::<Kind>(b"{\"C\":null}"));
+    assert_invalid_data(deserialize::<Kind>(b"{\"Bo\":null}"));
+    assert_invalid_data(deserialize::<Kind>(b"{\"A\":null,\"B\":1}"));
+    assert_invalid_data(deserialize::<Skipping>(b"{\"c\":true,\"a\":1}"));
+    assert_invalid_data(deserialize::<Skipping>(b"{\"a\":1,\"b\":0,\"c\":true}"));
+    assert_invalid_data(deserialize::<Skipping>(b"[1,true]"));
+}
+
+#[test]
+fn truncated_text_is_an_unexpected_eof() {
+    assert_eq!(deserialize::<bool>(b"tru").unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(deserialize::<String>(b"\"ab").unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(deserialize::<Vec<u8>>(b"[1,").unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(deserialize::<Kind>(b"{\"A\"").unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn decode_limits_are_enforced() {
+    let text = [&b"\""[..], &[b'x'; 64], b"\""].concat();
+    let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_bytes_len(63));
+    let err = deserialize_with::<String>(&format, &text).unwrap_err();
+    assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::BytesLen));
+    assert_eq!(deserialize_with::<String>(&format, &text[1..]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn encoded_len_matches_json_encoding() {
+    assert_eq!(diny::encoded_len(&diny_json::format(), &300u32).unwrap(), 3);
+    assert_eq!(diny::encoded_len(&diny_json::format(), &"x\n".repeat(10)).unwrap(), 32);
+    assert_eq!(diny::encoded_len(&diny_json::format(), &Kind::A).unwrap(), 10);
+    assert_eq!(diny::max_encoded_len::<Formatter, bool>(), Some(5));
+    assert_eq!(diny::max_encoded_len::<Formatter, ()>(), Some(4));
+    assert_eq!(diny::max_encoded_len::<Formatter, char>(), Some(8));
+    assert_eq!(diny::max_encoded_len::<Formatter, String>(), None);
+}
+
+#[test]
+fn serde_values_are_written_with_the_json_encoders() {
+    let format = diny_json::format();
+    let send = (vec![1u8, 2], BTreeMap::from([(String::from("k"), None::<bool>)]), Some(Vec::<i8>::new()));
+    let text = diny::serde::to_vec(&format, &send).unwrap();
+    assert_eq!(core::str::from_utf8(&text).unwrap(), "[[1,2],[[\"k\",null]],[]]");
+    assert_eq!(diny::serde::from_slice::<_, (Vec<u8>, BTreeMap<String, Option<bool>>, Option<Vec<i8>>)>(&format, &text).unwrap(), send);
+
+    test_encoding(diny::serde::Serde(vec![true]), b"[true]");
+    test_encoding(diny::serde::Serde(5u8), b"5");
+    test_encoding(diny::serde::Serde(String::from("]\\\"[")), br#""]\\\"[""#);
+    test_encoding(
+        (diny::serde::Serde(vec![vec![1u8], vec![]]), diny::serde::Serde(Some(false))),
+        b"[[[1],[]],false]",
+    );
+}
@@ -0,0 +1,77 @@
+use core::{pin::Pin, task::{Context, Poll}};
+use diny::{backend::{self, Encode as _}, io};
+use crate::Formatter as ThisFormat;
+use super::{bytes, header::Family};
+
+
+type Data = [u8];
+
+pub struct Encoder(bytes::Encoder);
+
+impl backend::Encode for Encoder
+{
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init(_data: &Self::Data) -> Self {
+        Self(bytes::Encoder::Init)
+    }
+
+    fn start_encode<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, <Self::Format as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        bytes::Encoder::start_encode(Family::Bin, writer, data, cx)
+        .map_pending(Encoder)
+    }
+
+    fn poll_encode<W>(&mut self, _format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<<Self::Format as backend::Format>::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.poll_encode(Family::Bin, writer, data, cx)
+    }
+}
+
+pub struct SerializeAll<'w, W> {
+    format: &'w ThisFormat,
+    writer: &'w mut W,
+    data: &'w Data,
+    encoder: Encoder,
+}
+
+pub(crate) fn serialize<'w, W>(format: &'w ThisFormat, writer: &'w mut W, data: &'w Data) -> SerializeAll<'w, W>
+where
+    W: ::diny::io::AsyncWrite + Unpin,
+{
+    SerializeAll::new(format, writer, data)
+}
+
+impl<'w, W> SerializeAll<'w, W> {
+    fn new(format: &'w ThisFormat, writer: &'w mut W, data: &'w Data) -> SerializeAll<'w, W>
+    where
+        W: ::diny::io::AsyncWrite + Unpin,
+    {
+        SerializeAll {
+            format,
+            writer,
+            data,
+            encoder: Encoder::init(data),
+        }
+    }
+}
+
+impl<'w, W> Unpin for SerializeAll<'w, W> {}
+
+impl<'w, W> core::future::Future for SerializeAll<'w, W>
+where
+    W: ::diny::io::AsyncWrite + Unpin,
+{
+    type Output = Result<(), <ThisFormat as backend::Format>::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        this.encoder.poll_encode(this.format, this.writer, this.data, cx).into()
+    }
+}
+
@@ -0,0 +1,129 @@
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+use core::task::Context;
+use diny::{backend::{self, Format}, buffer::{self, buffer_state::BufferState}, io};
+use crate::Formatter as ThisFormat;
+use super::header::{self, Family};
+
+type Error = <ThisFormat as Format>::Error;
+
+/// Encodes a byte slice as a header of the given [Family], followed by the bytes themselves
+pub enum Encoder {
+    Init,
+    Header(BufferState<{ header::MAX_LEN }>),
+    Cur(buffer::BufferCursor),
+    Fini,
+}
+
+impl Encoder {
+    pub fn start_encode<W>(family: Family, writer: &mut W, data: &[u8], cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match header::encode(family, data.len()) {
+            Some(mut buf) => match buf.start_write(writer, cx) {
+                backend::PollEncodeStatus::Fini     => Self::after_header(writer, data, cx),
+                backend::PollEncodeStatus::Pending  => backend::StartEncodeStatus::Pending(Self::Header(buf)),
+                backend::PollEncodeStatus::Error(e) => backend::StartEncodeStatus::Error(e),
+            },
+            None => backend::StartEncodeStatus::Error(ThisFormat::invalid_data_err()),
+        }
+    }
+
+    fn after_header<W>(writer: &mut W, data: &[u8], cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        if data.is_empty() {
+            backend::StartEncodeStatus::Fini
+        } else {
+            let mut cur = buffer::BufferCursor::new(data);
+            match cur.write_remaining(writer, data, cx) {
+                backend::PollEncodeStatus::Fini     => backend::StartEncodeStatus::Fini,
+                backend::PollEncodeStatus::Pending  => backend::StartEncodeStatus::Pending(Self::Cur(cur)),
+                backend::PollEncodeStatus::Error(e) => backend::StartEncodeStatus::Error(e),
+            }
+        }
+    }
+
+    pub fn poll_encode<W>(&mut self, family: Family, writer: &mut W, data: &[u8], cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Init        => diny::encode_chain!(*self, Self::start_encode(family, writer, data, cx)),
+            Self::Header(buf) => diny::encode_poll_chain!(*self, buf.write_remaining(writer, cx), Self::after_header(writer, data, cx)),
+            Self::Cur(cur)    => diny::encode_poll_fini!(*self, cur.write_remaining(writer, data, cx)),
+            Self::Fini        => backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+enum DecodeCursor {
+    Header(header::Decoder),
+    Cur(buffer::BufferCursor),
+    Fini,
+}
+
+/// Decodes the bytes following a header of the given [Family]
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct Decoder {
+    data: Vec<u8>,
+    cursor: DecodeCursor,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Decoder {
+    pub fn new(family: Family) -> Self {
+        Self {
+            data: Vec::new(),
+            cursor: DecodeCursor::Header(header::Decoder::new(family)),
+        }
+    }
+
+    /// Reads the bytes into the emptied storage of `data`, rather than into a new vector
+    pub fn reuse(&mut self, mut data: Vec<u8>) {
+        data.clear();
+        self.data = data;
+    }
+
+    fn fini(&mut self) -> backend::PollDecodeStatus<Vec<u8>, Error> {
+        self.cursor = DecodeCursor::Fini;
+        backend::PollDecodeStatus::Fini(core::mem::take(&mut self.data))
+    }
+
+    fn error(&mut self, err: Error) -> backend::PollDecodeStatus<Vec<u8>, Error> {
+        self.cursor = DecodeCursor::Fini;
+        backend::PollDecodeStatus::Error(err)
+    }
+
+    pub fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Vec<u8>, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match &mut self.cursor {
+                DecodeCursor::Header(dec) => match dec.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(len) => {
+                        if let Err(kind) = backend::FormatDecode::decode_limits(format).check_bytes_len(len) {
+                            return self.error(ThisFormat::limit_exceeded_err(kind));
+                        } else if len == 0 {
+                            return self.fini();
+                        }
+                        self.data.reserve_exact(diny::backend::limits::prealloc_len::<u8>(len));
+                        self.cursor = DecodeCursor::Cur(buffer::BufferCursor::with_len(len));
+                    }
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => return self.error(e),
+                },
+                DecodeCursor::Cur(cur) => return match cur.fill_vec(reader, &mut self.data, cx) {
+                    backend::PollDecodeStatus::Fini(()) => self.fini(),
+                    backend::PollDecodeStatus::Pending  => backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => self.error(e),
+                },
+                DecodeCursor::Fini => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use core::{convert::TryFrom, task::Context};
+use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::{int::from_be, marker::*};
+
+type Error = <ThisFormat as Format>::Error;
+
+// A marker byte, followed by at most a four byte, big endian length
+pub const MAX_LEN: usize = 5;
+
+/// The kinds of MessagePack values that are prefixed by a length
+#[derive(Clone, Copy)]
+pub enum Family {
+    Str,
+    Bin,
+    Array,
+    Map,
+}
+
+enum Head {
+    // The length is held within the marker byte itself
+    Fix(usize),
+    // The length follows the marker byte in the given number of bytes
+    Follows(usize),
+}
+
+impl Family {
+    // The marker and maximum length of the family's single byte form, if it has one
+    fn fix(self) -> Option<(u8, usize)> {
+        match self {
+            Family::Str   => Some((FIXSTR  , 31)),
+            Family::Bin   => None,
+            Family::Array => Some((FIXARRAY, 15)),
+            Family::Map   => Some((FIXMAP  , 15)),
+        }
+    }
+
+    // The markers of the forms followed by an 8, 16 and 32 bit length respectively
+    fn markers(self) -> (Option<u8>, u8, u8) {
+        match self {
+            Family::Str   => (Some(STR8), STR16  , STR32  ),
+            Family::Bin   => (Some(BIN8), BIN16  , BIN32  ),
+            Family::Array => (None      , ARRAY16, ARRAY32),
+            Family::Map   => (None      , MAP16  , MAP32  ),
+        }
+    }
+
+    fn head(self, marker: u8) -> Option<Head> {
+        let (m8, m16, m32) = self.markers();
+        match self.fix() {
+            Some((fix, max)) if marker & !(max as u8) == fix => Some(Head::Fix((marker & max as u8) as usize)),
+            _ if Some(marker) == m8 => Some(Head::Follows(1)),
+            _ if marker == m16      => Some(Head::Follows(2)),
+            _ if marker == m32      => Some(Head::Follows(4)),
+            _ => None,
+        }
+    }
+}
+
+/// The length of the header of an array holding `len` items
+pub const fn array_len(len: usize) -> usize {
+    if len <= 15 {
+        1
+    } else if len <= u16::MAX as usize {
+        3
+    } else {
+        MAX_LEN
+    }
+}
+
+/// Encode a header for a value of the given `family` using the smallest form able
+/// to hold `len`, or `None` if `len` exceeds the format's maximum of `u32::MAX`
+pub fn encode(family: Family, len: usize) -> Option<BufferState<MAX_LEN>> {
+    let mut buf = [0u8; MAX_LEN];
+    let (m8, m16, m32) = family.markers();
+    let n = match (family.fix(), m8) {
+        (Some((fix, max)), _) if len <= max => {
+            buf[0] = fix | len as u8;
+            1
+        }
+        (_, Some(m8)) if len <= u8::MAX as usize => {
+            buf[0] = m8;
+            buf[1] = len as u8;
+            2
+        }
+        _ if len <= u16::MAX as usize => {
+            buf[0] = m16;
+            buf[1..3].copy_from_slice(&(len as u16).to_be_bytes());
+            3
+        }
+        _ => {
+            buf[0] = m32;
+            buf[1..5].copy_from_slice(&u32::try_from(len).ok()?.to_be_bytes());
+            5
+        }
+    };
+    Some(BufferState::with_partial_contents(buf, n))
+}
+
+// Reads the marker byte on its own, in order to determine how many
+// length bytes follow it.
+pub struct Decoder {
+    family: Family,
+    buf: BufferState<MAX_LEN>,
+}
+
+impl Decoder {
+    pub fn new(family: Family) -> Self {
+        Self {
+            family,
+            buf: BufferState::with_partial_contents([0u8; MAX_LEN], 1),
+        }
+    }
+
+    pub fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<usize, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self.buf.read_remaining(reader, cx) {
+                backend::PollDecodeStatus::Fini(()) => {
+                    let bytes = &self.buf.buffer()[..self.buf.len()];
+                    if bytes.len() > 1 {
+                        return usize::try_from(from_be(&bytes[1..]))
+                            .map_err(|_| ThisFormat::invalid_data_err())
+                            .into();
+                    }
+                    match self.family.head(bytes[0]) {
+                        Some(Head::Fix(len))   => return backend::PollDecodeStatus::Fini(len),
+                        Some(Head::Follows(n)) => self.buf.extend_len(n),
+                        None                   => return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+                    }
+                }
+                backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(err) => return backend::PollDecodeStatus::Error(err),
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use core::task::Context;
+use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::marker::*;
+
+type Error = <ThisFormat as Format>::Error;
+
+// A marker byte, followed by at most eight bytes of big endian payload
+pub const MAX_LEN: usize = 9;
+
+/// An integer as read from any member of the MessagePack int family
+pub enum Int {
+    Unsigned(u64),
+    Signed(i64),
+}
+
+#[inline(always)]
+fn with_payload(marker: u8, payload: &[u8]) -> BufferState<MAX_LEN> {
+    let mut buf = [0u8; MAX_LEN];
+    buf[0] = marker;
+    buf[1..=payload.len()].copy_from_slice(payload);
+    BufferState::with_partial_contents(buf, 1 + payload.len())
+}
+
+/// Encode `v` using the smallest int family member able to represent it
+pub fn encode_unsigned(v: u64) -> BufferState<MAX_LEN> {
+    if v <= POSITIVE_FIXINT_MAX as u64 {
+        with_payload(v as u8, &[])
+    } else if v <= u8::MAX as u64 {
+        with_payload(UINT8, &[v as u8])
+    } else if v <= u16::MAX as u64 {
+        with_payload(UINT16, &(v as u16).to_be_bytes())
+    } else if v <= u32::MAX as u64 {
+        with_payload(UINT32, &(v as u32).to_be_bytes())
+    } else {
+        with_payload(UINT64, &v.to_be_bytes())
+    }
+}
+
+/// Encode `v` using the smallest int family member able to represent it
+///
+/// Non-negative values share the unsigned encodings.
+pub fn encode_signed(v: i64) -> BufferState<MAX_LEN> {
+    if v >= 0 {
+        encode_unsigned(v as u64)
+    } else if v >= -32 {
+        with_payload(v as u8, &[])
+    } else if v >= i8::MIN as i64 {
+        with_payload(INT8, &[v as u8])
+    } else if v >= i16::MIN as i64 {
+        with_payload(INT16, &(v as i16).to_be_bytes())
+    } else if v >= i32::MIN as i64 {
+        with_payload(INT32, &(v as i32).to_be_bytes())
+    } else {
+        with_payload(INT64, &v.to_be_bytes())
+    }
+}
+
+/// Interpret `bytes` as an unsigned, big endian integer of at most eight bytes
+#[inline(always)]
+pub fn from_be(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |v, b| (v << 8) | *b as u64)
+}
+
+#[inline(always)]
+fn payload_len(marker: u8) -> Option<usize> {
+    match marker {
+        0x00..=POSITIVE_FIXINT_MAX | NEGATIVE_FIXINT_MIN..=0xff => Some(0),
+        UINT8  | INT8  => Some(1),
+        UINT16 | INT16 => Some(2),
+        UINT32 | INT32 => Some(4),
+        UINT64 | INT64 => Some(8),
+        _ => None,
+    }
+}
+
+#[inline(always)]
+fn to_int(bytes: &[u8]) -> Int {
+    let payload = &bytes[1..];
+    match bytes[0] {
+        m @ 0x00..=POSITIVE_FIXINT_MAX => Int::Unsigned(m as u64),
+        m @ NEGATIVE_FIXINT_MIN..=0xff => Int::Signed(m as i8 as i64),
+        UINT8 | UINT16 | UINT32 | UINT64 => Int::Unsigned(from_be(payload)),
+        _ => {
+            // Sign extend the payload to the full width
+            let shift = 64 - 8 * payload.len() as u32;
+            Int::Signed(((from_be(payload) << shift) as i64) >> shift)
+        }
+    }
+}
+
+// Reads the marker byte on its own, in order to determine how many
+// payload bytes follow it.
+pub struct Decoder(BufferState<MAX_LEN>);
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self(BufferState::with_partial_contents([0u8; MAX_LEN], 1))
+    }
+
+    pub fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Int, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self.0.read_remaining(reader, cx) {
+                backend::PollDecodeStatus::Fini(()) => {
+                    let bytes = &self.0.buffer()[..self.0.len()];
+                    match payload_len(bytes[0]) {
+                        Some(n) if n > 0 && bytes.len() == 1 => self.0.extend_len(n),
+                        Some(_) => return backend::PollDecodeStatus::Fini(to_int(bytes)),
+                        None    => return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+                    }
+                }
+                backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(err) => return backend::PollDecodeStatus::Error(err),
+            }
+        }
+    }
+}
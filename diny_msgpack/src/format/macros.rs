@@ -0,0 +1,434 @@
+macro_rules! fixed_encode_decode_def {
+    () => {
+        pub struct Encoder(BufferState<BUF_SIZE>);
+
+        impl diny::buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(BUF_SIZE);
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(BufferState::with_contents(to_bytes(*data)))
+            }
+        
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                enc.0.start_write(writer, cx)
+                .lift(enc)
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                self.0.write_remaining(writer, cx)
+            }
+        }                
+
+        pub struct Decoder(BufferState<BUF_SIZE>);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(BufferState::init())
+            }
+        
+            fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                match (&mut dec.0).start_read(reader, cx) {
+                    backend::PollDecodeStatus::Fini(())   => from_bytes(*dec.0.buffer()).into(),
+                    backend::PollDecodeStatus::Pending    => backend::StartDecodeStatus::Pending(dec),
+                    backend::PollDecodeStatus::Error(err) => backend::StartDecodeStatus::Error(err),
+                }
+            }
+
+            fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                match (&mut self.0).read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(())   => from_bytes(*self.0.buffer()).into(),
+                    backend::PollDecodeStatus::Pending    => backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(err) => backend::PollDecodeStatus::Error(err),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! tag_def {
+    ($t: ty, $repr: ty) => {
+        tag_coders_def!($t, $repr);
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! tag_coders_def {
+    ($t: ty, $repr: ty) => {
+        use core::{convert::TryFrom, task::Context};
+        use diny::{backend::{self, Format}, buffer::{self, buffer_state::BufferState}, io};
+        use crate::Formatter as ThisFormat;
+        use super::{header::{self, Family}, int, marker::FIXARRAY};
+
+        pub type Data = $t;
+        pub type Error = <ThisFormat as Format>::Error;
+
+        // A fixarray marker, followed by the tag itself
+        const BUF_SIZE: usize = 1 + int::MAX_LEN;
+
+        pub struct Encoder(Option<BufferState<BUF_SIZE>>);
+
+        impl buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(1 + 1 + core::mem::size_of::<$repr>());
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(
+                    <$repr>::try_from(**data)
+                    .map(|tag| {
+                        let tag = int::encode_unsigned(tag as u64);
+                        let mut buf = [0u8; BUF_SIZE];
+                        buf[0] = FIXARRAY | 2;
+                        buf[1..=tag.len()].copy_from_slice(&tag.buffer()[..tag.len()]);
+                        BufferState::with_partial_contents(buf, 1 + tag.len())
+                    })
+                    .ok()
+                )
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                match &mut enc.0 {
+                    Some(buf) => buf.start_write(writer, cx).lift(enc),
+                    None      => backend::StartEncodeStatus::Error(ThisFormat::invalid_data_err()),
+                }
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                match &mut self.0 {
+                    Some(buf) => buf.write_remaining(writer, cx),
+                    None      => backend::PollEncodeStatus::Error(ThisFormat::invalid_data_err()),
+                }
+            }
+        }
+
+        #[inline(always)]
+        fn from_int(v: int::Int) -> Result<Data, Error> {
+            match v {
+                int::Int::Unsigned(v) => <$repr>::try_from(v).ok(),
+                int::Int::Signed  (v) => <$repr>::try_from(v).ok(),
+            }
+            .map(|tag| Data::from(tag as usize))
+            .ok_or_else(ThisFormat::invalid_data_err)
+        }
+
+        // The tag is the first of a two element array, the second being the payload
+        pub enum Decoder {
+            Array(header::Decoder),
+            Tag(int::Decoder),
+            Fini,
+        }
+
+        impl Decoder {
+            fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                loop {
+                    let status = match self {
+                        Self::Array(dec) => match dec.read_remaining(reader, cx) {
+                            backend::PollDecodeStatus::Fini(2) => { *self = Self::Tag(int::Decoder::new()); continue },
+                            backend::PollDecodeStatus::Fini(_) => backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+                            backend::PollDecodeStatus::Pending => return backend::PollDecodeStatus::Pending,
+                            backend::PollDecodeStatus::Error(err) => backend::PollDecodeStatus::Error(err),
+                        },
+                        Self::Tag(dec) => match dec.read_remaining(reader, cx) {
+                            backend::PollDecodeStatus::Pending => return backend::PollDecodeStatus::Pending,
+                            status => status.and_then(|v| from_int(v).into()),
+                        },
+                        Self::Fini => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+                    };
+                    *self = Self::Fini;
+                    return status;
+                }
+            }
+        }
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self::Array(header::Decoder::new(Family::Array))
+            }
+
+            fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.read_remaining(reader, cx)
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.read_remaining(reader, cx)
+            }
+        }
+    };
+}
+
+macro_rules! be_bytes_def {
+    ($t:ty, $prefix:expr) => {
+        use core::task::Context;
+        use diny::{backend, buffer::{buffer_state::BufferState}, io};
+        use crate::Formatter as ThisFormat;
+
+        type Error = <ThisFormat as backend::Format>::Error;
+        type Data = $t;
+
+        // The marker bytes that precede the big endian representation of the data
+        const PREFIX: &[u8] = &$prefix;
+        const PAYLOAD_LEN: usize = core::mem::size_of::<Data>();
+        const BUF_SIZE: usize = PREFIX.len() + PAYLOAD_LEN;
+
+        #[inline(always)]
+        fn to_bytes(v: Data) -> [u8; BUF_SIZE] {
+            let mut buf = [0u8; BUF_SIZE];
+            buf[..PREFIX.len()].copy_from_slice(PREFIX);
+            buf[PREFIX.len()..].copy_from_slice(&v.to_be_bytes());
+            buf
+        }
+
+        #[inline(always)]
+        fn from_bytes(bytes: [u8; BUF_SIZE]) -> io::Result<Data> {
+            let (prefix, payload) = bytes.split_at(PREFIX.len());
+            if prefix == PREFIX {
+                let mut be_bytes = [0u8; PAYLOAD_LEN];
+                be_bytes.copy_from_slice(payload);
+                Ok(Data::from_be_bytes(be_bytes))
+            } else {
+                Err(<ThisFormat as backend::Format>::invalid_data_err())
+            }
+        }
+
+        fixed_encode_decode_def!();
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! float_def {
+    ($t:ty, $marker:ident) => {
+        be_bytes_def!($t, [super::marker::$marker]);
+    };
+}
+
+// MessagePack has no 128 bit integers, so these are written as a 16 byte bin
+macro_rules! wide_def {
+    ($t:ty) => {
+        be_bytes_def!($t, [super::marker::BIN8, core::mem::size_of::<$t>() as u8]);
+    };
+}
+
+macro_rules! int_def {
+    ($t:ty, $bytes:literal, $encode:ident, $repr:ty) => {
+        use core::{convert::TryFrom, task::Context};
+        use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+        use crate::Formatter as ThisFormat;
+        use super::int;
+
+        type Error = <ThisFormat as Format>::Error;
+        type Data = $t;
+
+        pub struct Encoder(BufferState<{ int::MAX_LEN }>);
+
+        impl diny::buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some($bytes);
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(int::$encode(<$repr>::from(*data)))
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                enc.0.start_write(writer, cx)
+                .lift(enc)
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                self.0.write_remaining(writer, cx)
+            }
+        }
+
+        #[inline(always)]
+        fn from_int(v: int::Int) -> Result<Data, Error> {
+            match v {
+                int::Int::Unsigned(v) => Data::try_from(v).ok(),
+                int::Int::Signed  (v) => Data::try_from(v).ok(),
+            }
+            .ok_or_else(ThisFormat::invalid_data_err)
+        }
+
+        pub struct Decoder(int::Decoder);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(int::Decoder::new())
+            }
+
+            fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.0.read_remaining(reader, cx)
+                .and_then(|v| from_int(v).into())
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.0.read_remaining(reader, cx)
+                .and_then(|v| from_int(v).into())
+            }
+        }
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! unsigned_def {
+    ($t:ty, $bytes:literal) => {
+        int_def!($t, $bytes, encode_unsigned, u64);
+    };
+}
+
+macro_rules! signed_def {
+    ($t:ty, $bytes:literal) => {
+        int_def!($t, $bytes, encode_signed, i64);
+    };
+}
+
+macro_rules! header_def {
+    ($t: ty, $family: ident) => {
+        header_coders_def!($t, $family);
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! header_coders_def {
+    ($t: ty, $family: ident) => {
+        use core::task::Context;
+        use diny::{backend::{self, Format}, buffer::{self, buffer_state::BufferState}, io};
+        use crate::Formatter as ThisFormat;
+        use super::header::{self, Family};
+
+        pub type Data = $t;
+        pub type Error = <ThisFormat as Format>::Error;
+
+        const FAMILY: Family = Family::$family;
+
+        pub struct Encoder(Option<BufferState<{ header::MAX_LEN }>>);
+
+        impl buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = Some(header::MAX_LEN);
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Encoder(header::encode(FAMILY, **data))
+            }
+
+            fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                let mut enc = Self::init_buffer(data);
+                match &mut enc.0 {
+                    Some(buf) => buf.start_write(writer, cx).lift(enc),
+                    None      => backend::StartEncodeStatus::Error(ThisFormat::invalid_data_err()),
+                }
+            }
+
+            fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                match &mut self.0 {
+                    Some(buf) => buf.write_remaining(writer, cx),
+                    None      => backend::PollEncodeStatus::Error(ThisFormat::invalid_data_err()),
+                }
+            }
+        }
+
+        pub struct Decoder(header::Decoder);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(header::Decoder::new(FAMILY))
+            }
+
+            fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                dec.0.read_remaining(reader, cx)
+                .map(Data::from)
+                .lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                self.0.read_remaining(reader, cx)
+                .map(Data::from)
+            }
+        }
+    };
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+#[macro_use]
+mod macros;
+
+mod marker;
+mod int;
+mod header;
+mod bytes;
+
+pub mod unit;
+pub mod bool;
+pub mod char;
+pub mod str;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod string;
+pub mod byte_slice;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod byte_vec;
+pub mod struct_begin;
+
+pub mod i8   { signed_def!(i8 , 2); }
+pub mod i16  { signed_def!(i16, 3); }
+pub mod i32  { signed_def!(i32, 5); }
+pub mod i64  { signed_def!(i64, 9); }
+pub mod i128 { wide_def!(i128); }
+
+pub mod u8   { unsigned_def!(u8 , 2); }
+pub mod u16  { unsigned_def!(u16, 3); }
+pub mod u32  { unsigned_def!(u32, 5); }
+pub mod u64  { unsigned_def!(u64, 9); }
+pub mod u128 { wide_def!(u128); }
+
+pub mod f32  { float_def!(f32, FLOAT32); }
+pub mod f64  { float_def!(f64, FLOAT64); }
+
+pub mod variant_idx  { tag_def!(diny::backend::internal::VariantIdx, u32); }
+pub mod option_tag   { tag_coders_def!(diny::backend::internal::OptionTag , u8 ); }
+pub mod sequence_len { header_def!(diny::backend::internal::SequenceLen, Array); }
+pub mod map_len      { header_coders_def!(diny::backend::internal::MapLen     , Map  ); }
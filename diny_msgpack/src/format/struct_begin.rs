@@ -0,0 +1,86 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend::{self, Format, internal::StructDef}, buffer::{self, buffer_state::BufferState}, io};
+use crate::Formatter as ThisFormat;
+use super::header::{self, Family};
+
+type Error = <ThisFormat as Format>::Error;
+
+// Structs, tuples and fixed size arrays are all written as an array holding
+// their members, so only the header of that array needs to be written here.
+pub struct Encoder<S>(Option<BufferState<{ header::MAX_LEN }>>, PhantomData<fn() -> S>);
+
+impl<S: StructDef> buffer::BufferEncode for Encoder<S> {
+    type Data = ();
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(header::array_len(S::FIELD_COUNT));
+
+    fn init_buffer(_data: &Self::Data) -> Self {
+        Self(header::encode(Family::Array, S::FIELD_COUNT), PhantomData)
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        match &mut enc.0 {
+            Some(buf) => buf.start_write(writer, cx).lift(enc),
+            None      => backend::StartEncodeStatus::Error(ThisFormat::invalid_data_err()),
+        }
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match &mut self.0 {
+            Some(buf) => buf.write_remaining(writer, cx),
+            None      => backend::PollEncodeStatus::Error(ThisFormat::invalid_data_err()),
+        }
+    }
+}
+
+// The array must hold exactly as many members as are expected
+pub struct Decoder<S>(header::Decoder, PhantomData<fn() -> S>);
+
+impl<S: StructDef> Decoder<S> {
+    fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.read_remaining(reader, cx)
+        .and_then(|len|
+            if len == S::FIELD_COUNT {
+                backend::PollDecodeStatus::Fini(())
+            } else {
+                backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err())
+            }
+        )
+    }
+}
+
+impl<S: StructDef> backend::Decode for Decoder<S> {
+    type Data = ();
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self(header::Decoder::new(Family::Array), PhantomData)
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.read_remaining(reader, cx)
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(reader, cx)
+    }
+}
@@ -0,0 +1,25 @@
+use core::task::Context;
+use diny::{backend, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::marker::NIL;
+
+type Error = <ThisFormat as backend::Format>::Error;
+type Data = ();
+const BUF_SIZE: usize = 1;
+
+#[inline(always)]
+fn to_bytes(_v: Data) -> [u8; BUF_SIZE] {
+    [NIL]
+}
+
+#[inline(always)]
+fn from_bytes(bytes: [u8; BUF_SIZE]) -> io::Result<Data> {
+    match bytes[0] {
+        NIL => Ok(()),
+        _ => Err(<ThisFormat as backend::Format>::invalid_data_err()),
+    }
+}
+
+fixed_encode_decode_def!();
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
@@ -0,0 +1,238 @@
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+//! The expected byte vectors in this module follow the MessagePack specification at
+//! https://github.com/msgpack/msgpack/blob/master/spec.md
+
+use core::fmt::Debug;
+use std::collections::BTreeMap;
+use diny::{backend::{DecodeLimits, LimitExceeded}, io, AsyncDeserialize, AsyncSerialization, AsyncSerialize, util::AsyncSliceReader};
+use futures::executor::block_on;
+
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Rec {
+    id: u32,
+    name: String,
+    tag: Option<u8>,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub enum Kind {
+    A,
+    B(i64),
+}
+
+fn serialize<T: AsyncSerialize + ?Sized>(send: &T) -> Vec<u8> {
+    let mut tx = Vec::new();
+    block_on(send.serialize(&diny_msgpack::format(), &mut tx)).expect("unable to serialize");
+    tx
+}
+
+fn deserialize<T: AsyncDeserialize>(bytes: &[u8]) -> io::Result<T> {
+    let mut rx = AsyncSliceReader::from(bytes);
+    let format = diny_msgpack::Formatter::with_limits(DecodeLimits::unlimited().with_max_bytes_len(1 << 20));
+    let recv = block_on(T::deserialize(&format, &mut rx));
+    if recv.is_ok() {
+        assert_eq!(rx.bytes_read(), bytes.len());
+    }
+    recv
+}
+
+// The length of the single MessagePack value at the start of `bytes`, found by
+// walking its structure as any generic MessagePack reader would.
+fn value_len(bytes: &[u8]) -> usize {
+    let be = |n: usize| bytes[1..=n].iter().fold(0, |v, b| (v << 8) | *b as usize);
+    let values = |header: usize, count: usize| (0..count).fold(header, |at, _| at + value_len(&bytes[at..]));
+    match bytes[0] {
+        0x00..=0x7f | 0xc0 | 0xc2 | 0xc3 | 0xe0..=0xff => 1,
+        m @ 0x80..=0x8f => values(1, 2 * (m & 0x0f) as usize),
+        m @ 0x90..=0x9f => values(1, (m & 0x0f) as usize),
+        m @ 0xa0..=0xbf => 1 + (m & 0x1f) as usize,
+        0xc4 | 0xd9 => 2 + be(1),
+        0xc5 | 0xda => 3 + be(2),
+        0xc6 | 0xdb => 5 + be(4),
+        0xcc | 0xd0 => 2,
+        0xcd | 0xd1 => 3,
+        0xca | 0xce | 0xd2 => 5,
+        0xcb | 0xcf | 0xd3 => 9,
+        0xdc => values(3, be(2)),
+        0xdd => values(5, be(4)),
+        0xde => values(3, 2 * be(2)),
+        0xdf => values(5, 2 * be(4)),
+        m => panic!("unexpected marker {:#04x}", m),
+    }
+}
+
+fn test_encoding<T>(send: T, expected: &[u8])
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    let bytes = serialize(&send);
+    assert_eq!(bytes, expected);
+    assert_eq!(value_len(&bytes), bytes.len(), "not a single MessagePack value");
+    assert_eq!(deserialize::<T>(&bytes).expect("unable to deserialize"), send);
+    diny_test::roundtrip_all_schedules(&diny_msgpack::format(), &send);
+}
+
+
+#[test]
+fn unsigned_ints_use_the_smallest_encoding() {
+    test_encoding(0u8, &[0x00]);
+    test_encoding(127u8, &[0x7f]);
+    test_encoding(128u8, &[0xcc, 0x80]);
+    test_encoding(255u16, &[0xcc, 0xff]);
+    test_encoding(256u16, &[0xcd, 0x01, 0x00]);
+    test_encoding(65_536u32, &[0xce, 0x00, 0x01, 0x00, 0x00]);
+    test_encoding(1u64 << 32, &[0xcf, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]);
+    test_encoding(u64::MAX, &[0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+}
+
+#[test]
+fn signed_ints_use_the_smallest_encoding() {
+    test_encoding(100i64, &[0x64]);
+    test_encoding(-1i8, &[0xff]);
+    test_encoding(-32i16, &[0xe0]);
+    test_encoding(-33i32, &[0xd0, 0xdf]);
+    test_encoding(200i16, &[0xcc, 0xc8]);
+    test_encoding(-129i32, &[0xd1, 0xff, 0x7f]);
+    test_encoding(i32::MIN, &[0xd2, 0x80, 0x00, 0x00, 0x00]);
+    test_encoding(i64::MIN, &[0xd3, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn wide_ints_are_bin_encoded() {
+    test_encoding(1u128, &[&[0xc4, 0x10][..], &[0x00; 15], &[0x01]].concat());
+    test_encoding(-1i128, &[&[0xc4, 0x10][..], &[0xff; 16]].concat());
+}
+
+#[test]
+fn floats_bools_and_units_are_encoded() {
+    test_encoding(1.5f32, &[0xca, 0x3f, 0xc0, 0x00, 0x00]);
+    test_encoding(1.0f64, &[0xcb, 0x3f, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    test_encoding(false, &[0xc2]);
+    test_encoding(true, &[0xc3]);
+    test_encoding((), &[0xc0]);
+}
+
+#[test]
+fn chars_and_strings_are_str_encoded() {
+    test_encoding('a', &[0xa1, 0x61]);
+    test_encoding('€', &[0xa3, 0xe2, 0x82, 0xac]);
+    test_encoding(String::new(), &[0xa0]);
+    test_encoding(String::from("hi"), &[0xa2, b'h', b'i']);
+    test_encoding("x".repeat(31), &[&[0xbf][..], &[b'x'; 31]].concat());
+    test_encoding("x".repeat(32), &[&[0xd9, 0x20][..], &[b'x'; 32]].concat());
+    test_encoding("x".repeat(256), &[&[0xda, 0x01, 0x00][..], &[b'x'; 256]].concat());
+    test_encoding("x".repeat(65_536), &[&[0xdb, 0x00, 0x01, 0x00, 0x00][..], &[b'x'; 65_536]].concat());
+}
+
+#[test]
+fn byte_slices_are_bin_encoded() {
+    assert_eq!(serialize::<[u8]>(&[]), [0xc4, 0x00]);
+    assert_eq!(serialize::<[u8]>(&[1, 2, 3]), [0xc4, 0x03, 0x01, 0x02, 0x03]);
+    assert_eq!(serialize::<[u8]>(&[7; 256]), [&[0xc5, 0x01, 0x00][..], &[7; 256]].concat());
+}
+
+#[test]
+fn sequences_have_array_headers() {
+    test_encoding(Vec::<u8>::new(), &[0x90]);
+    test_encoding(vec![1u8, 200], &[0x92, 0x01, 0xcc, 0xc8]);
+    test_encoding(vec![0u8; 16], &[&[0xdc, 0x00, 0x10][..], &[0x00; 16]].concat());
+    test_encoding(vec![String::from("a")], &[0x91, 0xa1, b'a']);
+}
+
+#[test]
+fn maps_have_map_headers() {
+    test_encoding(BTreeMap::<u8, bool>::new(), &[0x80]);
+    test_encoding(BTreeMap::from([(1u8, true), (2u8, false)]), &[0x82, 0x01, 0xc3, 0x02, 0xc2]);
+
+    let map: BTreeMap<u8, ()> = (0..16).map(|k| (k, ())).collect();
+    let expected: Vec<u8> = [0xde, 0x00, 0x10].into_iter().chain((0..16).flat_map(|k| [k, 0xc0])).collect();
+    test_encoding(map, &expected);
+}
+
+#[test]
+fn options_and_enums_are_index_payload_pairs() {
+    test_encoding(None::<u32>, &[0x92, 0x00, 0xc0]);
+    test_encoding(Some(300u32), &[0x92, 0x01, 0xcd, 0x01, 0x2c]);
+    test_encoding(Some(None::<u8>), &[0x92, 0x01, 0x92, 0x00, 0xc0]);
+    test_encoding(Kind::A, &[0x92, 0x00, 0xc0]);
+    test_encoding(Kind::B(-2), &[0x92, 0x01, 0xfe]);
+    test_encoding(Err::<u8, String>(String::from("e")), &[0x92, 0x01, 0xa1, b'e']);
+    test_encoding(vec![Some(1u8), None], &[0x92, 0x92, 0x01, 0x01, 0x92, 0x00, 0xc0]);
+}
+
+#[test]
+fn structs_tuples_and_arrays_have_array_headers() {
+    test_encoding((1u8, 2u8), &[0x92, 0x01, 0x02]);
+    test_encoding(vec![(1u8, 2u8), (3, 4)], &[0x92, 0x92, 0x01, 0x02, 0x92, 0x03, 0x04]);
+    test_encoding([7u8; 3], &[0x93, 0x07, 0x07, 0x07]);
+    test_encoding([0u8; 16], &[&[0xdc, 0x00, 0x10][..], &[0x00; 16]].concat());
+    test_encoding(((), (true,)), &[0x92, 0xc0, 0x91, 0xc3]);
+}
+
+#[test]
+fn can_serialize_rec() {
+    test_encoding(
+        Rec { id: 300, name: String::from("ab"), tag: Some(2), kind: Kind::B(3) },
+        &[0x94, 0xcd, 0x01, 0x2c, 0xa2, b'a', b'b', 0x92, 0x01, 0x02, 0x92, 0x01, 0x03],
+    );
+    test_encoding(
+        vec![Rec { id: 1, name: String::new(), tag: None, kind: Kind::A }],
+        &[0x91, 0x94, 0x01, 0xa0, 0x92, 0x00, 0xc0, 0x92, 0x00, 0xc0],
+    );
+}
+
+#[test]
+fn accepts_any_encoding_that_fits() {
+    assert_eq!(deserialize::<u16>(&[0xcf, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05]).unwrap(), 5);
+    assert_eq!(deserialize::<i8>(&[0xcc, 0x7f]).unwrap(), 127);
+    assert_eq!(deserialize::<u32>(&[0xd0, 0x05]).unwrap(), 5);
+    assert_eq!(deserialize::<String>(&[0xd9, 0x02, b'h', b'i']).unwrap(), "hi");
+    assert_eq!(deserialize::<Vec<u8>>(&[0xdd, 0x00, 0x00, 0x00, 0x01, 0x05]).unwrap(), vec![5]);
+    assert_eq!(deserialize::<BTreeMap<u8, u8>>(&[0xde, 0x00, 0x01, 0x01, 0x02]).unwrap(), BTreeMap::from([(1, 2)]));
+}
+
+fn assert_invalid_data<T: Debug>(recv: io::Result<T>) {
+    assert_eq!(recv.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn rejects_mismatched_values() {
+    assert_invalid_data(deserialize::<u8>(&[0xcd, 0x01, 0x00]));
+    assert_invalid_data(deserialize::<u8>(&[0xff]));
+    assert_invalid_data(deserialize::<i64>(&[0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]));
+    assert_invalid_data(deserialize::<u32>(&[0xc0]));
+    assert_invalid_data(deserialize::<bool>(&[0x01]));
+    assert_invalid_data(deserialize::<()>(&[0x00]));
+    assert_invalid_data(deserialize::<f64>(&[0xca, 0x3f, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]));
+    assert_invalid_data(deserialize::<String>(&[0xc4, 0x01, b'a']));
+    assert_invalid_data(deserialize::<String>(&[0xa1, 0xff]));
+    assert_invalid_data(deserialize::<char>(&[0xa2, b'a', b'b']));
+    assert_invalid_data(deserialize::<char>(&[0xa0]));
+    assert_invalid_data(deserialize::<Vec<u8>>(&[0x81, 0x01, 0x02]));
+    assert_invalid_data(deserialize::<BTreeMap<u8, u8>>(&[0x91, 0x01]));
+    assert_invalid_data(deserialize::<(u8, u8)>(&[0x93, 0x01, 0x02, 0x03]));
+    assert_invalid_data(deserialize::<(u8, u8)>(&[0x01, 0x02]));
+    assert_invalid_data(deserialize::<[u8; 2]>(&[0x91, 0x01]));
+    assert_invalid_data(deserialize::<Option<u8>>(&[0x93, 0x01, 0x02, 0x03]));
+    assert_invalid_data(deserialize::<Kind>(&[0x01, 0x02]));
+}
+
+#[test]
+fn decode_limits_are_enforced() {
+    let err = deserialize::<String>(&[0xdb, 0xff, 0xff, 0xff, 0xff]).unwrap_err();
+    assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::BytesLen));
+}
+
+#[test]
+fn encoded_len_matches_msgpack_encoding() {
+    assert_eq!(diny::encoded_len(&diny_msgpack::format(), &300u32).unwrap(), 3);
+    assert_eq!(diny::encoded_len(&diny_msgpack::format(), &"x".repeat(40)).unwrap(), 42);
+    assert_eq!(diny::max_encoded_len::<diny_msgpack::Formatter, u64>(), Some(9));
+    assert_eq!(diny::max_encoded_len::<diny_msgpack::Formatter, Option<i16>>(), Some(3 + 3));
+    assert_eq!(diny::max_encoded_len::<diny_msgpack::Formatter, (char, u128)>(), Some(1 + 5 + 18));
+    assert_eq!(diny::encoded_len(&diny_msgpack::format(), &Kind::B(-2)).unwrap(), 3);
+}
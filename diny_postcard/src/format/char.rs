@@ -0,0 +1,112 @@
+use core::task::Context;
+use diny::{backend, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+
+type Error = <ThisFormat as backend::Format>::Error;
+type Data = char;
+
+// The maximum number of bytes required to encode a char as UTF-8
+const MAX_UTF8_LEN: usize = 4;
+
+// A single length byte, followed by the UTF-8 encoding of the char.
+// Lengths of at most four bytes never require a continuation byte.
+const BUF_SIZE: usize = 1 + MAX_UTF8_LEN;
+
+pub struct Encoder(BufferState<BUF_SIZE>);
+
+impl diny::buffer::BufferEncode for Encoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(BUF_SIZE);
+
+    fn init_buffer(data: &Self::Data) -> Self {
+        let mut buf = [0u8; BUF_SIZE];
+        let n = data.encode_utf8(&mut buf[1..]).len();
+        buf[0] = n as u8;
+        Encoder(BufferState::with_partial_contents(buf, 1 + n))
+    }
+
+    fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut enc = Self::init_buffer(data);
+        enc.0.start_write(writer, cx)
+        .lift(enc)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, _format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.write_remaining(writer, cx)
+    }
+}
+
+// Reads the length byte on its own, in order to determine how many
+// bytes belong to the UTF-8 encoding.
+pub struct Decoder(BufferState<BUF_SIZE>);
+
+impl Decoder {
+    fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self.0.read_remaining(reader, cx) {
+                backend::PollDecodeStatus::Fini(()) => {
+                    let bytes = &self.0.buffer()[..self.0.len()];
+                    let len = bytes[0] as usize;
+                    if !(1..=MAX_UTF8_LEN).contains(&len) {
+                        return backend::PollDecodeStatus::Error(<ThisFormat as backend::Format>::invalid_data_err());
+                    } else if bytes.len() == 1 {
+                        self.0.extend_len(len);
+                    } else {
+                        let mut chars = match core::str::from_utf8(&bytes[1..]) {
+                            Ok(s)  => s.chars(),
+                            Err(_) => return backend::PollDecodeStatus::Error(<ThisFormat as backend::Format>::invalid_data_err()),
+                        };
+                        return match (chars.next(), chars.next()) {
+                            (Some(c), None) => backend::PollDecodeStatus::Fini(c),
+                            _               => backend::PollDecodeStatus::Error(<ThisFormat as backend::Format>::invalid_data_err()),
+                        };
+                    }
+                }
+                backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(err) => return backend::PollDecodeStatus::Error(err),
+            }
+        }
+    }
+}
+
+impl backend::Decode for Decoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self(BufferState::with_partial_contents([0u8; BUF_SIZE], 1))
+    }
+
+    fn start_decode<R>(_format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        match dec.read_remaining(reader, cx) {
+            backend::PollDecodeStatus::Fini(d)    => backend::StartDecodeStatus::Fini(d),
+            backend::PollDecodeStatus::Pending    => backend::StartDecodeStatus::Pending(dec),
+            backend::PollDecodeStatus::Error(err) => backend::StartDecodeStatus::Error(err),
+        }
+    }
+
+    fn poll_decode<R>(&mut self, _format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(reader, cx)
+    }
+}
+
+serialize_all_def!    (ThisFormat, Data, Encoder);
+deserialize_exact_def!(ThisFormat, Data, Decoder);
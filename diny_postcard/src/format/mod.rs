@@ -0,0 +1,31 @@
+#[macro_use]
+mod macros;
+
+pub mod unit;
+pub mod bool;
+pub mod char;
+pub mod str;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod string;
+pub mod byte_slice;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod byte_vec;
+
+pub mod i8   { numeric_def!(i8  , 1 ); }
+pub mod i16  { signed_varint_def!(i16 , u16 , 3 ); }
+pub mod i32  { signed_varint_def!(i32 , u32 , 5 ); }
+pub mod i64  { signed_varint_def!(i64 , u64 , 10); }
+pub mod i128 { signed_varint_def!(i128, u128, 19); }
+
+pub mod u8   { numeric_def!(u8  , 1 ); }
+pub mod u16  { unsigned_varint_def!(u16 , 3 ); }
+pub mod u32  { unsigned_varint_def!(u32 , 5 ); }
+pub mod u64  { unsigned_varint_def!(u64 , 10); }
+pub mod u128 { unsigned_varint_def!(u128, 19); }
+
+pub mod f32  { numeric_def!(f32 , 4 ); }
+pub mod f64  { numeric_def!(f64 , 8 ); }
+
+pub mod variant_idx  { usize_wrapper_def!(diny::backend::internal::VariantIdx , u32, format::u32); }
+pub mod option_tag   { usize_wrapper_coders_def!(diny::backend::internal::OptionTag  , u8 , format::u8 ); }
+pub mod sequence_len { usize_wrapper_def!(diny::backend::internal::SequenceLen, u64, format::u64); }
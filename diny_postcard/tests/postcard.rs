@@ -0,0 +1,160 @@
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+//! The expected byte vectors in this module are those produced by
+//! `postcard::to_slice` (postcard 1.x) for the same values.
+
+use core::fmt::Debug;
+use std::collections::BTreeMap;
+use diny::{io, AsyncDeserialize, AsyncSerialization, AsyncSerialize, util::AsyncSliceReader};
+use futures::executor::block_on;
+
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Rec {
+    id: u32,
+    delta: i16,
+    name: String,
+    tag: Option<u8>,
+    kind: Kind,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub enum Kind {
+    A,
+    B(i64),
+}
+
+fn serialize<T: AsyncSerialize>(send: &T) -> Vec<u8> {
+    let mut tx = Vec::new();
+    block_on(send.serialize(&diny_postcard::format(), &mut tx)).expect("unable to serialize");
+    tx
+}
+
+fn deserialize<T: AsyncDeserialize>(bytes: &[u8]) -> io::Result<T> {
+    let mut rx = AsyncSliceReader::from(bytes);
+    let recv = block_on(T::deserialize(&diny_postcard::format(), &mut rx));
+    if recv.is_ok() {
+        assert_eq!(rx.bytes_read(), bytes.len());
+    }
+    recv
+}
+
+fn test_encoding<T>(send: T, expected: &[u8])
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    let bytes = serialize(&send);
+    assert_eq!(bytes, expected);
+    assert_eq!(deserialize::<T>(&bytes).expect("unable to deserialize"), send);
+    diny_test::roundtrip_all_schedules(&diny_postcard::format(), &send);
+}
+
+
+#[test]
+fn bytes_are_fixed_width() {
+    test_encoding(200u8, &[0xc8]);
+    test_encoding(-1i8, &[0xff]);
+    test_encoding(false, &[0x00]);
+    test_encoding(true, &[0x01]);
+}
+
+#[test]
+fn unsigned_ints_are_varints() {
+    test_encoding(0u16, &[0x00]);
+    test_encoding(127u32, &[0x7f]);
+    test_encoding(300u16, &[0xac, 0x02]);
+    test_encoding(u32::MAX, &[0xff, 0xff, 0xff, 0xff, 0x0f]);
+    test_encoding(u64::MAX, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+    test_encoding(u128::MAX, &[&[0xff; 18][..], &[0x03]].concat());
+}
+
+#[test]
+fn signed_ints_are_zigzag_varints() {
+    test_encoding(0i16, &[0x00]);
+    test_encoding(-1i16, &[0x01]);
+    test_encoding(1i32, &[0x02]);
+    test_encoding(-2i64, &[0x03]);
+    test_encoding(i32::MIN, &[0xff, 0xff, 0xff, 0xff, 0x0f]);
+    test_encoding(i128::MIN, &[&[0xff; 18][..], &[0x03]].concat());
+}
+
+#[test]
+fn floats_are_little_endian() {
+    test_encoding(1.5f32, &[0x00, 0x00, 0xc0, 0x3f]);
+    test_encoding(1.0f64, &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f]);
+}
+
+#[test]
+fn chars_are_length_prefixed_utf8() {
+    test_encoding('a', &[0x01, 0x61]);
+    test_encoding('é', &[0x02, 0xc3, 0xa9]);
+    test_encoding('€', &[0x03, 0xe2, 0x82, 0xac]);
+    test_encoding('😀', &[0x04, 0xf0, 0x9f, 0x98, 0x80]);
+}
+
+#[test]
+fn strings_and_sequences_have_varint_lengths() {
+    test_encoding(String::new(), &[0x00]);
+    test_encoding(String::from("hi"), &[0x02, b'h', b'i']);
+    test_encoding("x".repeat(128), &[&[0x80, 0x01][..], &[b'x'; 128]].concat());
+    test_encoding(vec![1u16, 300], &[0x02, 0x01, 0xac, 0x02]);
+    test_encoding(BTreeMap::from([(1u8, true)]), &[0x01, 0x01, 0x01]);
+}
+
+#[test]
+fn arrays_tuples_and_units_have_no_length() {
+    test_encoding([1u8, 2, 3], &[0x01, 0x02, 0x03]);
+    test_encoding((1u8, 300u16), &[0x01, 0xac, 0x02]);
+    test_encoding((), &[]);
+}
+
+#[test]
+fn options_have_a_single_byte_tag() {
+    test_encoding(None::<u16>, &[0x00]);
+    test_encoding(Some(300u16), &[0x01, 0xac, 0x02]);
+}
+
+#[test]
+fn enum_discriminants_are_varints() {
+    test_encoding(Kind::A, &[0x00]);
+    test_encoding(Kind::B(-2), &[0x01, 0x03]);
+    test_encoding(Ok::<u8, u16>(7), &[0x00, 0x07]);
+    test_encoding(Err::<u8, u16>(300), &[0x01, 0xac, 0x02]);
+}
+
+#[test]
+fn can_serialize_rec() {
+    test_encoding(
+        Rec { id: 300, delta: -3, name: String::from("ab"), tag: Some(2), kind: Kind::B(64) },
+        &[0xac, 0x02, 0x05, 0x02, b'a', b'b', 0x01, 0x02, 0x01, 0x80, 0x01],
+    );
+    test_encoding(
+        Rec { id: 0, delta: 0, name: String::new(), tag: None, kind: Kind::A },
+        &[0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+}
+
+#[test]
+fn rejects_invalid_tags() {
+    assert_eq!(deserialize::<bool>(&[0x02]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert!(deserialize::<Option<u8>>(&[0x02, 0x00]).is_err());
+    assert!(deserialize::<Option<u8>>(&[0x81, 0x00]).is_err());
+}
+
+#[test]
+fn rejects_invalid_chars() {
+    assert_eq!(deserialize::<char>(&[0x00]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<char>(&[0x05, b'a', b'b', b'c', b'd', b'e']).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<char>(&[0x02, b'a', b'b']).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<char>(&[0x01, 0xff]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(deserialize::<char>(&[0x03, 0xe2, 0x82]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn encoded_len_matches_postcard_encoding() {
+    assert_eq!(diny::encoded_len(&diny_postcard::format(), &'€').unwrap(), 4);
+    assert_eq!(diny::encoded_len(&diny_postcard::format(), &Kind::B(-1)).unwrap(), 2);
+    assert_eq!(diny::max_encoded_len::<diny_postcard::Formatter, char>(), Some(5));
+    assert_eq!(diny::max_encoded_len::<diny_postcard::Formatter, Option<u32>>(), Some(1 + 5));
+}
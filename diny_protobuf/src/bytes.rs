@@ -0,0 +1,93 @@
+//! Encodes a `Vec<u8>` field as a protobuf `bytes` field.
+//!
+//! A `Vec<u8>` is otherwise encoded as a sequence of `u8`, i.e. as a repeated
+//! `uint32` field.  Use with `#[diny(with = "diny_protobuf::bytes")]`:
+//!
+//! ```ignore
+//! // message Blob { bytes contents = 1; }
+//! #[derive(diny::AsyncSerialization)]
+//! struct Blob {
+//!     #[diny(with = "diny_protobuf::bytes")]
+//!     contents: Vec<u8>,
+//! }
+//! ```
+//!
+//! The coders are generic over the format, and use the format's own byte
+//! slice encoder and byte vector decoder, so the same field may be used with
+//! any of them.
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+use core::task::Context;
+use diny::{backend::{self, Decode, Encode}, io};
+
+/// Encodes a `Vec<u8>` with the format's byte slice encoder
+pub struct Encoder<F: backend::FormatEncode>(F::EncodeByteSlice);
+
+impl<F: backend::FormatEncode> Encode for Encoder<F> {
+    type Data = Vec<u8>;
+    type Format = F;
+
+    const MAX_ENCODED_LEN: Option<usize> = None;
+
+    fn init(data: &Self::Data) -> Self {
+        Self(F::EncodeByteSlice::init(data))
+    }
+
+    fn start_encode<W>(format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, F::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        F::EncodeByteSlice::start_encode(format, writer, data, cx)
+        .map_pending(Self)
+    }
+
+    fn poll_encode<W>(&mut self, format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<F::Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        self.0.poll_encode(format, writer, data, cx)
+    }
+}
+
+/// Decodes a `Vec<u8>` with the format's byte vector decoder
+pub struct Decoder<F: backend::FormatDecode>(F::DecodeByteVec);
+
+impl<F: backend::FormatDecode> Decode for Decoder<F> {
+    type Data = Vec<u8>;
+    type Format = F;
+
+    fn init() -> Self {
+        Self(F::DecodeByteVec::init())
+    }
+
+    fn start_decode<R>(format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, F::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        F::DecodeByteVec::start_decode(format, reader, cx)
+        .bimap(|data| data, Self)
+    }
+
+    fn poll_decode<R>(&mut self, format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, F::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.poll_decode(format, reader, cx)
+    }
+
+    fn start_decode_into<R>(format: &F, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, F::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        F::DecodeByteVec::start_decode_into(format, reader, data, cx)
+        .bimap(|()| (), Self)
+    }
+
+    fn poll_decode_into<R>(&mut self, format: &F, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), F::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.0.poll_decode_into(format, reader, data, cx)
+    }
+}
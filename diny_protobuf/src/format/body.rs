@@ -0,0 +1,139 @@
+use core::{cmp::min, pin::Pin, task::{Context, Poll}};
+use diny::io::{self, AsyncBufRead, AsyncRead};
+use super::wire::KEY_LEN;
+
+/// The part of the input holding the fields of a message.
+///
+/// Fields are decoded by peeking at their keys, which requires a complete
+/// key to be buffered at once, so up to [KEY_LEN] bytes are copied aside
+/// whenever the underlying reader has fewer than that buffered.
+pub struct Window {
+    buf: [u8; KEY_LEN],
+    start: usize,
+    end: usize,
+    // The number of bytes left in the message, including any copied aside,
+    // or `None` when it extends to the end of the input
+    remaining: Option<usize>,
+}
+
+impl Window {
+    pub fn new(remaining: Option<usize>) -> Self {
+        Self {
+            buf: [0u8; KEY_LEN],
+            start: 0,
+            end: 0,
+            remaining,
+        }
+    }
+
+    /// The number of bytes left in a message of known length
+    pub fn remaining(&self) -> usize {
+        self.remaining.unwrap_or(0)
+    }
+
+    fn buffered(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// Reads the fields of a message through its [Window]
+pub struct Body<'a, R> {
+    reader: &'a mut R,
+    window: &'a mut Window,
+}
+
+impl<'a, R> Body<'a, R> {
+    pub fn new(reader: &'a mut R, window: &'a mut Window) -> Self {
+        Self { reader, window }
+    }
+}
+
+impl<R> AsyncBufRead for Body<'_, R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        let window = &mut *this.window;
+        let limit = window.remaining.unwrap_or(usize::MAX);
+        let want = min(KEY_LEN, limit);
+        if limit == 0 {
+            return Poll::Ready(Ok(&[]));
+        }
+
+        if window.buffered() == 0 {
+            let available = match Pin::new(&mut *this.reader).poll_fill_buf(cx) {
+                Poll::Ready(Ok(buf)) => buf.len(),
+                Poll::Ready(Err(e))  => return Poll::Ready(Err(e)),
+                Poll::Pending        => return Poll::Pending,
+            };
+
+            // Nothing needs copying when enough is already buffered, or at the end of the input
+            if available >= want || available == 0 {
+                return match Pin::new(&mut *this.reader).poll_fill_buf(cx) {
+                    Poll::Ready(Ok(buf)) => Poll::Ready(Ok(&buf[..min(buf.len(), limit)])),
+                    other                => other,
+                };
+            }
+
+            window.start = 0;
+            window.end = 0;
+        }
+
+        while window.buffered() < want {
+            if window.start > 0 {
+                window.buf.copy_within(window.start..window.end, 0);
+                window.end -= window.start;
+                window.start = 0;
+            }
+
+            match Pin::new(&mut *this.reader).poll_fill_buf(cx) {
+                Poll::Ready(Ok(buf)) => {
+                    if buf.is_empty() {
+                        break;
+                    }
+                    let n = min(buf.len(), want - window.buffered());
+                    window.buf[window.end..window.end + n].copy_from_slice(&buf[..n]);
+                    window.end += n;
+                    Pin::new(&mut *this.reader).consume(n);
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending       => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(&window.buf[window.start..window.end]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        if this.window.buffered() > 0 {
+            this.window.start += min(amt, this.window.buffered());
+        } else {
+            Pin::new(&mut *this.reader).consume(amt);
+        }
+        if let Some(remaining) = &mut this.window.remaining {
+            *remaining -= min(amt, *remaining);
+        }
+    }
+}
+
+impl<R> AsyncRead for Body<'_, R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, out: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = match Pin::new(&mut *this).poll_fill_buf(cx) {
+            Poll::Ready(Ok(buf)) => {
+                let n = min(buf.len(), out.len());
+                out[..n].copy_from_slice(&buf[..n]);
+                n
+            }
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending       => return Poll::Pending,
+        };
+        Pin::new(this).consume(n);
+        Poll::Ready(Ok(n))
+    }
+}
@@ -0,0 +1,132 @@
+use core::{pin::Pin, task::{Context, Poll}};
+use diny::backend::{self, Encode as _, Format};
+use diny::{buffer::{self, buffer_state::BufferState}, io};
+use crate::Formatter as ThisFormat;
+use super::{field, wire};
+
+type Data = [u8];
+type Error = <ThisFormat as Format>::Error;
+
+const HEADER_LEN: usize = wire::KEY_LEN + wire::LEN_LEN;
+
+// The bytes are preceded by their field's key, if they have one, and always
+// by their length.
+pub enum Encoder
+{
+    Init,
+    Header(BufferState<HEADER_LEN>),
+    Cur(buffer::BufferCursor),
+    Fini,
+}
+
+impl Encoder
+{
+    fn header(format: &ThisFormat, data: &Data) -> BufferState<HEADER_LEN> {
+        let mut buf = [0u8; HEADER_LEN];
+        let mut n = 0;
+        if let Some(tag) = field::tag(format) {
+            n += wire::put_key(tag, wire::LEN, &mut buf);
+        }
+        n += wire::put_varint(data.len() as u128, &mut buf[n..]);
+        BufferState::with_partial_contents(buf, n)
+    }
+
+    fn after_init<W>(format: &ThisFormat, writer: &mut W, data: &Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut header = Self::header(format, data);
+        match header.start_write(writer, cx) {
+            backend::PollEncodeStatus::Fini     => Self::after_header(writer, data, cx),
+            backend::PollEncodeStatus::Pending  => backend::StartEncodeStatus::Pending(Self::Header(header)),
+            backend::PollEncodeStatus::Error(e) => backend::StartEncodeStatus::Error(e),
+        }
+    }
+
+    fn after_header<W>(writer: &mut W, data: &Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        if !data.is_empty() {
+            let mut cur = buffer::BufferCursor::new(data);
+            match cur.write_remaining(writer, data, cx) {
+                backend::PollEncodeStatus::Fini     => backend::StartEncodeStatus::Fini,
+                backend::PollEncodeStatus::Pending  => backend::StartEncodeStatus::Pending(Self::Cur(cur)),
+                backend::PollEncodeStatus::Error(e) => backend::StartEncodeStatus::Error(e),
+            }
+        } else {
+            backend::StartEncodeStatus::Fini
+        }
+    }
+}
+
+impl backend::Encode for Encoder
+{
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init(_data: &Self::Data) -> Self {
+        Self::Init
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        Self::after_init(format, writer, data, cx)
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Init           => diny::encode_chain!(*self, Self::start_encode(format, writer, data, cx)),
+            Self::Header(header) => diny::encode_poll_chain!(*self, header.write_remaining(writer, cx), Self::after_header(writer, data, cx)),
+            Self::Cur(cur)       => diny::encode_poll_fini!(*self, cur.write_remaining(writer, data, cx)),
+            Self::Fini           => backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+}
+
+pub struct SerializeAll<'w, W> {
+    format: &'w ThisFormat,
+    writer: &'w mut W,
+    data: &'w Data,
+    encoder: Encoder,
+}
+
+pub(crate) fn serialize<'w, W>(format: &'w ThisFormat, writer: &'w mut W, data: &'w Data) -> SerializeAll<'w, W>
+where
+    W: ::diny::io::AsyncWrite + Unpin,
+{
+    SerializeAll::new(format, writer, data)
+}
+
+impl<'w, W> SerializeAll<'w, W> {
+    fn new(format: &'w ThisFormat, writer: &'w mut W, data: &'w Data) -> SerializeAll<'w, W>
+    where
+        W: ::diny::io::AsyncWrite + Unpin,
+    {
+        SerializeAll {
+            format,
+            writer,
+            data,
+            encoder: Encoder::init(data),
+        }
+    }
+}
+
+impl<'w, W> Unpin for SerializeAll<'w, W> {}
+
+impl<'w, W> core::future::Future for SerializeAll<'w, W>
+where
+    W: ::diny::io::AsyncWrite + Unpin,
+{
+    type Output = Result<(), Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        this.encoder.poll_encode(this.format, this.writer, this.data, cx).into()
+    }
+}
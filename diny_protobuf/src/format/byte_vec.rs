@@ -0,0 +1,162 @@
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+use core::task::Context;
+use diny::backend::{self, Format, FormatDecode as _};
+use diny::buffer;
+use diny::io;
+use crate::Formatter as ThisFormat;
+use super::wire::{self, Value};
+
+type Data = Vec<u8>;
+type ByteEncoder = <ThisFormat as backend::FormatEncode>::EncodeByteSlice;
+type Error = <ThisFormat as Format>::Error;
+
+pub struct Encoder(ByteEncoder);
+
+impl backend::Encode for Encoder
+{
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init(data: &Self::Data) -> Self {
+        Self(<ByteEncoder as backend::Encode>::init(data))
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        <ByteEncoder as backend::Encode>::start_encode(format, writer, data, cx)
+        .map_pending(Encoder)
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        backend::Encode::poll_encode(&mut self.0, format, writer, data, cx)
+    }
+}
+
+enum DecodeCursor {
+    Key,
+    Len(Value),
+    Cur(buffer::BufferCursor),
+    Fini,
+}
+
+// Bytes that are absent from a message decode as empty
+pub struct Decoder
+{
+    data: Data,
+    cursor: DecodeCursor,
+}
+
+impl Decoder
+{
+    // Whether there are any bytes to read after the length
+    fn after_len(&mut self, format: &ThisFormat, len: usize) -> Result<bool, Error> {
+        if let Err(kind) = format.decode_limits().check_bytes_len(len) {
+            return Err(ThisFormat::limit_exceeded_err(kind));
+        }
+        if len > 0 {
+            self.data.reserve_exact(diny::backend::limits::prealloc_len::<u8>(len));
+            self.cursor = DecodeCursor::Cur(buffer::BufferCursor::with_len(len));
+        }
+        Ok(len > 0)
+    }
+
+    fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match &mut self.cursor {
+                DecodeCursor::Key => match wire::expect_field(format, reader, wire::LEN, cx) {
+                    backend::PollDecodeStatus::Fini(true)  => self.cursor = DecodeCursor::Len(Value::varint(wire::LEN_LEN)),
+                    backend::PollDecodeStatus::Fini(false) => {
+                        self.cursor = DecodeCursor::Fini;
+                        return backend::PollDecodeStatus::Fini(());
+                    }
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => {
+                        self.cursor = DecodeCursor::Fini;
+                        return backend::PollDecodeStatus::Error(e);
+                    }
+                },
+                DecodeCursor::Len(len) => match len.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(()) => {
+                        let len = len.as_len();
+                        match len.and_then(|n| self.after_len(format, n)) {
+                            Ok(true)  => {}
+                            Ok(false) => {
+                                self.cursor = DecodeCursor::Fini;
+                                return backend::PollDecodeStatus::Fini(());
+                            }
+                            Err(e) => {
+                                self.cursor = DecodeCursor::Fini;
+                                return backend::PollDecodeStatus::Error(e);
+                            }
+                        }
+                    }
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => {
+                        self.cursor = DecodeCursor::Fini;
+                        return backend::PollDecodeStatus::Error(e);
+                    }
+                },
+                DecodeCursor::Cur(cur) => {
+                    let status = cur.fill_vec(reader, &mut self.data, cx);
+                    if !matches!(status, backend::PollDecodeStatus::Pending) {
+                        self.cursor = DecodeCursor::Fini;
+                    }
+                    return status;
+                }
+                DecodeCursor::Fini => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+}
+
+impl backend::Decode for Decoder
+{
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self { data: Data::new(), cursor: DecodeCursor::Key }
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        match dec.read_remaining(format, reader, cx) {
+            backend::PollDecodeStatus::Fini(()) => backend::StartDecodeStatus::Fini(dec.data),
+            backend::PollDecodeStatus::Pending  => backend::StartDecodeStatus::Pending(dec),
+            backend::PollDecodeStatus::Error(e) => backend::StartDecodeStatus::Error(e),
+        }
+    }
+
+    // The bytes are read into the emptied storage of the existing vector
+    fn start_decode_into<R>(format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        dec.data = core::mem::take(data);
+        dec.data.clear();
+        dec.read_remaining(format, reader, cx)
+        .map(|()| *data = core::mem::take(&mut dec.data))
+        .lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(format, reader, cx)
+        .map(|()| core::mem::take(&mut self.data))
+    }
+}
@@ -0,0 +1,50 @@
+use core::task::Context;
+use diny::{backend::{self, Format}, io};
+use crate::Formatter as ThisFormat;
+use super::{field, wire};
+
+type Error = <ThisFormat as Format>::Error;
+
+// The items of a repeated field continue for as long as the field's key is
+// next, or a run of packed items has bytes left.  Sequences of indefinite
+// length are only decoded within a field.
+pub struct Decoder;
+
+impl Decoder {
+    fn read_remaining<R>(format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<bool, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        if field::tag(format).is_none() {
+            backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err())
+        } else if field::packed(format) > 0 {
+            backend::PollDecodeStatus::Fini(true)
+        } else {
+            wire::peek_field(format, reader, cx)
+        }
+    }
+}
+
+impl backend::Decode for Decoder {
+    type Data = bool;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        Self::read_remaining(format, reader, cx)
+        .lift(Self)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        Self::read_remaining(format, reader, cx)
+    }
+}
@@ -0,0 +1,80 @@
+//! The field whose value is currently being encoded or decoded.
+//!
+//! A field's coder owns its tag and the state of any run of packed scalars,
+//! and polls the value's coder with a copy of the formatter in which that
+//! field is current, taking the run's state back once the poll returns.  The
+//! scalar, bytes and message coders consult the formatter they are given to
+//! decide whether to write or expect a key, and a message polls its own
+//! fields with a formatter in which no field is current.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::Formatter;
+
+/// The tag of a field, and the number of bytes remaining in a run of packed
+/// scalars read from it
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    tag: u32,
+    packed: usize,
+}
+
+impl Field {
+    pub fn new(tag: u32) -> Self {
+        Self { tag, packed: 0 }
+    }
+}
+
+/// The field current within a formatter, if any.  Only the scalar decoder
+/// updates the run of packed scalars, through the shared formatter it is
+/// polled with.
+#[derive(Debug, Default)]
+pub struct Current {
+    tag: Option<u32>,
+    packed: AtomicUsize,
+}
+
+impl Current {
+    fn new(tag: Option<u32>, packed: usize) -> Self {
+        Self { tag, packed: AtomicUsize::new(packed) }
+    }
+}
+
+impl Clone for Current {
+    fn clone(&self) -> Self {
+        Self::new(self.tag, self.packed.load(Ordering::Relaxed))
+    }
+}
+
+fn with_current(format: &Formatter, field: Current) -> Formatter {
+    Formatter { limits: format.limits.clone(), field }
+}
+
+/// Run `f` with `field` current, and update `field` with the state it was left in
+pub fn within<T>(format: &Formatter, field: &mut Field, f: impl FnOnce(&Formatter) -> T) -> T {
+    let inner = with_current(format, Current::new(Some(field.tag), field.packed));
+    let result = f(&inner);
+    field.packed = inner.field.packed.load(Ordering::Relaxed);
+    result
+}
+
+/// Run `f` with no field current, as when coding the fields of a message
+pub fn outside<T>(format: &Formatter, f: impl FnOnce(&Formatter) -> T) -> T {
+    f(&with_current(format, Current::default()))
+}
+
+/// The tag of the current field, if there is one
+pub fn tag(format: &Formatter) -> Option<u32> {
+    format.field.tag
+}
+
+/// The number of bytes remaining in the current field's run of packed scalars
+pub fn packed(format: &Formatter) -> usize {
+    format.field.packed.load(Ordering::Relaxed)
+}
+
+/// Set the number of bytes remaining in the current field's run of packed scalars
+pub fn set_packed(format: &Formatter, packed: usize) {
+    if format.field.tag.is_some() {
+        format.field.packed.store(packed, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,102 @@
+macro_rules! scalar_def {
+    ($t: ty) => {
+        use crate::Formatter as ThisFormat;
+
+        type Data = $t;
+
+        pub type Encoder = super::scalar::Encoder<Data>;
+        pub type Decoder = super::scalar::Decoder<Data>;
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+// Within a field, the value is implied by whether the field is present, and
+// nothing is written.  Elsewhere, it is written as a varint.
+macro_rules! implicit_def {
+    ($t: ty, $present: expr) => {
+        implicit_coders_def!($t, $present);
+
+        serialize_all_def!    (ThisFormat, Data, Encoder);
+        deserialize_exact_def!(ThisFormat, Data, Decoder);
+    };
+}
+
+macro_rules! implicit_coders_def {
+    ($t: ty, $present: expr) => {
+        use core::task::Context;
+        use diny::{backend::{self, Format}, buffer, io};
+        use crate::Formatter as ThisFormat;
+        use super::{field, scalar, wire};
+
+        type Error = <ThisFormat as Format>::Error;
+        type Data = $t;
+
+        pub struct Encoder(scalar::Encoder<Data>);
+
+        impl buffer::BufferEncode for Encoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = <scalar::Encoder<Data> as buffer::BufferEncode>::MAX_ENCODED_LEN;
+
+            fn init_buffer(data: &Self::Data) -> Self {
+                Self(scalar::Encoder::init_buffer(data))
+            }
+
+            fn start_encode_buffer<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                if field::tag(format).is_some() {
+                    return backend::StartEncodeStatus::Fini;
+                }
+                scalar::Encoder::start_encode_buffer(format, writer, data, cx)
+                .map_pending(Self)
+            }
+
+            fn poll_encode_buffer<W>(&mut self, format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                if field::tag(format).is_some() {
+                    return backend::PollEncodeStatus::Fini;
+                }
+                self.0.poll_encode_buffer(format, writer, cx)
+            }
+        }
+
+        pub struct Decoder(scalar::Decoder<Data>);
+
+        impl backend::Decode for Decoder {
+            type Data = Data;
+            type Format = ThisFormat;
+
+            fn init() -> Self {
+                Self(scalar::Decoder::init())
+            }
+
+            fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                let mut dec = Self::init();
+                let status = dec.poll_decode(format, reader, cx);
+                status.lift(dec)
+            }
+
+            fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+            where
+                R: io::AsyncBufRead + Unpin,
+            {
+                if field::tag(format).is_some() {
+                    wire::peek_field(format, reader, cx)
+                    .map(|present| Data::new(if present { $present } else { 0 }))
+                } else {
+                    self.0.poll_decode(format, reader, cx)
+                }
+            }
+        }
+    };
+}
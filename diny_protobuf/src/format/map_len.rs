@@ -0,0 +1,79 @@
+use core::task::Context;
+use diny::{backend::{self, Format, internal::MapLen}, buffer, io};
+use crate::Formatter as ThisFormat;
+use super::{field, scalar, wire};
+
+type Error = <ThisFormat as Format>::Error;
+type Data = MapLen;
+
+// A map held by a message would be a repeated field of entry messages, each
+// of which must be preceded by its length, which the entries of a map cannot
+// provide.  So within a field, a map fails to encode, and only an absent one decodes.
+pub struct Encoder(scalar::Encoder<Data>);
+
+impl buffer::BufferEncode for Encoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = <scalar::Encoder<Data> as buffer::BufferEncode>::MAX_ENCODED_LEN;
+
+    fn init_buffer(data: &Self::Data) -> Self {
+        Self(scalar::Encoder::init_buffer(data))
+    }
+
+    fn start_encode_buffer<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        if field::tag(format).is_some() {
+            return backend::StartEncodeStatus::Error(ThisFormat::invalid_input_err());
+        }
+        scalar::Encoder::start_encode_buffer(format, writer, data, cx)
+        .map_pending(Self)
+    }
+
+    fn poll_encode_buffer<W>(&mut self, format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        if field::tag(format).is_some() {
+            return backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err());
+        }
+        self.0.poll_encode_buffer(format, writer, cx)
+    }
+}
+
+pub struct Decoder(scalar::Decoder<Data>);
+
+impl backend::Decode for Decoder {
+    type Data = Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self(scalar::Decoder::init())
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        let status = dec.poll_decode(format, reader, cx);
+        status.lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        if field::tag(format).is_some() {
+            wire::peek_field(format, reader, cx)
+            .and_then(|present| match present {
+                false => backend::PollDecodeStatus::Fini(MapLen::new(0)),
+                true  => backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+            })
+        } else {
+            self.0.poll_decode(format, reader, cx)
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use core::{marker::PhantomData, task::Context};
+use diny::backend::{self, Format, internal::StructDef, limits, size};
+use diny::{buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::{body::{Body, Window}, field, skip::Skip, wire::{self, Value}};
+
+type Error = <ThisFormat as Format>::Error;
+
+const HEADER_LEN: usize = wire::KEY_LEN + wire::LEN_LEN;
+
+// A message held by a field is preceded by the field's key and the length of
+// the message, which is found by encoding it once beforehand into a counting
+// writer.  A message at the top level is neither, and instead extends to the
+// end of the input.  The message's own fields are encoded with no field current.
+pub enum Encoder<S, E> {
+    Init,
+    Header(BufferState<HEADER_LEN>),
+    Body(E),
+    Fini,
+    #[doc(hidden)]
+    Phantom(PhantomData<fn() -> S>),
+}
+
+impl<S, E> Encoder<S, E>
+where
+    S: StructDef,
+    E: backend::Encode<Format=ThisFormat>,
+{
+    fn after_init<W>(format: &ThisFormat, writer: &mut W, data: &E::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let tag = match field::tag(format) {
+            Some(tag) => tag,
+            None      => return Self::after_header(format, writer, data, cx),
+        };
+
+        let len = match field::outside(format, |format| size::encoder_len::<E>(format, data)) {
+            Ok(len) => len,
+            Err(e)  => return backend::StartEncodeStatus::Error(e),
+        };
+        let mut buf = [0u8; HEADER_LEN];
+        let mut n = wire::put_key(tag, wire::LEN, &mut buf);
+        n += wire::put_varint(len as u128, &mut buf[n..]);
+        let mut header = BufferState::with_partial_contents(buf, n);
+        match header.start_write(writer, cx) {
+            backend::PollEncodeStatus::Fini     => Self::after_header(format, writer, data, cx),
+            backend::PollEncodeStatus::Pending  => backend::StartEncodeStatus::Pending(Self::Header(header)),
+            backend::PollEncodeStatus::Error(e) => backend::StartEncodeStatus::Error(e),
+        }
+    }
+
+    fn after_header<W>(format: &ThisFormat, writer: &mut W, data: &E::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match field::outside(format, |format| E::start_encode(format, writer, data, cx)) {
+            backend::StartEncodeStatus::Fini         => backend::StartEncodeStatus::Fini,
+            backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Body(enc)),
+            backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e),
+        }
+    }
+
+    fn chain(&mut self, status: backend::StartEncodeStatus<Self, Error>) -> backend::PollEncodeStatus<Error> {
+        match status {
+            backend::StartEncodeStatus::Fini         => { *self = Self::Fini; backend::PollEncodeStatus::Fini }
+            backend::StartEncodeStatus::Pending(enc) => { *self = enc; backend::PollEncodeStatus::Pending }
+            backend::StartEncodeStatus::Error(e)     => { *self = Self::Fini; backend::PollEncodeStatus::Error(e) }
+        }
+    }
+}
+
+impl<S, E> backend::Encode for Encoder<S, E>
+where
+    S: StructDef,
+    E: backend::Encode<Format=ThisFormat>,
+{
+    type Data = E::Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = size::sum(Some(HEADER_LEN), E::MAX_ENCODED_LEN);
+
+    fn init(_data: &Self::Data) -> Self {
+        Self::Init
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        Self::after_init(format, writer, data, cx)
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match core::mem::replace(self, Self::Fini) {
+            Self::Init => {
+                let status = Self::after_init(format, writer, data, cx);
+                self.chain(status)
+            }
+            Self::Header(mut header) => match header.write_remaining(writer, cx) {
+                backend::PollEncodeStatus::Fini     => {
+                    let status = Self::after_header(format, writer, data, cx);
+                    self.chain(status)
+                }
+                backend::PollEncodeStatus::Pending  => { *self = Self::Header(header); backend::PollEncodeStatus::Pending }
+                backend::PollEncodeStatus::Error(e) => backend::PollEncodeStatus::Error(e),
+            },
+            Self::Body(mut enc) => match field::outside(format, |format| enc.poll_encode(format, writer, data, cx)) {
+                backend::PollEncodeStatus::Fini     => backend::PollEncodeStatus::Fini,
+                backend::PollEncodeStatus::Pending  => { *self = Self::Body(enc); backend::PollEncodeStatus::Pending }
+                backend::PollEncodeStatus::Error(e) => backend::PollEncodeStatus::Error(e),
+            },
+            Self::Fini | Self::Phantom(_) => backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+}
+
+enum DecodeState<D: backend::Decode> {
+    Init,
+    Len(Value),
+    Body(Window, D),
+    Rest(Window, D::Data, Skip),
+    Fini,
+}
+
+// The fields of a message are read through a window onto the input that ends
+// with the message, so that a message decodes the same whether it is read
+// from a field or from the whole of the input.  Fields left over once the
+// struct has been decoded are skipped, so that the input ends up positioned
+// just after the message, unless they belong to the struct, in which case
+// they were either out of order or repeated, and the message is invalid.
+pub struct Decoder<S, D: backend::Decode> {
+    state: DecodeState<D>,
+    def: PhantomData<fn() -> S>,
+}
+
+impl<S, D> Decoder<S, D>
+where
+    S: StructDef,
+    D: backend::Decode<Format=ThisFormat>,
+{
+    fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<D::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match core::mem::replace(&mut self.state, DecodeState::Fini) {
+                DecodeState::Init => {
+                    if field::tag(format).is_none() {
+                        if let Err(e) = self.start_body(format, Window::new(None), reader, cx) {
+                            return backend::PollDecodeStatus::Error(e);
+                        }
+                        continue;
+                    }
+                    let window = match wire::expect_field(format, reader, wire::LEN, cx) {
+                        backend::PollDecodeStatus::Fini(true)  => { self.state = DecodeState::Len(Value::varint(wire::LEN_LEN)); continue }
+                        // An absent message decodes as if all of its fields were absent
+                        backend::PollDecodeStatus::Fini(false) => Window::new(Some(0)),
+                        backend::PollDecodeStatus::Pending     => { self.state = DecodeState::Init; return backend::PollDecodeStatus::Pending }
+                        backend::PollDecodeStatus::Error(e)    => return backend::PollDecodeStatus::Error(e),
+                    };
+                    if let Err(e) = self.start_body(format, window, reader, cx) {
+                        return backend::PollDecodeStatus::Error(e);
+                    }
+                }
+                DecodeState::Len(mut len) => match len.read_remaining(reader, cx).and_then(|()| len.as_len().into()) {
+                    backend::PollDecodeStatus::Fini(n) => {
+                        if let Err(e) = self.start_body(format, Window::new(Some(n)), reader, cx) {
+                            return backend::PollDecodeStatus::Error(e);
+                        }
+                    }
+                    backend::PollDecodeStatus::Pending  => { self.state = DecodeState::Len(len); return backend::PollDecodeStatus::Pending }
+                    backend::PollDecodeStatus::Error(e) => return backend::PollDecodeStatus::Error(e),
+                },
+                DecodeState::Body(mut window, mut dec) => {
+                    let status = field::outside(format, |format| dec.poll_decode(format, &mut Body::new(reader, &mut window), cx));
+                    match status {
+                        backend::PollDecodeStatus::Fini(data) => self.state = DecodeState::Rest(window, data, Skip::Key),
+                        backend::PollDecodeStatus::Pending    => { self.state = DecodeState::Body(window, dec); return backend::PollDecodeStatus::Pending }
+                        backend::PollDecodeStatus::Error(e)   => return backend::PollDecodeStatus::Error(e),
+                    }
+                }
+                DecodeState::Rest(mut window, data, mut skip) => {
+                    let status = skip.read_remaining(&mut Body::new(reader, &mut window), Self::known, cx);
+                    match status {
+                        backend::PollDecodeStatus::Fini(Some(_)) => return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+                        // The input ended before the message did
+                        backend::PollDecodeStatus::Fini(None) if window.remaining() > 0 => return backend::PollDecodeStatus::Error(io::error::unexpected_eof()),
+                        backend::PollDecodeStatus::Fini(None) => return backend::PollDecodeStatus::Fini(data),
+                        backend::PollDecodeStatus::Pending  => { self.state = DecodeState::Rest(window, data, skip); return backend::PollDecodeStatus::Pending }
+                        backend::PollDecodeStatus::Error(e) => return backend::PollDecodeStatus::Error(e),
+                    }
+                }
+                DecodeState::Fini => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+
+    fn known(tag: u32) -> bool {
+        S::FIELD_TAGS.is_some_and(|tags| tags.binary_search(&tag).is_ok())
+    }
+
+    // The fields are decoded with no field current
+    fn start_body<R>(&mut self, format: &ThisFormat, mut window: Window, reader: &mut R, cx: &mut Context<'_>) -> Result<(), Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.state = match field::outside(format, |format| D::start_decode(format, &mut Body::new(reader, &mut window), cx)) {
+            backend::StartDecodeStatus::Fini(data)   => DecodeState::Rest(window, data, Skip::Key),
+            backend::StartDecodeStatus::Pending(dec) => DecodeState::Body(window, dec),
+            backend::StartDecodeStatus::Error(e)     => return Err(e),
+        };
+        Ok(())
+    }
+}
+
+impl<S, D> backend::Decode for Decoder<S, D>
+where
+    S: StructDef,
+    D: backend::Decode<Format=ThisFormat>,
+{
+    type Data = D::Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self { state: DecodeState::Init, def: PhantomData }
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        limits::start_nested(format, || {
+            let mut dec = Self::init();
+            let status = dec.read_remaining(format, reader, cx);
+            status.lift(dec)
+        })
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        limits::poll_nested(format, || self.read_remaining(format, reader, cx))
+    }
+}
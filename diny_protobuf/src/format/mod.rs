@@ -0,0 +1,43 @@
+#[macro_use]
+mod macros;
+
+pub mod wire;
+pub mod body;
+pub mod skip;
+pub mod field;
+pub mod scalar;
+
+pub mod unit;
+pub mod str;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod string;
+pub mod byte_slice;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod byte_vec;
+
+pub mod bool { scalar_def!(bool); }
+pub mod char { scalar_def!(char); }
+
+pub mod i8   { scalar_def!(i8  ); }
+pub mod i16  { scalar_def!(i16 ); }
+pub mod i32  { scalar_def!(i32 ); }
+pub mod i64  { scalar_def!(i64 ); }
+pub mod i128 { scalar_def!(i128); }
+
+pub mod u8   { scalar_def!(u8  ); }
+pub mod u16  { scalar_def!(u16 ); }
+pub mod u32  { scalar_def!(u32 ); }
+pub mod u64  { scalar_def!(u64 ); }
+pub mod u128 { scalar_def!(u128); }
+
+pub mod f32  { scalar_def!(f32 ); }
+pub mod f64  { scalar_def!(f64 ); }
+
+pub mod variant_idx  { scalar_def!(diny::backend::internal::VariantIdx); }
+pub mod option_tag   { implicit_coders_def!(diny::backend::internal::OptionTag  , 1); }
+pub mod sequence_len { implicit_def!(diny::backend::internal::SequenceLen, usize::MAX); }
+pub mod map_len;
+pub mod collection_next;
+
+pub mod struct_field;
+pub mod message;
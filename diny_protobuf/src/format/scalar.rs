@@ -0,0 +1,342 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend::{self, Format, internal::{MapLen, OptionTag, SequenceLen, VariantIdx}}, buffer::{self, buffer_state::BufferState}, io};
+use crate::Formatter as ThisFormat;
+use super::{field, wire::{self, Value}};
+
+type Error = <ThisFormat as Format>::Error;
+
+/// A value encoded as a single varint or fixed width value, preceded by the
+/// key of its field when it has one.
+pub trait Scalar: Copy {
+    /// The wire type of the encoded value
+    const WIRE_TYPE: u8;
+
+    /// The maximum number of bytes in the encoded value
+    const MAX_LEN: usize;
+
+    /// The value of a field that is absent
+    const DEFAULT: Self;
+
+    /// Write the value to the start of `buf`, returning the number of bytes
+    /// written, or `None` if it cannot be represented
+    fn put(self, buf: &mut [u8]) -> Option<usize>;
+
+    /// Read the value from exactly `bytes`, or `None` if they are invalid
+    fn get(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! unsigned_scalar {
+    ($t: ty, $len: literal) => {
+        impl Scalar for $t {
+            const WIRE_TYPE: u8 = wire::VARINT;
+            const MAX_LEN: usize = $len;
+            const DEFAULT: Self = 0;
+
+            fn put(self, buf: &mut [u8]) -> Option<usize> {
+                Some(wire::put_varint(self as u128, buf))
+            }
+
+            fn get(bytes: &[u8]) -> Option<Self> {
+                wire::get_varint(bytes).and_then(|v| Self::try_from(v).ok())
+            }
+        }
+    };
+}
+
+// As with protobuf's int32 and int64, a signed value is sign extended to 64
+// bits and written as the unsigned varint of its two's complement, so that
+// negative values always take ten bytes.
+macro_rules! signed_scalar {
+    ($t: ty, $wide: ty, $uwide: ty, $len: literal) => {
+        impl Scalar for $t {
+            const WIRE_TYPE: u8 = wire::VARINT;
+            const MAX_LEN: usize = $len;
+            const DEFAULT: Self = 0;
+
+            fn put(self, buf: &mut [u8]) -> Option<usize> {
+                Some(wire::put_varint(self as $wide as $uwide as u128, buf))
+            }
+
+            fn get(bytes: &[u8]) -> Option<Self> {
+                wire::get_varint(bytes)
+                .and_then(|v| <$uwide>::try_from(v).ok())
+                .and_then(|v| Self::try_from(v as $wide).ok())
+            }
+        }
+    };
+}
+
+macro_rules! float_scalar {
+    ($t: ty, $wire_type: path, $len: literal) => {
+        impl Scalar for $t {
+            const WIRE_TYPE: u8 = $wire_type;
+            const MAX_LEN: usize = $len;
+            const DEFAULT: Self = 0.0;
+
+            fn put(self, buf: &mut [u8]) -> Option<usize> {
+                buf[..$len].copy_from_slice(&self.to_le_bytes());
+                Some($len)
+            }
+
+            fn get(bytes: &[u8]) -> Option<Self> {
+                bytes.try_into().ok().map(Self::from_le_bytes)
+            }
+        }
+    };
+}
+
+macro_rules! usize_scalar {
+    ($t: ty, $repr: ty, $len: literal) => {
+        impl Scalar for $t {
+            const WIRE_TYPE: u8 = wire::VARINT;
+            const MAX_LEN: usize = $len;
+            const DEFAULT: Self = Self::new(0);
+
+            fn put(self, buf: &mut [u8]) -> Option<usize> {
+                <$repr>::try_from(*self).ok()
+                .and_then(|v| v.put(buf))
+            }
+
+            fn get(bytes: &[u8]) -> Option<Self> {
+                <$repr>::get(bytes)
+                .and_then(|v| usize::try_from(v).ok())
+                .map(Self::new)
+            }
+        }
+    };
+}
+
+unsigned_scalar!(u8  , 2 );
+unsigned_scalar!(u16 , 3 );
+unsigned_scalar!(u32 , 5 );
+unsigned_scalar!(u64 , 10);
+unsigned_scalar!(u128, 19);
+
+signed_scalar!(i8  , i64 , u64 , 10);
+signed_scalar!(i16 , i64 , u64 , 10);
+signed_scalar!(i32 , i64 , u64 , 10);
+signed_scalar!(i64 , i64 , u64 , 10);
+signed_scalar!(i128, i128, u128, 19);
+
+float_scalar!(f32, wire::FIXED32, 4);
+float_scalar!(f64, wire::FIXED64, 8);
+
+usize_scalar!(VariantIdx , u32, 5 );
+usize_scalar!(OptionTag  , u8 , 2 );
+usize_scalar!(SequenceLen, u64, 10);
+usize_scalar!(MapLen     , u64, 10);
+
+// Any non-zero varint is true, as with protobuf's own decoders
+impl Scalar for bool {
+    const WIRE_TYPE: u8 = wire::VARINT;
+    const MAX_LEN: usize = 10;
+    const DEFAULT: Self = false;
+
+    fn put(self, buf: &mut [u8]) -> Option<usize> {
+        Some(wire::put_varint(self as u128, buf))
+    }
+
+    fn get(bytes: &[u8]) -> Option<Self> {
+        wire::get_varint(bytes).map(|v| v != 0)
+    }
+}
+
+impl Scalar for char {
+    const WIRE_TYPE: u8 = wire::VARINT;
+    const MAX_LEN: usize = 5;
+    const DEFAULT: Self = '\0';
+
+    fn put(self, buf: &mut [u8]) -> Option<usize> {
+        (self as u32).put(buf)
+    }
+
+    fn get(bytes: &[u8]) -> Option<Self> {
+        u32::get(bytes).and_then(char::from_u32)
+    }
+}
+
+const BUF_LEN: usize = wire::KEY_LEN + wire::VALUE_LEN;
+
+pub enum Encoder<T> {
+    Init(T),
+    Buffer(BufferState<BUF_LEN>),
+}
+
+impl<T: Scalar> Encoder<T> {
+    fn buffer(format: &ThisFormat, data: T) -> Result<BufferState<BUF_LEN>, Error> {
+        let mut buf = [0u8; BUF_LEN];
+        let mut n = 0;
+        if let Some(tag) = field::tag(format) {
+            n += wire::put_key(tag, T::WIRE_TYPE, &mut buf);
+        }
+        n += data.put(&mut buf[n..]).ok_or_else(ThisFormat::invalid_data_err)?;
+        Ok(BufferState::with_partial_contents(buf, n))
+    }
+}
+
+impl<T: Scalar> buffer::BufferEncode for Encoder<T> {
+    type Data = T;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = Some(wire::KEY_LEN + T::MAX_LEN);
+
+    fn init_buffer(data: &Self::Data) -> Self {
+        Self::Init(*data)
+    }
+
+    fn start_encode_buffer<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match Self::buffer(format, *data) {
+            Ok(mut state) => {
+                let status = state.start_write(writer, cx);
+                status.lift(Self::Buffer(state))
+            }
+            Err(e) => backend::StartEncodeStatus::Error(e),
+        }
+    }
+
+    fn poll_encode_buffer<W>(&mut self, format: &Self::Format, writer: &mut W, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Init(data) => match Self::buffer(format, *data) {
+                Ok(mut state) => {
+                    let status = state.start_write(writer, cx);
+                    *self = Self::Buffer(state);
+                    status
+                }
+                Err(e) => backend::PollEncodeStatus::Error(e),
+            },
+            Self::Buffer(state) => state.write_remaining(writer, cx),
+        }
+    }
+}
+
+enum DecodeState {
+    Key,
+    Packed(Value),
+    Value(Value),
+    Fini,
+}
+
+// Within a field, a scalar is either preceded by its own key, or is one of a
+// run of packed scalars that share a single key and length.  Once a run has
+// begun, the number of its bytes yet to be read is tracked by the field.
+pub struct Decoder<T> {
+    state: DecodeState,
+    data: PhantomData<fn() -> T>,
+}
+
+impl<T: Scalar> Decoder<T> {
+    fn value() -> DecodeState {
+        DecodeState::Value(Value::of(T::WIRE_TYPE, T::MAX_LEN))
+    }
+
+    fn read_remaining<R>(&mut self, format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<T, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match &mut self.state {
+                DecodeState::Key => {
+                    if field::tag(format).is_none() || field::packed(format) > 0 {
+                        self.state = Self::value();
+                        continue;
+                    }
+
+                    match wire::peek_key(reader, cx) {
+                        backend::PollDecodeStatus::Fini(Some(key)) if Some(key.tag) == field::tag(format) => {
+                            if key.wire_type == T::WIRE_TYPE {
+                                self.state = Self::value();
+                            } else if key.wire_type == wire::LEN {
+                                self.state = DecodeState::Packed(Value::varint(wire::LEN_LEN));
+                            } else {
+                                self.state = DecodeState::Fini;
+                                return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err());
+                            }
+                            wire::consume(reader, key.len);
+                        }
+                        backend::PollDecodeStatus::Fini(_) => {
+                            self.state = DecodeState::Fini;
+                            return backend::PollDecodeStatus::Fini(T::DEFAULT);
+                        }
+                        backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                        backend::PollDecodeStatus::Error(e) => {
+                            self.state = DecodeState::Fini;
+                            return backend::PollDecodeStatus::Error(e);
+                        }
+                    }
+                }
+                DecodeState::Packed(len) => match len.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(()) => match len.as_len() {
+                        // An empty run would leave nothing to decode
+                        Ok(n) if n > 0 => {
+                            field::set_packed(format, n);
+                            self.state = Self::value();
+                        }
+                        Ok(_) => {
+                            self.state = DecodeState::Fini;
+                            return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err());
+                        }
+                        Err(e) => {
+                            self.state = DecodeState::Fini;
+                            return backend::PollDecodeStatus::Error(e);
+                        }
+                    },
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => {
+                        self.state = DecodeState::Fini;
+                        return backend::PollDecodeStatus::Error(e);
+                    }
+                },
+                DecodeState::Value(value) => match value.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(()) => {
+                        let len = value.bytes().len();
+                        let data = match field::packed(format) {
+                            0                       => T::get(value.bytes()),
+                            packed if packed >= len => { field::set_packed(format, packed - len); T::get(value.bytes()) }
+                            // The value overran the run it belongs to
+                            _                       => None,
+                        };
+                        self.state = DecodeState::Fini;
+                        return data.ok_or_else(ThisFormat::invalid_data_err).into();
+                    }
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => {
+                        self.state = DecodeState::Fini;
+                        return backend::PollDecodeStatus::Error(e);
+                    }
+                },
+                DecodeState::Fini => return backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+            }
+        }
+    }
+}
+
+impl<T: Scalar> backend::Decode for Decoder<T> {
+    type Data = T;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self { state: DecodeState::Key, data: PhantomData }
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut dec = Self::init();
+        let status = dec.read_remaining(format, reader, cx);
+        status.lift(dec)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        self.read_remaining(format, reader, cx)
+    }
+}
@@ -0,0 +1,73 @@
+use core::{cmp::min, task::Context};
+use diny::{backend::{self, Format}, io};
+use crate::Formatter as ThisFormat;
+use super::wire::{self, Value};
+
+type Error = <ThisFormat as Format>::Error;
+
+/// Skips over whole fields, until one with a wanted tag is next or the
+/// input ends, reporting the tag of the field it stopped at.
+pub enum Skip {
+    Key,
+    Value(Value),
+    Len(Value),
+    Bytes(usize),
+}
+
+impl Skip {
+    /// Skip fields until one whose tag satisfies `wanted` is next, without reading its key,
+    /// and return that tag, or `None` at the end of the input
+    pub fn read_remaining<R>(&mut self, reader: &mut R, wanted: impl Fn(u32) -> bool, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Option<u32>, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self {
+                Self::Key => match wire::peek_key(reader, cx) {
+                    backend::PollDecodeStatus::Fini(None)      => return backend::PollDecodeStatus::Fini(None),
+                    backend::PollDecodeStatus::Fini(Some(key)) => {
+                        if wanted(key.tag) {
+                            return backend::PollDecodeStatus::Fini(Some(key.tag));
+                        }
+                        *self = match key.wire_type {
+                            wire::VARINT  => Self::Value(Value::varint(10)),
+                            wire::FIXED64 => Self::Value(Value::fixed(8)),
+                            wire::FIXED32 => Self::Value(Value::fixed(4)),
+                            wire::LEN     => Self::Len(Value::varint(wire::LEN_LEN)),
+                            // Groups were deprecated before proto3, and are not supported
+                            _             => return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err()),
+                        };
+                        wire::consume(reader, key.len);
+                    }
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => return backend::PollDecodeStatus::Error(e),
+                },
+                Self::Value(value) => match value.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(()) => *self = Self::Key,
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => return backend::PollDecodeStatus::Error(e),
+                },
+                Self::Len(len) => match len.read_remaining(reader, cx) {
+                    backend::PollDecodeStatus::Fini(()) => match len.as_len() {
+                        Ok(n)  => *self = Self::Bytes(n),
+                        Err(e) => return backend::PollDecodeStatus::Error(e),
+                    },
+                    backend::PollDecodeStatus::Pending  => return backend::PollDecodeStatus::Pending,
+                    backend::PollDecodeStatus::Error(e) => return backend::PollDecodeStatus::Error(e),
+                },
+                Self::Bytes(0) => *self = Self::Key,
+                Self::Bytes(n) => {
+                    let available = match wire::fill_buf(reader, cx) {
+                        backend::PollDecodeStatus::Fini([])  => return backend::PollDecodeStatus::Error(io::error::unexpected_eof()),
+                        backend::PollDecodeStatus::Fini(buf) => buf.len(),
+                        backend::PollDecodeStatus::Pending   => return backend::PollDecodeStatus::Pending,
+                        backend::PollDecodeStatus::Error(e)  => return backend::PollDecodeStatus::Error(e),
+                    };
+                    let consumed = min(available, *n);
+                    wire::consume(reader, consumed);
+                    *n -= consumed;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use core::{marker::PhantomData, task::Context};
+use diny::{backend::{self, Format, internal::StructDef}, io};
+use crate::Formatter as ThisFormat;
+use super::{field::{self, Field}, skip::Skip};
+
+type Error = <ThisFormat as Format>::Error;
+
+// Each field's value is encoded and decoded with a formatter in which the
+// field is current, so that the scalar, bytes and message codecs write its
+// tag before, or expect it in front of, their values.
+fn tag<S: StructDef, const IDX: usize>() -> Option<u32> {
+    S::FIELD_TAGS.and_then(|tags| tags.get(IDX).copied())
+}
+
+pub enum Encoder<S, const IDX: usize, E> {
+    Init,
+    Field(Field, E),
+    Fini,
+    #[doc(hidden)]
+    Phantom(PhantomData<fn() -> S>),
+}
+
+impl<S, const IDX: usize, E> Encoder<S, IDX, E>
+where
+    S: StructDef,
+    E: backend::Encode<Format=ThisFormat>,
+{
+    fn after_init<W>(format: &ThisFormat, writer: &mut W, data: &E::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        let mut field = match tag::<S, IDX>() {
+            Some(tag) => Field::new(tag),
+            None      => return backend::StartEncodeStatus::Error(ThisFormat::invalid_input_err()),
+        };
+        match field::within(format, &mut field, |format| E::start_encode(format, writer, data, cx)) {
+            backend::StartEncodeStatus::Fini         => backend::StartEncodeStatus::Fini,
+            backend::StartEncodeStatus::Pending(enc) => backend::StartEncodeStatus::Pending(Self::Field(field, enc)),
+            backend::StartEncodeStatus::Error(e)     => backend::StartEncodeStatus::Error(e),
+        }
+    }
+}
+
+impl<S, const IDX: usize, E> backend::Encode for Encoder<S, IDX, E>
+where
+    S: StructDef,
+    E: backend::Encode<Format=ThisFormat>,
+{
+    type Data = E::Data;
+    type Format = ThisFormat;
+
+    const MAX_ENCODED_LEN: Option<usize> = E::MAX_ENCODED_LEN;
+
+    fn init(_data: &Self::Data) -> Self {
+        Self::Init
+    }
+
+    fn start_encode<W>(format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        Self::after_init(format, writer, data, cx)
+    }
+
+    fn poll_encode<W>(&mut self, format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<Error>
+    where
+        W: io::AsyncWrite + Unpin,
+    {
+        match self {
+            Self::Init             => diny::encode_chain!(*self, Self::after_init(format, writer, data, cx)),
+            Self::Field(field, enc) => diny::encode_poll_fini!(*self, field::within(format, field, |format| enc.poll_encode(format, writer, data, cx))),
+            Self::Fini | Self::Phantom(_) => backend::PollEncodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+}
+
+// Before decoding a field, any unknown fields preceding it are skipped.
+// Skipping stops at a field with this or any later tag, so that absent fields
+// decode to their defaults, and fails at one belonging to an earlier field,
+// which is either out of order or repeated.
+pub enum Decoder<S, const IDX: usize, D> {
+    Init,
+    Seek(Field, Skip),
+    Field(Field, D),
+    Fini,
+    #[doc(hidden)]
+    Phantom(PhantomData<fn() -> S>),
+}
+
+impl<S, const IDX: usize, D> Decoder<S, IDX, D>
+where
+    S: StructDef,
+    D: backend::Decode<Format=ThisFormat>,
+{
+    fn known(tag: u32) -> bool {
+        S::FIELD_TAGS.is_some_and(|tags| tags.binary_search(&tag).is_ok())
+    }
+
+    fn earlier(tag: u32) -> bool {
+        S::FIELD_TAGS
+        .and_then(|tags| tags.get(..IDX))
+        .is_some_and(|tags| tags.binary_search(&tag).is_ok())
+    }
+
+    fn after_init<R>(format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<D::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match tag::<S, IDX>() {
+            Some(tag) => Self::after_skip(format, Field::new(tag), Skip::Key, reader, cx),
+            None      => backend::StartDecodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+
+    fn after_skip<R>(format: &ThisFormat, field: Field, mut skip: Skip, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<D::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match skip.read_remaining(reader, Self::known, cx) {
+            backend::PollDecodeStatus::Fini(Some(tag)) if Self::earlier(tag) => backend::StartDecodeStatus::Error(ThisFormat::invalid_data_err()),
+            backend::PollDecodeStatus::Fini(_)  => Self::after_seek(format, field, reader, cx),
+            backend::PollDecodeStatus::Pending  => backend::StartDecodeStatus::Pending(Self::Seek(field, skip)),
+            backend::PollDecodeStatus::Error(e) => backend::StartDecodeStatus::Error(e),
+        }
+    }
+
+    fn after_seek<R>(format: &ThisFormat, mut field: Field, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<D::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match field::within(format, &mut field, |format| D::start_decode(format, reader, cx)) {
+            backend::StartDecodeStatus::Fini(data)   => backend::StartDecodeStatus::Fini(data),
+            backend::StartDecodeStatus::Pending(dec) => backend::StartDecodeStatus::Pending(Self::Field(field, dec)),
+            backend::StartDecodeStatus::Error(e)     => backend::StartDecodeStatus::Error(e),
+        }
+    }
+
+    fn chain(&mut self, status: backend::StartDecodeStatus<D::Data, Self, Error>) -> backend::PollDecodeStatus<D::Data, Error> {
+        match status {
+            backend::StartDecodeStatus::Fini(data)   => { *self = Self::Fini; backend::PollDecodeStatus::Fini(data) }
+            backend::StartDecodeStatus::Pending(dec) => { *self = dec; backend::PollDecodeStatus::Pending }
+            backend::StartDecodeStatus::Error(e)     => { *self = Self::Fini; backend::PollDecodeStatus::Error(e) }
+        }
+    }
+}
+
+impl<S, const IDX: usize, D> backend::Decode for Decoder<S, IDX, D>
+where
+    S: StructDef,
+    D: backend::Decode<Format=ThisFormat>,
+{
+    type Data = D::Data;
+    type Format = ThisFormat;
+
+    fn init() -> Self {
+        Self::Init
+    }
+
+    fn start_decode<R>(format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        Self::after_init(format, reader, cx)
+    }
+
+    fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match core::mem::replace(self, Self::Fini) {
+            Self::Init             => {
+                let status = Self::after_init(format, reader, cx);
+                self.chain(status)
+            }
+            Self::Seek(field, skip) => {
+                let status = Self::after_skip(format, field, skip, reader, cx);
+                self.chain(status)
+            }
+            Self::Field(mut field, mut dec) => match field::within(format, &mut field, |format| dec.poll_decode(format, reader, cx)) {
+                backend::PollDecodeStatus::Fini(data) => backend::PollDecodeStatus::Fini(data),
+                backend::PollDecodeStatus::Pending    => { *self = Self::Field(field, dec); backend::PollDecodeStatus::Pending }
+                backend::PollDecodeStatus::Error(e)   => backend::PollDecodeStatus::Error(e),
+            },
+            Self::Fini | Self::Phantom(_) => backend::PollDecodeStatus::Error(ThisFormat::invalid_input_err()),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use core::{pin::Pin, task::{Context, Poll}};
+use diny::{backend::{self, Format}, buffer::buffer_state::BufferState, io};
+use crate::Formatter as ThisFormat;
+use super::field;
+
+type Error = <ThisFormat as Format>::Error;
+
+/// The wire type of a varint encoded value
+pub const VARINT : u8 = 0;
+/// The wire type of a little endian, eight byte value
+pub const FIXED64: u8 = 1;
+/// The wire type of a length delimited value, i.e. strings, bytes, messages and packed scalars
+pub const LEN    : u8 = 2;
+/// The wire type of a little endian, four byte value
+pub const FIXED32: u8 = 5;
+
+/// The maximum number of bytes in the key of a field
+pub const KEY_LEN: usize = 5;
+/// The maximum number of bytes in the length of a length delimited value
+pub const LEN_LEN: usize = 10;
+/// The maximum number of bytes in any scalar value, i.e. a 128 bit varint
+pub const VALUE_LEN: usize = 19;
+
+const CONTINUATION: u8 = 0x80;
+const PAYLOAD: u8 = 0x7f;
+
+/// Write `v` to the start of `buf` as a varint, returning the number of bytes written
+pub fn put_varint(mut v: u128, buf: &mut [u8]) -> usize {
+    let mut n = 0;
+    loop {
+        let b = (v as u8) & PAYLOAD;
+        v >>= 7;
+        if v == 0 {
+            buf[n] = b;
+            return n + 1;
+        }
+        buf[n] = b | CONTINUATION;
+        n += 1;
+    }
+}
+
+/// Read the varint that ends with the last of `bytes`, failing if it overflows
+pub fn get_varint(bytes: &[u8]) -> Option<u128> {
+    let mut v: u128 = 0;
+    for (i, b) in bytes.iter().enumerate() {
+        let part = (b & PAYLOAD) as u128;
+        let shift = 7 * i as u32;
+        let shifted = part.checked_shl(shift)?;
+        if shifted >> shift != part {
+            return None;
+        }
+        v |= shifted;
+    }
+    Some(v)
+}
+
+/// Write the key of the field with `tag` to the start of `buf`, returning the number of bytes written
+pub fn put_key(tag: u32, wire_type: u8, buf: &mut [u8]) -> usize {
+    put_varint(((tag as u128) << 3) | wire_type as u128, buf)
+}
+
+/// The key preceding the value of a field
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Key {
+    pub tag: u32,
+    pub wire_type: u8,
+    /// The number of bytes the key was encoded in
+    pub len: usize,
+}
+
+// Readers are expected to buffer a complete key (see the body module), so
+// an incomplete one can only be followed by the end of the input.
+fn parse_key(buf: &[u8]) -> Result<Option<Key>, Error> {
+    if buf.is_empty() {
+        return Ok(None);
+    }
+
+    match buf.iter().take(KEY_LEN).position(|b| b & CONTINUATION == 0) {
+        Some(i) => {
+            let v = get_varint(&buf[..=i])
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(ThisFormat::invalid_data_err)?;
+            match v >> 3 {
+                0   => Err(ThisFormat::invalid_data_err()),
+                tag => Ok(Some(Key { tag, wire_type: (v & 7) as u8, len: i + 1 })),
+            }
+        }
+        None if buf.len() < KEY_LEN => Err(io::error::unexpected_eof()),
+        None                        => Err(ThisFormat::invalid_data_err()),
+    }
+}
+
+/// The bytes currently buffered by `reader`, which are only empty at the end of the stream
+pub fn fill_buf<'r, R>(reader: &'r mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<&'r [u8], Error>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    match Pin::new(reader).poll_fill_buf(cx) {
+        Poll::Ready(Ok(buf)) => backend::PollDecodeStatus::Fini(buf),
+        Poll::Ready(Err(e))  => backend::PollDecodeStatus::Error(e),
+        Poll::Pending        => backend::PollDecodeStatus::Pending,
+    }
+}
+
+/// Mark the first `n` buffered bytes as read
+#[inline(always)]
+pub fn consume<R>(reader: &mut R, n: usize)
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    Pin::new(reader).consume(n)
+}
+
+/// Peek at the next key without reading it, or `None` at the end of the stream
+pub fn peek_key<R>(reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Option<Key>, Error>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    fill_buf(reader, cx)
+    .and_then(|buf| parse_key(buf).into())
+}
+
+/// Read the key of the field being decoded, if it is next, and report
+/// whether it was.  A field that is absent decodes to its default value.
+///
+/// Without a field, the value alone is expected.
+pub fn expect_field<R>(format: &ThisFormat, reader: &mut R, wire_type: u8, cx: &mut Context<'_>) -> backend::PollDecodeStatus<bool, Error>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    let tag = match field::tag(format) {
+        Some(tag) => tag,
+        None      => return backend::PollDecodeStatus::Fini(true),
+    };
+
+    peek_key(reader, cx)
+    .and_then(|key| match key {
+        Some(key) if key.tag == tag => {
+            if key.wire_type != wire_type {
+                return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err());
+            }
+            consume(reader, key.len);
+            backend::PollDecodeStatus::Fini(true)
+        }
+        _ => backend::PollDecodeStatus::Fini(false),
+    })
+}
+
+/// Whether the field being decoded is next, without reading any of it
+pub fn peek_field<R>(format: &ThisFormat, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<bool, Error>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    let tag = field::tag(format);
+    peek_key(reader, cx)
+    .map(|key| key.map(|key| key.tag) == tag)
+}
+
+/// Reads a single varint or fixed width value, one byte at a time in the
+/// case of a varint, until its terminating byte has been received.
+pub struct Value {
+    state: BufferState<VALUE_LEN>,
+    max_len: Option<usize>,
+}
+
+impl Value {
+    /// Read a varint of at most `max_len` bytes
+    pub fn varint(max_len: usize) -> Self {
+        Self {
+            state: BufferState::with_partial_contents([0u8; VALUE_LEN], 1),
+            max_len: Some(max_len),
+        }
+    }
+
+    /// Read exactly `len` bytes
+    pub fn fixed(len: usize) -> Self {
+        Self {
+            state: BufferState::with_partial_contents([0u8; VALUE_LEN], len),
+            max_len: None,
+        }
+    }
+
+    /// Read a value of `wire_type`, which is at most `max_len` bytes long
+    pub fn of(wire_type: u8, max_len: usize) -> Self {
+        match wire_type {
+            VARINT => Self::varint(max_len),
+            _      => Self::fixed(max_len),
+        }
+    }
+
+    /// The bytes read so far
+    pub fn bytes(&self) -> &[u8] {
+        &self.state.buffer()[..self.state.len()]
+    }
+
+    /// The value read as a varint length, failing if it does not fit in a `usize`
+    pub fn as_len(&self) -> Result<usize, Error> {
+        get_varint(self.bytes())
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(ThisFormat::invalid_data_err)
+    }
+
+    pub fn read_remaining<R>(&mut self, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<(), Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        loop {
+            match self.state.read_remaining(reader, cx) {
+                backend::PollDecodeStatus::Fini(()) => match self.max_len {
+                    Some(max_len) => {
+                        let len = self.state.len();
+                        if self.state.buffer()[len - 1] & CONTINUATION == 0 {
+                            return backend::PollDecodeStatus::Fini(());
+                        } else if len >= max_len {
+                            return backend::PollDecodeStatus::Error(ThisFormat::invalid_data_err());
+                        } else {
+                            self.state.extend_len(1);
+                        }
+                    }
+                    None => return backend::PollDecodeStatus::Fini(()),
+                },
+                backend::PollDecodeStatus::Pending    => return backend::PollDecodeStatus::Pending,
+                backend::PollDecodeStatus::Error(err) => return backend::PollDecodeStatus::Error(err),
+            }
+        }
+    }
+}
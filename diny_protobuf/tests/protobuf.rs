@@ -0,0 +1,325 @@
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+//! The expected bytes in this module are those written by the reference
+//! protobuf implementations for the equivalent `.proto` message declarations,
+//! several of them taken from the protobuf encoding guide.
+
+use core::fmt::Debug;
+use diny::{backend::{DecodeLimits, LimitExceeded}, io, AsyncDeserialize, AsyncSerialization, AsyncSerialize, util::AsyncSliceReader};
+use diny_protobuf::Formatter;
+use futures::executor::block_on;
+
+
+// message Test1 { uint32 a = 1; }
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub struct Test1 {
+    a: u32,
+}
+
+// message Test2 { string b = 2; }
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub struct Test2 {
+    #[diny(tag = 2)]
+    b: String,
+}
+
+// message Test3 { Test1 c = 3; }
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub struct Test3 {
+    #[diny(tag = 3)]
+    c: Test1,
+}
+
+// message Test4 { string d = 1; repeated uint32 e = 4; }
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub struct Test4 {
+    d: String,
+    #[diny(tag = 4)]
+    e: Vec<u32>,
+}
+
+// message Scalars { int32 i = 1; bool b = 2; float f = 3; double d = 4; int64 l = 5; }
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub struct Scalars {
+    i: i32,
+    b: bool,
+    f: f32,
+    d: f64,
+    l: i64,
+}
+
+// message Test5 { int32 a = 1; int32 b = 2; }
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub struct Test5 {
+    a: i32,
+    b: i8,
+}
+
+// message Sparse { optional uint64 id = 1; bytes data = 7; optional Test1 inner = 8; uint32 last = 15; }
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub struct Sparse {
+    id: Option<u64>,
+    #[diny(tag = 7, with = "diny_protobuf::bytes")]
+    data: Vec<u8>,
+    inner: Option<Test1>,
+    #[diny(tag = 15)]
+    last: u32,
+}
+
+// message Tree { uint32 value = 1; repeated Test3 children = 2; }
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub struct Tree {
+    value: u32,
+    children: Vec<Test3>,
+}
+
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub enum Kind {
+    #[default]
+    A,
+    B(i64),
+}
+
+#[derive(Debug, Default, PartialEq, diny::AsyncSerialization)]
+pub struct Kinded(u8, Kind, #[diny(skip)] u8, char);
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Mapped {
+    m: std::collections::BTreeMap<u8, u8>,
+}
+
+fn serialize<T: AsyncSerialize + ?Sized>(send: &T) -> Vec<u8> {
+    let mut tx = Vec::new();
+    block_on(send.serialize(&diny_protobuf::format(), &mut tx)).expect("unable to serialize");
+    tx
+}
+
+fn deserialize_with<T: AsyncDeserialize>(format: &Formatter, bytes: &[u8]) -> io::Result<T> {
+    let mut rx = AsyncSliceReader::from(bytes);
+    let recv = block_on(T::deserialize(format, &mut rx));
+    if recv.is_ok() {
+        assert_eq!(rx.bytes_read(), bytes.len());
+    }
+    recv
+}
+
+fn deserialize<T: AsyncDeserialize>(bytes: &[u8]) -> io::Result<T> {
+    deserialize_with(&diny_protobuf::format(), bytes)
+}
+
+fn test_encoding<T>(send: T, expected: &[u8])
+where
+    T: AsyncSerialization + PartialEq + Debug,
+{
+    assert_eq!(serialize(&send), expected);
+    assert_eq!(diny_test::roundtrip_all_schedules(&diny_protobuf::format(), &send), expected);
+}
+
+fn assert_invalid_data<T: Debug>(recv: io::Result<T>) {
+    assert_eq!(recv.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+
+#[test]
+fn scalar_fields_are_keyed_varints() {
+    test_encoding(Test1 { a: 150 }, &[0x08, 0x96, 0x01]);
+    test_encoding(Test1 { a: 0 }, &[0x08, 0x00]);
+    test_encoding(Test1 { a: u32::MAX }, &[0x08, 0xff, 0xff, 0xff, 0xff, 0x0f]);
+}
+
+#[test]
+fn signed_floating_and_bool_fields_are_encoded() {
+    test_encoding(
+        Scalars { i: -2, b: true, f: 1.0, d: -2.0, l: i64::MIN },
+        &[
+            0x08, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+            0x10, 0x01,
+            0x1d, 0x00, 0x00, 0x80, 0x3f,
+            0x21, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0,
+            0x28, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01,
+        ],
+    );
+}
+
+#[test]
+fn strings_and_messages_are_length_delimited() {
+    test_encoding(Test2 { b: String::from("testing") }, b"\x12\x07testing");
+    test_encoding(Test3 { c: Test1 { a: 150 } }, &[0x1a, 0x03, 0x08, 0x96, 0x01]);
+    test_encoding(Test2 { b: "x".repeat(200) }, &[&[0x12, 0xc8, 0x01][..], &[b'x'; 200]].concat());
+}
+
+#[test]
+fn repeated_fields_are_written_unpacked() {
+    test_encoding(
+        Test4 { d: String::from("hello"), e: vec![1, 2, 3] },
+        &[0x0a, 0x05, b'h', b'e', b'l', b'l', b'o', 0x20, 0x01, 0x20, 0x02, 0x20, 0x03],
+    );
+    test_encoding(Test4 { d: String::new(), e: vec![] }, &[0x0a, 0x00]);
+}
+
+#[test]
+fn packed_repeated_fields_are_accepted() {
+    assert_eq!(
+        deserialize::<Test4>(&[0x0a, 0x00, 0x22, 0x06, 0x03, 0x8e, 0x02, 0x9e, 0xa7, 0x05]).unwrap(),
+        Test4 { d: String::new(), e: vec![3, 270, 86942] },
+    );
+    assert_eq!(
+        deserialize::<Test4>(&[0x22, 0x01, 0x01, 0x20, 0x02, 0x22, 0x02, 0x03, 0x04]).unwrap(),
+        Test4 { d: String::new(), e: vec![1, 2, 3, 4] },
+    );
+    assert_invalid_data(deserialize::<Test4>(&[0x22, 0x00]));
+    assert_invalid_data(deserialize::<Test4>(&[0x22, 0x01, 0x80, 0x01]));
+}
+
+#[test]
+fn formatter_is_shared_between_threads() {
+    let format = diny_protobuf::format();
+    let bytes = [0x22, 0x01, 0x01, 0x20, 0x02, 0x22, 0x02, 0x03, 0x04];
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| assert_eq!(
+                deserialize_with::<Test4>(&format, &bytes).unwrap(),
+                Test4 { d: String::new(), e: vec![1, 2, 3, 4] },
+            ));
+        }
+    });
+}
+
+#[test]
+fn optional_fields_are_omitted_when_none() {
+    test_encoding(Sparse::default(), &[0x3a, 0x00, 0x78, 0x00]);
+    test_encoding(
+        Sparse { id: Some(1), data: vec![0xff, 0x00], inner: Some(Test1 { a: 2 }), last: 3 },
+        &[0x08, 0x01, 0x3a, 0x02, 0xff, 0x00, 0x42, 0x02, 0x08, 0x02, 0x78, 0x03],
+    );
+}
+
+#[test]
+fn repeated_messages_are_each_delimited() {
+    let tree = Tree {
+        value: 1,
+        children: vec![
+            Test3 { c: Test1 { a: 2 } },
+            Test3 { c: Test1 { a: 300 } },
+        ],
+    };
+    test_encoding(tree, &[0x08, 0x01, 0x12, 0x04, 0x1a, 0x02, 0x08, 0x02, 0x12, 0x05, 0x1a, 0x03, 0x08, 0xac, 0x02]);
+    test_encoding(Tree::default(), &[0x08, 0x00]);
+}
+
+#[test]
+fn enums_and_tuple_structs_are_keyed_by_position() {
+    test_encoding(Kinded(1, Kind::A, 0, 'a'), &[0x08, 0x01, 0x10, 0x00, 0x18, 0x61]);
+    test_encoding(Kinded(1, Kind::B(-1), 0, 'a'), &[0x08, 0x01, 0x10, 0x01, 0x10, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x18, 0x61]);
+}
+
+#[test]
+fn absent_fields_decode_as_defaults() {
+    assert_eq!(deserialize::<Test4>(&[]).unwrap(), Test4::default());
+    assert_eq!(deserialize::<Sparse>(&[0x78, 0x05]).unwrap(), Sparse { last: 5, ..Sparse::default() });
+    assert_eq!(deserialize::<Test3>(&[]).unwrap(), Test3::default());
+    assert_eq!(deserialize::<Scalars>(&[0x10, 0x01]).unwrap(), Scalars { b: true, ..Scalars::default() });
+}
+
+#[test]
+fn unknown_fields_are_skipped() {
+    let bytes = [
+        0x30, 0x96, 0x01,                   // 6: varint
+        0x49, 0, 0, 0, 0, 0, 0, 0, 0,       // 9: fixed64
+        0x08, 0x2a,                         // 1: id
+        0x2d, 0, 0, 0, 0,                   // 5: fixed32
+        0x52, 0x03, 0x08, 0x96, 0x01,       // 10: length delimited
+        0x3a, 0x01, 0x07,                   // 7: data
+        0x80, 0x01, 0x01,                   // 16: varint
+    ];
+    assert_eq!(deserialize::<Sparse>(&bytes).unwrap(), Sparse { id: Some(42), data: vec![7], ..Sparse::default() });
+    assert_eq!(deserialize::<Test3>(&[0x1a, 0x05, 0x10, 0x01, 0x08, 0x96, 0x01]).unwrap(), Test3 { c: Test1 { a: 150 } });
+}
+
+#[test]
+fn out_of_order_and_repeated_fields_are_rejected() {
+    assert_invalid_data(deserialize::<Sparse>(&[0x78, 0x05, 0x08, 0x01]));
+    assert_invalid_data(deserialize::<Test1>(&[0x08, 0x01, 0x08, 0x02]));
+    assert_invalid_data(deserialize::<Test4>(&[0x0a, 0x00, 0x20, 0x01, 0x0a, 0x00]));
+    assert_invalid_data(deserialize::<Test3>(&[0x1a, 0x00, 0x1a, 0x00]));
+    // Unknown fields may still appear anywhere
+    assert_eq!(
+        deserialize::<Test4>(&[0x30, 0x07, 0x0a, 0x00, 0x30, 0x07, 0x20, 0x01, 0x30, 0x07]).unwrap(),
+        Test4 { d: String::new(), e: vec![1] },
+    );
+}
+
+#[test]
+fn negative_integers_are_sign_extended() {
+    test_encoding(Test5 { a: -1, b: i8::MAX }, &[0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x10, 0x7f]);
+    assert_eq!(deserialize::<Test5>(&[0x08, 0x80, 0x80, 0x80, 0x80, 0xf8, 0xff, 0xff, 0xff, 0xff, 0x01]).unwrap(), Test5 { a: i32::MIN, b: 0 });
+    // A value outside of the range of the field's type
+    assert_invalid_data(deserialize::<Test5>(&[0x08, 0x80, 0x80, 0x80, 0x80, 0x08]));
+}
+
+#[test]
+fn rejects_invalid_fields() {
+    assert_invalid_data(deserialize::<Test1>(&[0x0a, 0x00]));
+    assert_invalid_data(deserialize::<Test1>(&[0x00, 0x01]));
+    assert_invalid_data(deserialize::<Test1>(&[0x08, 0x80, 0x80, 0x80, 0x80, 0x10]));
+    assert_invalid_data(deserialize::<Test1>(&[0x0b]));
+    assert_invalid_data(deserialize::<Test2>(&[0x12, 0x01, 0xff]));
+    assert_invalid_data(deserialize::<Scalars>(&[0x18, 0x01]));
+}
+
+#[test]
+fn truncated_input_is_an_unexpected_eof() {
+    assert_eq!(deserialize::<Test1>(&[0x08]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(deserialize::<Test1>(&[0x08, 0x96]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(deserialize::<Test2>(&[0x12, 0x07, b't']).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(deserialize::<Test3>(&[0x1a, 0x03, 0x08, 0x96]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(deserialize::<Test3>(&[0x1a, 0x04, 0x08, 0x96, 0x01]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn maps_cannot_be_fields() {
+    let mut tx = Vec::new();
+    let send = Mapped { m: [(1, 2)].into() };
+    let err = block_on(send.serialize(&diny_protobuf::format(), &mut tx)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(deserialize::<Mapped>(&[]).unwrap(), Mapped { m: Default::default() });
+}
+
+#[test]
+fn decode_limits_are_enforced() {
+    let bytes = [&b"\x12\x40"[..], &[b'x'; 64]].concat();
+    let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_bytes_len(63));
+    let err = deserialize_with::<Test2>(&format, &bytes).unwrap_err();
+    assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::BytesLen));
+
+    let bytes = [0x12, 0x04, 0x1a, 0x02, 0x08, 0x01];
+    assert!(deserialize::<Tree>(&bytes).is_ok());
+    let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_depth(2));
+    let err = deserialize_with::<Tree>(&format, &bytes).unwrap_err();
+    assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::Depth));
+}
+
+#[test]
+fn messages_end_at_the_total_bytes_limit() {
+    use futures::{FutureExt, StreamExt, TryStreamExt};
+
+    // The input stays open after the message, as a connection would
+    let open_input = || futures::stream::iter([Ok(vec![0x08, 0x96, 0x01])]).chain(futures::stream::pending()).into_async_read();
+
+    let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_total_bytes(3));
+    let recv = Test1::deserialize(&format, &mut open_input()).now_or_never().expect("read beyond the limit");
+    assert_eq!(recv.unwrap(), Test1 { a: 150 });
+
+    let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_total_bytes(2));
+    let err = Test1::deserialize(&format, &mut open_input()).now_or_never().expect("read beyond the limit").unwrap_err();
+    assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::TotalBytes));
+}
+
+#[test]
+fn encoded_len_includes_nested_lengths() {
+    assert_eq!(diny::encoded_len(&diny_protobuf::format(), &Test3 { c: Test1 { a: 150 } }).unwrap(), 5);
+    assert_eq!(diny::encoded_len(&diny_protobuf::format(), &300u32).unwrap(), 2);
+    assert_eq!(diny::max_encoded_len::<Formatter, u64>(), Some(15));
+    assert_eq!(diny::max_encoded_len::<Formatter, String>(), None);
+}
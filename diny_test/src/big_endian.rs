@@ -0,0 +1,49 @@
+//! A variant of the test formatter that encodes all multi-byte primitives,
+//! including [variant indexes](diny::backend::internal::VariantIdx) and
+//! [sequence lengths](diny::backend::internal::SequenceLen), in big endian
+//! (i.e. network) byte order.
+//!
+//! The encoders and decoders are shared with the little endian [Formatter](crate::Formatter),
+//! so the two differ only in the order of the bytes within each primitive.
+//!
+//! ```
+//! # #![feature(generic_associated_types)]
+//! use futures::executor::block_on;
+//! use diny::AsyncSerialize;
+//!
+//! let mut bytes = vec!();
+//! block_on(0x0102_0304u32.serialize(&diny_test::big_endian::format(), &mut bytes)).unwrap();
+//! assert_eq!(bytes, [1, 2, 3, 4]);
+//! ```
+use diny::{backend::DecodeLimits, io};
+use crate::ByteOrder;
+
+#[doc(hidden)]
+#[path = "format/mod.rs"]
+pub mod format;
+
+const BYTE_ORDER: ByteOrder = ByteOrder::Big;
+
+/// Create a big endian test formatter instance
+pub fn format() -> Formatter {
+    Formatter::default()
+}
+
+/// A test format that encodes the primitives as their big endian
+/// byte representations.
+///
+/// No [decode limits](DecodeLimits) are enforced unless they are
+/// explicitly provided via [Formatter::with_limits].
+#[derive(Clone, Debug, Default)]
+pub struct Formatter {
+    limits: DecodeLimits,
+}
+
+impl Formatter {
+    /// Create a formatter that enforces the provided [decode limits](DecodeLimits)
+    pub fn with_limits(limits: DecodeLimits) -> Self {
+        Self { limits }
+    }
+}
+
+formatter_impl!();
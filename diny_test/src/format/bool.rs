@@ -1,6 +1,6 @@
 use core::task::Context;
 use diny::{backend, buffer::buffer_state::BufferState, io};
-use crate::Formatter as ThisFormat;
+use super::ThisFormat;
 
 type Error = <ThisFormat as backend::Format>::Error;
 type Data = bool;
@@ -10,7 +10,7 @@ const TRUE:  u8 = 1;
 const FALSE: u8 = 0;
 
 #[inline(always)]
-fn to_le_bytes(v: Data) -> [u8; BUF_SIZE] {
+fn to_bytes(v: Data) -> [u8; BUF_SIZE] {
     match v {
         true  => [TRUE],
         false => [FALSE],
@@ -18,7 +18,7 @@ fn to_le_bytes(v: Data) -> [u8; BUF_SIZE] {
 }
 
 #[inline(always)]
-fn from_le_bytes(bytes: [u8; BUF_SIZE]) -> io::Result<Data> {
+fn from_bytes(bytes: [u8; BUF_SIZE]) -> io::Result<Data> {
     match bytes[0] {
         TRUE  => Ok(true),
         FALSE => Ok(false),
@@ -1,7 +1,7 @@
 use core::{pin::Pin, task::{Context, Poll}};
 use diny::backend::{self, Encode as _, internal::SequenceLen};
 use diny::{buffer, io};
-use crate::Formatter as ThisFormat;
+use super::ThisFormat;
 
 
 type Data = [u8];
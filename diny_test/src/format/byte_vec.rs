@@ -6,7 +6,7 @@ use core::task::Context;
 use diny::backend::{self, Decode as _, internal::SequenceLen};
 use diny::buffer;
 use diny::io;
-use crate::Formatter as ThisFormat;
+use super::ThisFormat;
 
 
 type Data = Vec<u8>;
@@ -84,12 +84,16 @@ where
         )
     }
 
-    fn after_len<R>(_format: &F, reader: &mut R, len: usize, data: &mut Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
+    fn after_len<R>(format: &F, reader: &mut R, len: usize, data: &mut Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <F as backend::Format>::Error>
     where
         R: io::AsyncBufRead + Unpin,
     {
+        if let Err(kind) = format.decode_limits().check_bytes_len(len) {
+            return backend::StartDecodeStatus::Error(F::limit_exceeded_err(kind));
+        }
+
         if len > 0 {
-            data.reserve_exact(len);
+            data.reserve_exact(diny::backend::limits::prealloc_len::<u8>(len));
             let mut cur = buffer::BufferCursor::with_len(len);
             match cur.fill_vec(reader, data, cx) {
                 backend::PollDecodeStatus::Fini(()) => backend::StartDecodeStatus::Fini(()),
@@ -107,6 +111,20 @@ pub struct Decoder
     state: Option<DecodeState<ThisFormat>>,
 }
 
+impl Decoder
+{
+    fn start_decode_with<R>(format: &ThisFormat, reader: &mut R, mut data: Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Data, Self, <ThisFormat as backend::Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        match DecodeCursor::after_init(format, reader, &mut data, cx) {
+            backend::StartDecodeStatus::Fini(())        => data.into(),
+            backend::StartDecodeStatus::Pending(cursor) => backend::StartDecodeStatus::Pending(Self { state: Some(DecodeState { data, cursor }) }),
+            backend::StartDecodeStatus::Error(e)        => backend::StartDecodeStatus::Error(e),
+        }
+    }
+}
+
 impl backend::Decode for Decoder
 {
     type Data = Data;
@@ -120,12 +138,21 @@ impl backend::Decode for Decoder
     where
         R: io::AsyncBufRead + Unpin,
     {
-        let mut data = Data::new();
-        match DecodeCursor::after_init(format, reader, &mut data, cx) {
-            backend::StartDecodeStatus::Fini(())        => data.into(),
-            backend::StartDecodeStatus::Pending(cursor) => backend::StartDecodeStatus::Pending(Self { state: Some(DecodeState { data, cursor }) }),
-            backend::StartDecodeStatus::Error(e)        => backend::StartDecodeStatus::Error(e),
-        }
+        Self::start_decode_with(format, reader, Data::new(), cx)
+    }
+
+    // The bytes are read into the emptied storage of the existing vector
+    fn start_decode_into<R>(format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <Self::Format as backend::Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut seed = core::mem::take(data);
+        seed.clear();
+        Self::start_decode_with(format, reader, seed, cx)
+        .and_then(
+            |d| { *data = d; backend::StartDecodeStatus::Fini(()) },
+            |dec| dec,
+        )
     }
 
     fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <Self::Format as backend::Format>::Error>
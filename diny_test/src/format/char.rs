@@ -1,19 +1,26 @@
 use core::task::Context;
 use diny::{backend, buffer::buffer_state::BufferState, io};
-use crate::Formatter as ThisFormat;
+use crate::ByteOrder;
+use super::{BYTE_ORDER, ThisFormat};
 
 type Error = <ThisFormat as backend::Format>::Error;
 type Data = char;
 const BUF_SIZE: usize = 4;
 
 #[inline(always)]
-fn to_le_bytes(v: Data) -> [u8; BUF_SIZE] {
-    (v as u32).to_le_bytes()
+fn to_bytes(v: Data) -> [u8; BUF_SIZE] {
+    match BYTE_ORDER {
+        ByteOrder::Little => (v as u32).to_le_bytes(),
+        ByteOrder::Big    => (v as u32).to_be_bytes(),
+    }
 }
 
 #[inline(always)]
-fn from_le_bytes(bytes: [u8; BUF_SIZE]) -> io::Result<Data> {
-    let u = u32::from_le_bytes(bytes);
+fn from_bytes(bytes: [u8; BUF_SIZE]) -> io::Result<Data> {
+    let u = match BYTE_ORDER {
+        ByteOrder::Little => u32::from_le_bytes(bytes),
+        ByteOrder::Big    => u32::from_be_bytes(bytes),
+    };
     match core::char::from_u32(u) {
         None    => Err(<ThisFormat as backend::Format>::invalid_data_err()),
         Some(c) => Ok(c),
@@ -6,8 +6,10 @@ macro_rules! numeric_encode_decode_def {
             type Data = Data;
             type Format = ThisFormat;
 
+            const MAX_ENCODED_LEN: Option<usize> = Some(BUF_SIZE);
+
             fn init_buffer(data: &Self::Data) -> Self {
-                Encoder(BufferState::with_contents(to_le_bytes(*data)))
+                Encoder(BufferState::with_contents(to_bytes(*data)))
             }
         
             fn start_encode_buffer<W>(_format: &Self::Format, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, Error>
@@ -43,7 +45,7 @@ macro_rules! numeric_encode_decode_def {
             {
                 let mut dec = Self::init();
                 match (&mut dec.0).start_read(reader, cx) {
-                    backend::PollDecodeStatus::Fini(())   => from_le_bytes(*dec.0.buffer()).into(),
+                    backend::PollDecodeStatus::Fini(())   => from_bytes(*dec.0.buffer()).into(),
                     backend::PollDecodeStatus::Pending    => backend::StartDecodeStatus::Pending(dec),
                     backend::PollDecodeStatus::Error(err) => backend::StartDecodeStatus::Error(err),
                 }
@@ -54,7 +56,7 @@ macro_rules! numeric_encode_decode_def {
                 R: io::AsyncBufRead + Unpin,
             {
                 match (&mut self.0).read_remaining(reader, cx) {
-                    backend::PollDecodeStatus::Fini(())   => from_le_bytes(*self.0.buffer()).into(),
+                    backend::PollDecodeStatus::Fini(())   => from_bytes(*self.0.buffer()).into(),
                     backend::PollDecodeStatus::Pending    => backend::PollDecodeStatus::Pending,
                     backend::PollDecodeStatus::Error(err) => backend::PollDecodeStatus::Error(err),
                 }
@@ -67,20 +69,27 @@ macro_rules! numeric_def {
     ($t:ty, $bytes:literal) => {
         use core::task::Context;
         use diny::{backend, buffer::{buffer_state::BufferState}, io};
-        use $crate::Formatter as ThisFormat;
+        use $crate::ByteOrder;
+        use super::{BYTE_ORDER, ThisFormat};
 
         type Error = <ThisFormat as backend::Format>::Error;
         type Data = $t;
         const BUF_SIZE: usize = $bytes;
 
         #[inline(always)]
-        fn to_le_bytes(v: Data) -> [u8; BUF_SIZE] {
-            v.to_le_bytes()
+        fn to_bytes(v: Data) -> [u8; BUF_SIZE] {
+            match BYTE_ORDER {
+                ByteOrder::Little => v.to_le_bytes(),
+                ByteOrder::Big    => v.to_be_bytes(),
+            }
         }
         
         #[inline(always)]
-        fn from_le_bytes(bytes: [u8; BUF_SIZE]) -> Data {
-            Data::from_le_bytes(bytes)
+        fn from_bytes(bytes: [u8; BUF_SIZE]) -> Data {
+            match BYTE_ORDER {
+                ByteOrder::Little => Data::from_le_bytes(bytes),
+                ByteOrder::Big    => Data::from_be_bytes(bytes),
+            }
         }
         
 
@@ -95,10 +104,8 @@ macro_rules! usize_wrapper_def {
         use core::{convert::TryInto, task::Context};
         use diny::{backend::{self, Format}, buffer, io};
         
-        use crate::{
-            Formatter as ThisFormat,
-            $m as wrapper,
-        };
+        use super::ThisFormat;
+        use $m as wrapper;
         
         pub type Data = $t;
         pub type Error = <ThisFormat as Format>::Error;
@@ -108,6 +115,8 @@ macro_rules! usize_wrapper_def {
         impl buffer::BufferEncode for Encoder {
             type Data = Data;
             type Format = ThisFormat;
+
+            const MAX_ENCODED_LEN: Option<usize> = <wrapper::Encoder as buffer::BufferEncode>::MAX_ENCODED_LEN;
         
             fn init_buffer(data: &Self::Data) -> Self {
                 Encoder(
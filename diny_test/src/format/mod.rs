@@ -1,3 +1,7 @@
+// The formatter and byte order are resolved relative to the including module,
+// which allows the same encoders and decoders to be shared between formatters.
+use super::{BYTE_ORDER, Formatter as ThisFormat};
+
 #[macro_use]
 mod macros;
 
@@ -26,5 +30,5 @@ pub mod u128 { numeric_def!(u128, 16); }
 pub mod f32  { numeric_def!(f32 , 4 ); }
 pub mod f64  { numeric_def!(f64 , 8 ); }
 
-pub mod variant_idx  { usize_wrapper_def!(diny::backend::internal::VariantIdx , u32, format::u32); }
-pub mod sequence_len { usize_wrapper_def!(diny::backend::internal::SequenceLen, u64, format::u64); }
\ No newline at end of file
+pub mod variant_idx  { usize_wrapper_def!(diny::backend::internal::VariantIdx , u32, super::u32); }
+pub mod sequence_len { usize_wrapper_def!(diny::backend::internal::SequenceLen, u64, super::u64); }
@@ -1,7 +1,7 @@
 use core::task::Context;
 use diny::backend::{self, Encode as _};
 use diny::io;
-use crate::Formatter as ThisFormat;
+use super::ThisFormat;
 
 type Data = str;
 type ByteEncoder = <ThisFormat as backend::FormatEncode>::EncodeByteSlice;
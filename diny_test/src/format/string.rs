@@ -4,7 +4,7 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use core::task::Context;
 use diny::{backend, io};
-use crate::Formatter as ThisFormat;
+use super::ThisFormat;
 
 type Data = String;
 type StrEncoder = <ThisFormat as backend::FormatEncode>::EncodeStr;
@@ -74,6 +74,20 @@ impl backend::Decode for Decoder {
         )
     }
 
+    // The bytes are read into the storage of the existing string, which this
+    // format's byte vector decoder retains should the read be pending
+    fn start_decode_into<R>(format: &Self::Format, reader: &mut R, data: &mut Self::Data, cx: &mut Context<'_>) -> backend::StartDecodeStatus<(), Self, <<Self as backend::Decode>::Format as backend::Format>::Error>
+    where
+        R: io::AsyncBufRead + Unpin,
+    {
+        let mut bytes = core::mem::take(data).into_bytes();
+        ByteVecDecoder::start_decode_into(format, reader, &mut bytes, cx)
+        .and_then(
+            |()| into_string(bytes).map(|s| *data = s).into(),
+            Self,
+        )
+    }
+
     fn poll_decode<R>(&mut self, format: &Self::Format, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, <<Self as backend::Decode>::Format as backend::Format>::Error>
     where
         R: io::AsyncBufRead + Unpin,
@@ -1,6 +1,6 @@
 use core::task::Context;
 use diny::{backend, buffer};
-use crate::Formatter as ThisFormat;
+use super::ThisFormat;
 
 type Error = <ThisFormat as backend::Format>::Error;
 type Data = ();
@@ -11,6 +11,8 @@ impl buffer::BufferEncode for Encoder {
     type Data = Data;
     type Format = ThisFormat;
 
+    const MAX_ENCODED_LEN: Option<usize> = Some(0);
+
     fn init_buffer(_data: &Self::Data) -> Self {
         Encoder
     }
@@ -0,0 +1,281 @@
+use core::{cmp::min, pin::Pin, task::{Context, Poll}};
+use diny::io;
+
+/// Determines when a [ScheduledReader] or [ScheduledWriter] returns
+/// [Poll::Pending], and how many bytes it transfers once it is ready.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Schedule {
+    /// Never return pending, and transfer as many bytes as the inner reader or writer allows
+    Ready,
+    /// Return pending before every byte, and then transfer a single byte
+    PendingEachByte,
+    /// Never return pending, but only ever transfer a single byte at a time
+    OneByteChunks,
+    /// Randomly return pending, and transfer chunks of between 1 and 16 bytes,
+    /// following the pseudo-random sequence determined by the seed
+    Random(u64),
+}
+
+impl Schedule {
+    /// The number of [random](Schedule::Random) schedules included in [Schedule::all]
+    pub const RANDOM_SEEDS: u64 = 32;
+
+    /// Every deterministic schedule, followed by [RANDOM_SEEDS](Schedule::RANDOM_SEEDS)
+    /// random schedules seeded from zero.
+    pub fn all() -> impl Iterator<Item = Schedule> {
+        [Schedule::Ready, Schedule::PendingEachByte, Schedule::OneByteChunks]
+        .into_iter()
+        .chain((0..Self::RANDOM_SEEDS).map(Schedule::Random))
+    }
+}
+
+struct Scheduler {
+    schedule: Schedule,
+    state: u64,
+    pending: bool,
+    chunk: usize,
+}
+
+impl Scheduler {
+    fn new(schedule: Schedule) -> Self {
+        let state = match schedule {
+            Schedule::Random(seed) => seed,
+            _                      => 0,
+        };
+        let mut scheduler = Self { schedule, state, pending: false, chunk: usize::MAX };
+        scheduler.advance();
+        scheduler
+    }
+
+    // splitmix64, which produces a usable sequence for any seed
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Called once some bytes have been transferred, to determine the behavior
+    /// of the next transfer.
+    fn advance(&mut self) {
+        match self.schedule {
+            Schedule::Ready           => { self.pending = false; self.chunk = usize::MAX; }
+            Schedule::PendingEachByte => { self.pending = true;  self.chunk = 1;          }
+            Schedule::OneByteChunks   => { self.pending = false; self.chunk = 1;          }
+            Schedule::Random(_)       => {
+                let r = self.next_u64();
+                self.pending = r & 1 == 1;
+                self.chunk = 1 + (r >> 1) as usize % 16;
+            }
+        }
+    }
+
+    /// Returns true, after scheduling a wake up, if the current transfer should be pending
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> bool {
+        if self.pending {
+            self.pending = false;
+            cx.waker().wake_by_ref();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Wraps an [asynchronous reader](io::AsyncBufRead) in order to deliver
+/// its bytes according to a [Schedule].
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use diny::{AsyncDeserialize, util::AsyncSliceReader};
+/// use diny_test::{Schedule, ScheduledReader};
+///
+/// let bytes = 7u32.to_le_bytes();
+/// let mut reader = ScheduledReader::new(AsyncSliceReader::from(&bytes[..]), Schedule::PendingEachByte);
+/// assert_eq!(block_on(u32::deserialize(&diny_test::format(), &mut reader)).unwrap(), 7);
+/// ```
+pub struct ScheduledReader<R> {
+    inner: R,
+    scheduler: Scheduler,
+}
+
+impl<R> ScheduledReader<R> {
+    /// Wrap the provided reader with the indicated schedule
+    pub fn new(inner: R, schedule: Schedule) -> Self {
+        Self { inner, scheduler: Scheduler::new(schedule) }
+    }
+
+    /// Returns a reference to the wrapped reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes the scheduled reader, returning the wrapped reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> io::AsyncRead for ScheduledReader<R>
+where
+    R: io::AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.scheduler.poll_pending(cx) {
+            return Poll::Pending;
+        }
+
+        let n = min(buf.len(), this.scheduler.chunk);
+        let result = futures::ready!(Pin::new(&mut this.inner).poll_read(cx, &mut buf[..n]));
+        if let Ok(n) = result {
+            if n > 0 {
+                this.scheduler.advance();
+            }
+        }
+        Poll::Ready(result)
+    }
+}
+
+impl<R> io::AsyncBufRead for ScheduledReader<R>
+where
+    R: io::AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        if this.scheduler.poll_pending(cx) {
+            return Poll::Pending;
+        }
+
+        let chunk = this.scheduler.chunk;
+        Pin::new(&mut this.inner).poll_fill_buf(cx)
+        .map_ok(|buf| &buf[..min(buf.len(), chunk)])
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        Pin::new(&mut this.inner).consume(amt);
+        if amt > 0 {
+            this.scheduler.advance();
+        }
+    }
+}
+
+/// Wraps an [asynchronous writer](io::AsyncWrite) in order to accept
+/// bytes according to a [Schedule].
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use diny::AsyncSerialize;
+/// use diny_test::{Schedule, ScheduledWriter};
+///
+/// let mut writer = ScheduledWriter::new(Vec::new(), Schedule::Random(7));
+/// block_on(7u32.serialize(&diny_test::format(), &mut writer)).unwrap();
+/// assert_eq!(writer.into_inner(), 7u32.to_le_bytes());
+/// ```
+pub struct ScheduledWriter<W> {
+    inner: W,
+    scheduler: Scheduler,
+}
+
+impl<W> ScheduledWriter<W> {
+    /// Wrap the provided writer with the indicated schedule
+    pub fn new(inner: W, schedule: Schedule) -> Self {
+        Self { inner, scheduler: Scheduler::new(schedule) }
+    }
+
+    /// Returns a reference to the wrapped writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Consumes the scheduled writer, returning the wrapped writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> io::AsyncWrite for ScheduledWriter<W>
+where
+    W: io::AsyncWrite + Unpin,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.scheduler.poll_pending(cx) {
+            return Poll::Pending;
+        }
+
+        let n = min(buf.len(), this.scheduler.chunk);
+        let result = futures::ready!(Pin::new(&mut this.inner).poll_write(cx, &buf[..n]));
+        if let Ok(n) = result {
+            if n > 0 {
+                this.scheduler.advance();
+            }
+        }
+        Poll::Ready(result)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+/// Serializes and deserializes `send` under every [Schedule], panicking unless
+/// each schedule produces the same encoding and decodes a value equal to `send`.
+/// Each decoded value is then decoded into a second time, which must also
+/// reproduce `send`.
+///
+/// Returns the encoded bytes.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(diny_test::roundtrip_all_schedules(&diny_test::format(), &(1u8, 2u16)), [1, 2, 0]);
+/// ```
+#[cfg(feature = "std")]
+pub fn roundtrip_all_schedules<F, T>(format: &F, send: &T) -> Vec<u8>
+where
+    F: diny::backend::FormatSerialize + diny::backend::FormatDeserialize,
+    F::Error: core::fmt::Debug,
+    T: diny::AsyncSerialization + PartialEq + core::fmt::Debug,
+{
+    use futures::executor::block_on;
+    use diny::util::AsyncSliceReader;
+
+    let mut expected = Vec::new();
+    block_on(send.serialize(format, &mut expected)).expect("unable to serialize");
+
+    for schedule in Schedule::all() {
+        let mut writer = ScheduledWriter::new(Vec::new(), schedule);
+        if let Err(e) = block_on(send.serialize(format, &mut writer)) {
+            panic!("unable to serialize with {:?}: {:?}", schedule, e);
+        }
+        assert_eq!(writer.into_inner(), expected, "encoding differs with {:?}", schedule);
+
+        let mut reader = ScheduledReader::new(AsyncSliceReader::from(&expected[..]), schedule);
+        let mut recv = match block_on(T::deserialize(format, &mut reader)) {
+            Ok(recv) => recv,
+            Err(e)   => panic!("unable to deserialize with {:?}: {:?}", schedule, e),
+        };
+        assert_eq!(&recv, send, "decoded value differs with {:?}", schedule);
+        assert_eq!(reader.get_ref().bytes_read(), expected.len(), "encoding not fully consumed with {:?}", schedule);
+
+        let mut reader = ScheduledReader::new(AsyncSliceReader::from(&expected[..]), schedule);
+        if let Err(e) = block_on(diny::backend::DeserializeInto::<_, _, _, T::Decoder<F>>::new(format, &mut reader, &mut recv)) {
+            panic!("unable to deserialize into with {:?}: {:?}", schedule, e);
+        }
+        assert_eq!(&recv, send, "value decoded into differs with {:?}", schedule);
+        assert_eq!(reader.get_ref().bytes_read(), expected.len(), "encoding not fully consumed into with {:?}", schedule);
+    }
+
+    expected
+}
@@ -0,0 +1,178 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+use core::marker::PhantomData;
+use common::VAR_IDX_LEN;
+use common::method::eq::*;
+
+
+/// Encodes a [Duration](core::time::Duration) as its whole number of seconds
+mod duration_secs {
+    use core::{task::Context, time::Duration};
+    use diny::{backend::{self, Decode, Decodable, Encode, Encodable}, io};
+
+    pub struct Encoder<F: backend::FormatEncode>(<u64 as Encodable>::Encoder<F>);
+
+    impl<F: backend::FormatEncode> Encode for Encoder<F> {
+        type Data = Duration;
+        type Format = F;
+
+        fn init(data: &Self::Data) -> Self {
+            Self(Encode::init(&data.as_secs()))
+        }
+
+        fn start_encode<W>(format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, F::Error>
+        where
+            W: io::AsyncWrite + Unpin,
+        {
+            <u64 as Encodable>::Encoder::<F>::start_encode(format, writer, &data.as_secs(), cx)
+            .map_pending(Self)
+        }
+
+        fn poll_encode<W>(&mut self, format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<F::Error>
+        where
+            W: io::AsyncWrite + Unpin,
+        {
+            self.0.poll_encode(format, writer, &data.as_secs(), cx)
+        }
+    }
+
+    pub struct Decoder<F: backend::FormatDecode>(<u64 as Decodable>::Decoder<F>);
+
+    impl<F: backend::FormatDecode> Decode for Decoder<F> {
+        type Data = Duration;
+        type Format = F;
+
+        fn init() -> Self {
+            Self(Decode::init())
+        }
+
+        fn start_decode<R>(format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::StartDecodeStatus<Self::Data, Self, F::Error>
+        where
+            R: io::AsyncBufRead + Unpin,
+        {
+            <u64 as Decodable>::Decoder::<F>::start_decode(format, reader, cx)
+            .bimap(Duration::from_secs, Self)
+        }
+
+        fn poll_decode<R>(&mut self, format: &F, reader: &mut R, cx: &mut Context<'_>) -> backend::PollDecodeStatus<Self::Data, F::Error>
+        where
+            R: io::AsyncBufRead + Unpin,
+        {
+            self.0.poll_decode(format, reader, cx)
+            .map(Duration::from_secs)
+        }
+    }
+}
+
+fn answer() -> u32 {
+    42
+}
+
+
+#[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct Skipped {
+    pub id: u32,
+    #[diny(skip)]
+    pub cache: Option<u64>,
+    pub flag: bool,
+    #[diny(skip, default = "answer")]
+    pub answer: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct SkippedTuple(u8, #[diny(skip)] u64, u16);
+
+#[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct AllSkipped {
+    #[diny(skip)]
+    pub a: u64,
+    #[diny(skip, default)]
+    pub b: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct Timed {
+    pub id: u8,
+    #[diny(with = "duration_secs")]
+    pub elapsed: core::time::Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+pub enum TimedEnum {
+    Never,
+    After(#[diny(with = "duration_secs")] core::time::Duration),
+}
+
+#[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+pub enum Cached {
+    Empty,
+    Computed(#[diny(skip)] u64),
+    Answer(#[diny(skip, default = "answer")] u32),
+    Value(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct Tagged<T> {
+    pub id: u16,
+    #[diny(skip)]
+    pub tag: PhantomData<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+pub enum TimedGeneric<T> {
+    At(#[diny(with = "duration_secs")] core::time::Duration),
+    Tag(PhantomData<T>),
+}
+
+
+#[test]
+fn skipped_fields_are_not_encoded() {
+    let send = Skipped { id: 7, cache: Some(9), flag: true, answer: 0 };
+    let recv = test_serialize_exact_no_cmp::<Skipped, {4 + 1}>(send.clone());
+    assert_eq!(recv, Skipped { cache: None, answer: 42, ..send });
+}
+
+#[test]
+fn skipped_tuple_fields_are_not_encoded() {
+    let recv = test_serialize_exact_no_cmp::<SkippedTuple, {1 + 2}>(SkippedTuple(1, 2, 3));
+    assert_eq!(recv, SkippedTuple(1, 0, 3));
+}
+
+#[test]
+fn struct_with_only_skipped_fields_is_encoded_as_unit() {
+    let recv = test_serialize_exact_no_cmp::<AllSkipped, 0>(AllSkipped { a: 3, b: true });
+    assert_eq!(recv, AllSkipped { a: 0, b: false });
+}
+
+#[test]
+fn skipped_variant_fields_are_not_encoded() {
+    let recv = test_serialize_exact_no_cmp::<Cached, VAR_IDX_LEN>(Cached::Computed(9));
+    assert_eq!(recv, Cached::Computed(0));
+    let recv = test_serialize_exact_no_cmp::<Cached, VAR_IDX_LEN>(Cached::Answer(0));
+    assert_eq!(recv, Cached::Answer(42));
+    test_serialize_exact::<Cached, VAR_IDX_LEN>(Cached::Empty);
+    test_serialize_exact::<Cached, {VAR_IDX_LEN + 2}>(Cached::Value(7));
+}
+
+#[test]
+fn with_field_uses_custom_coder() {
+    let send = Timed { id: 1, elapsed: core::time::Duration::from_secs(90) };
+    test_serialize_exact::<Timed, {1 + 8}>(send);
+}
+
+#[test]
+fn with_variant_uses_custom_coder() {
+    test_serialize_exact::<TimedEnum, {VAR_IDX_LEN + 8}>(TimedEnum::After(core::time::Duration::from_secs(5)));
+    test_serialize_exact::<TimedEnum, VAR_IDX_LEN>(TimedEnum::Never);
+}
+
+#[test]
+fn skipped_generic_fields_leave_parameters_unused() {
+    test_serialize_exact::<Tagged<u64>, 2>(Tagged { id: 3, tag: PhantomData });
+    test_serialize_exact::<TimedGeneric<u8>, {VAR_IDX_LEN + 8}>(TimedGeneric::At(core::time::Duration::from_secs(1)));
+}
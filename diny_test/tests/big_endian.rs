@@ -0,0 +1,106 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+#[cfg(feature = "std")]
+mod test {
+    use diny::{backend::DecodeLimits, AsyncDeserialize, AsyncSerialize, util::AsyncSliceReader};
+    use diny_test::{big_endian, roundtrip_all_schedules};
+    use futures::executor::block_on;
+    use super::common::{my_enum::MyEnum, my_rec::MyRec, SEQ_LEN, VAR_IDX_LEN};
+
+    fn be<T>(data: &T) -> Vec<u8>
+    where
+        T: diny::AsyncSerialization + PartialEq + core::fmt::Debug,
+    {
+        roundtrip_all_schedules(&big_endian::format(), data)
+    }
+
+    fn le<T>(data: &T) -> Vec<u8>
+    where
+        T: diny::AsyncSerialization + PartialEq + core::fmt::Debug,
+    {
+        roundtrip_all_schedules(&diny_test::format(), data)
+    }
+
+    fn reversed(mut bytes: Vec<u8>) -> Vec<u8> {
+        bytes.reverse();
+        bytes
+    }
+
+    #[test]
+    fn integers_are_encoded_in_network_byte_order() {
+        assert_eq!(be(&0x01u8), [0x01]);
+        assert_eq!(be(&0x0102u16), [0x01, 0x02]);
+        assert_eq!(be(&0x0102_0304u32), [0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(be(&0x0102_0304_0506_0708u64), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(be(&-2i16), [0xff, 0xfe]);
+        assert_eq!(be(&1i128), [&[0u8; 15][..], &[1]].concat());
+    }
+
+    #[test]
+    fn primitives_are_the_reverse_of_the_little_endian_encoding() {
+        macro_rules! assert_reversed {
+            ($($v:expr),*) => { $( assert_eq!(be(&$v), reversed(le(&$v)), "{}", stringify!($v)); )* };
+        }
+
+        assert_reversed!(
+            i16::MIN, i32::MIN, i64::MIN, i128::MIN,
+            u16::MAX - 1, u32::MAX - 1, u64::MAX - 1, u128::MAX - 1,
+            1.5f32, -1.5f64, f64::MIN_POSITIVE,
+            'a', '\u{10ffff}', true, ()
+        );
+    }
+
+    #[test]
+    fn floats_are_encoded_in_network_byte_order() {
+        assert_eq!(be(&1.0f32), 1.0f32.to_be_bytes());
+        assert_eq!(be(&-2.5f64), (-2.5f64).to_be_bytes());
+    }
+
+    #[test]
+    fn variant_indexes_and_sequence_lengths_are_big_endian() {
+        let bytes = be(&Some(7u8));
+        assert_eq!(bytes, [&1u32.to_be_bytes()[..], &[7]].concat());
+        assert_eq!(bytes.len(), VAR_IDX_LEN + 1);
+
+        let bytes = be(&"abc".to_string());
+        assert_eq!(bytes, [&3u64.to_be_bytes()[..], b"abc"].concat());
+        assert_eq!(bytes.len(), SEQ_LEN + 3);
+
+        assert_eq!(be(&vec![0x0102u16]), [0, 0, 0, 0, 0, 0, 0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn composites_roundtrip_with_the_same_length() {
+        let rec = MyRec::new();
+        assert_eq!(be(&rec).len(), le(&rec).len());
+
+        for e in [MyEnum::Bool0(true), MyEnum::U81(2)] {
+            assert_eq!(be(&e).len(), le(&e).len());
+        }
+    }
+
+    #[test]
+    fn little_endian_bytes_do_not_decode_as_big_endian() {
+        let bytes = le(&"abc".to_string());
+        let mut reader = AsyncSliceReader::from(&bytes[..]);
+        let format = big_endian::Formatter::with_limits(DecodeLimits::unlimited().with_max_bytes_len(1024));
+        assert!(block_on(String::deserialize(&format, &mut reader)).is_err());
+    }
+
+    #[test]
+    fn max_encoded_len_is_independent_of_byte_order() {
+        assert_eq!(
+            diny::max_encoded_len::<big_endian::Formatter, (u64, Option<u16>)>(),
+            diny::max_encoded_len::<diny_test::Formatter, (u64, Option<u16>)>(),
+        );
+
+        let mut bytes = vec!();
+        block_on(0x0102u16.serialize(&big_endian::format(), &mut bytes)).unwrap();
+        assert_eq!(diny::encoded_len(&big_endian::format(), &0x0102u16).unwrap(), bytes.len());
+    }
+}
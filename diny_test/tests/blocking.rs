@@ -0,0 +1,157 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+#[cfg(feature = "std")]
+mod test {
+    use std::io::{BufReader, Read, Write};
+    use diny::{backend::{DecodeLimits, LimitExceeded}, io, AsyncSerialize};
+    use diny_test::Formatter;
+    use futures::executor::block_on;
+    use super::common::{my_enum::MyEnum, my_rec::MyRec};
+
+    #[derive(Debug, PartialEq, diny::AsyncSerialization)]
+    pub struct Message {
+        pub id: u32,
+        pub body: String,
+        pub recs: Vec<MyRec>,
+        pub kind: MyEnum,
+    }
+
+    fn message() -> Message {
+        Message {
+            id: 7,
+            body: "hello blocking world".to_string(),
+            recs: vec![MyRec::new(); 3],
+            kind: MyEnum::U81(3),
+        }
+    }
+
+    fn serialize<T: AsyncSerialize>(send: &T) -> Vec<u8> {
+        let mut tx = Vec::new();
+        block_on(send.serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+        tx
+    }
+
+    // Accepts a single byte per write, and is interrupted before every other one
+    struct Trickle {
+        bytes: Vec<u8>,
+        interrupt: bool,
+    }
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            self.bytes.extend_from_slice(&buf[..1]);
+            Ok(1)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Yields a single byte per read, and is interrupted before every other one
+    struct Drip<'a> {
+        bytes: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for Drip<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let n = self.bytes.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.bytes[..n]);
+            self.bytes = &self.bytes[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn to_vec_matches_async_serialization() {
+        let send = message();
+        assert_eq!(diny::to_vec(&diny_test::format(), &send).unwrap(), serialize(&send));
+    }
+
+    #[test]
+    fn can_roundtrip_through_a_slice() {
+        let send = message();
+        let bytes = diny::to_vec(&diny_test::format(), &send).unwrap();
+        let recv: Message = diny::from_slice(&diny_test::format(), &bytes).unwrap();
+        assert_eq!(send, recv);
+    }
+
+    #[test]
+    fn can_serialize_unsized_data() {
+        let bytes = diny::to_vec(&diny_test::format(), "text").unwrap();
+        assert_eq!(bytes, serialize(&"text".to_string()));
+    }
+
+    #[test]
+    fn from_slice_rejects_trailing_bytes() {
+        let mut bytes = diny::to_vec(&diny_test::format(), &message()).unwrap();
+        bytes.push(0);
+        let err = diny::from_slice::<_, Message>(&diny_test::format(), &bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn from_slice_rejects_truncated_input() {
+        let bytes = diny::to_vec(&diny_test::format(), &message()).unwrap();
+        let err = diny::from_slice::<_, Message>(&diny_test::format(), &bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn can_write_to_a_slow_writer() {
+        let send = message();
+        let mut writer = Trickle { bytes: Vec::new(), interrupt: false };
+        diny::to_writer(&diny_test::format(), &mut writer, &send).unwrap();
+        assert_eq!(writer.bytes, serialize(&send));
+    }
+
+    #[test]
+    fn can_read_consecutive_objects_from_a_slow_reader() {
+        let send = message();
+        let mut bytes = Vec::new();
+        diny::to_writer(&diny_test::format(), &mut bytes, &send).unwrap();
+        diny::to_writer(&diny_test::format(), &mut bytes, &42u64).unwrap();
+
+        let mut reader = BufReader::with_capacity(1, Drip { bytes: &bytes, interrupt: false });
+        let recv: Message = diny::from_reader(&diny_test::format(), &mut reader).unwrap();
+        assert_eq!(send, recv);
+        let recv: u64 = diny::from_reader(&diny_test::format(), &mut reader).unwrap();
+        assert_eq!(recv, 42);
+        assert_eq!(reader.bytes().count(), 0);
+    }
+
+    #[test]
+    fn from_reader_reports_an_unexpected_eof() {
+        let bytes = diny::to_vec(&diny_test::format(), &message()).unwrap();
+        let err = diny::from_reader::<_, _, Message>(&diny_test::format(), &bytes[..bytes.len() / 2]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn total_bytes_limit_is_enforced() {
+        let bytes = diny::to_vec(&diny_test::format(), &message()).unwrap();
+        let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_total_bytes(bytes.len() - 1));
+
+        let err = diny::from_slice::<_, Message>(&format, &bytes).unwrap_err();
+        assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::TotalBytes));
+        let err = diny::from_reader::<_, _, Message>(&format, &bytes[..]).unwrap_err();
+        assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::TotalBytes));
+
+        let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_total_bytes(bytes.len()));
+        assert_eq!(diny::from_slice::<_, Message>(&format, &bytes).unwrap(), message());
+    }
+}
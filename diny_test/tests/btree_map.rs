@@ -31,4 +31,16 @@ mod test {
         vd.insert(3, true );
         test_serialize_exact::<BTreeMap<u64, bool>, {SEQ_LEN+(8+1)*3}>(vd);
     }
+
+    #[test]
+    fn map_lengths_default_to_sequence_lengths() {
+        let mut vd = BTreeMap::new();
+        vd.insert(1u64, true );
+        vd.insert(2u64, false);
+        let format = diny_test::format();
+        assert_eq!(
+            diny::to_vec(&format, &vd).unwrap(),
+            diny::to_vec(&format, &vec![(1u64, true), (2u64, false)]).unwrap(),
+        );
+    }
 }
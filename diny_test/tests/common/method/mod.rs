@@ -8,7 +8,6 @@ extern crate alloc;
 use alloc::vec::Vec;
 
 use diny::util::{AsyncSliceReader, AsyncSliceWriter};
-use diny_test::Formatter;
 use crate::common::*;
 
 fn cmp_eq<T: PartialEq + core::fmt::Debug>(t1: &T, t2: &T) -> bool {
@@ -20,7 +19,7 @@ fn serialize_slice_err<T>(send: &T, buf: &mut [u8])
 where
     T: diny::AsyncSerialization,
 {
-    let fmt = &Formatter;
+    let fmt = &diny_test::format();
 
     let mut tx = AsyncSliceWriter::from(buf);
     let write = send.serialize(fmt, &mut tx);
@@ -60,7 +59,7 @@ fn serialize_slice_int<T>(send: &T, buf: &mut [u8]) -> (T, usize)
 where
     T: diny::AsyncSerialization,
 {
-    let fmt = &Formatter;
+    let fmt = &diny_test::format();
 
     let mut tx = AsyncSliceWriter::from(buf);
     let write = send.serialize(fmt, &mut tx);
@@ -95,7 +94,7 @@ where
     use futures::SinkExt;
     use futures::StreamExt;
 
-    let fmt = Formatter;
+    let fmt = diny_test::format();
 
     let tx = AsyncSliceWriter::from(buf);
     let write = async move {
@@ -140,7 +139,7 @@ where
     use futures::StreamExt;
 
     let write = async move {
-        let mut sink = diny::serializer(Formatter, Vec::<u8>::new()).into_sink();
+        let mut sink = diny::serializer(diny_test::format(), Vec::<u8>::new()).into_sink();
         let ret = sink.send(send).await;
         assert!(sink.is_ready());
         match ret {
@@ -171,7 +170,7 @@ fn serialize_vec<T>(send: &T) -> T
 where
     T: diny::AsyncSerialization,
 {
-    let fmt = &Formatter;
+    let fmt = &diny_test::format();
 
     let mut tx = Vec::<u8>::new();
     let write = send.serialize(fmt, &mut tx);
@@ -195,7 +194,7 @@ fn serialize_pin_hole<T>(send: &T) -> T
 where
     T: diny::AsyncSerialization,
 {
-    let fmt = &Formatter;
+    let fmt = &diny_test::format();
     let (mut tx, mut rx) = pin_hole::channel();
     
     let write = send.serialize(fmt, &mut tx);
@@ -0,0 +1,171 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+#[cfg(feature = "std")]
+mod test {
+    use diny::{error::{DecodeError, PathSegment}, io, util::AsyncSliceReader, AsyncDeserialize, AsyncSerialize};
+    use diny_test::{Schedule, ScheduledReader};
+    use futures::{executor::block_on, StreamExt};
+    use super::common::SEQ_LEN;
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Line {
+        pub quantity: u16,
+        pub sku: char,
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Card(pub u32, pub char);
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub enum Payment {
+        Cash,
+        Card(Card),
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Order {
+        pub id: u32,
+        pub lines: Vec<Line>,
+        pub payment: Payment,
+    }
+
+    // The offset of the sku of the line at `idx`, and of the byte following it
+    fn sku_offsets(idx: usize) -> (usize, usize) {
+        let sku = 4 + SEQ_LEN + idx * 6 + 2;
+        (sku, sku + 4)
+    }
+
+    fn order() -> Order {
+        Order {
+            id: 7,
+            lines: (0..5).map(|i| Line { quantity: i, sku: 'a' }).collect(),
+            payment: Payment::Card(Card(1234, 'x')),
+        }
+    }
+
+    fn serialize<T: AsyncSerialize>(send: &T) -> Vec<u8> {
+        let mut tx = Vec::new();
+        block_on(send.serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+        tx
+    }
+
+    fn deserialize<T: AsyncDeserialize>(bytes: &[u8], schedule: Schedule) -> io::Result<T> {
+        let mut reader = ScheduledReader::new(AsyncSliceReader::from(bytes), schedule);
+        block_on(T::deserialize(&diny_test::format(), &mut reader))
+    }
+
+    fn invalid_sku(idx: usize) -> Vec<u8> {
+        let mut bytes = serialize(&order());
+        let (start, end) = sku_offsets(idx);
+        bytes[start..end].copy_from_slice(&0xd800u32.to_le_bytes());
+        bytes
+    }
+
+    fn context(err: &io::Error) -> &DecodeError {
+        DecodeError::find(err).expect("no decode context")
+    }
+
+    #[test]
+    fn errors_carry_the_path_and_offset() {
+        let bytes = invalid_sku(3);
+        let (_, end) = sku_offsets(3);
+
+        for schedule in Schedule::all() {
+            let err = deserialize::<Order>(&bytes, schedule).expect_err("unexpected success");
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "with {:?}", schedule);
+
+            let context = context(&err);
+            assert_eq!(context.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(context.offset(), Some(end), "with {:?}", schedule);
+            assert_eq!(context.path().to_string(), "Order.lines[3].sku");
+            assert_eq!(
+                context.path().segments(),
+                &[
+                    PathSegment::Field { ty: Some("Order"), name: Some("lines"), pos: 1 },
+                    PathSegment::Index(3),
+                    PathSegment::Field { ty: Some("Line"), name: Some("sku"), pos: 1 },
+                ],
+            );
+            assert_eq!(err.to_string(), format!("invalid data at byte {} in Order.lines[3].sku", end));
+        }
+    }
+
+    #[test]
+    fn variants_and_positional_fields_are_named() {
+        let mut bytes = serialize(&order());
+        let len = bytes.len();
+        bytes[len - 4..].copy_from_slice(&0xd800u32.to_le_bytes());
+
+        let err = deserialize::<Order>(&bytes, Schedule::OneByteChunks).expect_err("unexpected success");
+        assert_eq!(context(&err).path().to_string(), "Order.payment::Card.1");
+        assert_eq!(context(&err).offset(), Some(len));
+
+        let err = deserialize::<Payment>(&bytes[bytes.len() - 12..], Schedule::OneByteChunks).expect_err("unexpected success");
+        assert_eq!(err.to_string(), "invalid data at byte 12 in Payment::Card.1");
+    }
+
+    #[test]
+    fn truncated_input_reports_where_it_ended() {
+        let bytes = serialize(&order());
+        let (sku, _) = sku_offsets(2);
+
+        for schedule in Schedule::all() {
+            let err = deserialize::<Order>(&bytes[..sku + 1], schedule).expect_err("unexpected success");
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof, "with {:?}", schedule);
+            assert_eq!(context(&err).offset(), Some(sku + 1), "with {:?}", schedule);
+            assert_eq!(context(&err).path().to_string(), "Order.lines[2].sku");
+        }
+    }
+
+    #[test]
+    fn every_entry_point_reports_the_offset() {
+        let bytes = invalid_sku(1);
+        let (_, end) = sku_offsets(1);
+
+        let err = diny::from_slice::<_, Order>(&diny_test::format(), &bytes).expect_err("unexpected success");
+        assert_eq!(err.to_string(), format!("invalid data at byte {} in Order.lines[1].sku", end));
+
+        let mut recv = order();
+        let mut deserializer = diny::deserializer(diny_test::format(), AsyncSliceReader::from(&bytes[..]));
+        let err = block_on(deserializer.deserialize_into(&mut recv)).expect_err("unexpected success");
+        assert_eq!(context(&err).offset(), Some(end));
+        assert_eq!(context(&err).path().to_string(), "Order.lines[1].sku");
+
+        // Offsets within a stream are relative to the start of the failed message
+        let mut stream = serialize(&order());
+        stream.extend(&bytes);
+        let recv = block_on(diny::deserializer(diny_test::format(), AsyncSliceReader::from(&stream[..])).into_try_stream::<Order>().collect::<Vec<_>>());
+        assert_eq!(recv.len(), 2);
+        assert_eq!(recv[0].as_ref().expect("unable to deserialize"), &order());
+        let err = recv[1].as_ref().expect_err("unexpected success");
+        assert_eq!(context(err).offset(), Some(end));
+    }
+
+    #[test]
+    fn trailing_bytes_are_reported_where_they_begin() {
+        let mut bytes = serialize(&order());
+        let len = bytes.len();
+        bytes.push(0);
+
+        let err = diny::from_slice::<_, Order>(&diny_test::format(), &bytes).expect_err("unexpected success");
+        assert_eq!(err.to_string(), format!("invalid data at byte {}", len));
+    }
+
+    #[test]
+    fn the_underlying_error_remains_available() {
+        use diny::backend::{DecodeLimits, LimitExceeded};
+
+        let bytes = serialize(&order());
+        let format = diny_test::Formatter::with_limits(DecodeLimits::unlimited().with_max_sequence_len(2));
+        let err = block_on(Order::deserialize(&format, &mut AsyncSliceReader::from(&bytes[..]))).expect_err("unexpected success");
+
+        assert_eq!(context(&err).path().to_string(), "Order.lines");
+        assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::SequenceLen));
+        assert_eq!(err.to_string(), format!("maximum sequence length exceeded at byte {} in Order.lines", 4 + SEQ_LEN));
+    }
+}
@@ -0,0 +1,139 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+#[cfg(feature = "std")]
+mod test {
+    use std::collections::HashMap;
+    use diny::{util::AsyncSliceReader, AsyncSerialize};
+    use diny_test::{Schedule, ScheduledReader};
+    use futures::executor::block_on;
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Telemetry {
+        pub id: u64,
+        pub payload: Vec<u8>,
+        pub label: String,
+        pub counters: HashMap<u8, u32>,
+        #[diny(skip)]
+        pub cached: Option<u32>,
+    }
+
+    fn serialize<T: AsyncSerialize>(send: &T) -> Vec<u8> {
+        let mut tx = Vec::new();
+        block_on(send.serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+        tx
+    }
+
+    fn deserialize_into<T: diny::backend::Decodable>(bytes: &[u8], schedule: Schedule, data: &mut T) {
+        let reader = ScheduledReader::new(AsyncSliceReader::from(bytes), schedule);
+        let mut deserializer = diny::deserializer(diny_test::format(), reader);
+        block_on(deserializer.deserialize_into(data)).expect("unable to deserialize into");
+    }
+
+
+    #[test]
+    fn byte_vec_capacity_is_reused() {
+        let bytes = serialize(&vec![1u8, 2, 3]);
+
+        for schedule in Schedule::all() {
+            let mut recv: Vec<u8> = Vec::with_capacity(64);
+            recv.extend_from_slice(&[9; 10]);
+            let ptr = recv.as_ptr();
+
+            deserialize_into(&bytes, schedule, &mut recv);
+            assert_eq!(recv, [1, 2, 3]);
+            assert_eq!(recv.as_ptr(), ptr, "reallocated with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn string_capacity_is_reused() {
+        let bytes = serialize(&String::from("diny"));
+
+        for schedule in Schedule::all() {
+            let mut recv = String::with_capacity(64);
+            recv.push_str("previous contents");
+            let ptr = recv.as_ptr();
+
+            deserialize_into(&bytes, schedule, &mut recv);
+            assert_eq!(recv, "diny");
+            assert_eq!(recv.as_ptr(), ptr, "reallocated with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn sequence_capacity_is_reused() {
+        let bytes = serialize(&vec![1u32, 2, 3]);
+
+        for schedule in Schedule::all() {
+            let mut recv = vec![7u32; 32];
+            let ptr = recv.as_ptr();
+
+            deserialize_into(&bytes, schedule, &mut recv);
+            assert_eq!(recv, [1, 2, 3]);
+            assert_eq!(recv.as_ptr(), ptr, "reallocated with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn map_is_cleared_before_decoding() {
+        let send: HashMap<u8, u32> = [(1, 10), (2, 20)].into_iter().collect();
+        let bytes = serialize(&send);
+
+        for schedule in Schedule::all() {
+            let mut recv: HashMap<u8, u32> = (0..32).map(|k| (k, 0)).collect();
+            let capacity = recv.capacity();
+
+            deserialize_into(&bytes, schedule, &mut recv);
+            assert_eq!(recv, send);
+            assert_eq!(recv.capacity(), capacity, "reallocated with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn derived_struct_fields_are_decoded_in_place() {
+        let send = Telemetry {
+            id: 42,
+            payload: vec![1, 2, 3, 4],
+            label: String::from("sensor"),
+            counters: [(1, 100)].into_iter().collect(),
+            cached: None,
+        };
+        let bytes = serialize(&send);
+
+        for schedule in Schedule::all() {
+            let mut recv = Telemetry {
+                id: 0,
+                payload: Vec::with_capacity(64),
+                label: String::with_capacity(64),
+                counters: HashMap::new(),
+                cached: Some(7),
+            };
+            let payload = recv.payload.as_ptr();
+            let label = recv.label.as_ptr();
+
+            deserialize_into(&bytes, schedule, &mut recv);
+            assert_eq!(recv, send);
+            assert_eq!(recv.payload.as_ptr(), payload, "payload reallocated with {:?}", schedule);
+            assert_eq!(recv.label.as_ptr(), label, "label reallocated with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn stream_of_messages_decodes_into_one_value() {
+        let mut bytes = serialize(&vec![1u8; 8]);
+        bytes.extend(serialize(&vec![2u8; 4]));
+
+        let mut deserializer = diny::deserializer(diny_test::format(), AsyncSliceReader::from(&bytes[..]));
+        let mut recv: Vec<u8> = Vec::with_capacity(16);
+        let ptr = recv.as_ptr();
+
+        block_on(deserializer.deserialize_into(&mut recv)).unwrap();
+        assert_eq!(recv, [1; 8]);
+        block_on(deserializer.deserialize_into(&mut recv)).unwrap();
+        assert_eq!(recv, [2; 4]);
+        assert_eq!(recv.as_ptr(), ptr);
+    }
+}
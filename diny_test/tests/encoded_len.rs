@@ -0,0 +1,110 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+use core::marker::PhantomData;
+use diny::{encoded_len, max_encoded_len, AsyncSerialize};
+use diny_test::Formatter;
+use futures::executor::block_on;
+use common::{SEQ_LEN, VAR_IDX_LEN};
+use common::my_enum::MyEnum;
+use common::my_rec::MyRec;
+
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Header {
+    pub kind: u8,
+    pub flags: Option<u16>,
+    pub id: [u32; 2],
+    #[diny(skip)]
+    pub cache: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub enum Frame {
+    Empty,
+    Header(Header),
+    Ack(u64),
+}
+
+#[derive(Debug, PartialEq, diny::AsyncSerialization)]
+pub struct Packet<T> {
+    pub seq: u32,
+    pub payload: T,
+}
+
+#[cfg(feature = "std")]
+fn assert_exact<T: AsyncSerialize>(data: &T) {
+    let mut tx = Vec::new();
+    block_on(data.serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+    assert_eq!(encoded_len(&diny_test::format(), data).unwrap(), tx.len());
+    if let Some(max) = max_encoded_len::<Formatter, T>() {
+        assert!(tx.len() <= max);
+    }
+}
+
+
+#[test]
+fn primitives_have_fixed_max_len() {
+    assert_eq!(max_encoded_len::<Formatter, ()>(), Some(0));
+    assert_eq!(max_encoded_len::<Formatter, bool>(), Some(1));
+    assert_eq!(max_encoded_len::<Formatter, u16>(), Some(2));
+    assert_eq!(max_encoded_len::<Formatter, i128>(), Some(16));
+    assert_eq!(max_encoded_len::<Formatter, f64>(), Some(8));
+    assert_eq!(max_encoded_len::<Formatter, char>(), Some(4));
+}
+
+#[test]
+fn intrinsics_and_wrappers_have_fixed_max_len() {
+    assert_eq!(max_encoded_len::<Formatter, Option<u64>>(), Some(VAR_IDX_LEN + 8));
+    assert_eq!(max_encoded_len::<Formatter, Result<u8, u32>>(), Some(VAR_IDX_LEN + 4));
+    assert_eq!(max_encoded_len::<Formatter, [u16; 5]>(), Some(10));
+    assert_eq!(max_encoded_len::<Formatter, (u8, bool, u32)>(), Some(6));
+    assert_eq!(max_encoded_len::<Formatter, PhantomData<String>>(), Some(0));
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(max_encoded_len::<Formatter, Box<u32>>(), Some(4));
+        assert_eq!(max_encoded_len::<Formatter, std::cell::RefCell<(u8, u8)>>(), Some(2));
+    }
+}
+
+#[test]
+fn derived_types_have_fixed_max_len() {
+    const HEADER: Option<usize> = max_encoded_len::<Formatter, Header>();
+    assert_eq!(HEADER, Some(1 + VAR_IDX_LEN + 2 + 8));
+    assert_eq!(max_encoded_len::<Formatter, Frame>(), Some(VAR_IDX_LEN + 1 + VAR_IDX_LEN + 2 + 8));
+    assert_eq!(max_encoded_len::<Formatter, Packet<u64>>(), Some(12));
+    assert_eq!(max_encoded_len::<Formatter, MyRec>(), Some(1 + 1 + (VAR_IDX_LEN + 2) + (2 * VAR_IDX_LEN + 4)));
+    assert_eq!(max_encoded_len::<Formatter, MyEnum>(), Some(VAR_IDX_LEN + 1));
+}
+
+#[test]
+fn unbounded_types_have_no_max_len() {
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(max_encoded_len::<Formatter, String>(), None);
+        assert_eq!(max_encoded_len::<Formatter, Vec<u8>>(), None);
+        assert_eq!(max_encoded_len::<Formatter, (u8, Vec<u8>)>(), None);
+        assert_eq!(max_encoded_len::<Formatter, Packet<String>>(), None);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn encoded_len_is_exact() {
+    use std::collections::BTreeMap;
+
+    assert_exact(&0u8);
+    assert_exact(&String::from("exact"));
+    assert_exact(&vec![1u32, 2, 3]);
+    assert_exact(&(1u8, String::from("a"), vec![Some(1u16), None]));
+    assert_exact(&(0u8..4).map(|k| (k, vec![k; k as usize])).collect::<BTreeMap<_, _>>());
+    assert_exact(&Frame::Empty);
+    assert_exact(&Frame::Header(Header { kind: 1, flags: None, id: [2, 3], cache: vec![1, 2, 3] }));
+    assert_exact(&Packet { seq: 9, payload: vec![String::from("x"), String::new()] });
+
+    assert_eq!(encoded_len(&diny_test::format(), &String::from("exact")).unwrap(), SEQ_LEN + 5);
+}
@@ -0,0 +1,162 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+#[cfg(feature = "std")]
+mod test {
+    use diny::{io, schema::{self, FingerprintMismatch}, util::AsyncSliceReader};
+    use futures::{executor::block_on, StreamExt};
+
+    mod v1 {
+        #[derive(Debug, PartialEq, diny::AsyncSerialization)]
+        pub struct Order {
+            pub id: u32,
+            pub quantity: u32,
+        }
+
+        #[derive(diny::AsyncSerialization)]
+        pub enum Status {
+            Open(u32),
+            Closed(bool),
+        }
+    }
+
+    mod v2 {
+        #[derive(Debug, PartialEq, diny::AsyncSerialization)]
+        pub struct Order {
+            pub quantity: u32,
+            pub id: u32,
+        }
+
+        #[derive(diny::AsyncSerialization)]
+        pub enum Status {
+            Open(u32),
+            #[diny(index = 2)]
+            Closed(bool),
+        }
+    }
+
+    mod renamed {
+        #[derive(diny::AsyncSerialization)]
+        pub struct Purchase {
+            pub id: u32,
+            pub quantity: u32,
+        }
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Node {
+        pub value: u8,
+        pub children: Vec<Node>,
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Pair {
+        pub left: Box<Node>,
+        pub right: Option<Box<Node>>,
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Wrapper<T> {
+        pub inner: T,
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Tree<T> {
+        pub value: T,
+        pub children: Vec<Tree<T>>,
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        assert_eq!(schema::fingerprint::<u32>(), 0xaf63_c44c_8601_c3c4);
+        assert_eq!(schema::fingerprint::<v1::Order>(), 0xb614_cb7d_4a7b_2343);
+    }
+
+    #[test]
+    fn fingerprint_reflects_structural_changes() {
+        assert_ne!(schema::fingerprint::<v1::Order>(), schema::fingerprint::<v2::Order>());
+        assert_ne!(schema::fingerprint::<v1::Status>(), schema::fingerprint::<v2::Status>());
+        assert_ne!(schema::fingerprint::<Vec<u32>>(), schema::fingerprint::<[u32; 2]>());
+        assert_ne!(schema::fingerprint::<(u8, u16)>(), schema::fingerprint::<(u16, u8)>());
+        assert_eq!(schema::fingerprint::<v1::Order>(), schema::fingerprint::<renamed::Purchase>());
+    }
+
+    #[test]
+    fn fingerprint_of_recursive_types_terminates() {
+        assert_ne!(schema::fingerprint::<Node>(), schema::fingerprint::<Pair>());
+        assert_eq!(schema::fingerprint::<Box<Node>>(), schema::fingerprint::<Node>());
+    }
+
+    #[test]
+    fn nested_instantiations_of_a_generic_type_are_distinct() {
+        let fingerprints = [
+            schema::fingerprint::<Wrapper<Wrapper<u8>>>(),
+            schema::fingerprint::<Wrapper<Wrapper<u64>>>(),
+            schema::fingerprint::<Wrapper<Wrapper<String>>>(),
+            schema::fingerprint::<Wrapper<u8>>(),
+        ];
+        for (i, a) in fingerprints.iter().enumerate() {
+            for b in &fingerprints[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+
+        assert_ne!(schema::fingerprint::<Tree<u8>>(), schema::fingerprint::<Tree<u16>>());
+        assert_ne!(schema::fingerprint::<Tree<Tree<u8>>>(), schema::fingerprint::<Tree<u8>>());
+        assert_eq!(schema::fingerprint::<Tree<u8>>(), schema::fingerprint::<Node>());
+    }
+
+    fn write_fingerprinted<D: diny::AsyncSerialize + diny::Describe>(items: &[D]) -> Vec<u8> {
+        let mut serializer = diny::serializer(diny_test::format(), Vec::new());
+        block_on(async {
+            serializer.write_fingerprint::<D>().await.expect("unable to write fingerprint");
+            for item in items {
+                serializer.serialize(item).await.expect("unable to serialize");
+            }
+        });
+        serializer.writer
+    }
+
+    #[test]
+    fn matching_fingerprint_is_verified_before_the_first_item() {
+        let sent = [v1::Order { id: 1, quantity: 2 }, v1::Order { id: 3, quantity: 4 }];
+        let bytes = write_fingerprinted(&sent);
+
+        let mut deserializer = diny::deserializer(diny_test::format(), AsyncSliceReader::from(&bytes[..]));
+        block_on(deserializer.verify_fingerprint::<v1::Order>()).expect("fingerprint mismatch");
+        let recv = block_on(deserializer.into_stream::<v1::Order>().collect::<Vec<_>>());
+        assert_eq!(recv, sent);
+    }
+
+    #[test]
+    fn mismatched_fingerprint_is_reported() {
+        let bytes = write_fingerprinted(&[v1::Order { id: 1, quantity: 2 }]);
+
+        let mut deserializer = diny::deserializer(diny_test::format(), AsyncSliceReader::from(&bytes[..]));
+        let err = block_on(deserializer.verify_fingerprint::<v2::Order>()).expect_err("unexpected success");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mismatch = err.get_ref().and_then(|e| e.downcast_ref::<FingerprintMismatch>()).expect("not a fingerprint mismatch");
+        assert_eq!(*mismatch, FingerprintMismatch {
+            expected: schema::fingerprint::<v2::Order>(),
+            found: schema::fingerprint::<v1::Order>(),
+        });
+        assert!(err.to_string().starts_with("schema fingerprint mismatch: expected "));
+    }
+
+    #[test]
+    fn fingerprints_may_be_exchanged() {
+        let mut a = diny::serializer(diny_test::format(), Vec::new());
+        let mut b = diny::serializer(diny_test::format(), Vec::new());
+        block_on(a.write_fingerprint::<v1::Order>()).expect("unable to write fingerprint");
+        block_on(b.write_fingerprint::<v2::Order>()).expect("unable to write fingerprint");
+
+        let mut from_b = diny::deserializer(diny_test::format(), AsyncSliceReader::from(&b.writer[..]));
+        let mut from_a = diny::deserializer(diny_test::format(), AsyncSliceReader::from(&a.writer[..]));
+        assert!(block_on(from_b.verify_fingerprint::<v1::Order>()).is_err());
+        assert!(block_on(from_a.verify_fingerprint::<v2::Order>()).is_err());
+        assert!(block_on(diny::deserializer(diny_test::format(), AsyncSliceReader::from(&a.writer[..])).verify_fingerprint::<v1::Order>()).is_ok());
+    }
+}
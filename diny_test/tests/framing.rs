@@ -0,0 +1,159 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+#[cfg(feature = "std")]
+mod test {
+    use diny::{backend::LimitExceeded, framing::{FrameHeader, Framing, Oversized}, io, util::AsyncSliceReader};
+    use diny_test::{Schedule, ScheduledReader, ScheduledWriter};
+    use futures::{executor::block_on, SinkExt, StreamExt};
+    use super::common::SEQ_LEN;
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub enum V1 {
+        Ping(u32),
+        Text(String),
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub enum V2 {
+        Ping(u32),
+        Text(String),
+        Blob(Vec<u8>),
+    }
+
+    fn send_all<T: diny::backend::Encodable>(framing: Framing, items: Vec<T>) -> Vec<u8> {
+        let mut sink = diny::serializer(diny_test::format(), vec!()).into_framed_sink(framing);
+        for item in items {
+            block_on(sink.send(item)).unwrap();
+        }
+        sink.try_into_inner().unwrap().writer
+    }
+
+    fn recv_all<T: diny::backend::Decodable>(framing: Framing, bytes: &[u8]) -> Vec<Result<T, io::Error>> {
+        let stream = diny::deserializer(diny_test::format(), AsyncSliceReader::from(bytes)).into_framed_try_stream(framing);
+        block_on(stream.collect())
+    }
+
+    fn text(s: &str) -> V1 {
+        V1::Text(s.to_string())
+    }
+
+    #[test]
+    fn frames_are_prefixed_with_a_sequence_len() {
+        let bytes = send_all(Framing::new(), vec![V1::Ping(7)]);
+        assert_eq!(bytes.len(), SEQ_LEN + 8);
+        assert_eq!(&bytes[..SEQ_LEN], &8u64.to_le_bytes());
+    }
+
+    #[test]
+    fn frames_are_prefixed_with_a_fixed_header() {
+        let bytes = send_all(Framing::new().with_header(FrameHeader::FixedU32), vec![V1::Ping(7)]);
+        assert_eq!(&bytes[..4], &[0, 0, 0, 8]);
+        assert_eq!(bytes.len(), 4 + 8);
+    }
+
+    #[test]
+    fn can_roundtrip_with_each_header() {
+        for header in [FrameHeader::SequenceLen, FrameHeader::FixedU32] {
+            let framing = Framing::new().with_header(header);
+            let send = vec![V1::Ping(1), text("hello"), text(""), V1::Ping(u32::MAX)];
+            let bytes = send_all(framing, send.clone());
+            let recv: Vec<V1> = recv_all(framing, &bytes).into_iter().map(Result::unwrap).collect();
+            assert_eq!(recv, send);
+        }
+    }
+
+    #[test]
+    fn can_roundtrip_with_every_schedule() {
+        let framing = Framing::new().with_header(FrameHeader::FixedU32);
+        let send = vec![text("abc"), V1::Ping(3), text("defghijklmnopqrstuvwxyz")];
+        let expected = send_all(framing, send.clone());
+
+        for schedule in Schedule::all() {
+            let mut sink = diny::serializer(diny_test::format(), ScheduledWriter::new(vec!(), schedule)).into_framed_sink(framing);
+            for item in send.iter().cloned() {
+                block_on(sink.send(item)).unwrap();
+            }
+            assert_eq!(sink.try_into_inner().unwrap().writer.into_inner(), expected, "encoding differs with {:?}", schedule);
+
+            let reader = ScheduledReader::new(AsyncSliceReader::from(&expected[..]), schedule);
+            let recv: Vec<V1> = block_on(diny::deserializer(diny_test::format(), reader).into_framed_stream(framing).collect());
+            assert_eq!(recv, send, "decoded values differ with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn undecodable_frames_are_skipped() {
+        let bytes = send_all(Framing::new(), vec![
+            V2::Ping(1),
+            V2::Blob(vec![1, 2, 3]),
+            V2::Text("after".to_string()),
+        ]);
+
+        let recv = recv_all::<V1>(Framing::new(), &bytes);
+        assert_eq!(recv.len(), 3);
+        assert_eq!(recv[0].as_ref().unwrap(), &V1::Ping(1));
+        assert_eq!(recv[1].as_ref().unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(recv[2].as_ref().unwrap(), &text("after"));
+
+        let stream = diny::deserializer(diny_test::format(), AsyncSliceReader::from(&bytes[..])).into_framed_stream(Framing::new());
+        let recv: Vec<V1> = block_on(stream.collect());
+        assert_eq!(recv, vec![V1::Ping(1), text("after")]);
+    }
+
+    #[test]
+    fn trailing_frame_bytes_are_skipped() {
+        let mut bytes = send_all(Framing::new().with_header(FrameHeader::FixedU32), vec![(1u32, 2u32)]);
+        bytes.extend(send_all(Framing::new().with_header(FrameHeader::FixedU32), vec![3u32]));
+
+        let recv = recv_all::<u32>(Framing::new().with_header(FrameHeader::FixedU32), &bytes);
+        assert_eq!(recv.into_iter().map(Result::unwrap).collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn oversized_frames_are_not_sent() {
+        let mut sink = diny::serializer(diny_test::format(), vec!()).into_framed_sink(Framing::new().with_max_frame_len(8));
+        block_on(sink.send(V1::Ping(1))).unwrap();
+
+        let err = block_on(sink.send(text("too long"))).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.into_inner().unwrap().downcast::<LimitExceeded>().unwrap().as_ref(), &LimitExceeded::FrameLen);
+
+        assert_eq!(sink.try_into_inner().unwrap().writer.len(), SEQ_LEN + 8);
+    }
+
+    #[test]
+    fn oversized_frames_are_rejected() {
+        let bytes = send_all(Framing::new(), vec![V1::Ping(1), text("too long"), V1::Ping(2)]);
+
+        let recv = recv_all::<V1>(Framing::new().with_max_frame_len(8), &bytes);
+        assert_eq!(recv.len(), 2);
+        assert_eq!(recv[0].as_ref().unwrap(), &V1::Ping(1));
+        assert_eq!(recv[1].as_ref().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn oversized_frames_are_skipped() {
+        let bytes = send_all(Framing::new(), vec![V1::Ping(1), text("too long"), V1::Ping(2)]);
+
+        let framing = Framing::new().with_max_frame_len(8).with_oversized(Oversized::Skip);
+        let recv = recv_all::<V1>(framing, &bytes);
+        assert_eq!(recv.into_iter().map(Result::unwrap).collect::<Vec<_>>(), vec![V1::Ping(1), V1::Ping(2)]);
+    }
+
+    #[test]
+    fn truncated_frames_are_an_error() {
+        let bytes = send_all(Framing::new(), vec![V1::Ping(1), V1::Ping(2)]);
+
+        for len in bytes.len() - SEQ_LEN - 8 + 1..bytes.len() {
+            let recv = recv_all::<V1>(Framing::new(), &bytes[..len]);
+            assert_eq!(recv.len(), 2, "truncated to {} bytes", len);
+            assert_eq!(recv[0].as_ref().unwrap(), &V1::Ping(1));
+            assert_eq!(recv[1].as_ref().unwrap_err().kind(), io::ErrorKind::UnexpectedEof, "truncated to {} bytes", len);
+        }
+    }
+}
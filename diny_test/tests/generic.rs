@@ -0,0 +1,97 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+use core::marker::PhantomData;
+use common::VAR_IDX_LEN;
+use common::method::eq::*;
+use common::my_rec::*;
+
+
+#[derive(Debug, Copy, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct Envelope<T> {
+    pub id: u32,
+    pub body: T,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct Pair<A, B>(A, B)
+where
+    A: Copy;
+
+#[derive(Debug, Copy, Clone, PartialEq, diny::AsyncSerialization)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+    Neither,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct Borrowed<'a, T> {
+    pub id: u32,
+    pub body: T,
+    #[diny(skip)]
+    pub marker: PhantomData<&'a ()>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Opaque;
+
+#[derive(Debug, Copy, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct Cached<K, T> {
+    #[diny(skip)]
+    pub key: Option<K>,
+    pub body: T,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, diny::AsyncSerialization)]
+pub struct Block<const N: usize> {
+    pub bytes: [u8; N],
+}
+
+
+#[test]
+fn can_serialize_generic_struct() {
+    let send = Envelope { id: 7, body: 2u16.pow(9) };
+    test_serialize_exact::<Envelope<u16>, 6>(send);
+}
+
+#[test]
+fn can_serialize_nested_generic_struct() {
+    let send = Envelope { id: 7, body: MyRec::new() };
+    test_serialize_exact::<Envelope<MyRec>, {4 + 20}>(send);
+}
+
+#[test]
+fn can_serialize_generic_tuple_struct_with_where_clause() {
+    let send = Pair(true, Some(2u32.pow(20)));
+    test_serialize_exact::<Pair<bool, Option<u32>>, {1 + VAR_IDX_LEN + 4}>(send);
+}
+
+#[test]
+fn can_serialize_generic_enum() {
+    test_serialize_exact::<Either<u8, u64>, {VAR_IDX_LEN + 1}>(Either::Left(2u8.pow(3)));
+    test_serialize_exact::<Either<u8, u64>, {VAR_IDX_LEN + 8}>(Either::Right(2u64.pow(40)));
+    test_serialize_exact::<Either<u8, u64>, VAR_IDX_LEN>(Either::Neither);
+}
+
+#[test]
+fn can_serialize_const_generic_struct() {
+    let send = Block { bytes: [1, 2, 3, 4, 5] };
+    test_serialize_exact::<Block<5>, 5>(send);
+}
+
+#[test]
+fn can_serialize_struct_with_lifetime() {
+    let send = Borrowed { id: 7, body: 2u16.pow(9), marker: PhantomData };
+    test_serialize_exact::<Borrowed<'static, u16>, 6>(send);
+}
+
+#[test]
+fn can_serialize_struct_with_unencodable_skipped_parameter() {
+    let send = Cached { key: None, body: 2u16.pow(9) };
+    test_serialize_exact::<Cached<Opaque, u16>, 2>(send);
+}
@@ -0,0 +1,150 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+use std::collections::BTreeMap;
+use diny::{backend::{DecodeLimits, LimitExceeded}, io, AsyncDeserialize, AsyncSerialize, util::AsyncSliceReader};
+use diny_test::Formatter;
+use futures::{executor::block_on, StreamExt};
+
+
+fn serialize<T: AsyncSerialize>(send: &T) -> Vec<u8> {
+    let mut tx = Vec::new();
+    block_on(send.serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+    tx
+}
+
+fn deserialize<T: AsyncDeserialize>(limits: DecodeLimits, bytes: &[u8]) -> io::Result<T> {
+    let mut rx = AsyncSliceReader::from(bytes);
+    block_on(T::deserialize(&Formatter::with_limits(limits), &mut rx))
+}
+
+fn assert_limit_exceeded(err: io::Error, kind: LimitExceeded) {
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&kind));
+}
+
+
+#[test]
+fn hostile_sequence_len_is_rejected_before_allocating() {
+    let bytes = u64::MAX.to_le_bytes();
+    let limits = DecodeLimits::unlimited().with_max_sequence_len(16);
+
+    assert_limit_exceeded(deserialize::<Vec<u64>>(limits.clone(), &bytes).unwrap_err(), LimitExceeded::SequenceLen);
+    assert_limit_exceeded(deserialize::<BTreeMap<u8, u8>>(limits, &bytes).unwrap_err(), LimitExceeded::SequenceLen);
+}
+
+#[test]
+fn sequence_len_at_limit_is_accepted() {
+    let send = vec![1u16, 2, 3, 4];
+    let bytes = serialize(&send);
+
+    assert_eq!(deserialize::<Vec<u16>>(DecodeLimits::unlimited().with_max_sequence_len(4), &bytes).unwrap(), send);
+    assert_limit_exceeded(deserialize::<Vec<u16>>(DecodeLimits::unlimited().with_max_sequence_len(3), &bytes).unwrap_err(), LimitExceeded::SequenceLen);
+}
+
+#[test]
+fn hostile_bytes_len_is_rejected_before_allocating() {
+    let bytes = u64::MAX.to_le_bytes();
+    let limits = DecodeLimits::unlimited().with_max_bytes_len(16);
+
+    assert_limit_exceeded(deserialize::<String>(limits, &bytes).unwrap_err(), LimitExceeded::BytesLen);
+}
+
+#[test]
+fn string_len_at_limit_is_accepted() {
+    let send = String::from("diny");
+    let bytes = serialize(&send);
+
+    assert_eq!(deserialize::<String>(DecodeLimits::unlimited().with_max_bytes_len(4), &bytes).unwrap(), send);
+    assert_limit_exceeded(deserialize::<String>(DecodeLimits::unlimited().with_max_bytes_len(3), &bytes).unwrap_err(), LimitExceeded::BytesLen);
+}
+
+#[test]
+fn total_bytes_are_limited() {
+    let send = vec![0u8; 10];
+    let bytes = serialize(&send);
+    assert_eq!(bytes.len(), 18);
+
+    assert_eq!(deserialize::<Vec<u8>>(DecodeLimits::unlimited().with_max_total_bytes(18), &bytes).unwrap(), send);
+    assert_limit_exceeded(deserialize::<Vec<u8>>(DecodeLimits::unlimited().with_max_total_bytes(17), &bytes).unwrap_err(), LimitExceeded::TotalBytes);
+}
+
+#[test]
+fn total_bytes_are_limited_per_stream_item() {
+    let send = vec![0u8; 10];
+    let mut bytes = serialize(&send);
+    bytes.extend(serialize(&send));
+
+    let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_total_bytes(18));
+    let mut stream = diny::deserializer(format, AsyncSliceReader::from(&bytes[..])).into_try_stream::<Vec<u8>>();
+    block_on(async {
+        assert_eq!(stream.next().await.expect("stream ended early").expect("decode error"), send);
+        assert_eq!(stream.next().await.expect("stream ended early").expect("decode error"), send);
+        assert!(stream.next().await.is_none());
+    });
+
+    let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_total_bytes(17));
+    let mut stream = diny::deserializer(format, AsyncSliceReader::from(&bytes[..])).into_try_stream::<Vec<u8>>();
+    block_on(async {
+        assert_limit_exceeded(stream.next().await.expect("stream ended early").expect_err("unexpected success"), LimitExceeded::TotalBytes);
+    });
+}
+
+#[test]
+fn nesting_depth_is_limited() {
+    let send = vec![vec![vec![1u8]]];
+    let bytes = serialize(&send);
+
+    assert_eq!(deserialize::<Vec<Vec<Vec<u8>>>>(DecodeLimits::unlimited().with_max_depth(3), &bytes).unwrap(), send);
+    assert_limit_exceeded(deserialize::<Vec<Vec<Vec<u8>>>>(DecodeLimits::unlimited().with_max_depth(2), &bytes).unwrap_err(), LimitExceeded::Depth);
+
+    let send = Box::new(Box::new(7u8));
+    let bytes = serialize(&send);
+    assert_eq!(deserialize::<Box<Box<u8>>>(DecodeLimits::unlimited().with_max_depth(2), &bytes).unwrap(), send);
+    assert_limit_exceeded(deserialize::<Box<Box<u8>>>(DecodeLimits::unlimited().with_max_depth(1), &bytes).unwrap_err(), LimitExceeded::Depth);
+}
+
+#[test]
+fn depth_is_restored_after_decoding() {
+    let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_depth(1));
+    let bytes = serialize(&vec![1u8, 2, 3]);
+
+    for _ in 0..3 {
+        let mut rx = AsyncSliceReader::from(&bytes[..]);
+        assert_eq!(block_on(Vec::<u8>::deserialize(&format, &mut rx)).unwrap(), vec![1, 2, 3]);
+    }
+}
+
+#[test]
+fn a_format_may_be_shared_between_threads() {
+    let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_depth(3));
+    let send = vec![vec![vec![1u8; 64]; 8]; 8];
+    let bytes = serialize(&send);
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..64 {
+                    let mut rx = AsyncSliceReader::from(&bytes[..]);
+                    assert_eq!(block_on(Vec::<Vec<Vec<u8>>>::deserialize(&format, &mut rx)).unwrap(), send);
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn hostile_lengths_are_not_reserved_up_front() {
+    let bytes = (u64::MAX >> 8).to_le_bytes();
+
+    for kind in [
+        deserialize::<Vec<u64>>(DecodeLimits::unlimited(), &bytes).unwrap_err().kind(),
+        deserialize::<Vec<u8>>(DecodeLimits::unlimited(), &bytes).unwrap_err().kind(),
+        deserialize::<String>(DecodeLimits::unlimited(), &bytes).unwrap_err().kind(),
+        deserialize::<BTreeMap<u8, u8>>(DecodeLimits::unlimited(), &bytes).unwrap_err().kind(),
+    ] {
+        assert_eq!(kind, io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -88,3 +88,20 @@ fn can_serialize_option_my_rec() {
 fn can_serialize_option_array() {
     test_serialize_exact::<Option<[u64; 3]>, {VAR_IDX_LEN+8*3}>(Some([1,2,3]));
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn option_tags_default_to_variant_indices() {
+    use diny::backend::internal::VariantIdx;
+
+    let format = diny_test::format();
+    assert_eq!(
+        diny::to_vec(&format, &Some(7u8)).unwrap(),
+        diny::to_vec(&format, &(VariantIdx::new(1), 7u8)).unwrap(),
+    );
+    assert_eq!(
+        diny::to_vec(&format, &None::<u8>).unwrap(),
+        diny::to_vec(&format, &VariantIdx::new(0)).unwrap(),
+    );
+    assert_eq!(diny::from_slice::<_, Option<u8>>(&format, &[1, 0, 0, 0, 7]).unwrap(), Some(7));
+}
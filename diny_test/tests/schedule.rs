@@ -0,0 +1,165 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+#[cfg(feature = "std")]
+mod test {
+    use core::{pin::Pin, task::{Context, Poll}};
+    use std::collections::{BTreeMap, HashMap, VecDeque};
+    use diny::{io, AsyncDeserialize, util::AsyncSliceReader};
+    use diny_test::{roundtrip_all_schedules, Schedule, ScheduledReader, ScheduledWriter};
+    use futures::{executor::block_on, future::poll_fn, AsyncBufRead, AsyncWrite};
+    use super::common::{my_enum::MyEnum, my_rec::MyRec};
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Envelope<T> {
+        pub id: u64,
+        pub body: T,
+        pub tags: Vec<(u16, String)>,
+        #[diny(skip)]
+        pub cached: Option<u32>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub enum Event {
+        Empty,
+        Named(Named),
+        Boxed(Box<Option<[u8; 3]>>),
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Named {
+        pub name: String,
+        pub count: u32,
+    }
+
+    fn roundtrip<T>(send: T)
+    where
+        T: diny::AsyncSerialization + PartialEq + core::fmt::Debug,
+    {
+        roundtrip_all_schedules(&diny_test::format(), &send);
+    }
+
+    /// Counts the number of times each operation is pending before its data is transferred
+    fn count_pending<F>(mut poll: F) -> usize
+    where
+        F: FnMut(&mut Context<'_>) -> Poll<usize>,
+    {
+        let mut pending = 0;
+        block_on(poll_fn(|cx| match poll(cx) {
+            Poll::Ready(n) => Poll::Ready(n),
+            Poll::Pending  => { pending += 1; Poll::Pending }
+        }));
+        pending
+    }
+
+
+    #[test]
+    fn reader_follows_schedule() {
+        let bytes = [1u8, 2, 3, 4, 5];
+        for (schedule, pending, chunk) in [(Schedule::Ready, 0, 5), (Schedule::PendingEachByte, 1, 1), (Schedule::OneByteChunks, 0, 1)] {
+            let mut reader = ScheduledReader::new(AsyncSliceReader::from(&bytes[..]), schedule);
+            let mut len = 0;
+            let n = count_pending(|cx| Pin::new(&mut reader).poll_fill_buf(cx).map(|r| { len = r.unwrap().len(); len }));
+            assert_eq!((n, len), (pending, chunk), "{:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn pending_each_byte_pends_before_every_byte() {
+        let bytes = [1u8, 2, 3];
+        let mut reader = ScheduledReader::new(AsyncSliceReader::from(&bytes[..]), Schedule::PendingEachByte);
+        for b in bytes {
+            assert_eq!(count_pending(|cx| Pin::new(&mut reader).poll_fill_buf(cx).map(|r| r.unwrap().len())), 1);
+            assert_eq!(block_on(futures::AsyncBufReadExt::fill_buf(&mut reader)).unwrap(), [b]);
+            Pin::new(&mut reader).consume(1);
+        }
+
+        let mut writer = ScheduledWriter::new(Vec::new(), Schedule::PendingEachByte);
+        for b in bytes {
+            assert_eq!(count_pending(|cx| Pin::new(&mut writer).poll_write(cx, &[b, b]).map(|r| r.unwrap())), 1);
+        }
+        assert_eq!(writer.into_inner(), bytes);
+    }
+
+    #[test]
+    fn random_schedule_is_reproducible() {
+        let bytes = [0u8; 64];
+        let chunks = |seed| {
+            let mut reader = ScheduledReader::new(AsyncSliceReader::from(&bytes[..]), Schedule::Random(seed));
+            let mut chunks = Vec::new();
+            while reader.get_ref().bytes_read() < bytes.len() {
+                let pending = count_pending(|cx| Pin::new(&mut reader).poll_fill_buf(cx).map(|r| r.unwrap().len()));
+                let len = block_on(futures::AsyncBufReadExt::fill_buf(&mut reader)).unwrap().len();
+                Pin::new(&mut reader).consume(len);
+                chunks.push((pending, len));
+            }
+            chunks
+        };
+
+        assert_eq!(chunks(3), chunks(3));
+        assert_ne!(chunks(3), chunks(4));
+        assert!(chunks(3).iter().any(|&(pending, _)| pending > 0));
+        assert!(chunks(3).iter().all(|&(_, len)| (1..=16).contains(&len)));
+    }
+
+    #[test]
+    fn primitives_roundtrip_all_schedules() {
+        roundtrip(());
+        roundtrip(true);
+        roundtrip(-5i8);
+        roundtrip(u16::MAX);
+        roundtrip(-123456i32);
+        roundtrip(u64::MAX - 1);
+        roundtrip(i128::MIN);
+        roundtrip(1.5f32);
+        roundtrip(-2.25f64);
+        roundtrip('λ');
+    }
+
+    #[test]
+    fn intrinsics_roundtrip_all_schedules() {
+        roundtrip(Some(7u32));
+        roundtrip(Option::<u32>::None);
+        roundtrip(Result::<u8, u64>::Err(9));
+        roundtrip([1u16, 2, 3, 4]);
+        roundtrip((1u8, 2u32, (true, 'x')));
+    }
+
+    #[test]
+    fn collections_roundtrip_all_schedules() {
+        roundtrip(String::from("a longer string that spans many chunks"));
+        roundtrip(vec![1u64, 2, 3, 4, 5]);
+        roundtrip(vec![vec![1u8], vec![], vec![2, 3]]);
+        roundtrip((0u8..5).collect::<VecDeque<_>>());
+        roundtrip((0u16..10).map(|k| (k, k.to_string())).collect::<BTreeMap<_, _>>());
+        roundtrip((0u32..4).map(|k| (k, vec![k; k as usize])).collect::<HashMap<_, _>>());
+        roundtrip(Box::new(Some(String::from("boxed"))));
+    }
+
+    #[test]
+    fn derived_types_roundtrip_all_schedules() {
+        roundtrip(MyRec::new());
+        roundtrip(vec![MyEnum::Bool0(true), MyEnum::U81(3)]);
+        roundtrip(Envelope {
+            id: 42,
+            body: vec![Event::Named(Named { name: String::from("inner"), count: 3 }), Event::Boxed(Box::new(Some([1, 2, 3])))],
+            tags: vec![(1, String::from("one")), (2, String::new())],
+            cached: None,
+        });
+        roundtrip(Envelope { id: 0, body: Event::Empty, tags: Vec::new(), cached: None });
+    }
+
+    #[test]
+    fn truncated_input_is_an_error_under_all_schedules() {
+        let bytes = roundtrip_all_schedules(&diny_test::format(), &String::from("truncated"));
+        for schedule in Schedule::all() {
+            let mut reader = ScheduledReader::new(AsyncSliceReader::from(&bytes[..bytes.len() - 1]), schedule);
+            let recv: io::Result<String> = block_on(String::deserialize(&diny_test::format(), &mut reader));
+            assert!(recv.is_err(), "{:?}", schedule);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+#[cfg(feature = "std")]
+mod test {
+    use std::collections::{BTreeSet, HashMap};
+    use diny::schema::{Describe, FieldSchema, Schema, SchemaRef, StructSchema};
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Line {
+        pub sku: String,
+        #[diny(tag = 5)]
+        pub quantity: u32,
+        #[diny(skip)]
+        pub cached: Option<u64>,
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Order {
+        pub id: u64,
+        pub lines: Vec<Line>,
+        pub notes: HashMap<String, Option<bool>>,
+        pub digest: [u8; 4],
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Meters(pub f64);
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Marker;
+
+    #[derive(diny::AsyncSerialization)]
+    pub enum Shape {
+        Circle(f32),
+        #[diny(index = 4)]
+        Rect((f32, f32)),
+        Empty,
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Tree<T> {
+        pub value: T,
+        pub children: Vec<Box<Tree<T>>>,
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Wrapper<T> {
+        pub inner: T,
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Outer {
+        pub a: Wrapper<u8>,
+        pub b: Wrapper<String>,
+        pub c: Wrapper<Wrapper<u8>>,
+        pub d: Either<Wrapper<u8>, bool>,
+    }
+
+    mod as_u64 {
+        pub type Encoder<F> = <u64 as diny::backend::Encodable>::Encoder<F>;
+        pub type Decoder<F> = <u64 as diny::backend::Decodable>::Decoder<F>;
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Timer {
+        #[diny(with = "as_u64")]
+        pub after: u64,
+    }
+
+    fn definitions<T: Describe>() -> Vec<String> {
+        T::SCHEMA.definitions().iter().map(|d| d.definition().to_string()).collect()
+    }
+
+    #[test]
+    fn primitives_and_intrinsics_are_described() {
+        assert_eq!(<()>::SCHEMA, Schema::Unit);
+        assert_eq!(u8::SCHEMA, Schema::U8);
+        assert_eq!(i128::SCHEMA, Schema::I128);
+        assert_eq!(String::SCHEMA, Schema::String);
+        assert_eq!(<[u8]>::SCHEMA, Schema::Bytes);
+        assert_eq!(Option::<char>::SCHEMA, Schema::Option(SchemaRef::of::<char>()));
+        assert_eq!(Result::<u32, String>::SCHEMA.to_string(), "Result<u32, string>");
+        assert_eq!(<(bool,)>::SCHEMA.to_string(), "(bool,)");
+        assert_eq!(<(bool, i16, f64)>::SCHEMA.to_string(), "(bool, i16, f64)");
+        assert_eq!(<[u16; 3]>::SCHEMA, Schema::Array(SchemaRef::of::<u16>(), 3));
+        assert_eq!(core::marker::PhantomData::<String>::SCHEMA, Schema::Unit);
+    }
+
+    #[test]
+    fn collections_and_wrappers_are_described() {
+        assert_eq!(Vec::<u8>::SCHEMA.to_string(), "[u8]");
+        assert_eq!(BTreeSet::<i8>::SCHEMA, Vec::<i8>::SCHEMA);
+        assert_eq!(HashMap::<String, Vec<u32>>::SCHEMA.to_string(), "{string: [u32]}");
+        assert_eq!(Box::<u32>::SCHEMA, u32::SCHEMA);
+        assert_eq!(std::rc::Rc::<Option<u32>>::SCHEMA, Option::<u32>::SCHEMA);
+        assert_eq!(std::cell::RefCell::<u32>::SCHEMA, u32::SCHEMA);
+    }
+
+    #[test]
+    fn struct_fields_are_described_in_order() {
+        let Schema::Struct(schema) = Line::SCHEMA else { panic!("not a struct") };
+        const EXPECTED: StructSchema = StructSchema {
+            name: "Line",
+            params: &[],
+            fields: &[
+                FieldSchema { name: Some("sku"), tag: 1, schema: SchemaRef::of::<String>(), since: None },
+                FieldSchema { name: Some("quantity"), tag: 5, schema: SchemaRef::of::<u32>(), since: None },
+            ],
+            versioned: false,
+        };
+        assert_eq!(schema, &EXPECTED);
+
+        assert_eq!(definitions::<Order>(), [
+            "struct Order { #1 id: u64, #2 lines: [Line], #3 notes: {string: Option<bool>}, #4 digest: [u8; 4] }",
+            "struct Line { #1 sku: string, #5 quantity: u32 }",
+        ]);
+        assert_eq!(definitions::<Meters>(), ["struct Meters(#1 f64);"]);
+        assert_eq!(definitions::<Marker>(), ["struct Marker;"]);
+    }
+
+    #[test]
+    fn enum_variants_are_described_with_their_indices() {
+        let Schema::Enum(schema) = Shape::SCHEMA else { panic!("not an enum") };
+        assert_eq!(schema.variants.iter().map(|v| (v.index, v.name)).collect::<Vec<_>>(), [(0, "Circle"), (4, "Rect"), (5, "Empty")]);
+        assert_eq!(definitions::<Shape>(), ["enum Shape { Circle(#1 f32) = 0, Rect(#1 (f32, f32)) = 4, Empty = 5 }"]);
+    }
+
+    #[test]
+    fn recursive_and_generic_types_are_described() {
+        assert_eq!(definitions::<Tree<u8>>(), ["struct Tree<u8> { #1 value: u8, #2 children: [Tree<u8>] }"]);
+        assert_eq!(Tree::<u8>::SCHEMA, Tree::<u8>::SCHEMA);
+        assert_ne!(Tree::<u8>::SCHEMA, Tree::<u16>::SCHEMA);
+    }
+
+    #[test]
+    fn generic_instantiations_are_distinct() {
+        assert_eq!(Wrapper::<Vec<u8>>::SCHEMA.to_string(), "Wrapper<[u8]>");
+        assert_eq!(Wrapper::<u8>::SCHEMA.params(), &[SchemaRef::of::<u8>()]);
+        assert_ne!(SchemaRef::of::<Wrapper<u8>>(), SchemaRef::of::<Wrapper<String>>());
+        assert_ne!(SchemaRef::of::<Wrapper<Wrapper<u8>>>(), SchemaRef::of::<Wrapper<Wrapper<u64>>>());
+        assert!(!Wrapper::<u8>::SCHEMA.is_same_definition(&Wrapper::<String>::SCHEMA));
+
+        assert_eq!(definitions::<Outer>(), [
+            "struct Outer { #1 a: Wrapper<u8>, #2 b: Wrapper<string>, #3 c: Wrapper<Wrapper<u8>>, #4 d: Either<Wrapper<u8>, bool> }",
+            "struct Wrapper<u8> { #1 inner: u8 }",
+            "struct Wrapper<string> { #1 inner: string }",
+            "struct Wrapper<Wrapper<u8>> { #1 inner: Wrapper<u8> }",
+            "enum Either<Wrapper<u8>, bool> { Left(#1 Wrapper<u8>) = 0, Right(#1 bool) = 1 }",
+        ]);
+    }
+
+    #[test]
+    fn custom_fields_are_described_by_module() {
+        assert_eq!(definitions::<Timer>(), ["struct Timer { #1 after: custom(as_u64) }"]);
+    }
+}
@@ -0,0 +1,165 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+#[cfg(feature = "std")]
+mod test {
+    use std::{borrow::Cow, collections::BTreeMap};
+    use diny::{backend::{DecodeLimits, LimitExceeded}, io, serde::Serde, util::AsyncSliceReader, AsyncDeserialize, AsyncSerialize};
+    use diny_test::{roundtrip_all_schedules, Formatter};
+    use futures::executor::block_on;
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+    /// Stands in for a third-party type that only implements serde's traits
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Reading {
+        pub sensor: String,
+        pub samples: Vec<i16>,
+        pub calibrated: Option<f64>,
+        pub raw: Vec<u8>,
+        pub units: BTreeMap<String, (u8, char)>,
+        pub state: State,
+        pub history: Vec<State>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub enum State {
+        Idle,
+        Fault(u128),
+        Range(i32, i32),
+        Moving { speed: f32, heading: Option<u16> },
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(untagged)]
+    pub enum Untagged {
+        Num(u32),
+        Text(String),
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Envelope {
+        pub id: u32,
+        #[diny(serde)]
+        pub reading: Reading,
+        pub trailer: u8,
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub enum Message {
+        Empty,
+        Reading(#[diny(serde)] Reading),
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Labelled<'a, T>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        #[diny(serde)]
+        pub label: Cow<'a, str>,
+        #[diny(serde)]
+        pub state: T,
+    }
+
+    fn reading() -> Reading {
+        Reading {
+            sensor: String::from("thermo-1"),
+            samples: vec![-3, 0, 7],
+            calibrated: Some(1.5),
+            raw: vec![0xde, 0xad],
+            units: [(String::from("celsius"), (1, 'C')), (String::from("kelvin"), (2, 'K'))].into_iter().collect(),
+            state: State::Moving { speed: 2.5, heading: None },
+            history: vec![State::Idle, State::Fault(u128::MAX), State::Range(-1, 1)],
+        }
+    }
+
+    fn serialize<T: AsyncSerialize>(send: &T) -> Vec<u8> {
+        let mut tx = Vec::new();
+        block_on(send.serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+        tx
+    }
+
+    fn deserialize<T: AsyncDeserialize>(format: &Formatter, bytes: &[u8]) -> io::Result<T> {
+        let mut rx = AsyncSliceReader::from(bytes);
+        block_on(T::deserialize(format, &mut rx))
+    }
+
+
+    #[test]
+    fn serde_types_roundtrip() {
+        roundtrip_all_schedules(&diny_test::format(), &Serde(reading()));
+        roundtrip_all_schedules(&diny_test::format(), &Serde(State::Idle));
+        roundtrip_all_schedules(&diny_test::format(), &Serde(Vec::<State>::new()));
+    }
+
+    #[test]
+    fn serde_fields_roundtrip() {
+        roundtrip_all_schedules(&diny_test::format(), &Envelope { id: 7, reading: reading(), trailer: 9 });
+        roundtrip_all_schedules(&diny_test::format(), &Message::Empty);
+        roundtrip_all_schedules(&diny_test::format(), &Message::Reading(reading()));
+    }
+
+    #[test]
+    fn serde_fields_with_lifetimes_and_parameters_roundtrip() {
+        let label = String::from("thermo-1");
+        roundtrip_all_schedules(&diny_test::format(), &Labelled { label: Cow::Borrowed(&label), state: State::Range(-1, 1) });
+        roundtrip_all_schedules(&diny_test::format(), &Labelled::<State> { label: Cow::Owned(label), state: State::Idle });
+    }
+
+    #[test]
+    fn serde_value_is_written_as_a_byte_vector() {
+        assert_eq!(serialize(&Serde(0x0102_0304u32)), [4, 0, 0, 0, 0, 0, 0, 0, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn serde_data_model_is_written_as_diny_writes_it() {
+        let format = diny_test::format();
+        let map: BTreeMap<u8, Option<String>> = [(1, None), (2, Some(String::from("two")))].into_iter().collect();
+        assert_eq!(diny::serde::to_vec(&format, &map).unwrap(), diny::to_vec(&format, &map).unwrap());
+        assert_eq!(diny::serde::to_vec(&format, &vec![-1i64, 1]).unwrap(), diny::to_vec(&format, &vec![-1i64, 1]).unwrap());
+        assert_eq!(diny::serde::to_vec(&format, &(true, false)).unwrap(), diny::to_vec(&format, &vec![true, false]).unwrap());
+    }
+
+    #[test]
+    fn self_describing_types_fail_to_deserialize() {
+        let bytes = serialize(&Serde(Untagged::Num(3)));
+        let err = deserialize::<Serde<Untagged>>(&diny_test::format(), &bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn trailing_bytes_are_rejected() {
+        let mut bytes = 5u64.to_le_bytes().to_vec();
+        bytes.extend(7u32.to_le_bytes());
+        bytes.push(0);
+        let err = deserialize::<Serde<u32>>(&diny_test::format(), &bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn scratch_buffer_is_bounded_by_the_bytes_limit() {
+        let bytes = serialize(&Serde(reading()));
+        let len = bytes.len() - 8;
+
+        let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_bytes_len(len));
+        assert_eq!(deserialize::<Serde<Reading>>(&format, &bytes).unwrap().0, reading());
+
+        let format = Formatter::with_limits(DecodeLimits::unlimited().with_max_bytes_len(len - 1));
+        let err = deserialize::<Serde<Reading>>(&format, &bytes).unwrap_err();
+        assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::BytesLen));
+    }
+
+    #[test]
+    fn scratch_buffer_is_bounded_when_serializing() {
+        let max = diny::backend::limits::MAX_BUFFERED_BYTES;
+        let mut tx = Vec::new();
+        let err = block_on(AsyncSerialize::serialize(&Serde("x".repeat(max)), &diny_test::format(), &mut tx)).unwrap_err();
+        assert_eq!(diny::error::underlying(&err).get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()), Some(&LimitExceeded::BytesLen));
+        assert!(tx.is_empty());
+
+        // The string's own length is buffered along with it
+        assert_eq!(serialize(&Serde("x".repeat(max - 8))).len(), 8 + max);
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+use diny::{io, AsyncSerialize, util::{AsyncSliceReader, AsyncSliceWriter}};
+use futures::{executor::block_on, StreamExt};
+use common::VAR_IDX_LEN;
+use common::my_enum::*;
+use common::my_rec::*;
+
+
+fn write_recs(buf: &mut [u8], count: usize) -> usize {
+    let mut tx = AsyncSliceWriter::from(buf);
+    for _ in 0..count {
+        block_on(MyRec::new().serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+    }
+    tx.bytes_written()
+}
+
+#[test]
+fn try_stream_ends_cleanly_on_message_boundary() {
+    let mut buf = [0u8; 64];
+    let len = write_recs(&mut buf, 3);
+    let rx: AsyncSliceReader = buf[..len].into();
+
+    let mut stream = diny::deserializer(diny_test::format(), rx).into_try_stream::<MyRec>();
+    block_on(async {
+        for _ in 0..3 {
+            assert_eq!(stream.next().await.expect("stream ended early").expect("decode error"), MyRec::new());
+        }
+        assert!(stream.next().await.is_none());
+    });
+    assert!(stream.is_ready());
+}
+
+#[test]
+fn try_stream_reports_truncated_message() {
+    let mut buf = [0u8; 64];
+    let len = write_recs(&mut buf, 2);
+    let rx: AsyncSliceReader = buf[..len - 1].into();
+
+    let mut stream = diny::deserializer(diny_test::format(), rx).into_try_stream::<MyRec>();
+    block_on(async {
+        assert_eq!(stream.next().await.expect("stream ended early").expect("decode error"), MyRec::new());
+        let err = stream.next().await.expect("stream ended early").expect_err("unexpected success");
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(stream.next().await.is_none());
+    });
+    assert!(!stream.is_ready());
+}
+
+#[test]
+fn try_stream_reports_corrupt_message() {
+    let buf = [0xffu8; VAR_IDX_LEN + 1];
+    let rx: AsyncSliceReader = buf[..].into();
+
+    let mut stream = diny::deserializer(diny_test::format(), rx).into_try_stream::<MyEnum>();
+    block_on(async {
+        stream.next().await.expect("stream ended early").expect_err("unexpected success");
+        assert!(stream.next().await.is_none());
+    });
+}
+
+#[test]
+fn stream_ends_on_error() {
+    let mut buf = [0u8; 64];
+    let len = write_recs(&mut buf, 2);
+    let rx: AsyncSliceReader = buf[..len - 1].into();
+
+    let mut stream = diny::deserializer(diny_test::format(), rx).into_stream::<MyRec>();
+    block_on(async {
+        assert_eq!(stream.next().await, Some(MyRec::new()));
+        assert_eq!(stream.next().await, None);
+    });
+}
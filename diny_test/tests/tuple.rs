@@ -0,0 +1,79 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+use diny::{AsyncDeserialize, AsyncSerialize, util::AsyncSliceReader};
+use futures::executor::block_on;
+use common::{SEQ_LEN, VAR_IDX_LEN};
+use common::method::eq::*;
+use common::my_enum::*;
+use common::my_rec::*;
+
+
+#[test]
+fn can_serialize_tuple_single() {
+    test_serialize_exact::<(u64,), 8>((5,));
+}
+
+#[test]
+fn can_serialize_tuple_pair() {
+    test_serialize_exact::<(u8, u32), {1 + 4}>((1, 2));
+}
+
+#[test]
+fn can_serialize_tuple_max_arity() {
+    // The standard library only implements Debug and PartialEq for tuples up to arity 12
+    type Max = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u16);
+    let send: Max = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+
+    let mut tx = Vec::new();
+    block_on(send.serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+    assert_eq!(tx, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0]);
+
+    let mut rx = AsyncSliceReader::from(&tx[..]);
+    let recv = block_on(Max::deserialize(&diny_test::format(), &mut rx)).expect("unable to deserialize");
+    let (a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p) = recv;
+    assert_eq!([a, b, c, d, e, f, g, h, i, j, k, l, m, n, o], [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+    assert_eq!(p, 15);
+}
+
+#[test]
+fn can_serialize_tuple_of_options() {
+    test_serialize_exact::<(Option<u64>, Option<u64>), {2 * VAR_IDX_LEN + 8}>((Some(1), None));
+}
+
+#[test]
+fn can_serialize_tuple_of_records_and_enums() {
+    test_serialize::<(MyRec, MyEnum, [u16; 2])>((MyRec::new(), MyEnum::U81(4), [1, 2]));
+}
+
+#[test]
+fn can_serialize_nested_tuples() {
+    test_serialize::<((u8, (bool, u16)), u32)>(((1, (true, 3)), 4));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn can_serialize_tuple_with_string() {
+    test_serialize_exact::<(u32, String), {4 + SEQ_LEN + 4}>((7, String::from("diny")));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn can_serialize_vec_of_tuples() {
+    test_serialize::<Vec<(u32, String)>>(vec![(1, String::from("one")), (2, String::new()), (3, String::from("three"))]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn can_serialize_map_with_tuple_values() {
+    use std::collections::BTreeMap;
+
+    let mut map = BTreeMap::new();
+    map.insert(1u8, (10u32, String::from("ten")));
+    map.insert(2u8, (20u32, String::from("twenty")));
+    test_serialize::<BTreeMap<u8, (u32, String)>>(map);
+}
@@ -0,0 +1,82 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+use diny::{AsyncDeserialize, AsyncSerialize, util::AsyncSliceReader};
+use futures::executor::block_on;
+use common::VAR_IDX_LEN;
+use common::method::eq::*;
+
+
+#[derive(Debug, Copy, Clone, PartialEq, diny::AsyncSerialization)]
+pub enum Status {
+    Ok = 10,
+    Retry,
+    Fail = 3,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, diny::AsyncSerialization)]
+pub enum Message {
+    Ping,
+    #[diny(index = 100)]
+    Data(u32),
+    Pong,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, diny::AsyncSerialization)]
+#[repr(u8)]
+pub enum Overridden {
+    #[diny(index = 1)]
+    A = 200,
+    B(u8) = 0,
+}
+
+fn encoded_index<T: AsyncSerialize>(data: &T) -> u32 {
+    let mut tx = Vec::new();
+    block_on(data.serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+    u32::from_le_bytes(tx[..VAR_IDX_LEN].try_into().unwrap())
+}
+
+
+#[test]
+fn discriminants_determine_variant_index() {
+    assert_eq!(encoded_index(&Status::Ok), 10);
+    assert_eq!(encoded_index(&Status::Retry), 11);
+    assert_eq!(encoded_index(&Status::Fail), 3);
+
+    test_serialize_exact::<Status, VAR_IDX_LEN>(Status::Ok);
+    test_serialize_exact::<Status, VAR_IDX_LEN>(Status::Retry);
+    test_serialize_exact::<Status, VAR_IDX_LEN>(Status::Fail);
+}
+
+#[test]
+fn index_attribute_determines_variant_index() {
+    assert_eq!(encoded_index(&Message::Ping), 0);
+    assert_eq!(encoded_index(&Message::Data(1)), 100);
+    assert_eq!(encoded_index(&Message::Pong), 101);
+
+    test_serialize_exact::<Message, VAR_IDX_LEN>(Message::Ping);
+    test_serialize_exact::<Message, {VAR_IDX_LEN + 4}>(Message::Data(2u32.pow(20)));
+    test_serialize_exact::<Message, VAR_IDX_LEN>(Message::Pong);
+}
+
+#[test]
+fn index_attribute_takes_precedence_over_discriminant() {
+    assert_eq!(encoded_index(&Overridden::A), 1);
+    assert_eq!(encoded_index(&Overridden::B(7)), 0);
+
+    test_serialize_exact::<Overridden, VAR_IDX_LEN>(Overridden::A);
+    test_serialize_exact::<Overridden, {VAR_IDX_LEN + 1}>(Overridden::B(7));
+}
+
+#[test]
+fn unknown_sparse_index_is_rejected() {
+    for idx in [0u32, 1, 2, 12] {
+        let bytes = idx.to_le_bytes();
+        let mut rx = AsyncSliceReader::from(&bytes[..]);
+        assert!(block_on(Status::deserialize(&diny_test::format(), &mut rx)).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#![allow(incomplete_features)]
+#![feature(generic_associated_types)]
+
+mod common;
+
+#[cfg(feature = "std")]
+mod test {
+    use diny::{io, util::AsyncSliceReader, AsyncDeserialize, AsyncSerialize};
+    use diny_test::{Schedule, ScheduledReader};
+    use futures::{executor::block_on, StreamExt};
+    use super::common::SEQ_LEN;
+
+    mod v1 {
+        #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+        #[diny(versioned)]
+        pub struct Order {
+            pub id: u32,
+            pub quantity: u32,
+        }
+    }
+
+    mod v2 {
+        #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+        #[diny(versioned)]
+        pub struct Order {
+            pub id: u32,
+            pub quantity: u32,
+            #[diny(since = 2, default)]
+            pub note: String,
+            #[diny(since = 3, default = "default_priority")]
+            pub priority: u8,
+        }
+
+        fn default_priority() -> u8 {
+            5
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    #[diny(versioned)]
+    pub struct Marker;
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    #[diny(versioned)]
+    pub struct Layer<T> {
+        pub depth: u8,
+        pub inner: T,
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Leaf(#[diny(with = "counted")] pub u8);
+
+    /// Encodes a `u8` as usual, counting the encodings started on this thread
+    mod counted {
+        use core::{cell::Cell, task::Context};
+        use diny::{backend::{self, Decodable, Encode, Encodable}, io};
+
+        std::thread_local! {
+            pub static ENCODES: Cell<usize> = const { Cell::new(0) };
+        }
+
+        pub struct Encoder<F: backend::FormatEncode>(<u8 as Encodable>::Encoder<F>);
+
+        impl<F: backend::FormatEncode> Encode for Encoder<F> {
+            type Data = u8;
+            type Format = F;
+
+            fn init(data: &Self::Data) -> Self {
+                Self(Encode::init(data))
+            }
+
+            fn start_encode<W>(format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::StartEncodeStatus<Self, F::Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                ENCODES.with(|n| n.set(n.get() + 1));
+                <u8 as Encodable>::Encoder::<F>::start_encode(format, writer, data, cx)
+                .map_pending(Self)
+            }
+
+            fn poll_encode<W>(&mut self, format: &F, writer: &mut W, data: &Self::Data, cx: &mut Context<'_>) -> backend::PollEncodeStatus<F::Error>
+            where
+                W: io::AsyncWrite + Unpin,
+            {
+                self.0.poll_encode(format, writer, data, cx)
+            }
+        }
+
+        pub type Decoder<F> = <u8 as Decodable>::Decoder<F>;
+    }
+
+    macro_rules! layers {
+        ($inner: expr;) => { $inner };
+        ($inner: expr; $depth: literal $($rest: literal)*) => { layers!(Layer { depth: $depth, inner: $inner }; $($rest)*) };
+    }
+
+    #[derive(diny::AsyncSerialization)]
+    pub struct Unversioned {
+        pub id: u32,
+        pub quantity: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, diny::AsyncSerialization)]
+    pub struct Batch<T> {
+        pub orders: Vec<T>,
+        pub checksum: u16,
+    }
+
+    fn serialize<T: AsyncSerialize>(send: &T) -> Vec<u8> {
+        let mut tx = Vec::new();
+        block_on(send.serialize(&diny_test::format(), &mut tx)).expect("unable to serialize");
+        tx
+    }
+
+    fn deserialize<T: AsyncDeserialize>(bytes: &[u8], schedule: Schedule) -> Result<T, io::Error> {
+        let mut reader = ScheduledReader::new(AsyncSliceReader::from(bytes), schedule);
+        block_on(T::deserialize(&diny_test::format(), &mut reader))
+    }
+
+    fn v2_order(id: u32) -> v2::Order {
+        v2::Order { id, quantity: id * 10, note: format!("order {}", id), priority: 1 }
+    }
+
+    #[test]
+    fn versioned_structs_are_preceded_by_their_length() {
+        let bytes = diny_test::roundtrip_all_schedules(&diny_test::format(), &v1::Order { id: 1, quantity: 2 });
+        assert_eq!(bytes.len(), SEQ_LEN + 8);
+        assert_eq!(&bytes[..SEQ_LEN], &8u64.to_le_bytes());
+
+        diny_test::roundtrip_all_schedules(&diny_test::format(), &v2_order(7));
+        assert_eq!(diny_test::roundtrip_all_schedules(&diny_test::format(), &Marker), [0; SEQ_LEN]);
+    }
+
+    #[test]
+    fn new_readers_default_missing_fields() {
+        let bytes = serialize(&v1::Order { id: 3, quantity: 4 });
+
+        for schedule in Schedule::all() {
+            let recv = deserialize::<v2::Order>(&bytes, schedule).expect("unable to deserialize");
+            assert_eq!(recv, v2::Order { id: 3, quantity: 4, note: String::new(), priority: 5 }, "with {:?}", schedule);
+
+            let mut recv = v2_order(9);
+            let mut reader = ScheduledReader::new(AsyncSliceReader::from(&bytes[..]), schedule);
+            let mut deserializer = diny::deserializer(diny_test::format(), &mut reader);
+            block_on(deserializer.deserialize_into(&mut recv)).expect("unable to deserialize into");
+            assert_eq!(recv, v2::Order { id: 3, quantity: 4, note: String::new(), priority: 5 }, "into with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn old_readers_skip_unknown_trailing_fields() {
+        let bytes = serialize(&v2_order(6));
+
+        for schedule in Schedule::all() {
+            let mut reader = ScheduledReader::new(AsyncSliceReader::from(&bytes[..]), schedule);
+            let recv = block_on(v1::Order::deserialize(&diny_test::format(), &mut reader)).expect("unable to deserialize");
+            assert_eq!(recv, v1::Order { id: 6, quantity: 60 }, "with {:?}", schedule);
+            assert_eq!(reader.get_ref().bytes_read(), bytes.len(), "encoding not fully consumed with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn streams_and_collections_stay_aligned_across_versions() {
+        let sent = (1..=3).map(v2_order).collect::<Vec<_>>();
+        let mut bytes = Vec::new();
+        for order in &sent {
+            bytes.extend(serialize(order));
+        }
+
+        let recv = block_on(diny::deserializer(diny_test::format(), AsyncSliceReader::from(&bytes[..])).into_stream::<v1::Order>().collect::<Vec<_>>());
+        assert_eq!(recv, sent.iter().map(|o| v1::Order { id: o.id, quantity: o.quantity }).collect::<Vec<_>>());
+
+        let bytes = serialize(&Batch { orders: sent.clone(), checksum: 0xbeef });
+        for schedule in Schedule::all() {
+            let recv = deserialize::<Batch<v1::Order>>(&bytes, schedule).expect("unable to deserialize");
+            assert_eq!(recv.orders.len(), sent.len());
+            assert_eq!(recv.checksum, 0xbeef, "with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn truncated_records_are_rejected() {
+        let bytes = serialize(&v2_order(2));
+
+        for schedule in Schedule::all() {
+            let err = deserialize::<v1::Order>(&bytes[..bytes.len() - 1], schedule).expect_err("unexpected success");
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof, "with {:?}", schedule);
+
+            let err = deserialize::<v2::Order>(&bytes[..bytes.len() - 1], schedule).expect_err("unexpected success");
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof, "with {:?}", schedule);
+        }
+    }
+
+    #[test]
+    fn versions_are_described() {
+        use diny::schema::Describe;
+
+        assert_eq!(
+            v2::Order::SCHEMA.definition().to_string(),
+            "versioned struct Order { #1 id: u32, #2 quantity: u32, #3 note: string (since 2), #4 priority: u8 (since 3) }",
+        );
+        assert_ne!(diny::schema::fingerprint::<v1::Order>(), diny::schema::fingerprint::<Unversioned>());
+        assert_eq!(diny::schema::fingerprint::<v1::Order>(), diny::schema::fingerprint::<v2::Order>());
+    }
+
+    #[test]
+    fn deeply_nested_versioned_structs_are_measured_once_per_level() {
+        let send = layers!(Leaf(0); 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16);
+
+        counted::ENCODES.with(|n| n.set(0));
+        let bytes = serialize(&send);
+        assert_eq!(counted::ENCODES.with(|n| n.get()), 16 + 1);
+        assert_eq!(diny::encoded_len(&diny_test::format(), &send).unwrap(), bytes.len());
+        diny_test::roundtrip_all_schedules(&diny_test::format(), &send);
+    }
+}